@@ -333,6 +333,12 @@ impl RifInst {
         }
     }
 
+    /// True when a register of an internal page has an external implementation or done
+    pub fn has_external_reg(&self) -> bool {
+        self.pages.iter()
+            .filter(|p| !p.is_external())
+            .any(|p| p.regs.iter().any(|r| r.external != ExternalKind::None))
+    }
 
 }
 
@@ -575,6 +581,7 @@ pub struct RifRegInst {
     pub array: ArrayIdx,
     pub group_idx: usize,
     pub visibility: Visibility,
+    pub info: BTreeMap<String,String>,
 }
 
 impl RifRegInst {
@@ -638,6 +645,7 @@ impl RifRegInst {
             fields: Vec::new(),
             array : if let RegInstArgs::Arr(idx) = args {idx} else {ArrayIdx::Def(0,0)},
             visibility: def.visibility,
+            info: def.info.iter().map(|(k,v)| (k.to_owned(),v.to_owned())).collect(),
         };
         let mut next_lsb = 0;
         for f in def.fields.iter() {
@@ -675,8 +683,10 @@ impl RifRegInst {
                     } else {
                         ResetVal::Unsigned(val)
                     };
-                    if kind.is_pending() {
-                        f.sw_kind = FieldSwKind::ReadOnly;
+                    // Enable/mask are simple read/write fields not visible from hardware, pending is read-only
+                    f.sw_kind = if kind.is_pending() {FieldSwKind::ReadOnly} else {FieldSwKind::ReadWrite};
+                    if !kind.is_pending() {
+                        f.hw_access = Access::NA;
                     }
                     f.hw_kind.clear();
                 }
@@ -737,6 +747,9 @@ impl RifRegInst {
                     if let Some(limit) = &ovr_f.limit {
                         reg_field.limit = limit.clone();
                     }
+                    for (k,v) in ovr_f.info.iter() {
+                        reg_field.info.insert(k.to_owned(), v.to_owned());
+                    }
                 }
             }
         }
@@ -830,11 +843,13 @@ pub struct RifFieldInst {
     pub reset: ResetVal,
     pub sw_kind: FieldSwKind,
     pub hw_kind: Vec<FieldHwKind>,
+    pub hw_access: Access,
     pub visibility: Visibility,
     pub enum_kind: EnumKind,
     pub partial: (Option<u16>, u16),
     pub array: ArrayIdx,
     pub limit: Limit,
+    pub info: BTreeMap<String,String>,
 }
 
 impl RifFieldInst {
@@ -895,6 +910,7 @@ impl RifFieldInst {
             reset,
             sw_kind: field.sw_kind.to_owned(),
             hw_kind,
+            hw_access: field.hw_acc,
             visibility: field.visibility,
             enum_kind: field.enum_kind.clone(),
             limit: field.limit.clone(),
@@ -902,6 +918,7 @@ impl RifFieldInst {
             lsb,
             width,
             array: idx,
+            info: field.info.iter().map(|(k,v)| (k.to_owned(),v.to_owned())).collect(),
         }
     }
 
@@ -1034,7 +1051,7 @@ impl PartialFieldInfos {
 
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct PartialFieldDict(BTreeMap<String, PartialFieldInfos>);
 
 impl PartialFieldDict {
@@ -1184,3 +1201,34 @@ impl Comp {
     }
 
 }
+
+#[cfg(test)]
+mod tests_comp {
+    use super::*;
+
+    #[test]
+    fn test_interrupt_derived() {
+        let src = RifGenSrc::from_file("test/model.rif").expect("Parsing failed");
+        let Ok(Comp::Rif(rif)) = Comp::compile(&src, &HashMap::new(), &ParamValues::new()) else {
+            panic!("Compilation failed");
+        };
+        let get_reg = |name: &str| rif.pages[0].regs.iter().find(|r| r.reg_name == name).expect("Register should exist");
+        // Base register keeps the field definition
+        let base = get_reg("irq");
+        assert!(base.fields.iter().all(|f| f.sw_kind == FieldSwKind::W1Clr));
+        // Enable/Mask are read/write registers without hardware access
+        for name in ["irq_en", "irq_mask"] {
+            let reg = get_reg(name);
+            assert_eq!(reg.sw_access, base.sw_access, "{name}");
+            assert_eq!(reg.hw_access, Access::NA, "{name}");
+            assert!(reg.fields.iter().all(|f| f.sw_kind == FieldSwKind::ReadWrite && f.hw_access == Access::NA && f.hw_kind.is_empty()), "{name}");
+        }
+        assert_eq!(get_reg("irq_en").reset, 0x3);
+        assert_eq!(get_reg("irq_mask").reset, 0x1);
+        // Pending is read-only, fields keep their hardware access
+        let reg = get_reg("irq_pending");
+        assert_eq!(reg.sw_access, Access::RO);
+        assert_eq!(reg.hw_access, Access::NA);
+        assert!(reg.fields.iter().all(|f| f.sw_kind == FieldSwKind::ReadOnly && f.hw_kind.is_empty()));
+    }
+}
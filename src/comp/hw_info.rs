@@ -115,6 +115,7 @@ impl PortInfo {
             PortWidth::Basic(w)  => w,
             PortWidth::Address   => addr_w,
             PortWidth::Data      => data_w,
+            PortWidth::Strobe    => data_w >> 3,
             PortWidth::Custom(_) => 0,
         }
     }
@@ -126,6 +127,8 @@ pub enum PortWidth {
     Basic(u8),
     Address,
     Data,
+    /// Byte strobe: one bit per byte of data
+    Strobe,
     Custom(String),
 }
 
@@ -306,6 +309,21 @@ impl PortList {
     }
 }
 
+/// Signals of the internal register interface: name, direction from the RIF side, width
+pub const RIF_IF_SIGNALS: [(&str, PortDir, PortWidth, &str); 11] = [
+    ("en"             , PortDir::In , PortWidth::Basic(1), "Access enable, held until done_next"),
+    ("rd_wrn"         , PortDir::In , PortWidth::Basic(1), "Read (high) / Write (low)"),
+    ("addr"           , PortDir::In , PortWidth::Address , "Address"),
+    ("wr_data"        , PortDir::In , PortWidth::Data    , "Write data"),
+    ("rd_data"        , PortDir::Out, PortWidth::Data    , "Read data"),
+    ("done"           , PortDir::Out, PortWidth::Basic(1), "Access done"),
+    ("err_addr"       , PortDir::Out, PortWidth::Basic(1), "Address error"),
+    ("err_access"     , PortDir::Out, PortWidth::Basic(1), "Access error"),
+    ("done_next"      , PortDir::Out, PortWidth::Basic(1), "Access done (combinatorial)"),
+    ("err_addr_next"  , PortDir::Out, PortWidth::Basic(1), "Address error (combinatorial)"),
+    ("err_access_next", PortDir::Out, PortWidth::Basic(1), "Access error (combinatorial)"),
+];
+
 #[derive(Clone, Debug, Default)]
 /// Define ports for a RIF interface
 pub struct RifIntfPorts (Vec<PortInfo>);
@@ -347,6 +365,63 @@ impl RifIntfPorts {
                 PortInfo::new_out("uaux_serial_sr".to_owned(), "AUX SR group flush ".to_owned()),
                 PortInfo::new_out("uaux_strict_sr".to_owned(), "AUX SR single flush".to_owned()),
             ],
+            Interface::Axi4Lite => vec![
+                PortInfo::new("awaddr".to_owned(), PortWidth::Address, PortDir::In, "AXI Write Address".to_owned(), 0),
+                PortInfo::new("awprot".to_owned(), PortWidth::Basic(3), PortDir::In, "AXI Write Protection (unused)".to_owned(), 0),
+                PortInfo::new_in("awvalid".to_owned(), "AXI Write Address Valid".to_owned()),
+                PortInfo::new_out("awready".to_owned(), "AXI Write Address Ready".to_owned()),
+                PortInfo::new("wdata".to_owned(), PortWidth::Data, PortDir::In, "AXI Write Data".to_owned(), 0),
+                PortInfo::new("wstrb".to_owned(), PortWidth::Strobe, PortDir::In, "AXI Write Strobe".to_owned(), 0),
+                PortInfo::new_in("wvalid".to_owned(), "AXI Write Data Valid".to_owned()),
+                PortInfo::new_out("wready".to_owned(), "AXI Write Data Ready".to_owned()),
+                PortInfo::new("bresp".to_owned(), PortWidth::Basic(2), PortDir::Out, "AXI Write Response".to_owned(), 0),
+                PortInfo::new_out("bvalid".to_owned(), "AXI Write Response Valid".to_owned()),
+                PortInfo::new_in("bready".to_owned(), "AXI Write Response Ready".to_owned()),
+                PortInfo::new("araddr".to_owned(), PortWidth::Address, PortDir::In, "AXI Read Address".to_owned(), 0),
+                PortInfo::new("arprot".to_owned(), PortWidth::Basic(3), PortDir::In, "AXI Read Protection (unused)".to_owned(), 0),
+                PortInfo::new_in("arvalid".to_owned(), "AXI Read Address Valid".to_owned()),
+                PortInfo::new_out("arready".to_owned(), "AXI Read Address Ready".to_owned()),
+                PortInfo::new("rdata".to_owned(), PortWidth::Data, PortDir::Out, "AXI Read Data".to_owned(), 0),
+                PortInfo::new("rresp".to_owned(), PortWidth::Basic(2), PortDir::Out, "AXI Read Response".to_owned(), 0),
+                PortInfo::new_out("rvalid".to_owned(), "AXI Read Data Valid".to_owned()),
+                PortInfo::new_in("rready".to_owned(), "AXI Read Data Ready".to_owned()),
+            ],
+            Interface::Ahb => vec![
+                PortInfo::new_in("hsel".to_owned(), "AHB Select".to_owned()),
+                PortInfo::new("haddr".to_owned(), PortWidth::Address, PortDir::In, "AHB Address".to_owned(), 0),
+                PortInfo::new("htrans".to_owned(), PortWidth::Basic(2), PortDir::In, "AHB Transfer type".to_owned(), 0),
+                PortInfo::new_in("hwrite".to_owned(), "AHB Write".to_owned()),
+                PortInfo::new("hsize".to_owned(), PortWidth::Basic(3), PortDir::In, "AHB Transfer size".to_owned(), 0),
+                PortInfo::new("hwdata".to_owned(), PortWidth::Data, PortDir::In, "AHB Write Data".to_owned(), 0),
+                PortInfo::new_in("hready".to_owned(), "AHB Ready from the bus".to_owned()),
+                PortInfo::new("hrdata".to_owned(), PortWidth::Data, PortDir::Out, "AHB Read Data".to_owned(), 0),
+                PortInfo::new_out("hreadyout".to_owned(), "AHB Ready".to_owned()),
+                PortInfo::new_out("hresp".to_owned(), "AHB Error Response".to_owned()),
+            ],
+            Interface::Wishbone => vec![
+                PortInfo::new("wb_adr_i".to_owned(), PortWidth::Address, PortDir::In, "Wishbone Address".to_owned(), 0),
+                PortInfo::new("wb_dat_i".to_owned(), PortWidth::Data, PortDir::In, "Wishbone Write Data".to_owned(), 0),
+                PortInfo::new("wb_dat_o".to_owned(), PortWidth::Data, PortDir::Out, "Wishbone Read Data".to_owned(), 0),
+                PortInfo::new("wb_sel_i".to_owned(), PortWidth::Strobe, PortDir::In, "Wishbone Byte Select".to_owned(), 0),
+                PortInfo::new_in("wb_we_i".to_owned(), "Wishbone Write Enable".to_owned()),
+                PortInfo::new_in("wb_cyc_i".to_owned(), "Wishbone Cycle".to_owned()),
+                PortInfo::new_in("wb_stb_i".to_owned(), "Wishbone Strobe".to_owned()),
+                PortInfo::new_out("wb_ack_o".to_owned(), "Wishbone Acknowledge".to_owned()),
+                PortInfo::new_out("wb_err_o".to_owned(), "Wishbone Error".to_owned()),
+                PortInfo::new_out("wb_stall_o".to_owned(), "Wishbone Stall".to_owned()),
+            ],
+            Interface::Avalon => vec![
+                PortInfo::new("avs_address".to_owned(), PortWidth::Address, PortDir::In, "Avalon Address".to_owned(), 0),
+                PortInfo::new_in("avs_read".to_owned(), "Avalon Read".to_owned()),
+                PortInfo::new_in("avs_write".to_owned(), "Avalon Write".to_owned()),
+                PortInfo::new("avs_writedata".to_owned(), PortWidth::Data, PortDir::In, "Avalon Write Data".to_owned(), 0),
+                PortInfo::new("avs_byteenable".to_owned(), PortWidth::Strobe, PortDir::In, "Avalon Byte Enable".to_owned(), 0),
+                PortInfo::new("avs_readdata".to_owned(), PortWidth::Data, PortDir::Out, "Avalon Read Data".to_owned(), 0),
+                PortInfo::new_out("avs_waitrequest".to_owned(), "Avalon Wait Request".to_owned()),
+                PortInfo::new_out("avs_readdatavalid".to_owned(), "Avalon Read Data Valid".to_owned()),
+                PortInfo::new_out("avs_writeresponsevalid".to_owned(), "Avalon Write Response Valid".to_owned()),
+                PortInfo::new("avs_response".to_owned(), PortWidth::Basic(2), PortDir::Out, "Avalon Response".to_owned(), 0),
+            ],
             Interface::Custom(name) => vec![
                 PortInfo::new_intf(
                     format!("if_{}", name.strip_suffix("_if").unwrap_or(name)),
@@ -1,3 +1,4 @@
 pub mod comp_inst;
 pub mod reg_impl;
-pub mod hw_info;
\ No newline at end of file
+pub mod hw_info;
+pub mod rif_model;
\ No newline at end of file
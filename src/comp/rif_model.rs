@@ -0,0 +1,709 @@
+// Behavioural model of a register interface: not used by the generators,
+// it serves as golden reference to check the RTL and the generated outputs
+
+use std::collections::HashMap;
+
+use crate::rifgen::{Access, EnumKind, FieldHwKind, FieldSwKind, InterruptRegKind, InterruptTrigger, LimitValue, PasswordInfo};
+
+use super::comp_inst::{RifFieldInst, RifInst, RifRegInst};
+
+/// Error returned on a bus access
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BusError {
+    /// No register mapped at this address
+    Addr,
+    /// Access not allowed: write to a read-only register, read of a write-only register
+    /// or write value outside the limit of a field
+    Access,
+}
+
+/// Action from the hardware on a field
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HwAction {
+    /// Write the field value (hardware write access or write enable)
+    Write(u128),
+    /// Set to 1 all bits high (hwset)
+    Set(u128),
+    /// Clear all bits high (hwclr)
+    Clear(u128),
+    /// Toggle all bits high (hwtgl)
+    Toggle(u128),
+    /// Increment a counter
+    Incr(u128),
+    /// Decrement a counter
+    Decr(u128),
+    /// Clear a counter to its reset value
+    CounterClr,
+    /// Level of the interrupt event input (one bit per field bit)
+    Event(u128),
+}
+
+/// Signal preventing the modification of a field
+#[derive(Clone, Debug, PartialEq)]
+enum LockSrc {
+    None,
+    /// Locked when the field (register index, field index) is non-zero
+    Field(usize, usize),
+    /// Locked when the hardware input is high
+    Input(String),
+}
+
+/// Valid values for a field written by software
+#[derive(Clone, Debug, PartialEq)]
+enum LimitCheck {
+    None,
+    Range(Option<i128>, Option<i128>),
+    List(Vec<i128>),
+}
+
+#[derive(Clone, Debug)]
+struct FieldModel {
+    name: String,
+    name_flat: String,
+    lsb: u8,
+    width: u8,
+    signed: bool,
+    reset: u128,
+    value: u128,
+    sw_kind: FieldSwKind,
+    hw_kind: Vec<FieldHwKind>,
+    hw_access: Access,
+    disabled: bool,
+    limit: LimitCheck,
+    bypass: String,
+    lock: LockSrc,
+    /// Value written on a pulse field during last access
+    pulse: u128,
+    /// Previous level of the interrupt event
+    event: u128,
+}
+
+impl FieldModel {
+
+    fn new(rif: &RifInst, reg: &RifRegInst, f: &RifFieldInst) -> Result<Self, String> {
+        let reset = if f.is_password() {1} else {f.reset.to_u128(f.width)};
+        let limit = match &f.limit.value {
+            LimitValue::None => LimitCheck::None,
+            LimitValue::Min(v) => LimitCheck::Range(Some(Self::to_i128(v.to_u128(f.width), f.width, f.is_signed())), None),
+            LimitValue::Max(v) => LimitCheck::Range(None, Some(Self::to_i128(v.to_u128(f.width), f.width, f.is_signed()))),
+            LimitValue::MinMax(min, max) => LimitCheck::Range(
+                Some(Self::to_i128(min.to_u128(f.width), f.width, f.is_signed())),
+                Some(Self::to_i128(max.to_u128(f.width), f.width, f.is_signed()))),
+            LimitValue::List(l) => LimitCheck::List(
+                l.iter().map(|v| Self::to_i128(v.to_u128(f.width), f.width, f.is_signed())).collect()),
+            LimitValue::Enum => {
+                let Some(enum_name) = f.enum_kind.name() else {
+                    return Err(format!("Using `limit enum` on non-enum field {}.{}!", reg.name(), f.name()));
+                };
+                let reg_impl = rif.get_hw_reg(&reg.group_type);
+                let enum_name = match (&reg_impl.pkg, &f.enum_kind) {
+                    (Some(pkg), EnumKind::Type(_)) if !enum_name.contains(':') => format!("{pkg}_pkg::{enum_name}"),
+                    _ => enum_name.to_owned(),
+                };
+                LimitCheck::List(rif.get_enum_def(&enum_name)?.iter().map(|e| e.value as i128).collect())
+            }
+        };
+        Ok(FieldModel {
+            name: f.name(),
+            name_flat: f.name_flat(),
+            lsb: f.lsb,
+            width: f.width,
+            signed: f.is_signed(),
+            reset,
+            value: reset,
+            sw_kind: f.sw_kind.clone(),
+            hw_kind: f.hw_kind.clone(),
+            hw_access: f.hw_access,
+            disabled: f.is_disabled(),
+            limit,
+            bypass: f.limit.bypass.to_owned(),
+            lock: LockSrc::None,
+            pulse: 0,
+            event: 0,
+        })
+    }
+
+    fn mask(&self) -> u128 {
+        if self.width >= 128 {u128::MAX} else {(1 << self.width) - 1}
+    }
+
+    /// Convert a raw value to a signed integer when needed
+    fn to_i128(v: u128, width: u8, signed: bool) -> i128 {
+        if signed && width < 128 && (v >> (width - 1)) & 1 == 1 {
+            (v as i128) - (1 << width)
+        } else {
+            v as i128
+        }
+    }
+
+    /// Check a value written by software against the field limits
+    fn is_valid(&self, v: u128) -> bool {
+        let v = Self::to_i128(v, self.width, self.signed);
+        match &self.limit {
+            LimitCheck::None => true,
+            LimitCheck::Range(min, max) => min.map(|m| v >= m).unwrap_or(true) && max.map(|m| v <= m).unwrap_or(true),
+            LimitCheck::List(l) => l.contains(&v),
+        }
+    }
+
+    fn is_interrupt(&self) -> bool {
+        self.hw_kind.iter().any(|k| matches!(k, FieldHwKind::Interrupt(_)))
+    }
+
+    /// Lock state driven by this field: password are locked when bit 0 of their state is high
+    fn is_locking(&self) -> bool {
+        if self.sw_kind.is_password() {
+            self.value & 1 == 1
+        } else {
+            self.value != 0
+        }
+    }
+
+    /// Value of the field when read by software
+    fn read_value(&self) -> u128 {
+        match self.sw_kind {
+            // Pulse are only high during the write access and password state is only visible by hardware
+            FieldSwKind::Password(_) |
+            FieldSwKind::W1Pulse(_,_) |
+            FieldSwKind::WriteOnly => 0,
+            _ => self.value,
+        }
+    }
+
+    /// Software write of the field
+    fn write(&mut self, v: u128) {
+        let v = v & self.mask();
+        match &self.sw_kind {
+            FieldSwKind::ReadWrite |
+            FieldSwKind::WriteOnly => self.value = v,
+            FieldSwKind::W1Clr => self.value &= !v,
+            FieldSwKind::W0Clr => self.value &= v,
+            FieldSwKind::W1Set => self.value |= v,
+            FieldSwKind::W1Tgl => self.value ^= v,
+            FieldSwKind::W1Pulse(_,_) => self.pulse = v,
+            FieldSwKind::Password(info) => self.value = Self::password_next(info, self.value, v),
+            FieldSwKind::ReadOnly |
+            FieldSwKind::ReadClr => {},
+        }
+    }
+
+    /// Password state after a write:
+    /// 0 for unlocked once, 1 for locked, 2 for unlocked hold and 3 for stuck until reset
+    fn password_next(info: &PasswordInfo, state: u128, v: u128) -> u128 {
+        if state == 3 {
+            3
+        } else if info.once.as_ref().map(|p| p.to_u128(128) == v).unwrap_or(false) {
+            0
+        } else if info.hold.as_ref().map(|p| p.to_u128(128) == v).unwrap_or(false) {
+            2
+        } else if info.protect && v != 0 {
+            3
+        } else {
+            1
+        }
+    }
+
+    /// Add or subtract a value to a counter, returning true on overflow/underflow
+    fn count(&mut self, v: u128, is_incr: bool) -> bool {
+        let sat = self.hw_kind.iter().any(|k| matches!(k, FieldHwKind::Counter(info) if info.sat));
+        let (min, max) = if self.signed {
+            (-(1_i128 << (self.width - 1)), (1_i128 << (self.width - 1)) - 1)
+        } else {
+            (0, self.mask() as i128)
+        };
+        let cur = Self::to_i128(self.value, self.width, self.signed);
+        let next = if is_incr {cur + v as i128} else {cur - v as i128};
+        let event = next > max || next < min;
+        let next = if event && sat {next.clamp(min, max)} else {next};
+        self.value = (next as u128) & self.mask();
+        event
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RegModel {
+    name: String,
+    group_name: String,
+    group_type: String,
+    addr: u64,
+    sw_access: Access,
+    intr_kind: InterruptRegKind,
+    /// Index of the interrupt status register (for derived registers)
+    intr_base: Option<usize>,
+    /// Index of the interrupt enable register
+    intr_en: Option<usize>,
+    /// Index of the interrupt mask register
+    intr_mask: Option<usize>,
+    fields: Vec<FieldModel>,
+}
+
+/// Behavioural model of a RIF instance:
+/// simulates bus access with the software semantic of each field,
+/// and allows to drive the hardware side (write, counter, interrupt events, lock inputs)
+#[derive(Clone, Debug)]
+pub struct RifModel {
+    data_width: u8,
+    regs: Vec<RegModel>,
+    addr_map: HashMap<u64, usize>,
+    inputs: HashMap<String, bool>,
+}
+
+impl RifModel {
+
+    /// Create the model from a compiled RIF: external pages and registers are not modelled
+    pub fn new(rif: &RifInst) -> Result<Self, String> {
+        let mut regs = Vec::new();
+        let mut addr_map = HashMap::new();
+        let reg_insts : Vec<(u64, &RifRegInst)> = rif.pages.iter()
+            .filter(|p| !p.is_external())
+            .flat_map(|p| p.regs.iter().map(|r| (p.addr + r.addr, r)))
+            .filter(|(_,r)| !r.is_external())
+            .collect();
+        for (addr, reg) in reg_insts.iter() {
+            let fields = reg.fields.iter()
+                .map(|f| FieldModel::new(rif, reg, f))
+                .collect::<Result<Vec<FieldModel>, String>>()?;
+            addr_map.insert(*addr, regs.len());
+            regs.push(RegModel {
+                name: reg.name(),
+                group_name: reg.group_name.to_owned(),
+                group_type: reg.group_type.to_owned(),
+                addr: *addr,
+                sw_access: reg.sw_access,
+                intr_kind: reg.intr_info.0,
+                intr_base: None,
+                intr_en: None,
+                intr_mask: None,
+                fields,
+            });
+        }
+        let mut model = RifModel {data_width: rif.data_width, regs, addr_map, inputs: HashMap::new()};
+        // Link interrupt registers together
+        for (i, (_, reg)) in reg_insts.iter().enumerate() {
+            if reg.intr_info.0 == InterruptRegKind::None {
+                continue;
+            }
+            let find = |kind: InterruptRegKind| reg_insts.iter()
+                .position(|(_,r)| r.intr_info.0 == kind && r.group_name() == reg.group_name() && r.array.idx() == reg.array.idx());
+            model.regs[i].intr_base = find(InterruptRegKind::Base);
+            model.regs[i].intr_en = find(InterruptRegKind::Enable);
+            model.regs[i].intr_mask = find(InterruptRegKind::Mask);
+        }
+        // Resolve lock signals
+        for (i, (_, reg)) in reg_insts.iter().enumerate() {
+            if reg.is_intr_derived() {
+                continue;
+            }
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            for (j, f) in reg.fields.iter().enumerate() {
+                let lock = &reg_impl.get_field(&f.name)?.lock;
+                if lock.is_some() {
+                    model.regs[i].fields[j].lock = model.lock_src(i, f, lock.name());
+                }
+            }
+        }
+        Ok(model)
+    }
+
+    /// Find the source of a lock, following the naming rules of the hardware signals
+    fn lock_src(&self, reg_idx: usize, f: &RifFieldInst, name: &Option<String>) -> LockSrc {
+        let reg = &self.regs[reg_idx];
+        let name = name.as_deref().unwrap_or("");
+        if name.is_empty() {
+            return LockSrc::Input(format!("{}.{}_lock", reg.name, f.name_flat()));
+        }
+        if name.starts_with('(') {
+            return LockSrc::Input(name.to_owned());
+        }
+        let (reg_name, field_name) = match name.split_once('.') {
+            Some(("", n)) => return LockSrc::Input(n.to_owned()),
+            Some((r, n)) if r == reg.group_type || r == "this" || r == "self" => (reg.group_name.as_str(), n),
+            Some((r, n)) => (r, n),
+            None => (reg.group_name.as_str(), name),
+        };
+        let field_name = field_name.strip_suffix("_locked").unwrap_or(field_name);
+        // Search first in the current register then in all registers of the group
+        let regs = std::iter::once(reg_idx).chain(0..self.regs.len());
+        for i in regs {
+            let r = &self.regs[i];
+            if r.name != reg_name && r.group_name != reg_name {
+                continue;
+            }
+            if let Some(j) = r.fields.iter().position(|x| x.name == field_name || x.name_flat == field_name) {
+                return LockSrc::Field(i, j);
+            }
+        }
+        LockSrc::Input(name.to_owned())
+    }
+
+    /// Set all registers to their reset value
+    pub fn reset(&mut self) {
+        for reg in self.regs.iter_mut() {
+            for f in reg.fields.iter_mut() {
+                f.value = f.reset;
+                f.pulse = 0;
+                f.event = 0;
+            }
+        }
+    }
+
+    /// Data width of the bus
+    pub fn data_width(&self) -> u8 {
+        self.data_width
+    }
+
+    /// Software read access
+    pub fn read(&mut self, addr: u64) -> Result<u128, BusError> {
+        self.clear_pulses();
+        let idx = *self.addr_map.get(&addr).ok_or(BusError::Addr)?;
+        if !self.regs[idx].sw_access.is_readable() {
+            return Err(BusError::Access);
+        }
+        let value = self.reg_value(idx);
+        // Clear on read
+        let locks = self.locks(idx);
+        for (f, locked) in self.regs[idx].fields.iter_mut().zip(locks) {
+            if f.sw_kind == FieldSwKind::ReadClr && !locked && !f.disabled {
+                f.value = 0;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Software write access
+    pub fn write(&mut self, addr: u64, data: u128) -> Result<(), BusError> {
+        self.clear_pulses();
+        let idx = *self.addr_map.get(&addr).ok_or(BusError::Addr)?;
+        let res = self.write_reg(idx, data);
+        // Password valid once are locked again after any other write
+        for (i, reg) in self.regs.iter_mut().enumerate() {
+            for f in reg.fields.iter_mut() {
+                if f.value == 0 && (i != idx || res.is_err()) && matches!(&f.sw_kind, FieldSwKind::Password(info) if info.once.is_some()) {
+                    f.value = 1;
+                }
+            }
+        }
+        res
+    }
+
+    fn write_reg(&mut self, idx: usize, data: u128) -> Result<(), BusError> {
+        let reg = &self.regs[idx];
+        if !reg.sw_access.is_writable() {
+            return Err(BusError::Access);
+        }
+        // Check limits: any invalid value prevents the register update
+        for f in reg.fields.iter() {
+            let v = (data >> f.lsb) & f.mask();
+            if !f.is_valid(v) && !self.input(&f.bypass) {
+                return Err(BusError::Access);
+            }
+        }
+        let locks = self.locks(idx);
+        for (f, locked) in self.regs[idx].fields.iter_mut().zip(locks) {
+            if !locked && !f.disabled {
+                f.write(data >> f.lsb);
+            }
+        }
+        Ok(())
+    }
+
+    /// Lock state of all fields of a register
+    fn locks(&self, idx: usize) -> Vec<bool> {
+        self.regs[idx].fields.iter()
+            .map(|f| match &f.lock {
+                LockSrc::None => false,
+                LockSrc::Field(i, j) => self.regs[*i].fields[*j].is_locking(),
+                LockSrc::Input(name) => self.input(name),
+            })
+            .collect()
+    }
+
+    fn clear_pulses(&mut self) {
+        for reg in self.regs.iter_mut() {
+            for f in reg.fields.iter_mut() {
+                f.pulse = 0;
+            }
+        }
+    }
+
+    /// Register value as seen by software
+    fn reg_value(&self, idx: usize) -> u128 {
+        let reg = &self.regs[idx];
+        if reg.intr_kind == InterruptRegKind::Pending {
+            return self.pending(idx);
+        }
+        reg.fields.iter().fold(0, |acc, f| acc | (f.read_value() << f.lsb))
+    }
+
+    /// Pending interrupt: status and mask
+    fn pending(&self, idx: usize) -> u128 {
+        let reg = &self.regs[idx];
+        let Some(base) = reg.intr_base else {
+            return 0;
+        };
+        let status = self.regs[base].fields.iter().fold(0, |acc, f| acc | (f.value << f.lsb));
+        match reg.intr_mask {
+            Some(mask) => status & self.regs[mask].fields.iter().fold(0, |acc, f| acc | (f.value << f.lsb)),
+            None => status,
+        }
+    }
+
+    fn reg_idx(&self, reg: &str) -> Result<usize, String> {
+        self.regs.iter().position(|r| r.name == reg)
+            .ok_or(format!("Register {reg} not found"))
+    }
+
+    fn field_idx(&self, reg: &str, field: &str) -> Result<(usize, usize), String> {
+        let i = self.reg_idx(reg)?;
+        let j = self.regs[i].fields.iter().position(|f| f.name == field || f.name_flat == field)
+            .ok_or(format!("Field {field} not found in register {reg}"))?;
+        Ok((i,j))
+    }
+
+    /// Value of a register without any side effect
+    pub fn peek(&self, reg: &str) -> Result<u128, String> {
+        Ok(self.reg_value(self.reg_idx(reg)?))
+    }
+
+    /// Address of a register
+    pub fn addr(&self, reg: &str) -> Result<u64, String> {
+        Ok(self.regs[self.reg_idx(reg)?].addr)
+    }
+
+    /// Value of a field as seen by the hardware (state for password fields)
+    pub fn get(&self, reg: &str, field: &str) -> Result<u128, String> {
+        let (i,j) = self.field_idx(reg, field)?;
+        if self.regs[i].intr_kind == InterruptRegKind::Pending {
+            let f = &self.regs[i].fields[j];
+            return Ok((self.pending(i) >> f.lsb) & f.mask());
+        }
+        Ok(self.regs[i].fields[j].value)
+    }
+
+    /// Value written on a pulse field during the last bus access
+    pub fn pulse(&self, reg: &str, field: &str) -> Result<u128, String> {
+        let (i,j) = self.field_idx(reg, field)?;
+        Ok(self.regs[i].fields[j].pulse)
+    }
+
+    /// State of a hardware input (lock, limit bypass)
+    pub fn input(&self, name: &str) -> bool {
+        self.inputs.get(name).copied().unwrap_or(false)
+    }
+
+    /// Drive a hardware input (lock, limit bypass):
+    /// default name of a lock input is `<reg>.<field>_lock`
+    pub fn set_input(&mut self, name: &str, value: bool) {
+        self.inputs.insert(name.to_owned(), value);
+    }
+
+    /// Interrupt line of an interrupt register: high when one pending bit is high
+    pub fn irq(&self, reg: &str) -> Result<bool, String> {
+        let idx = self.reg_idx(reg)?;
+        if self.regs[idx].intr_kind != InterruptRegKind::Base {
+            return Err(format!("Register {reg} is not an interrupt register"));
+        }
+        Ok(self.irq_idx(idx))
+    }
+
+    /// High when any interrupt is pending
+    pub fn irq_any(&self) -> bool {
+        self.regs.iter().enumerate()
+            .any(|(i,r)| r.intr_kind == InterruptRegKind::Base && self.irq_idx(i))
+    }
+
+    fn irq_idx(&self, idx: usize) -> bool {
+        let status = self.regs[idx].fields.iter().fold(0, |acc, f| acc | (f.value << f.lsb));
+        match self.regs[idx].intr_mask {
+            Some(mask) => status & self.regs[mask].fields.iter().fold(0, |acc, f| acc | (f.value << f.lsb)) != 0,
+            None => status != 0,
+        }
+    }
+
+    /// Apply an hardware action on a field.
+    /// Return true when a counter overflows or underflows
+    pub fn hw(&mut self, reg: &str, field: &str, action: HwAction) -> Result<bool, String> {
+        let (i,j) = self.field_idx(reg, field)?;
+        let locked = self.locks(i)[j];
+        let enable = self.regs[i].intr_en
+            .map(|en| &self.regs[en].fields[j])
+            .map(|f| f.value)
+            .unwrap_or(u128::MAX);
+        let f = &mut self.regs[i].fields[j];
+        let has_kind = |k: fn(&FieldHwKind) -> bool| f.hw_kind.iter().any(k);
+        let allowed = match action {
+            HwAction::Write(_) => f.hw_access.is_writable() || has_kind(|k| k.has_we()),
+            HwAction::Set(_) => has_kind(|k| matches!(k, FieldHwKind::Set(_))),
+            HwAction::Clear(_) => has_kind(|k| matches!(k, FieldHwKind::Clear(_))),
+            HwAction::Toggle(_) => has_kind(|k| matches!(k, FieldHwKind::Toggle(_))),
+            HwAction::Incr(_) => has_kind(|k| matches!(k, FieldHwKind::Counter(info) if info.is_up())),
+            HwAction::Decr(_) => has_kind(|k| matches!(k, FieldHwKind::Counter(info) if info.is_down())),
+            HwAction::CounterClr => has_kind(|k| matches!(k, FieldHwKind::Counter(info) if info.clr)),
+            HwAction::Event(_) => f.is_interrupt(),
+        };
+        if !allowed {
+            return Err(format!("Action {action:?} not supported by field {reg}.{field}"));
+        }
+        if locked || f.disabled {
+            return Ok(false);
+        }
+        let mask = f.mask();
+        let mut event = false;
+        match action {
+            HwAction::Write(v) => f.value = v & mask,
+            HwAction::Set(v) => f.value |= v & mask,
+            HwAction::Clear(v) => f.value &= !v,
+            HwAction::Toggle(v) => f.value ^= v & mask,
+            HwAction::Incr(v) => event = f.count(v, true),
+            HwAction::Decr(v) => event = f.count(v, false),
+            HwAction::CounterClr => f.value = f.reset,
+            HwAction::Event(level) => {
+                let level = level & mask;
+                let trigger = f.hw_kind.iter()
+                    .find_map(|k| if let FieldHwKind::Interrupt(t) = k {Some(*t)} else {None})
+                    .unwrap_or_default();
+                let set = match trigger {
+                    InterruptTrigger::High    => level,
+                    InterruptTrigger::Low     => !level & mask,
+                    InterruptTrigger::Rising  => level & !f.event,
+                    InterruptTrigger::Falling => !level & f.event,
+                    InterruptTrigger::Edge    => level ^ f.event,
+                };
+                f.event = level;
+                f.value |= set & enable;
+            }
+        }
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests_model {
+    use super::*;
+    use crate::comp::comp_inst::Comp;
+    use crate::parser::{parser_expr::ParamValues, RifGenSrc};
+
+    fn get_model() -> RifModel {
+        let src = RifGenSrc::from_file("test/model.rif").expect("Parsing failed");
+        let Ok(Comp::Rif(rif)) = Comp::compile(&src, &HashMap::new(), &ParamValues::new()) else {
+            panic!("Compilation failed");
+        };
+        RifModel::new(&rif).expect("Model creation failed")
+    }
+
+    #[test]
+    fn test_access() {
+        let mut m = get_model();
+        assert_eq!(m.read(0x0), Ok(0x12_0202));
+        assert_eq!(m.read(0x40), Err(BusError::Addr));
+        assert_eq!(m.write(0x40, 0), Err(BusError::Addr));
+        assert_eq!(m.write(m.addr("irq_pending").unwrap(), 0), Err(BusError::Access));
+        assert_eq!(m.get("pwd", "pwd"), Ok(1));
+        // Write pulse
+        assert_eq!(m.write(0x0, 0x12_0213), Ok(()));
+        assert_eq!(m.pulse("ctrl", "start"), Ok(1));
+        assert_eq!(m.read(0x0), Ok(0x12_0203));
+        assert_eq!(m.pulse("ctrl", "start"), Ok(0));
+        // Clear/Set/Toggle
+        m.hw("status", "flags", HwAction::Write(0xFF)).unwrap();
+        m.hw("status", "sticky", HwAction::Write(0xFF)).unwrap();
+        assert_eq!(m.write(0x8, 0x0503_0F0F), Ok(()));
+        assert_eq!(m.read(0x8), Ok(0x0503_0FF0));
+        assert_eq!(m.write(0x8, 0x0504_FF00), Ok(()));
+        assert_eq!(m.read(0x8), Ok(0x0007_0FF0));
+    }
+
+    #[test]
+    fn test_limit() {
+        let mut m = get_model();
+        assert_eq!(m.write(0x0, 0x12_0200), Err(BusError::Access));
+        assert_eq!(m.write(0x0, 0x12_020C), Err(BusError::Access));
+        assert_eq!(m.write(0x0, 0x12_010A), Err(BusError::Access));
+        assert_eq!(m.read(0x0), Ok(0x12_0202));
+        assert_eq!(m.write(0x0, 0x12_030A), Ok(()));
+        assert_eq!(m.get("ctrl", "mode"), Ok(5));
+        m.set_input("sel_bypass", true);
+        assert_eq!(m.write(0x0, 0x12_010A), Ok(()));
+        assert_eq!(m.get("ctrl", "sel"), Ok(1));
+    }
+
+    #[test]
+    fn test_lock() {
+        let mut m = get_model();
+        // Lock from input
+        m.set_input("en_lock", true);
+        assert_eq!(m.write(0x0, 0x12_0203), Ok(()));
+        assert_eq!(m.get("ctrl", "en"), Ok(0));
+        m.set_input("en_lock", false);
+        // Lock from password: unlocked for a single write
+        assert_eq!(m.write(0x0, 0x34_0202), Ok(()));
+        assert_eq!(m.get("ctrl", "cfg"), Ok(0x12));
+        assert_eq!(m.write(0x4, 0x5A), Ok(()));
+        assert_eq!(m.get("pwd", "pwd"), Ok(0));
+        assert_eq!(m.read(0x4), Ok(0));
+        assert_eq!(m.write(0x0, 0x34_0202), Ok(()));
+        assert_eq!(m.get("ctrl", "cfg"), Ok(0x34));
+        assert_eq!(m.get("pwd", "pwd"), Ok(1));
+        assert_eq!(m.write(0x0, 0x56_0202), Ok(()));
+        assert_eq!(m.get("ctrl", "cfg"), Ok(0x34));
+        // Hold unlock
+        assert_eq!(m.write(0x4, 0xA5), Ok(()));
+        assert_eq!(m.get("pwd", "pwd"), Ok(2));
+        assert_eq!(m.read(0x4), Ok(0));
+        assert_eq!(m.write(0x0, 0x56_0202), Ok(()));
+        assert_eq!(m.write(0x0, 0x78_0202), Ok(()));
+        assert_eq!(m.get("ctrl", "cfg"), Ok(0x78));
+        // Wrong password: stuck until reset
+        assert_eq!(m.write(0x4, 0x11), Ok(()));
+        assert_eq!(m.get("pwd", "pwd"), Ok(3));
+        assert_eq!(m.write(0x4, 0xA5), Ok(()));
+        assert_eq!(m.get("pwd", "pwd"), Ok(3));
+        m.reset();
+        assert_eq!(m.get("pwd", "pwd"), Ok(1));
+        assert_eq!(m.get("ctrl", "cfg"), Ok(0x12));
+    }
+
+    #[test]
+    fn test_counter() {
+        let mut m = get_model();
+        assert_eq!(m.hw("cnt", "evt_cnt", HwAction::Incr(250)), Ok(false));
+        assert_eq!(m.hw("cnt", "evt_cnt", HwAction::Incr(10)), Ok(true));
+        assert_eq!(m.get("cnt", "evt_cnt"), Ok(255));
+        assert_eq!(m.read(0xC), Ok(0x08FF));
+        assert_eq!(m.get("cnt", "evt_cnt"), Ok(0));
+        assert_eq!(m.hw("cnt", "lvl", HwAction::Decr(9)), Ok(true));
+        assert_eq!(m.get("cnt", "lvl"), Ok(255));
+        assert_eq!(m.hw("cnt", "lvl", HwAction::Incr(1)), Ok(true));
+        assert_eq!(m.get("cnt", "lvl"), Ok(0));
+        assert_eq!(m.hw("cnt", "sgn", HwAction::Incr(127)), Ok(false));
+        assert_eq!(m.hw("cnt", "sgn", HwAction::Incr(1)), Ok(true));
+        assert_eq!(m.get("cnt", "sgn"), Ok(127));
+        assert!(m.hw("cnt", "sgn", HwAction::Decr(1)).is_err());
+    }
+
+    #[test]
+    fn test_interrupt() {
+        let mut m = get_model();
+        let (en, mask, pending) = (m.addr("irq_en").unwrap(), m.addr("irq_mask").unwrap(), m.addr("irq_pending").unwrap());
+        // Rising edge on evt0
+        m.hw("irq", "evt0", HwAction::Event(1)).unwrap();
+        assert_eq!(m.read(pending), Ok(1));
+        assert_eq!(m.irq("irq"), Ok(true));
+        assert_eq!(m.write(0x10, 1), Ok(()));
+        m.hw("irq", "evt0", HwAction::Event(1)).unwrap();
+        assert_eq!(m.read(0x10), Ok(0));
+        assert!(!m.irq_any());
+        // Level on evt1, masked by default
+        m.hw("irq", "evt1", HwAction::Event(1)).unwrap();
+        assert_eq!(m.read(0x10), Ok(2));
+        assert_eq!(m.read(pending), Ok(0));
+        assert_eq!(m.write(mask, 3), Ok(()));
+        assert_eq!(m.read(pending), Ok(2));
+        assert!(m.irq_any());
+        // Disabled interrupt
+        assert_eq!(m.write(en, 2), Ok(()));
+        m.hw("irq", "evt0", HwAction::Event(0)).unwrap();
+        m.hw("irq", "evt0", HwAction::Event(1)).unwrap();
+        assert_eq!(m.get("irq", "evt0"), Ok(0));
+    }
+}
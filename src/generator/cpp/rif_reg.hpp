@@ -0,0 +1,115 @@
+// File generated automatically: DO NOT EDIT.
+// Register access support for generated C++ register headers (requires C++14)
+//
+// A register instance is described by a constexpr rif::Reg giving its offset
+// and reset value. Fields are types (rif::Field) declared in the register type
+// and used as template argument of the register accessors:
+//   block::ctrl.get<block::ctrl_reg_t::enable>(base)
+//   block::ctrl.set<block::ctrl_reg_t::enable>(base, true)
+// Any write to a read-only register/field (or read of a write-only one)
+// is rejected at compile time.
+// Fields with a side-effect on write (clear, set, toggle, pulse) are never
+// read back: set() writes them with their neutral value (0, or 1 for
+// write-0-to-clear) so that only the selected field is affected.
+// A register holding a field cleared on read cannot be read-modify-written:
+// set() is rejected at compile time and the full register must be written.
+#ifndef __RIF_REG_HPP__
+#define __RIF_REG_HPP__
+
+#include <cstdint>
+#include <type_traits>
+
+namespace rif {
+
+/// Software access of a register or a field.
+/// Fields can also be cleared on read (RC), cleared by writing 1 (W1C) or 0 (W0C),
+/// set (W1S) or toggled (W1T) by writing 1, or generate a pulse when written to 1 (PULSE)
+enum class Access { RO, WO, RW, RC, W1C, W0C, W1S, W1T, PULSE };
+
+/// Field descriptor: position/width inside the register R, software access and value type
+template<typename R, unsigned Pos, unsigned Width, Access A, typename T>
+struct Field {
+    using reg_t   = R;
+    using raw_t   = typename R::raw_t;
+    using value_t = T;
+    static constexpr unsigned pos    = Pos;
+    static constexpr unsigned width  = Width;
+    static constexpr Access   access = A;
+    static constexpr raw_t mask  = Width >= 8*sizeof(raw_t) ? static_cast<raw_t>(~raw_t(0)) : static_cast<raw_t>((raw_t(1) << Width) - 1);
+    static constexpr raw_t smask = static_cast<raw_t>(mask << Pos);
+
+    /// Extract the field value from a register value
+    template<Access B = A>
+    static constexpr T get(raw_t reg) {
+        static_assert(B != Access::WO && B != Access::PULSE, "Field is write-only");
+        raw_t v = (reg >> Pos) & mask;
+        // Sign extension
+        if (std::is_signed<T>::value && Width < 8*sizeof(raw_t) && ((v >> (Width-1)) & 1)) {
+            v |= static_cast<raw_t>(~mask);
+        }
+        return static_cast<T>(v);
+    }
+
+    /// Update the field value in a register value
+    template<Access B = A>
+    static constexpr raw_t set(raw_t reg, T value) {
+        static_assert(B != Access::RO && B != Access::RC, "Field is read-only");
+        return static_cast<raw_t>((reg & ~smask) | ((static_cast<raw_t>(value) & mask) << Pos));
+    }
+};
+
+/// Register descriptor: offset relative to the base address of the register file and reset value.
+/// The register type R gives the fields preserved by set() (keep), the value written
+/// in the fields with a write side-effect (neutral) and the presence of a field cleared on read (rd_clear)
+template<typename R, Access A>
+struct Reg {
+    using reg_t = R;
+    using raw_t = typename R::raw_t;
+    static constexpr Access access = A;
+    std::uintptr_t offset;
+    raw_t reset;
+
+    /// Address of the register for a register file at the base address
+    constexpr std::uintptr_t addr(std::uintptr_t base) const {
+        return base + offset;
+    }
+
+    /// Read the full register
+    template<Access B = A>
+    raw_t read(std::uintptr_t base) const {
+        static_assert(B != Access::WO, "Register is write-only");
+        return *reinterpret_cast<volatile raw_t*>(base + offset);
+    }
+
+    /// Write the full register
+    template<Access B = A>
+    void write(std::uintptr_t base, raw_t value) const {
+        static_assert(B != Access::RO, "Register is read-only");
+        *reinterpret_cast<volatile raw_t*>(base + offset) = value;
+    }
+
+    /// Read one field
+    template<typename F>
+    typename F::value_t get(std::uintptr_t base) const {
+        static_assert(std::is_same<typename F::reg_t, R>::value, "Field does not belong to the register");
+        return F::get(read(base));
+    }
+
+    /// Write one field: other readable fields keep their value (read-modify-write)
+    /// while fields with a write side-effect are written with their neutral value
+    template<typename F>
+    void set(std::uintptr_t base, typename F::value_t value) const {
+        static_assert(std::is_same<typename F::reg_t, R>::value, "Field does not belong to the register");
+        static_assert(!R::rd_clear, "Register has a field cleared on read: use write()");
+        constexpr raw_t keep = R::keep & ~F::smask;
+        raw_t reg = static_cast<raw_t>(R::neutral & ~F::smask);
+        if (keep != 0) {
+            reg |= static_cast<raw_t>(*reinterpret_cast<volatile raw_t*>(base + offset) & keep);
+        }
+        write(base, F::set(reg, value));
+    }
+};
+
+} // namespace rif
+
+#endif /* __RIF_REG_HPP__ */
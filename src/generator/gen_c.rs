@@ -1,6 +1,6 @@
 use std::{format, fs::create_dir_all, path::PathBuf};
 
-use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::Access};
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifPageInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, FieldSwKind}};
 
 use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RifList}};
 
@@ -8,16 +8,18 @@ use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RifLi
 pub struct GeneratorC {
     base_settings: GeneratorBaseSetting,
     base_addr_name: String,
+    driver: bool,
     txt: String,
     stash: String,
 }
 
 impl GeneratorC {
 
-    pub fn new(args: GeneratorBaseSetting, base_addr_name: String) -> Self {
+    pub fn new(args: GeneratorBaseSetting, base_addr_name: String, driver: bool) -> Self {
         GeneratorC {
             base_settings: args,
             base_addr_name,
+            driver,
             txt: String::with_capacity(10000),
             stash: String::with_capacity(1000)
         }
@@ -268,6 +270,11 @@ impl GeneratorC {
                 self.push_stash(&format!("#define {pname_uc}_{reg_name}_RESET {rst:#08X}\n", rst = reg.reset));
             }
             self.push_stash("#endif /* DOXYGEN_SHOULD_SKIP_THIS */\n\n");
+
+            // Optional inline functions for each register instance
+            if self.driver {
+                self.add_driver(page, &pname, w);
+            }
         }
 
         self.pop_stash();
@@ -287,6 +294,119 @@ impl GeneratorC {
         }
     }
 
+    /// Static inline functions to access registers and fields of a page.
+    /// Field writes never change other fields with a write side-effect (clear/set/toggle/pulse)
+    /// and registers with a clear-on-read field are never read back to update a field:
+    /// when they also contain other read/write fields, no field write function is generated.
+    fn add_driver(&mut self, page: &RifPageInst, pname: &str, w: u8) {
+        let pname_uc = pname.to_uppercase();
+        let type_reg = format!("uint{w}_t");
+        let hw = (w >> 2) as usize;
+        self.push_stash(&format!("// {} access functions\n", Casing::Title.format(pname)));
+        for reg in page.regs.iter() {
+            // Array are accessed with an index argument
+            if reg.sw_access == Access::NA || reg.array.idx() > 0 {
+                continue;
+            }
+            let reg_name = reg.reg_name.to_lowercase();
+            let regname_uc = reg.reg_type.to_uppercase();
+            let (idx_arg, idx) = if reg.array.dim() > 1 {(", unsigned idx", "[idx]")} else {("", "")};
+            let func = format!("{pname}_{reg_name}");
+            let args = format!("volatile {pname}_regs_t* regs{idx_arg}");
+            let ptr = format!("regs->{reg_name}{idx}.reg{w}");
+            let field_mask = |f: &RifFieldInst| ((1_u128 << f.width) - 1) << f.lsb;
+            // Value to write in W0Clr fields to leave them unchanged
+            let neutral = reg.fields.iter()
+                .filter(|f| f.sw_kind == FieldSwKind::W0Clr)
+                .fold(0, |acc, f| acc | field_mask(f));
+            // Register can be read back to preserve the other fields value
+            let rclr_fields : Vec<String> = reg.fields.iter()
+                .filter(|f| f.sw_kind == FieldSwKind::ReadClr)
+                .map(|f| self.get_field_name(reg, f).to_lowercase())
+                .collect();
+            let can_read = reg.sw_access.is_readable() && rclr_fields.is_empty();
+            let has_side_effect = reg.fields.iter().any(|f| !matches!(f.sw_kind, FieldSwKind::ReadWrite | FieldSwKind::ReadOnly | FieldSwKind::WriteOnly));
+            let desc = reg.base_description.get_short();
+            if reg.sw_access.is_readable() {
+                self.push_stash(&format!("/// Read {reg_name}: {desc}\n"));
+                self.push_stash(&format!("static inline {type_reg} {func}_read({args}) {{\n    return {ptr};\n}}\n"));
+            }
+            if reg.sw_access.is_writable() {
+                self.push_stash(&format!("/// Write {reg_name}: {desc}\n"));
+                self.push_stash(&format!("static inline void {func}_write({args}, {type_reg} value) {{\n    {ptr} = value;\n}}\n"));
+            }
+            // Read-modify-write only on registers without any side-effect
+            if reg.sw_access == Access::RW && !has_side_effect {
+                self.push_stash(&format!("/// Update bits of {reg_name} selected by mask\n"));
+                self.push_stash(&format!("static inline void {func}_modify({args}, {type_reg} mask, {type_reg} value) {{\n"));
+                self.push_stash(&format!("    {ptr} = ({ptr} & ~mask) | (value & mask);\n}}\n"));
+            }
+            for f in reg.fields.iter() {
+                if f.is_reserved() && self.base_settings.privacy.is_public() {
+                    continue;
+                }
+                let field = self.get_field_name(reg, f).to_lowercase();
+                let macro_name = format!("{pname_uc}_{regname_uc}_{}", self.get_field_name(reg, f).replace('_', "").to_uppercase());
+                let desc = f.base_description.get_short();
+                if reg.sw_access.is_readable() && !f.sw_kind.is_wo() {
+                    if f.sw_kind == FieldSwKind::ReadClr {
+                        self.push_stash(&format!("/// Get {field} (cleared by the read): {desc}\n"));
+                    } else if !rclr_fields.is_empty() {
+                        // The whole register is read, clearing all clear-on-read fields
+                        self.push_stash(&format!("/// Get {field} (the read clears {}): {desc}\n", rclr_fields.join(", ")));
+                    } else {
+                        self.push_stash(&format!("/// Get {field}: {desc}\n"));
+                    }
+                    self.push_stash(&format!("static inline {type_reg} {func}_{field}_get({args}) {{\n"));
+                    self.push_stash(&format!("    return ({ptr} >> {macro_name}_POS) & {macro_name}_MASK;\n}}\n"));
+                }
+                if !reg.sw_access.is_writable() || matches!(f.sw_kind, FieldSwKind::ReadOnly | FieldSwKind::ReadClr) {
+                    continue;
+                }
+                // Register readable but not read back due to a clear-on-read field:
+                // a field write would clear the other read/write fields, only the full register write is available
+                if reg.sw_access.is_readable() && !can_read && reg.fields.iter().any(|x| x.sw_kind == FieldSwKind::ReadWrite && x.lsb != f.lsb) {
+                    continue;
+                }
+                let (action, comment) = match f.sw_kind {
+                    FieldSwKind::W1Clr | FieldSwKind::W0Clr => ("clear" , format!("Clear bits of {field} selected by value")),
+                    FieldSwKind::W1Set                      => ("set"   , format!("Set bits of {field} selected by value")),
+                    FieldSwKind::W1Tgl                      => ("toggle", format!("Toggle bits of {field} selected by value")),
+                    FieldSwKind::W1Pulse(_,_)               => ("pulse" , format!("Pulse bits of {field} selected by value")),
+                    _ => ("set", format!("Set {field}")),
+                };
+                // Only fields storing a value written by software are preserved
+                let keep = if can_read {
+                    reg.fields.iter()
+                        .filter(|x| x.sw_kind == FieldSwKind::ReadWrite && x.lsb != f.lsb)
+                        .fold(0, |acc, x| acc | field_mask(x))
+                } else {
+                    0
+                };
+                let mut parts = Vec::new();
+                if keep != 0 {
+                    parts.push(format!("({ptr} & 0x{keep:0hw$X})"));
+                }
+                let neutral = neutral & !field_mask(f);
+                if neutral != 0 {
+                    parts.push(format!("0x{neutral:0hw$X}"));
+                }
+                // W0Clr: bits to clear are given as 1 and written as 0
+                if f.sw_kind == FieldSwKind::W0Clr {
+                    parts.push(format!("((~value & {macro_name}_MASK) << {macro_name}_POS)"));
+                } else {
+                    parts.push(format!("((value & {macro_name}_MASK) << {macro_name}_POS)"));
+                }
+                self.push_stash(&format!("/// {comment}: {desc}\n"));
+                // Avoid repeating the action when it is already the field name (e.g. a field named set)
+                let fname = if field == action {format!("{func}_{field}")} else {format!("{func}_{field}_{action}")};
+                self.push_stash(&format!("static inline void {fname}({args}, {type_reg} value) {{\n"));
+                self.push_stash(&format!("    {ptr} = {};\n}}\n", parts.join(" | ")));
+            }
+        }
+        self.push_stash("\n");
+    }
+
     fn add_field_decl(&mut self, reg_width: u8, l:usize, name: &str, field_width: u8, desc: &str, mask: Option<usize>) {
         let mask = if let Some(v) = mask {format!("0x{v:08X} ")} else {"".to_owned()};
         self.write(&format!("    uint{reg_width}_t {name:<l$} : {field_width:>2}; //!< {mask}{desc}\n"));
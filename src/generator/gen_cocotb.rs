@@ -0,0 +1,247 @@
+use std::{fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, ClockingInfo, Interface, InterruptRegKind, LimitValue}};
+
+use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RifList}};
+
+/// Support library for the cocotb testbench helpers
+const RIF_COCOTB_LIB: &str = include_str!("py/rif_cocotb.py");
+
+pub struct GeneratorCocotb {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorCocotb {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorCocotb {
+            base_settings: args,
+            txt: String::with_capacity(10000),
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        if !matches!(obj, Comp::External(_)) {
+            self.write(RIF_COCOTB_LIB);
+            self.save("rif_cocotb.py")?;
+        }
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_module(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_module(rifmux, &rif_list)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_module(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    /// Write file header and module docstring
+    fn write_header(&mut self, title: &str, desc: &str) {
+        self.write("# File generated automatically: DO NOT EDIT.\n");
+        self.write(&format!("\"\"\"{title}\n"));
+        if !desc.trim().is_empty() {
+            self.write("\n");
+            for l in desc.trim().lines() {
+                self.write(&format!("{}\n", l.trim().replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"")));
+            }
+        }
+        self.write("\"\"\"\n\n");
+        self.write("import cocotb\n");
+        self.write("from cocotb.clock import Clock\n");
+        self.write("from cocotb.triggers import ClockCycles\n\n");
+        self.write("from rif_cocotb import ApbDriver, RifIfDriver, Field, Register, RifTb, check_reset, check_walking_ones, check_read_only\n");
+    }
+
+    /// Python module for each RIF:
+    ///  - description of all registers and fields
+    ///  - testbench helper class connected to the ports of the SV module
+    ///  - ready-made cocotb tests
+    fn gen_rif_module(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let modname = rif.name(false).to_lowercase();
+        let is_public = self.base_settings.privacy.is_public();
+        let hw = (rif.data_width / 4) as usize;
+
+        self.write_header(&format!("Cocotb testbench helper for {}", modname.to_uppercase()), rif.description.get());
+        self.write(&format!("\nADDR_WIDTH = {}\n", rif.addr_width));
+        self.write(&format!("DATA_WIDTH = {}\n\n", rif.data_width));
+
+        self.write("# Description of all registers\n");
+        self.write("REGISTERS = [\n");
+        for page in rif.pages.iter() {
+            if page.is_external() {
+                continue;
+            }
+            let prefix = if rif.pages.len() > 1 {format!("{}.", page.name.to_lowercase())} else {"".to_owned()};
+            for reg in page.regs.iter() {
+                if reg.sw_access == Access::NA || reg.is_external() || (is_public && reg.visibility.is_hidden()) {
+                    continue;
+                }
+                let access = match reg.sw_access {
+                    Access::RO => "ro",
+                    Access::WO => "wo",
+                    _ => "rw",
+                };
+                self.write(&format!("    Register(\"{prefix}{}\", 0x{:04X}, 0x{:0hw$X}, \"{access}\", [\n",
+                    reg.name().to_lowercase(), page.addr + reg.addr, reg.reset));
+                for f in reg.fields.iter() {
+                    if is_public && f.visibility.is_hidden() {
+                        continue;
+                    }
+                    let decl = self.field_decl(rif, reg, f)?;
+                    self.write(&format!("        {decl},\n"));
+                }
+                self.write("    ]),\n");
+            }
+        }
+        self.write("]\n");
+
+        self.write_tb(&modname, &rif.interface, &rif.sw_clocking, rif.data_width);
+
+        // Write file
+        self.save(&format!("{modname}_cocotb.py"))
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat()
+        } else {
+            f.name.to_owned()
+        }
+    }
+
+    /// Field declaration with flags for fields not fully controlled by software
+    fn field_decl(&self, rif: &RifInst, reg: &RifRegInst, f: &RifFieldInst) -> Result<String, String> {
+        let mut decl = format!("Field(\"{}\", {}, {}, \"{}\"",
+            self.get_field_name(reg, f).to_lowercase(), f.lsb, f.width, f.sw_kind.keyword());
+        let reset = f.reset.to_u128(f.width);
+        if reset != 0 {
+            decl.push_str(&format!(", reset=0x{reset:X}"));
+        }
+        // Pending register is a combination of status and mask
+        if f.hw_access.is_writable() || f.is_hw_write() || reg.intr_info.0 == InterruptRegKind::Pending {
+            decl.push_str(", hw=True");
+        }
+        let is_locked = !reg.is_intr_derived() && rif.get_hw_reg(&reg.group_type).get_field(&f.name)?.lock.is_some();
+        if is_locked || f.limit.value != LimitValue::None || f.is_disabled() {
+            decl.push_str(", fixed=True");
+        }
+        decl.push(')');
+        Ok(decl)
+    }
+
+    /// Testbench class, setup function and ready-made tests
+    fn write_tb(&mut self, modname: &str, intf: &Interface, clocking: &ClockingInfo, data_width: u8) {
+        let class_name = format!("{}Tb", modname.to_casing(Casing::Pascal));
+        let clk = &clocking.clk;
+        let intf_name = if intf.is_default() {"rif_if"} else {intf.name()};
+        let (rst_on, rst_off) = if clocking.rst.active_high {(1,0)} else {(0,1)};
+        let driver = match intf {
+            Interface::Default => Some(format!("RifIfDriver(dut.if_rif, dut.{clk})")),
+            Interface::Apb => Some(format!("ApbDriver(dut, dut.{clk})")),
+            _ => None,
+        };
+
+        self.write(&format!("\n\nclass {class_name}(RifTb):\n"));
+        self.write(&format!("    \"\"\"Access to registers of {} through the {intf_name} interface\"\"\"\n\n", modname.to_uppercase()));
+        self.write("    def __init__(self, dut, driver=None, base_addr=0):\n");
+        match &driver {
+            Some(driver) => {
+                self.write("        if driver is None:\n");
+                self.write(&format!("            driver = {driver}\n"));
+            }
+            None => {
+                self.write("        if driver is None:\n");
+                self.write(&format!("            raise NotImplementedError(\"No driver available for interface {intf_name}\")\n"));
+            }
+        }
+        self.write(&format!("        super().__init__(driver, REGISTERS, base_addr, {data_width})\n"));
+
+        self.write("\n\nasync def setup(dut, period_ns=10):\n");
+        self.write("    \"\"\"Start the software clock, apply reset and return the testbench helper\"\"\"\n");
+        self.write(&format!("    cocotb.start_soon(Clock(dut.{clk}, period_ns, units=\"ns\").start())\n"));
+        self.write(&format!("    dut.{}.value = {rst_on}\n", clocking.rst.name));
+        self.write(&format!("    await ClockCycles(dut.{clk}, 5)\n"));
+        self.write(&format!("    dut.{}.value = {rst_off}\n", clocking.rst.name));
+        self.write(&format!("    await ClockCycles(dut.{clk}, 2)\n"));
+        self.write(&format!("    return {class_name}(dut)\n"));
+
+        for (name, desc) in [
+            ("reset", "Check reset value of all registers"),
+            ("walking_ones", "Walking ones on all read/write fields"),
+            ("read_only", "Write on read-only registers and fields"),
+        ] {
+            self.write("\n\n@cocotb.test()\n");
+            self.write(&format!("async def test_{name}(dut):\n"));
+            self.write(&format!("    \"\"\"{desc}\"\"\"\n"));
+            self.write("    tb = await setup(dut)\n");
+            self.write(&format!("    await check_{name}(tb)\n"));
+        }
+    }
+
+    /// Python module for the rifmux: registers of all RIF instances relocated at their base address
+    fn gen_rifmux_module(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = rifmux.inst_name.to_lowercase();
+        self.write_header(&format!("Cocotb testbench helper for {}", rifname.to_uppercase()), rifmux.description.get());
+        for rif in rif_list.iter() {
+            self.write(&format!("import {}_cocotb\n", rif.name(false).to_lowercase()));
+        }
+        self.write(&format!("\nADDR_WIDTH = {}\n", rifmux.addr_width));
+        self.write(&format!("DATA_WIDTH = {}\n\n", rifmux.data_width));
+
+        self.write("# All RIF instances: (name, base address, registers)\n");
+        self.write("INSTANCES = [\n");
+        self.add_rifmux_inst(rifmux, "", 0);
+        self.write("]\n\n");
+        self.write("REGISTERS = [r.relocate(name + \".\", addr) for name, addr, regs in INSTANCES for r in regs]\n");
+
+        self.write_tb(&rifname, &rifmux.interface, &rifmux.sw_clocking, rifmux.data_width);
+
+        // Write file
+        self.save(&format!("{rifname}_cocotb.py"))
+    }
+
+    fn add_rifmux_inst(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64) {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}{}", remove_rif(comp.get_name())).to_lowercase();
+            match &comp.inst {
+                Comp::Rifmux(r) => self.add_rifmux_inst(r, &format!("{name}."), addr),
+                Comp::Rif(r) => self.write(&format!("    (\"{name}\", 0x{addr:08X}, {}_cocotb.REGISTERS),\n", r.name(false).to_lowercase())),
+                // No register description for external component
+                Comp::External(_) => {},
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use crate::{comp::comp_inst::{Comp, RifInst, RifmuxInst}, rifgen::SuffixInfo};
+use crate::{comp::comp_inst::{Comp, RifInst, RifRegInst, RifmuxInst}, rifgen::{FieldSwKind, ResetDef, SuffixInfo}};
 
 use super::casing::Casing;
 
@@ -32,6 +32,65 @@ impl<'a> RifList<'a> {
     }
 }
 
+/// Expression driving the reset of an interface bridge (asynchronous, active low)
+/// from the software reset, using `not_op` to invert an active high reset
+pub fn bridge_rst_n(rst: &ResetDef, not_op: &str) -> Result<String, String> {
+    if rst.sync {
+        return Err(format!("Synchronous reset {} is not supported by the interface bridges", rst.name));
+    }
+    if rst.active_high {
+        Ok(format!("{not_op}{}", rst.name))
+    } else {
+        Ok(rst.name.to_owned())
+    }
+}
+
+/// Software semantic of a register as bit masks
+#[derive(Default)]
+pub struct RegSwMasks {
+    pub rw: u128,
+    pub w1c: u128,
+    pub w0c: u128,
+    pub w1s: u128,
+    pub w1t: u128,
+    pub pulse: u128,
+    pub rclr: u128,
+    pub rz: u128,
+}
+
+impl RegSwMasks {
+    pub fn new(reg: &RifRegInst, data_width: u8) -> Self {
+        let mut m = RegSwMasks::default();
+        for f in reg.fields.iter() {
+            let mask = ((1_u128 << f.width) - 1) << f.lsb;
+            // Fields which are not readable always read as zero
+            if f.sw_kind.is_wo() || matches!(f.sw_kind, FieldSwKind::W1Pulse(_,_)) {
+                m.rz |= mask;
+            }
+            match f.sw_kind {
+                FieldSwKind::ReadWrite | FieldSwKind::WriteOnly => m.rw |= mask,
+                FieldSwKind::ReadClr => m.rclr |= mask,
+                FieldSwKind::W1Clr => m.w1c |= mask,
+                FieldSwKind::W0Clr => m.w0c |= mask,
+                FieldSwKind::W1Set => m.w1s |= mask,
+                FieldSwKind::W1Tgl => m.w1t |= mask,
+                // Password have no storage: the value is only forwarded to the hardware hook
+                FieldSwKind::W1Pulse(_,_) | FieldSwKind::Password(_) => m.pulse |= mask,
+                FieldSwKind::ReadOnly => {},
+            }
+        }
+        // Access at register level has precedence
+        if !reg.sw_access.is_writable() {
+            m.rw = 0; m.w1c = 0; m.w0c = 0; m.w1s = 0; m.w1t = 0; m.pulse = 0;
+        }
+        if !reg.sw_access.is_readable() {
+            m.rz = (1_u128 << data_width) - 1;
+            m.rclr = 0;
+        }
+        m
+    }
+}
+
 
 #[derive(Clone, Copy, PartialEq, Debug, Default)]
 pub enum Privacy {#[default]
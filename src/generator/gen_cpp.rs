@@ -0,0 +1,320 @@
+use std::{format, fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, EnumKind, FieldSwKind}};
+
+use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RegSwMasks, RifList}};
+
+
+/// Support header for register/field access
+const RIF_REG_LIB: &str = include_str!("cpp/rif_reg.hpp");
+
+pub struct GeneratorCpp {
+    base_settings: GeneratorBaseSetting,
+    base_addr_name: String,
+    txt: String,
+}
+
+impl GeneratorCpp {
+
+    pub fn new(args: GeneratorBaseSetting, base_addr_name: String) -> Self {
+        GeneratorCpp {
+            base_settings: args,
+            base_addr_name,
+            txt: String::with_capacity(10000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        if !matches!(obj, Comp::External(_)) {
+            self.write(RIF_REG_LIB);
+            self.save("rif_reg.hpp")?;
+        }
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_cpp_header(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_cpp_header(rifmux, &rif_list)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_cpp_header(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+
+    /// C++ header definition for each RIF, inside a namespace named after the RIF
+    ///  - one enum class per enum definition
+    ///  - one struct by register type declaring the fields type
+    ///  - one constexpr descriptor per register instance
+    fn gen_rif_cpp_header(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rif.type_name.to_lowercase();
+        let rifname_uc = rifname.to_uppercase();
+        let basename = remove_rif(rifname);
+        // Write header
+        self.write(&format!("// Register definition for {rifname_uc}\n"));
+        self.write(&format!("#ifndef __{rifname_uc}_HPP__\n"));
+        self.write(&format!("#define __{rifname_uc}_HPP__\n\n"));
+        self.write("#include \"rif_reg.hpp\"\n\n");
+        self.write(&format!("namespace {basename} {{\n\n"));
+
+        let w = rif.data_width;
+        let type_reg = format!("uint{w}_t");
+
+        // Add enum declaration
+        for def in rif.enum_defs.iter() {
+            if def.name.starts_with("doc:") {
+                continue;
+            }
+            let etn = Self::enum_name(&def.name);
+            self.write(&format!("/// {}\n", def.description));
+            self.write(&format!("enum class {etn} : uint8_t {{\n"));
+            for entry in def.iter() {
+                self.write(&format!("    {} = {}, //!< {}\n",
+                    entry.name.to_uppercase(),
+                    entry.value,
+                    entry.description.get_short()
+                ));
+            }
+            self.write("};\n\n");
+        }
+
+        for page in rif.pages.iter() {
+            if page.is_external() {
+                continue;
+            }
+            if rif.pages.len() > 1 {
+                self.write(&format!("namespace {} {{\n\n", page.name.to_lowercase()));
+            }
+
+            // Add register definition
+            // Derived interrupt registers get their own type since their fields differ from the base register
+            let mut reg_types : Vec<String> = Vec::new();
+            for reg in page.regs.iter() {
+                let reg_type = Self::reg_type_name(reg);
+                if reg_types.contains(&reg_type) {
+                    continue;
+                }
+                reg_types.push(reg_type.clone());
+                // Check if register is hidden/reserved in all instances
+                if reg.sw_access == Access::NA {
+                    continue;
+                }
+                let max_len = reg.fields.iter().map(|f| self.get_field_name(reg, f).len()).max().expect("Registers should have fields");
+                self.write(&format!("/// {} register fields\n", reg.expanded_type_name().to_casing(Casing::Title)));
+                for l in reg.base_description.get().lines() {
+                    self.write(&format!("/// {l}\n"));
+                }
+                self.write(&format!("struct {reg_type} {{\n"));
+                self.write(&format!("    using raw_t = {type_reg};\n"));
+                // Write behavior of Reg::set: fields preserved, value of the fields with a write side-effect
+                let masks = RegSwMasks::new(reg, w);
+                let digits = (w as usize).div_ceil(4);
+                self.write(&format!("    static constexpr raw_t keep     = 0x{:0digits$X}; //!< Fields kept by a read-modify-write\n", masks.rw & !masks.rz));
+                self.write(&format!("    static constexpr raw_t neutral  = 0x{:0digits$X}; //!< Value without side-effect on write\n", masks.w0c));
+                self.write(&format!("    static constexpr bool  rd_clear = {}; //!< Read clears some fields\n", masks.rclr != 0));
+                for f in reg.fields.iter() {
+                    let name = self.get_field_name(reg, f).to_casing(self.base_settings.casing);
+                    let value_type = self.field_type(rif, reg, f)?;
+                    let desc = f.base_description.get_short();
+                    self.write(&format!("    using {name:<max_len$} = ::rif::Field<{reg_type}, {:>2}, {:>2}, ::rif::Access::{}, {value_type}>; //!< {desc}\n",
+                        f.lsb,
+                        f.width,
+                        Self::field_access(f),
+                    ));
+                }
+                self.write("};\n\n");
+            }
+
+            // Add one descriptor per register instance, offset relative to the RIF base address
+            let len_name = page.regs.iter().map(|r| r.reg_name.len()).max().expect("Page should have registers");
+            let len_type = page.regs.iter().map(|r| Self::reg_type_name(r).len()).max().expect("Page should have registers");
+            self.write("// Registers\n");
+            for reg in page.regs.iter().filter(|r| r.sw_access != Access::NA) {
+                // Array are declared once with all elements
+                if reg.array.idx() > 0 {
+                    continue;
+                }
+                let reg_type = Self::reg_type_name(reg);
+                let access = Self::reg_access(reg);
+                let desc = if reg.array.dim() > 1 {reg.base_description.get_short()} else {reg.description.get_short()};
+                let mut reg_name = reg.reg_name.to_lowercase();
+                let descriptors = if reg.array.dim() > 1 {
+                    reg_name.push_str(&format!("[{}]",reg.array.dim()));
+                    let elts : Vec<String> = page.regs.iter()
+                        .filter(|r| r.reg_name == reg.reg_name)
+                        .map(|r| format!("{{0x{:04X}, 0x{:08X}}}", page.addr + r.addr, r.reset))
+                        .collect();
+                    format!("{{{}}}", elts.join(", "))
+                } else {
+                    format!("{{0x{:04X}, 0x{:08X}}}", page.addr + reg.addr, reg.reset)
+                };
+                self.write(&format!("constexpr ::rif::Reg<{reg_type:<len_type$}, ::rif::Access::{access}> {reg_name:<len_name$} = {descriptors}; //!< {desc}\n"));
+            }
+            self.write("\n");
+
+            if rif.pages.len() > 1 {
+                self.write(&format!("}} // namespace {}\n\n", page.name.to_lowercase()));
+            }
+        }
+
+        self.write(&format!("}} // namespace {basename}\n\n"));
+        self.write(&format!("#endif /* __{rifname_uc}_HPP__ */\n"));
+
+        // Write file
+        self.save(&format!("{}.hpp",rif.name(false).to_lowercase()))
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat()
+        } else {
+            f.name.to_owned()
+        }
+    }
+
+    /// Register type name, including interrupt name and suffix for derived interrupt registers
+    fn reg_type_name(reg: &RifRegInst) -> String {
+        format!("{}_reg_t", reg.expanded_type_name().to_lowercase())
+    }
+
+    /// Enum class name: remove package and e_ prefix
+    fn enum_name(name: &str) -> String {
+        let mut etn = match name.rfind("::") {
+            Some(pos) => &name[pos+2..],
+            None => name,
+        };
+        if etn.starts_with("e_") {
+            etn = &etn[2..];
+        }
+        format!("{etn}_t")
+    }
+
+    /// Type of a field value: enum class, bool or smallest integer type
+    fn field_type(&self, rif: &RifInst, reg: &RifRegInst, f: &RifFieldInst) -> Result<String, String> {
+        if let EnumKind::Type(enum_name) = &f.enum_kind {
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                if enum_name.contains(':') {enum_name.to_owned()}
+                else {format!("{pkg}_pkg::{enum_name}")}
+            } else {
+                enum_name.to_owned()
+            };
+            // Ensure enum exists
+            rif.get_enum_def(&enum_name)?;
+            return Ok(Self::enum_name(&enum_name));
+        }
+        if f.width == 1 {
+            return Ok("bool".to_owned());
+        }
+        let int_width = f.width.next_power_of_two().max(8);
+        Ok(format!("{}int{int_width}_t", if f.is_signed() {""} else {"u"}))
+    }
+
+    /// Software access of a field
+    fn field_access(f: &RifFieldInst) -> &'static str {
+        match f.sw_kind {
+            FieldSwKind::ReadOnly => "RO",
+            FieldSwKind::ReadClr => "RC",
+            FieldSwKind::ReadWrite => "RW",
+            FieldSwKind::WriteOnly | FieldSwKind::Password(_) => "WO",
+            FieldSwKind::W1Clr => "W1C",
+            FieldSwKind::W0Clr => "W0C",
+            FieldSwKind::W1Set => "W1S",
+            FieldSwKind::W1Tgl => "W1T",
+            FieldSwKind::W1Pulse(_,_) => "PULSE",
+        }
+    }
+
+    /// Software access of a register
+    fn reg_access(reg: &RifRegInst) -> &'static str {
+        match reg.sw_access {
+            Access::RO => "RO",
+            Access::WO => "WO",
+            _ => "RW",
+        }
+    }
+
+    fn gen_rifmux_cpp_header(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        let rifname_uc = rifname.to_uppercase();
+        // Write header
+        self.write("// Register File mapping\n");
+        self.write(&format!("#ifndef __{rifname_uc}_HPP__\n"));
+        self.write(&format!("#define __{rifname_uc}_HPP__\n\n"));
+
+        // Includes
+        self.write("// Includes Register File definition\n");
+        for rif in rif_list.iter() {
+            self.write(&format!("#include \"{}.hpp\"\n", rif.name(false).to_lowercase()));
+        }
+        self.write("\n");
+
+        // Mapping
+        self.write(&format!("namespace {rifname} {{\n\n"));
+        self.add_addr_rifmux(rifmux, "", 0);
+        self.write(&format!("\n}} // namespace {rifname}\n\n"));
+
+        self.write(&format!("#endif /* __{rifname_uc}_HPP__ */\n"));
+        // Write file
+        self.save(&format!("{rifname}.hpp"))
+    }
+
+    fn add_addr_rifmux(&mut self, rifmux: &RifmuxInst, top_name: &str, offset: u64) {
+        let prefix = if top_name.is_empty() {
+            "".to_owned()
+        } else {
+            format!("{}_",top_name)
+        };
+        for comp in rifmux.components.iter() {
+            let mut base_addr_name = self.base_addr_name.clone();
+            if !comp.group.is_empty() && prefix.is_empty() {
+                base_addr_name.push('_');
+                base_addr_name.push_str(&comp.group);
+            }
+            match &comp.inst {
+                Comp::Rifmux(r) => {
+                    let comp_name = format!("{prefix}{}",r.inst_name.to_lowercase());
+                    self.add_addr_rifmux(r, &comp_name, offset + comp.addr)
+                }
+                Comp::Rif(r) => {
+                    let rif_inst_name = remove_rif(&r.inst_name).to_lowercase();
+                    let name = format!("{prefix}{rif_inst_name}");
+                    let name_tt = name.to_casing(Casing::Title);
+                    let addr = comp.addr + offset;
+                    self.write(&format!("/// {name_tt} base address ({}): {}\n", remove_rif(&r.type_name).to_lowercase(), r.description.get_short()));
+                    self.write(&format!("constexpr std::uintptr_t {}_BASE_ADDR = {base_addr_name} + 0x{addr:08X};\n", name.to_uppercase()));
+                }
+                Comp::External(_) => {},
+            }
+        }
+    }
+}
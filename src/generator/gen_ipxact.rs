@@ -0,0 +1,290 @@
+use std::{fs::create_dir_all, path::PathBuf};
+
+use crate::{
+    comp::comp_inst::{Comp, RifFieldInst, RifInst, RifPageInst, RifRegInst, RifmuxInst},
+    parser::remove_rif,
+    rifgen::{Access, FieldSwKind, LimitValue, ResetVal},
+};
+
+use super::gen_common::GeneratorBaseSetting;
+
+pub struct GeneratorIpxact {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorIpxact {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorIpxact {
+            base_settings: args,
+            txt: String::with_capacity(10000),
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    /// Write a simple XML element on a single line
+    fn write_elt(&mut self, ind: usize, tag: &str, value: &str) {
+        self.write(&format!("{:ind$}<ipxact:{tag}>{value}</ipxact:{tag}>\n", ""));
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // One component per file: a rifmux is flattened into a list of address blocks
+        let (name, desc, data_width) = match obj {
+            Comp::Rifmux(rifmux) => (&rifmux.inst_name, rifmux.description.get(), rifmux.data_width),
+            Comp::Rif(rif) => (&rif.type_name, rif.description.get(), rif.data_width),
+            // Nothing to do for external RIF
+            Comp::External(_) => return Ok(()),
+        };
+        // VLNV can be provided through the info property of a RIF
+        let info = |key: &str, default: &str| {
+            if let Comp::Rif(rif) = obj {
+                rif.info.get(key).map(|s| s.to_owned()).unwrap_or(default.to_owned())
+            } else {
+                default.to_owned()
+            }
+        };
+        self.write("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        self.write("<!-- File generated automatically: DO NOT EDIT. -->\n");
+        self.write("<ipxact:component xmlns:ipxact=\"http://www.accellera.org/XMLSchema/IPXACT/1685-2014\"\n");
+        self.write("  xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\"\n");
+        self.write("  xsi:schemaLocation=\"http://www.accellera.org/XMLSchema/IPXACT/1685-2014 http://www.accellera.org/XMLSchema/IPXACT/1685-2014/index.xsd\">\n");
+        self.write_elt(2, "vendor", &info("vendor", "yarig"));
+        self.write_elt(2, "library", &info("library", "rif"));
+        self.write_elt(2, "name", name);
+        self.write_elt(2, "version", &info("version", "1.0"));
+        self.write("  <ipxact:memoryMaps>\n");
+        self.write("    <ipxact:memoryMap>\n");
+        self.write_elt(6, "name", &format!("{name}_map"));
+        match obj {
+            Comp::Rifmux(rifmux) => self.add_rifmux(rifmux, "", 0)?,
+            Comp::Rif(rif) => self.add_rif(rif, None, 0)?,
+            Comp::External(_) => {},
+        }
+        self.write_elt(6, "addressUnitBits", "8");
+        self.write("    </ipxact:memoryMap>\n");
+        self.write("  </ipxact:memoryMaps>\n");
+        self.write_elt(2, "description", &Self::sanitize(desc, name));
+        self.write("  <ipxact:parameters>\n");
+        self.write("    <ipxact:parameter parameterId=\"DATA_WIDTH\" resolve=\"immediate\">\n");
+        self.write_elt(6, "name", "DATA_WIDTH");
+        self.write_elt(6, "value", &format!("{data_width}"));
+        self.write("    </ipxact:parameter>\n");
+        self.write("  </ipxact:parameters>\n");
+        self.write("</ipxact:component>\n");
+
+        // Write file
+        self.save(&format!("{}.xml", name.to_lowercase()))
+    }
+
+    /// Add all components of a rifmux as address blocks (recursively for sub-rifmux)
+    fn add_rifmux(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64) -> Result<(), Box<dyn std::error::Error>> {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}{}", remove_rif(comp.get_name())).to_lowercase();
+            match &comp.inst {
+                Comp::Rifmux(r) => self.add_rifmux(r, &format!("{name}_"), addr)?,
+                Comp::Rif(r) => self.add_rif(r, Some(&name), addr)?,
+                Comp::External(r) => {
+                    self.write("      <ipxact:addressBlock>\n");
+                    self.write_elt(8, "name", &name);
+                    self.write_elt(8, "description", &Self::sanitize(r.description.get(), &r.inst_name));
+                    self.write_elt(8, "baseAddress", &format!("'h{addr:X}"));
+                    self.write_elt(8, "range", &format!("'h{:X}", 1_u64 << r.addr_width));
+                    self.write_elt(8, "width", &format!("{}", rifmux.data_width));
+                    self.write_elt(8, "usage", "memory");
+                    self.write("      </ipxact:addressBlock>\n");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add one address block per page of a RIF
+    /// Block name is the instance name, suffixed by the page name when there is more than one
+    fn add_rif(&mut self, rif: &RifInst, inst_name: Option<&str>, addr: u64) -> Result<(), Box<dyn std::error::Error>> {
+        for page in rif.pages.iter() {
+            let name = match inst_name {
+                Some(n) if rif.pages.len() > 1 => format!("{n}_{}", page.name.to_lowercase()),
+                Some(n) => n.to_owned(),
+                None => page.name.to_lowercase(),
+            };
+            // Page range: up to the next page or the end of the RIF
+            let end = rif.pages.iter()
+                .filter(|p| p.addr > page.addr)
+                .map(|p| p.addr)
+                .min()
+                .unwrap_or(1_u64 << rif.addr_width);
+            // Use RIF description for single page instance
+            let desc = if inst_name.is_some() && rif.pages.len() == 1 {rif.description.get()} else {page.description.get()};
+            self.add_address_block(rif, page, &name, desc, addr + page.addr, end - page.addr)?;
+        }
+        Ok(())
+    }
+
+    fn add_address_block(&mut self, rif: &RifInst, page: &RifPageInst, name: &str, desc: &str, addr: u64, range: u64) -> Result<(), Box<dyn std::error::Error>> {
+        self.write("      <ipxact:addressBlock>\n");
+        self.write_elt(8, "name", name);
+        self.write_elt(8, "description", &Self::sanitize(desc, name));
+        self.write_elt(8, "baseAddress", &format!("'h{addr:X}"));
+        self.write_elt(8, "range", &format!("'h{range:X}"));
+        self.write_elt(8, "width", &format!("{}", rif.data_width));
+        // External page are seen as memory
+        if page.is_external() {
+            self.write_elt(8, "usage", "memory");
+            self.write("      </ipxact:addressBlock>\n");
+            return Ok(());
+        }
+        self.write_elt(8, "usage", "register");
+        let is_public = self.base_settings.privacy.is_public();
+        for reg in page.regs.iter() {
+            if reg.sw_access == Access::NA || (is_public && reg.visibility.is_hidden()) {
+                continue;
+            }
+            self.add_register(rif, reg)?;
+        }
+        self.write("      </ipxact:addressBlock>\n");
+        Ok(())
+    }
+
+    fn add_register(&mut self, rif: &RifInst, reg: &RifRegInst) -> Result<(), Box<dyn std::error::Error>> {
+        let name = reg.name().to_lowercase();
+        self.write("        <ipxact:register>\n");
+        self.write_elt(10, "name", &name);
+        self.write_elt(10, "description", &Self::sanitize(reg.description.get(), &name));
+        self.write_elt(10, "addressOffset", &format!("'h{:X}", reg.addr));
+        self.write_elt(10, "size", &format!("{}", rif.data_width));
+        self.write_elt(10, "access", Self::access_str(&reg.sw_access));
+        let is_public = self.base_settings.privacy.is_public();
+        for field in reg.fields.iter() {
+            if is_public && (field.visibility.is_hidden() || field.is_reserved()) {
+                continue;
+            }
+            self.add_field(rif, reg, field)?;
+        }
+        self.write("        </ipxact:register>\n");
+        Ok(())
+    }
+
+    fn add_field(&mut self, rif: &RifInst, reg: &RifRegInst, field: &RifFieldInst) -> Result<(), Box<dyn std::error::Error>> {
+        let name = field.name_flat().to_lowercase();
+        self.write("          <ipxact:field>\n");
+        self.write_elt(12, "name", &name);
+        self.write_elt(12, "description", &Self::sanitize(field.description.get(), &name));
+        self.write_elt(12, "bitOffset", &format!("{}", field.lsb));
+        self.write("            <ipxact:resets>\n");
+        self.write("              <ipxact:reset>\n");
+        self.write_elt(16, "value", &format!("'h{:X}", field.reset.to_u128(field.width)));
+        self.write("              </ipxact:reset>\n");
+        self.write("            </ipxact:resets>\n");
+        self.write_elt(12, "bitWidth", &format!("{}", field.width));
+        // Field can change without software access when written by hardware
+        let volatile = field.is_hw_write() || field.hw_access.is_writable();
+        self.write_elt(12, "volatile", if volatile {"true"} else {"false"});
+        // Access type with optional write modifier and read side-effect
+        let (access, write_mod, read_action) = match field.sw_kind {
+            FieldSwKind::ReadWrite    => ("read-write", None, None),
+            FieldSwKind::ReadOnly     => ("read-only" , None, None),
+            FieldSwKind::WriteOnly    => ("write-only", None, None),
+            FieldSwKind::ReadClr      => ("read-only" , None, Some("clear")),
+            FieldSwKind::W1Clr        => ("read-write", Some("oneToClear"), None),
+            FieldSwKind::W0Clr        => ("read-write", Some("zeroToClear"), None),
+            FieldSwKind::W1Set        => ("read-write", Some("oneToSet"), None),
+            FieldSwKind::W1Tgl        => ("read-write", Some("oneToToggle"), None),
+            // Pulse is only high for one cycle after the write: always read as 0
+            FieldSwKind::W1Pulse(_,_) => ("write-only", None, None),
+            FieldSwKind::Password(_)  => ("write-only", None, None),
+        };
+        self.write_elt(12, "access", access);
+        // Enumerated values
+        if let Some(enum_name) = field.enum_kind.name() {
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                if enum_name.contains(':') {enum_name.to_owned()}
+                else {format!("{pkg}_pkg::{enum_name}")}
+            } else {
+                enum_name.to_owned()
+            };
+            let enum_def = rif.get_enum_def(&enum_name)?;
+            self.write("            <ipxact:enumeratedValues>\n");
+            for e in enum_def.iter() {
+                self.write("              <ipxact:enumeratedValue>\n");
+                self.write_elt(16, "name", &e.name);
+                self.write_elt(16, "description", &Self::sanitize(e.description.get(), &e.name));
+                self.write_elt(16, "value", &format!("'h{:X}", e.value));
+                self.write("              </ipxact:enumeratedValue>\n");
+            }
+            self.write("            </ipxact:enumeratedValues>\n");
+        }
+        if let Some(v) = write_mod {
+            self.write_elt(12, "modifiedWriteValue", v);
+        }
+        // Write constraint: only unsigned range or enum can be described
+        let max_val = (1_u128 << field.width) - 1;
+        let range = match &field.limit.value {
+            LimitValue::Min(min) => Some((min, None)),
+            LimitValue::Max(max) => Some((&ResetVal::Unsigned(0), Some(max))),
+            LimitValue::MinMax(min, max) => Some((min, Some(max))),
+            _ => None,
+        };
+        if let Some((min, max)) = range.filter(|_| !field.is_signed()) {
+            self.write("            <ipxact:writeValueConstraint>\n");
+            self.write_elt(14, "minimum", &format!("{}", min.to_u128(field.width)));
+            self.write_elt(14, "maximum", &format!("{}", max.map(|v| v.to_u128(field.width)).unwrap_or(max_val)));
+            self.write("            </ipxact:writeValueConstraint>\n");
+        } else if field.limit.value == LimitValue::Enum {
+            self.write("            <ipxact:writeValueConstraint>\n");
+            self.write_elt(14, "useEnumeratedValues", "true");
+            self.write("            </ipxact:writeValueConstraint>\n");
+        }
+        if let Some(v) = read_action {
+            self.write_elt(12, "readAction", v);
+        }
+        if field.is_reserved() {
+            self.write_elt(12, "reserved", "true");
+        }
+        self.write("          </ipxact:field>\n");
+        Ok(())
+    }
+
+    fn access_str(access: &Access) -> &'static str {
+        match access {
+            Access::RW => "read-write",
+            Access::RO => "read-only",
+            Access::WO => "write-only",
+            Access::NA => "read-only",
+        }
+    }
+
+    /// Sanitize description for XML: escape special characters and join lines
+    /// Use the default value when the description is empty
+    fn sanitize(desc: &str, default: &str) -> String {
+        let desc = desc.trim();
+        let desc = if desc.is_empty() {default} else {desc};
+        desc.lines()
+            .map(|l| l.trim())
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+}
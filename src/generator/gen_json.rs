@@ -0,0 +1,486 @@
+use std::{collections::BTreeMap, fs::create_dir_all, path::PathBuf};
+
+use crate::{
+    comp::comp_inst::{ArrayIdx, Comp, CompInst, RifFieldInst, RifInst, RifRegInst, RifmuxInst},
+    rifgen::{
+        CounterKind, EnumDef, FieldHwKind, InterruptClr, InterruptRegKind, InterruptTrigger, LimitValue, ResetVal, Visibility
+    },
+};
+
+use super::gen_common::{GeneratorBaseSetting, RifList};
+
+/// Minimal JSON value used to build the output tree
+enum Json {
+    Obj(Vec<(&'static str, Json)>),
+    Arr(Vec<Json>),
+    /// Dictionary of strings (info properties)
+    Map(BTreeMap<String,String>),
+    Str(String),
+    Num(String),
+    Bool(bool),
+    Null,
+}
+
+impl Json {
+
+    fn str(s: &str) -> Self {
+        Json::Str(s.to_owned())
+    }
+
+    fn num<T: ToString>(v: T) -> Self {
+        Json::Num(v.to_string())
+    }
+
+    /// Value or address: always written as an hexadecimal string since it can
+    /// be wider than the 53 bits represented exactly by most JSON parsers
+    fn hex<T: Into<u128>>(v: T) -> Self {
+        Json::Str(format!("{:#x}", v.into()))
+    }
+
+    /// Signed value: hexadecimal string of the magnitude with a minus sign when negative
+    fn hex_signed(v: i128) -> Self {
+        Json::Str(format!("{}{:#x}", if v < 0 {"-"} else {""}, v.unsigned_abs()))
+    }
+
+    fn dict(d: &BTreeMap<String,String>) -> Self {
+        Json::Map(d.clone())
+    }
+
+    fn render(&self, ind: usize, out: &mut String) {
+        match self {
+            Json::Obj(items) if items.is_empty() => out.push_str("{}"),
+            Json::Obj(items) => {
+                out.push_str("{\n");
+                for (i, (k, v)) in items.iter().enumerate() {
+                    out.push_str(&format!("{:w$}\"{k}\": ", "", w = ind + 2));
+                    v.render(ind + 2, out);
+                    out.push_str(if i + 1 < items.len() {",\n"} else {"\n"});
+                }
+                out.push_str(&format!("{:ind$}}}", ""));
+            }
+            Json::Map(items) if items.is_empty() => out.push_str("{}"),
+            Json::Map(items) => {
+                out.push_str("{\n");
+                for (i, (k, v)) in items.iter().enumerate() {
+                    out.push_str(&format!("{:w$}", "", w = ind + 2));
+                    Json::str(k).render(ind + 2, out);
+                    out.push_str(": ");
+                    Json::str(v).render(ind + 2, out);
+                    out.push_str(if i + 1 < items.len() {",\n"} else {"\n"});
+                }
+                out.push_str(&format!("{:ind$}}}", ""));
+            }
+            Json::Arr(items) if items.is_empty() => out.push_str("[]"),
+            Json::Arr(items) => {
+                out.push_str("[\n");
+                for (i, v) in items.iter().enumerate() {
+                    out.push_str(&format!("{:w$}", "", w = ind + 2));
+                    v.render(ind + 2, out);
+                    out.push_str(if i + 1 < items.len() {",\n"} else {"\n"});
+                }
+                out.push_str(&format!("{:ind$}]", ""));
+            }
+            Json::Str(s) => {
+                out.push('"');
+                for c in s.chars() {
+                    match c {
+                        '"' => out.push_str("\\\""),
+                        '\\' => out.push_str("\\\\"),
+                        '\n' => out.push_str("\\n"),
+                        '\r' => out.push_str("\\r"),
+                        '\t' => out.push_str("\\t"),
+                        c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => out.push(c),
+                    }
+                }
+                out.push('"');
+            }
+            Json::Num(v) => out.push_str(v),
+            Json::Bool(v) => out.push_str(if *v {"true"} else {"false"}),
+            Json::Null => out.push_str("null"),
+        }
+    }
+}
+
+/// JSON dump of the elaborated component tree.
+/// Addresses, offsets, reset and limit values are always hexadecimal strings
+/// (e.g. "0x1f", or "-0x3" for a negative signed value), whatever their width.
+pub struct GeneratorJson {
+    base_settings: GeneratorBaseSetting,
+    /// Flat mode: one file per RIF type with the list of registers
+    flat: bool,
+    txt: String,
+}
+
+impl GeneratorJson {
+
+    pub fn new(args: GeneratorBaseSetting, flat: bool) -> Self {
+        GeneratorJson {
+            base_settings: args,
+            flat,
+            txt: String::with_capacity(10000),
+        }
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    fn write_json(&mut self, obj: &Json, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        obj.render(0, &mut self.txt);
+        self.txt.push('\n');
+        self.save(filename)
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        match obj {
+            Comp::Rifmux(rifmux) => {
+                if self.flat {
+                    for rif in RifList::new(rifmux).iter() {
+                        let j = self.rif_flat(rif)?;
+                        self.write_json(&j, &format!("{}.json", rif.name(false).to_lowercase()))?;
+                    }
+                } else {
+                    let j = self.rifmux_hier(rifmux, 0)?;
+                    self.write_json(&j, &format!("{}.json", rifmux.inst_name))?;
+                }
+            }
+            Comp::Rif(rif) => {
+                let j = if self.flat {self.rif_flat(rif)?} else {self.rif_hier(rif, 0)?};
+                self.write_json(&j, &format!("{}.json", rif.name(false).to_lowercase()))?;
+            }
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    //-----------------------------------------------------------------------------
+    // Hierarchical view: the complete component tree with absolute addresses
+    //-----------------------------------------------------------------------------
+
+    fn rifmux_hier(&self, rifmux: &RifmuxInst, base: u64) -> Result<Json, String> {
+        let groups = rifmux.groups.iter()
+            .map(|g| Json::Obj(vec![
+                ("name", Json::str(&g.name)),
+                ("addr", Json::hex(base + g.addr)),
+                ("description", Json::str(g.description.get())),
+            ]))
+            .collect();
+        let mut components = Vec::with_capacity(rifmux.components.len());
+        for comp in rifmux.components.iter() {
+            components.push(self.comp_hier(comp, base + comp.full_addr(&rifmux.groups))?);
+        }
+        Ok(Json::Obj(vec![
+            ("kind", Json::str("rifmux")),
+            ("name", Json::str(&rifmux.inst_name)),
+            ("type", Json::str(&rifmux.type_name)),
+            ("addr", Json::hex(base)),
+            ("addrWidth", Json::num(rifmux.addr_width)),
+            ("dataWidth", Json::num(rifmux.data_width)),
+            ("interface", Json::str(rifmux.interface.name())),
+            ("description", Json::str(rifmux.description.get())),
+            ("groups", Json::Arr(groups)),
+            ("components", Json::Arr(components)),
+        ]))
+    }
+
+    fn comp_hier(&self, comp: &CompInst, addr: u64) -> Result<Json, String> {
+        let mut j = match &comp.inst {
+            Comp::Rifmux(r) => self.rifmux_hier(r, addr)?,
+            Comp::Rif(r) => self.rif_hier(r, addr)?,
+            Comp::External(r) => Json::Obj(vec![
+                ("kind", Json::str("external")),
+                ("name", Json::str(&r.inst_name)),
+                ("addr", Json::hex(addr)),
+                ("addrWidth", Json::num(r.addr_width)),
+                ("description", Json::str(r.description.get())),
+            ]),
+        };
+        // Add the group name after the address
+        if let Json::Obj(items) = &mut j {
+            let group = if comp.group.is_empty() {Json::Null} else {Json::str(&comp.group)};
+            items.insert(4, ("group", group));
+        }
+        Ok(j)
+    }
+
+    fn rif_hier(&self, rif: &RifInst, base: u64) -> Result<Json, String> {
+        let mut pages = Vec::with_capacity(rif.pages.len());
+        for page in rif.pages.iter() {
+            let mut regs = Vec::with_capacity(page.regs.len());
+            for reg in page.regs.iter().filter(|r| self.is_visible(r.visibility)) {
+                regs.push(self.reg(rif, reg, page.addr, Some(base))?);
+            }
+            pages.push(Json::Obj(vec![
+                ("name", Json::str(&page.name)),
+                ("addr", Json::hex(base + page.addr)),
+                ("offset", Json::hex(page.addr)),
+                ("external", page.external.map(Json::num).unwrap_or(Json::Null)),
+                ("description", Json::str(page.description.get())),
+                ("registers", Json::Arr(regs)),
+            ]));
+        }
+        let mut j = self.rif_base(rif);
+        if let Json::Obj(items) = &mut j {
+            items.insert(3, ("addr", Json::hex(base)));
+            items.push(("pages", Json::Arr(pages)));
+        }
+        Ok(j)
+    }
+
+    //-----------------------------------------------------------------------------
+    // Flat view: all registers of a RIF type with their offset
+    //-----------------------------------------------------------------------------
+
+    fn rif_flat(&self, rif: &RifInst) -> Result<Json, String> {
+        let mut regs = Vec::new();
+        for page in rif.pages.iter() {
+            for reg in page.regs.iter().filter(|r| self.is_visible(r.visibility)) {
+                let mut j = self.reg(rif, reg, page.addr, None)?;
+                if let Json::Obj(items) = &mut j {
+                    items.insert(1, ("page", Json::str(&page.name)));
+                }
+                regs.push(j);
+            }
+        }
+        let mut j = self.rif_base(rif);
+        if let Json::Obj(items) = &mut j {
+            items.push(("registers", Json::Arr(regs)));
+        }
+        Ok(j)
+    }
+
+    //-----------------------------------------------------------------------------
+    // Common elements
+    //-----------------------------------------------------------------------------
+
+    /// RIF properties shared by both view: enums, interrupts, parameters and info
+    fn rif_base(&self, rif: &RifInst) -> Json {
+        let enums = rif.enum_defs.iter().map(Self::enum_def).collect();
+        let mut interrupts = Vec::new();
+        for (inst_name, hw_reg) in rif.hw_regs.items().filter(|(_,r)| !r.intr_derived) {
+            let reg_impl = rif.get_hw_reg(&hw_reg.group);
+            // First interrupt uses the register name, other are suffixed by their name
+            for (i, info) in reg_impl.interrupt.iter().enumerate() {
+                let name = if i==0 {inst_name.to_owned()} else {format!("{inst_name}_{}", info.name)};
+                interrupts.push(Json::Obj(vec![
+                    ("name", Json::str(&name)),
+                    ("register", Json::str(inst_name)),
+                    ("irq", Json::str(&format!("rif_{name}_irq"))),
+                    ("trigger", Json::str(Self::trigger_str(&info.trigger))),
+                    ("clear", Json::str(match info.clear {
+                        InterruptClr::Read => "rclr",
+                        InterruptClr::Write0 => "w0clr",
+                        InterruptClr::Write1 => "w1clr",
+                        InterruptClr::Hw => "hwclr",
+                    })),
+                    ("enable", info.enable.as_ref().map(|v| Self::val(v, rif.data_width)).unwrap_or(Json::Null)),
+                    ("mask", info.mask.as_ref().map(|v| Self::val(v, rif.data_width)).unwrap_or(Json::Null)),
+                    ("pending", Json::Bool(info.pending)),
+                ]));
+            }
+        }
+        let params = rif.params.items()
+            .map(|(k,v)| Json::Obj(vec![("name", Json::str(k)), ("value", Json::num(v))]))
+            .collect();
+        Json::Obj(vec![
+            ("kind", Json::str("rif")),
+            ("name", Json::str(&rif.inst_name)),
+            ("type", Json::str(&rif.name(false))),
+            ("addrWidth", Json::num(rif.addr_width)),
+            ("dataWidth", Json::num(rif.data_width)),
+            ("interface", Json::str(rif.interface.name())),
+            ("description", Json::str(rif.description.get())),
+            ("info", Json::dict(&rif.info)),
+            ("parameters", Json::Arr(params)),
+            ("enums", Json::Arr(enums)),
+            ("interrupts", Json::Arr(interrupts)),
+        ])
+    }
+
+    fn enum_def(def: &EnumDef) -> Json {
+        let values = def.iter()
+            .map(|e| Json::Obj(vec![
+                ("name", Json::str(&e.name)),
+                ("value", Json::num(e.value)),
+                ("description", Json::str(e.description.get())),
+            ]))
+            .collect();
+        Json::Obj(vec![
+            ("name", Json::str(&def.name)),
+            ("description", Json::str(&def.description)),
+            ("values", Json::Arr(values)),
+        ])
+    }
+
+    /// Register instance: base is the RIF absolute address (None in the flat view)
+    fn reg(&self, rif: &RifInst, reg: &RifRegInst, page_addr: u64, base: Option<u64>) -> Result<Json, String> {
+        let offset = page_addr + reg.addr;
+        let mut items = vec![
+            ("name", Json::str(&reg.name())),
+            ("type", Json::str(&reg.reg_type)),
+            ("group", Json::str(&reg.group_name)),
+        ];
+        if let Some(base) = base {
+            items.push(("addr", Json::hex(base + offset)));
+        }
+        items.push(("offset", Json::hex(offset)));
+        items.push(("reset", Json::hex(reg.reset)));
+        items.push(("swAccess", Json::str(&reg.sw_access.to_string())));
+        items.push(("hwAccess", Json::str(&reg.hw_access.to_string())));
+        items.push(("array", Self::array(&reg.array)));
+        items.push(("external", Json::Bool(reg.is_external())));
+        items.push(("interrupt", match reg.intr_info.0 {
+            InterruptRegKind::None => Json::Null,
+            InterruptRegKind::Base => Json::str("status"),
+            InterruptRegKind::Enable => Json::str("enable"),
+            InterruptRegKind::Mask => Json::str("mask"),
+            InterruptRegKind::Pending => Json::str("pending"),
+        }));
+        items.push(("visibility", Self::visibility(reg.visibility)));
+        items.push(("description", Json::str(reg.description.get())));
+        items.push(("info", Json::dict(&reg.info)));
+        let mut fields = Vec::with_capacity(reg.fields.len());
+        for field in reg.fields.iter().filter(|f| self.is_visible(f.visibility)) {
+            fields.push(self.field(rif, reg, field)?);
+        }
+        items.push(("fields", Json::Arr(fields)));
+        Ok(Json::Obj(items))
+    }
+
+    fn field(&self, rif: &RifInst, reg: &RifRegInst, field: &RifFieldInst) -> Result<Json, String> {
+        // Replace reserved field name in public mode
+        let name = if field.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}", field.lsb)
+        } else {
+            field.name_flat()
+        };
+        let reset = match field.reset {
+            ResetVal::Signed(v) => Json::hex_signed(v),
+            _ => Json::hex(field.reset.to_u128(field.width)),
+        };
+        // Enum: resolve the full name to include package prefix
+        let enum_name = if let Some(enum_name) = field.enum_kind.name() {
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                if enum_name.contains(':') {enum_name.to_owned()}
+                else {format!("{pkg}_pkg::{enum_name}")}
+            } else {
+                enum_name.to_owned()
+            };
+            // Ensure enum exists
+            rif.get_enum_def(&enum_name)?;
+            Json::str(&enum_name)
+        } else {
+            Json::Null
+        };
+        let limit = match &field.limit.value {
+            LimitValue::None => Json::Null,
+            LimitValue::Min(v) => Json::Obj(vec![("min", Self::val(v, field.width))]),
+            LimitValue::Max(v) => Json::Obj(vec![("max", Self::val(v, field.width))]),
+            LimitValue::MinMax(min, max) => Json::Obj(vec![("min", Self::val(min, field.width)), ("max", Self::val(max, field.width))]),
+            LimitValue::List(l) => Json::Obj(vec![("values", Json::Arr(l.iter().map(|v| Self::val(v, field.width)).collect()))]),
+            LimitValue::Enum => Json::Obj(vec![("enum", Json::Bool(true))]),
+        };
+        let hw_kind = field.hw_kind.iter().map(Self::hw_kind).collect();
+        let partial = if let Some(pos) = field.partial.0 {Json::num(pos)} else {Json::Null};
+        Ok(Json::Obj(vec![
+            ("name", Json::str(&name)),
+            ("lsb", Json::num(field.lsb)),
+            ("msb", Json::num(field.msb())),
+            ("width", Json::num(field.width)),
+            ("reset", reset),
+            ("signed", Json::Bool(field.is_signed())),
+            ("swKind", Json::str(field.sw_kind.keyword())),
+            ("hwKind", Json::Arr(hw_kind)),
+            ("array", Self::array(&field.array)),
+            ("partial", partial),
+            ("enum", enum_name),
+            ("limit", limit),
+            ("visibility", Self::visibility(field.visibility)),
+            ("description", Json::str(field.description.get())),
+            ("info", Json::dict(&field.info)),
+        ]))
+    }
+
+    fn val(v: &ResetVal, width: u8) -> Json {
+        match v {
+            ResetVal::Signed(v) => Json::hex_signed(*v),
+            _ => Json::hex(v.to_u128(width)),
+        }
+    }
+
+    fn array(array: &ArrayIdx) -> Json {
+        if array.dim() == 0 {
+            Json::Null
+        } else {
+            Json::Obj(vec![("idx", Json::num(array.idx())), ("dim", Json::num(array.dim()))])
+        }
+    }
+
+    fn visibility(v: Visibility) -> Json {
+        Json::str(match v {
+            Visibility::Full => "full",
+            Visibility::Hidden => "hidden",
+            Visibility::Reserved => "reserved",
+            Visibility::Disabled => "disabled",
+        })
+    }
+
+    /// Hidden elements are removed in public mode
+    fn is_visible(&self, v: Visibility) -> bool {
+        !(v.is_hidden() && self.base_settings.privacy.is_public())
+    }
+
+    fn trigger_str(trigger: &InterruptTrigger) -> &'static str {
+        match trigger {
+            InterruptTrigger::High => "high",
+            InterruptTrigger::Low => "low",
+            InterruptTrigger::Rising => "rising",
+            InterruptTrigger::Falling => "falling",
+            InterruptTrigger::Edge => "edge",
+        }
+    }
+
+    /// Hardware kind using the RIF keyword with optional signal name
+    fn hw_kind(kind: &FieldHwKind) -> Json {
+        let signal = |s: &Option<String>| s.as_ref().map(|s| Json::str(s)).unwrap_or(Json::Null);
+        match kind {
+            FieldHwKind::ReadOnly => Json::Obj(vec![("kind", Json::str("ro"))]),
+            FieldHwKind::Set(s) => Json::Obj(vec![("kind", Json::str("hwset")), ("signal", signal(s))]),
+            FieldHwKind::Toggle(s) => Json::Obj(vec![("kind", Json::str("hwtgl")), ("signal", signal(s))]),
+            FieldHwKind::Clear(s) => Json::Obj(vec![("kind", Json::str("hwclr")), ("signal", signal(s))]),
+            FieldHwKind::WriteEn(s) => Json::Obj(vec![("kind", Json::str("we")), ("signal", signal(s))]),
+            FieldHwKind::WriteEnL(s) => Json::Obj(vec![("kind", Json::str("wel")), ("signal", signal(s))]),
+            FieldHwKind::Counter(info) => Json::Obj(vec![
+                ("kind", Json::str("counter")),
+                ("dir", Json::str(match info.kind {
+                    CounterKind::Up => "up",
+                    CounterKind::Down => "down",
+                    CounterKind::UpDown => "updown",
+                })),
+                ("incrVal", Json::num(info.incr_val)),
+                ("decrVal", Json::num(info.decr_val)),
+                ("sat", Json::Bool(info.sat)),
+                ("clr", Json::Bool(info.clr)),
+                ("event", Json::Bool(info.event)),
+            ]),
+            FieldHwKind::Interrupt(trigger) => Json::Obj(vec![
+                ("kind", Json::str("interrupt")),
+                ("trigger", Json::str(Self::trigger_str(trigger))),
+            ]),
+        }
+    }
+
+}
@@ -0,0 +1,355 @@
+use std::{format, fs::create_dir_all, path::PathBuf};
+
+use crate::{
+    comp::comp_inst::{Comp, CompInst, RifFieldInst, RifInst, RifRegInst, RifmuxGroupInst},
+    parser::remove_rif, rifgen::FieldSwKind,
+};
+
+use super::{casing::ToCasing, gen_common::{GeneratorBaseSetting, RifList}};
+use super::casing::Casing;
+
+pub struct GeneratorLatex {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorLatex {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorLatex {
+            base_settings: args,
+            txt: String::with_capacity(10000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        Ok(())
+    }
+
+    //-----------------------------
+
+    /// Generate a LaTeX file to be included in a document (no preamble):
+    /// one chapter for the rifmux mapping and one chapter per RIF type
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        self.write("% File generated automatically: DO NOT EDIT.\n");
+        self.write("% Requires packages longtable, bytefield, graphicx and hyperref\n\n");
+
+        let top_name;
+        match obj {
+            Comp::Rifmux(r) => {
+                top_name = &r.inst_name;
+                self.write(&format!("\\chapter{{{}}}\\label{{rif:{}}}\n", escape(&Casing::Title.format(&r.inst_name)), r.inst_name));
+                if !r.description.is_empty() {
+                    self.write(&format!("{}\n\n", sanitize(r.description.get())));
+                }
+                let w = ((r.addr_width+3) >> 2) as usize;
+                self.write("\\begin{longtable}{l l l p{0.45\\linewidth}}\n");
+                self.write("\\caption{Summary}\\\\\n\\hline\n");
+                self.write("\\textbf{Offset} & \\textbf{Type Name} & \\textbf{Instance Name} & \\textbf{Description} \\\\\n\\hline\n\\endhead\n");
+                for c in r.components.iter() {
+                    self.add_rifmux_entry(c, w, 0, None, &r.groups);
+                }
+                self.write("\\hline\n\\end{longtable}\n\n");
+                // Add RIF definition for all RIF type used
+                let rif_list = RifList::new(r);
+                for rif in rif_list.iter() {
+                    self.add_rif(rif)?;
+                }
+            },
+            Comp::Rif(r) => {
+                top_name = &r.type_name;
+                self.add_rif(r)?;
+            },
+            // Nothing todo for external RIF
+            Comp::External(_) => return Ok(()),
+        }
+
+        // Write file
+        self.save(&format!("{top_name}.tex"))
+    }
+
+    // Add row in the rifmux summary table: Address, Type name, Instance name and short description
+    fn add_rifmux_entry (&mut self, comp: &CompInst, w: usize, offset: u64, top_name: Option<&str>, groups: &[RifmuxGroupInst]) {
+        let rif_name = remove_rif(comp.inst.get_name()).to_casing(self.base_settings.casing);
+        let instname = if let Some(top) = top_name {format!("{}.{}", top, rif_name)} else {rif_name};
+        let addr = comp.full_addr(groups) + offset;
+        match &comp.inst {
+            Comp::Rifmux(c) => {
+                for sub in c.components.iter() {
+                    self.add_rifmux_entry(sub, w, addr, Some(&instname), &c.groups);
+                }
+            },
+            Comp::Rif(c) => {
+                let tn = remove_rif(&c.type_name);
+                self.write(&format!("0x{addr:0w$X} & \\hyperref[rif:{tn}]{{{}}} & {} & {} \\\\\n",
+                    escape(tn), escape(&instname), escape(c.description.get_short())));
+            }
+            Comp::External(c) => {
+                self.write(&format!("0x{addr:0w$X} & Memory & {} & {} \\\\\n",
+                    escape(&instname), escape(c.description.get_short())));
+            }
+        }
+    }
+
+    /// Chapter describing a RIF: register summary followed by the register details
+    fn add_rif(&mut self, rif: &RifInst) -> Result<(),String> {
+        let rif_name = remove_rif(&rif.type_name);
+        let desc = rif.base_description.get_split();
+        let title = if desc.0.is_empty() {Casing::Title.format(rif_name)} else {desc.0.to_owned()};
+        self.write(&format!("\\chapter{{{}}}\\label{{rif:{rif_name}}}\n", escape(&title)));
+        if let Some(desc_detail) = desc.1 {
+            self.write(&format!("{}\n\n", sanitize(desc_detail)));
+        }
+        self.add_reg_summary(rif);
+        self.add_reg_detail(rif)
+    }
+
+    /// Register must be documented
+    fn is_visible(&self, reg: &RifRegInst) -> bool {
+        !(self.base_settings.privacy.is_public() && reg.visibility.is_hidden())
+    }
+
+    fn add_reg_summary(&mut self, rif: &RifInst) {
+        let rif_name = remove_rif(&rif.type_name);
+        let addr_w = ((rif.addr_width+3)>>2) as usize;
+        let data_w = ((rif.data_width+3)>>2) as usize;
+        self.write("\\section{Register Summary}\n");
+        for page in rif.pages.iter() {
+            self.write("\\begin{longtable}{l l l p{0.4\\linewidth} l}\n");
+            self.write(&format!("\\caption{{{} Summary}}\\label{{regSummary:{rif_name}:{}}}\\\\\n\\hline\n", escape(&page.name), page.name));
+            self.write("\\textbf{Offset} & \\textbf{Instance Name} & \\textbf{Reset} & \\textbf{Description} & \\textbf{Register Type} \\\\\n\\hline\n\\endhead\n");
+            for reg in page.regs.iter() {
+                if !self.is_visible(reg) {
+                    continue;
+                }
+                let reg_type = reg.expanded_type_name().to_casing(self.base_settings.casing);
+                self.write(&format!("0x{:0addr_w$X} & {} & 0x{:0data_w$X} & {} & \\hyperref[reg:{rif_name}:{reg_type}]{{{}}} \\\\\n",
+                    page.addr + reg.addr,
+                    escape(&self.get_reg_name(reg)),
+                    reg.reset,
+                    escape(reg.description.get_short()),
+                    escape(&reg_type),
+                ));
+            }
+            self.write("\\hline\n\\end{longtable}\n\n");
+        }
+    }
+
+    /// Detailed description of registers: one section per register type in compact mode,
+    /// one section per register instance otherwise
+    fn add_reg_detail(&mut self, rif: &RifInst)  -> Result<(),String> {
+        let rifname = remove_rif(&rif.type_name);
+        let addr_w = ((rif.addr_width+3)>>2) as usize;
+        let data_w = ((rif.data_width+3)>>2) as usize;
+        let compact = self.base_settings.compact;
+        let is_public = self.base_settings.privacy.is_public();
+        for page in rif.pages.iter() {
+            if rif.pages.len() > 1 {
+                self.write(&format!("\\section{{{} Registers}}\n", escape(&page.name)));
+            } else {
+                self.write("\\section{Registers}\n");
+            }
+            let mut types_done : Vec<String> = Vec::new();
+            for reg in page.regs.iter() {
+                if !self.is_visible(reg) {
+                    continue;
+                }
+                let reg_type = reg.expanded_type_name().to_casing(self.base_settings.casing);
+                // In compact mode only the first instance of each type is described
+                let is_first = !types_done.contains(&reg_type);
+                if compact && !is_first {
+                    continue;
+                }
+                let instances : Vec<&RifRegInst> =
+                    if compact {
+                        page.regs.iter()
+                            .filter(|r| self.is_visible(r) && r.expanded_type_name().to_casing(self.base_settings.casing) == reg_type)
+                            .collect()
+                    } else {
+                        vec![reg]
+                    };
+                let reg_impl = rif.get_hw_reg(&reg.group_type);
+                // Title: label is set only on the first section of a type
+                let label = if is_first {
+                    types_done.push(reg_type.clone());
+                    format!("\\label{{reg:{rifname}:{reg_type}}}")
+                } else {
+                    "".to_owned()
+                };
+                let desc = if compact {reg.base_description.get_split()} else {reg.description.get_split()};
+                let name = if compact {reg_type.to_owned()} else {self.get_reg_name(reg)};
+                if desc.0.is_empty() {
+                    self.write(&format!("\\subsection{{{}}}{label}\n", escape(&name)));
+                } else {
+                    self.write(&format!("\\subsection{{{} ({})}}{label}\n", escape(desc.0), escape(&name)));
+                }
+                if let Some(desc_detail) = desc.1 {
+                    self.write(&format!("{}\n\n", sanitize(desc_detail)));
+                }
+                // Register instance summary: Name, offset, reset, Description
+                self.write("\\begin{tabular}{l l l");
+                if instances.len() > 1 {
+                    self.write(" p{0.45\\linewidth}");
+                }
+                self.write("}\n\\textbf{Instance Name} & \\textbf{Offset} & \\textbf{Reset}");
+                if instances.len() > 1 {
+                    self.write(" & \\textbf{Description}");
+                }
+                self.write(" \\\\\n");
+                for inst in instances.iter() {
+                    self.write(&format!("{} & 0x{:0addr_w$X} & 0x{:0data_w$X}", escape(&self.get_reg_name(inst)), page.addr + inst.addr, inst.reset));
+                    if instances.len() > 1 {
+                        self.write(&format!(" & {}", escape(inst.description.get_short())));
+                    }
+                    self.write(" \\\\\n");
+                }
+                self.write("\\end{tabular}\n\n");
+                // Fields mapping
+                self.add_bitfield(rif.data_width, reg);
+                if reg.intr_info.0.is_derived() {
+                    let base_type = reg.reg_type.to_casing(self.base_settings.casing);
+                    self.write(&format!("Fields details: see section~\\ref{{reg:{rifname}:{base_type}}}.\n\n"));
+                    continue;
+                }
+                // Fields details
+                self.write("\\begin{longtable}{l l l l p{0.5\\linewidth}}\n\\hline\n");
+                self.write("\\textbf{Bits} & \\textbf{Name} & \\textbf{Access} & \\textbf{Reset} & \\textbf{Description} \\\\\n\\hline\n\\endhead\n");
+                for f in reg.fields.iter().rev().filter(|f| !(f.visibility.is_hidden() && is_public)) {
+                    let fieldname = self.get_field_name(reg, f);
+                    let pos = if f.width==1 {format!("{}", f.lsb)} else {format!("{}:{}", f.msb(), f.lsb)};
+                    // Reset displayed as a dash when it differs between instances
+                    let is_single_reset = instances.iter().all(|r| r.fields.iter().find(|fi| fi.name==f.name && fi.lsb==f.lsb).map(|fi| fi.reset==f.reset).unwrap_or(true));
+                    let reset = if is_single_reset {
+                        let w = ((f.width+3)>>2) as usize;
+                        format!("0x{:0w$X}", f.reset.to_u128(f.width))
+                    } else {
+                        "-".to_owned()
+                    };
+                    self.write(&format!("{pos} & {} & {} & {reset} & ", escape(&fieldname), access(&f.sw_kind)));
+                    // Description of reserved field are removed in public documentation
+                    if f.is_reserved() && is_public {
+                        self.write("Reserved");
+                    } else {
+                        self.write(&sanitize(f.description.get()));
+                        if let Some(enum_name) = f.enum_kind.name() {
+                            let name = if let Some(pkg) = &reg_impl.pkg {
+                                if enum_name.contains(':') {enum_name.to_owned()}
+                                else {format!("{pkg}_pkg::{enum_name}")}
+                            } else {
+                                enum_name.to_owned()
+                            };
+                            let enum_def = rif.get_enum_def(&name)?;
+                            self.write("\\newline\n\\begin{tabular}[t]{@{}l l@{}}\n");
+                            for e in enum_def.iter() {
+                                self.write(&format!("0x{:X} & {} \\\\\n", e.value, escape(e.description.get_short())));
+                            }
+                            self.write("\\end{tabular}");
+                        }
+                    }
+                    self.write(" \\\\\n");
+                }
+                self.write("\\hline\n\\end{longtable}\n\n");
+            }
+        }
+        Ok(())
+    }
+
+    /// Bitfield diagram with field names and reset value of each bit
+    fn add_bitfield(&mut self, data_width: u8, reg: &RifRegInst) {
+        let is_public = self.base_settings.privacy.is_public();
+        // Scale bit width to fit the diagram on a page
+        let bit_w = 48.0 / data_width as f32;
+        self.write(&format!("\\begin{{center}}\n\\begin{{bytefield}}[bitwidth={bit_w:.1}em, endianness=big]{{{data_width}}}\n"));
+        self.write(&format!("\\bitheader{{0-{}}} \\\\\n", data_width-1));
+        let mut last_pos = data_width;
+        for f in reg.fields.iter().rev().filter(|f| !(f.visibility.is_hidden() && is_public)) {
+            // Reserved space between fields
+            if f.msb()+1 < last_pos {
+                self.write(&format!("\\bitbox{{{}}}{{}}\n", last_pos - (f.msb()+1)));
+            }
+            let fieldname = escape(&self.get_field_name(reg, f));
+            // Field name written vertically when it does not fit in the box
+            if (f.width as usize) * 3 < fieldname.len() {
+                self.write(&format!("\\bitbox{{{}}}{{\\rotatebox{{90}}{{\\tiny {fieldname}}}}}\n", f.width));
+            } else {
+                self.write(&format!("\\bitbox{{{}}}{{\\small {fieldname}}}\n", f.width));
+            }
+            last_pos = f.lsb;
+        }
+        if last_pos!=0 {
+            self.write(&format!("\\bitbox{{{last_pos}}}{{}}\n"));
+        }
+        self.write("\\\\\n");
+        for i in (0..data_width).rev() {
+            self.write(&format!("\\bitbox{{1}}{{\\tiny {}}}", (reg.reset >> i)&1));
+        }
+        self.write("\n\\end{bytefield}\n\\end{center}\n\n");
+    }
+
+    fn get_reg_name(&self, reg: &RifRegInst) -> String {
+        let arr_idx = if reg.array.dim() > 0 {format!("[{}]",reg.array.idx())} else {"".to_owned()};
+        format!("{}{arr_idx}", reg.reg_name.to_casing(self.base_settings.casing))
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat().to_casing(self.base_settings.casing)
+        } else {
+            f.name.to_casing(self.base_settings.casing)
+        }
+    }
+
+}
+
+/// Software access of a field as displayed in the documentation
+fn access(kind: &FieldSwKind) -> &'static str {
+    match kind {
+        FieldSwKind::ReadWrite   => "RW",
+        FieldSwKind::ReadOnly    => "RO",
+        FieldSwKind::WriteOnly   => "WO",
+        FieldSwKind::ReadClr     => "RCLR",
+        FieldSwKind::W1Clr       => "W1CLR",
+        FieldSwKind::W0Clr       => "W0CLR",
+        FieldSwKind::W1Set       => "W1SET",
+        FieldSwKind::W1Tgl       => "W1TGL",
+        FieldSwKind::W1Pulse(_,_) => "Pulse",
+        FieldSwKind::Password(_) => "WO",
+    }
+}
+
+/// Escape LaTeX special characters
+fn escape(txt: &str) -> String {
+    let mut s = String::with_capacity(txt.len());
+    for c in txt.chars() {
+        match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {s.push('\\'); s.push(c);},
+            '~' => s.push_str("\\textasciitilde{}"),
+            '^' => s.push_str("\\textasciicircum{}"),
+            '\\' => s.push_str("\\textbackslash{}"),
+            _ => s.push(c),
+        }
+    }
+    s
+}
+
+/// Escape a multi-line description: each line is separated by a line break
+fn sanitize(desc: &str) -> String {
+    desc.lines()
+        .map(|l| escape(l.trim()))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<String>>()
+        .join("\\newline\n")
+}
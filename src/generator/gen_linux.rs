@@ -0,0 +1,280 @@
+use std::{format, fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, FieldSwKind}};
+
+use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RifList}};
+
+
+pub struct GeneratorLinux {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorLinux {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorLinux {
+            base_settings: args,
+            txt: String::with_capacity(10000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_header(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_header(rifmux, &rif_list)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_header(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    /// Kernel header for each RIF
+    ///  - register offset and field mask (GENMASK/BIT) defines
+    ///  - regmap access tables and regmap_config
+    ///  - reg_field table for regmap_field_alloc
+    fn gen_rif_header(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = rif.name(false).to_lowercase();
+        let rifname_uc = rifname.to_uppercase();
+        let prefix = remove_rif(&rifname).to_lowercase();
+        let prefix_uc = prefix.to_uppercase();
+        let nb_byte = (rif.data_width >> 3) as u64;
+        let is_public = self.base_settings.privacy.is_public();
+
+        self.write("/* File generated automatically: DO NOT EDIT. */\n");
+        self.write(&format!("/* Register definition for {rifname_uc} */\n"));
+        self.write(&format!("#ifndef __{rifname_uc}_H__\n"));
+        self.write(&format!("#define __{rifname_uc}_H__\n\n"));
+        self.write("#include <linux/bits.h>\n");
+        self.write("#include <linux/regmap.h>\n\n");
+
+        // List all registers visible to software
+        let regs : Vec<(u64, &RifRegInst)> = rif.pages.iter()
+            .filter(|p| !p.is_external())
+            .flat_map(|p| p.regs.iter().map(|r| (p.addr + r.addr, r)))
+            .filter(|(_,r)| r.sw_access != Access::NA)
+            .collect();
+
+        // Register offsets
+        self.write("/* Register offsets */\n");
+        for (i, (addr, reg)) in regs.iter().enumerate() {
+            let name = format!("{prefix_uc}_{}", reg.reg_name.to_uppercase());
+            if reg.array.dim() > 1 {
+                if reg.array.idx() > 0 {
+                    continue;
+                }
+                let stride = regs.get(i+1).map(|(a,_)| a - addr).unwrap_or(nb_byte);
+                self.write(&format!("#define {name}(n) (0x{addr:04X} + (n) * 0x{stride:X})"));
+                self.add_comment(reg.base_description.get_short());
+            } else {
+                self.write(&format!("#define {name} 0x{addr:04X}"));
+                self.add_comment(reg.description.get_short());
+            }
+        }
+        self.write("\n");
+
+        // Field masks: compatible with FIELD_GET/FIELD_PREP
+        let mut types_done : Vec<String> = Vec::new();
+        for (_, reg) in regs.iter() {
+            let reg_type = reg.reg_type.to_uppercase();
+            if types_done.contains(&reg_type) {
+                continue;
+            }
+            types_done.push(reg_type.clone());
+            self.write(&format!("/* {} register fields */\n", reg.reg_type.to_casing(Casing::Title)));
+            for f in reg.fields.iter() {
+                if f.is_reserved() && is_public {
+                    continue;
+                }
+                let name = format!("{prefix_uc}_{reg_type}_{}", self.get_field_name(reg, f).to_uppercase());
+                if f.width == 1 {
+                    self.write(&format!("#define {name} BIT({})\n", f.lsb));
+                } else {
+                    self.write(&format!("#define {name} GENMASK({}, {})\n", f.msb(), f.lsb));
+                }
+                // Enum values
+                if let Some(enum_name) = f.enum_kind.name() {
+                    let reg_impl = rif.get_hw_reg(&reg.group_type);
+                    let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                        if enum_name.contains(':') {enum_name.to_owned()}
+                        else {format!("{pkg}_pkg::{enum_name}")}
+                    } else {
+                        enum_name.to_owned()
+                    };
+                    let enum_def = rif.get_enum_def(&enum_name)?;
+                    for e in enum_def.iter() {
+                        self.write(&format!("#define   {name}_{} {}", e.name.to_uppercase(), e.value));
+                        self.add_comment(e.description.get_short());
+                    }
+                }
+            }
+            self.write("\n");
+        }
+
+        // Access tables
+        let tables = [
+            ("rd", self.add_access_table(&prefix, "rd", &regs, |r| r.sw_access.is_readable(), nb_byte)),
+            ("wr", self.add_access_table(&prefix, "wr", &regs, |r| r.sw_access.is_writable(), nb_byte)),
+            ("volatile", self.add_access_table(&prefix, "volatile", &regs, |r| r.fields.iter().any(Self::is_volatile), nb_byte)),
+            ("precious", self.add_access_table(&prefix, "precious", &regs, |r| r.fields.iter().any(|f| f.sw_kind == FieldSwKind::ReadClr), nb_byte)),
+        ];
+
+        // Regmap configuration
+        let max_register = regs.iter().map(|(a,_)| *a).max().unwrap_or(0);
+        self.write(&format!("static const struct regmap_config {prefix}_regmap_config __maybe_unused = {{\n"));
+        self.write(&format!("\t.name = \"{prefix}\",\n"));
+        self.write(&format!("\t.reg_bits = {},\n", ((rif.addr_width + 7) >> 3) << 3));
+        self.write(&format!("\t.val_bits = {},\n", rif.data_width));
+        self.write(&format!("\t.reg_stride = {nb_byte},\n"));
+        self.write(&format!("\t.max_register = 0x{max_register:X},\n"));
+        for (kind, _) in tables.iter().filter(|(_, is_used)| *is_used) {
+            self.write(&format!("\t.{kind}_table = &{prefix}_{kind}_table,\n"));
+        }
+        self.write("\t.cache_type = REGCACHE_RBTREE,\n");
+        self.write("};\n\n");
+
+        // Register fields
+        self.write(&format!("enum {prefix}_fields {{\n"));
+        let mut fields = Vec::new();
+        for (_, reg) in regs.iter() {
+            let reg_name = reg.reg_name.to_uppercase();
+            let (reg_def, reg_id) = if reg.array.dim() > 1 {
+                (format!("{prefix_uc}_{reg_name}({})", reg.array.idx()), format!("{reg_name}{}", reg.array.idx()))
+            } else {
+                (format!("{prefix_uc}_{reg_name}"), reg_name)
+            };
+            for f in reg.fields.iter() {
+                if f.is_reserved() && is_public {
+                    continue;
+                }
+                let id = format!("{prefix_uc}_F_{reg_id}_{}", self.get_field_name(reg, f).to_uppercase());
+                self.write(&format!("\t{id},\n"));
+                fields.push(format!("\t[{id}] = REG_FIELD({reg_def}, {}, {}),\n", f.lsb, f.msb()));
+            }
+        }
+        self.write(&format!("\t{prefix_uc}_F_MAX\n}};\n\n"));
+        self.write(&format!("static const struct reg_field {prefix}_reg_fields[{prefix_uc}_F_MAX] __maybe_unused = {{\n"));
+        for f in fields {
+            self.write(&f);
+        }
+        self.write("};\n\n");
+
+        self.write(&format!("#endif /* __{rifname_uc}_H__ */\n"));
+
+        // Write file
+        self.save(&format!("{rifname}.h"))
+    }
+
+    /// Add a regmap access table with all registers matching the predicate:
+    /// consecutive registers are merged in a single range.
+    /// Return false when no register matches (no table generated)
+    fn add_access_table<F>(&mut self, prefix: &str, kind: &str, regs: &[(u64, &RifRegInst)], predicate: F, nb_byte: u64) -> bool
+        where F: Fn(&RifRegInst) -> bool
+    {
+        let mut ranges : Vec<(u64,u64)> = Vec::new();
+        for (addr, _) in regs.iter().filter(|(_,r)| predicate(r)) {
+            match ranges.last_mut() {
+                Some(range) if range.1 + nb_byte == *addr => range.1 = *addr,
+                Some(range) if range.1 == *addr => {},
+                _ => ranges.push((*addr, *addr)),
+            }
+        }
+        if ranges.is_empty() {
+            return false;
+        }
+        self.write(&format!("static const struct regmap_range {prefix}_{kind}_ranges[] = {{\n"));
+        for (start, end) in ranges.iter() {
+            self.write(&format!("\tregmap_reg_range(0x{start:04X}, 0x{end:04X}),\n"));
+        }
+        self.write("};\n\n");
+        self.write(&format!("static const struct regmap_access_table {prefix}_{kind}_table = {{\n"));
+        self.write(&format!("\t.yes_ranges = {prefix}_{kind}_ranges,\n"));
+        self.write(&format!("\t.n_yes_ranges = ARRAY_SIZE({prefix}_{kind}_ranges),\n"));
+        self.write("};\n\n");
+        true
+    }
+
+    /// Add end of line comment, if any
+    fn add_comment(&mut self, desc: &str) {
+        if desc.is_empty() {
+            self.write("\n");
+        } else {
+            self.write(&format!(" /* {desc} */\n"));
+        }
+    }
+
+    /// Field value can change without software access
+    fn is_volatile(f: &RifFieldInst) -> bool {
+        f.hw_access.is_writable() || f.is_hw_write() || matches!(f.sw_kind, FieldSwKind::ReadClr | FieldSwKind::W1Pulse(_,_))
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat()
+        } else {
+            f.name.to_owned()
+        }
+    }
+
+    /// Kernel header for the rifmux: base address offset of each component
+    fn gen_rifmux_header(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        let rifname_uc = rifname.to_uppercase();
+        self.write("/* File generated automatically: DO NOT EDIT. */\n");
+        self.write("/* Register File mapping */\n");
+        self.write(&format!("#ifndef __{rifname_uc}_H__\n"));
+        self.write(&format!("#define __{rifname_uc}_H__\n\n"));
+        for rif in rif_list.iter() {
+            self.write(&format!("#include \"{}.h\"\n", rif.name(false).to_lowercase()));
+        }
+        self.write("\n");
+        self.add_rifmux_offsets(rifmux, &rifname_uc, 0);
+        self.write(&format!("\n#endif /* __{rifname_uc}_H__ */\n"));
+        // Write file
+        self.save(&format!("{rifname}.h"))
+    }
+
+    fn add_rifmux_offsets(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64) {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}_{}", remove_rif(comp.get_name()).to_uppercase());
+            self.write(&format!("#define {name}_OFFSET 0x{addr:08X}"));
+            self.add_comment(comp.get_desc_short());
+            if let Comp::Rifmux(r) = &comp.inst {
+                self.add_rifmux_offsets(r, &name, addr);
+            }
+        }
+    }
+}
@@ -0,0 +1,356 @@
+use std::{collections::HashMap, format, fs::create_dir_all, path::PathBuf};
+
+use crate::{
+    comp::comp_inst::{Comp, CompInst, RifFieldInst, RifInst, RifRegInst, RifmuxGroupInst},
+    parser::remove_rif, rifgen::FieldSwKind,
+};
+
+use super::{casing::ToCasing, gen_common::{GeneratorBaseSetting, RifList}};
+use super::casing::Casing;
+
+type InstDict = HashMap<String,Vec<u16>>;
+
+pub struct GeneratorMd {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorMd {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorMd {
+            base_settings: args,
+            txt: String::with_capacity(10000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        self.write("<!-- File generated automatically: DO NOT EDIT. -->\n\n");
+
+        let top_name;
+
+        match obj {
+            Comp::Rifmux(r) => {
+                top_name = &r.inst_name;
+                // RifMux: Table describing mapping of all its element
+                // Sub-rifmux are expanded to only display RIFs
+                self.write(&format!("<a id=\"rifSummary\"></a>\n\n# {}\n\n", Casing::Title.format(&r.inst_name)));
+                if !r.description.is_empty() {
+                    self.write(&format!("{}\n\n", r.description.get().trim()));
+                }
+                self.write("| Offset | Type Name | Instance Name | Description |\n");
+                self.write("|--------|-----------|---------------|-------------|\n");
+                let rif_list = RifList::new(r);
+                let w = ((r.addr_width+3) >> 2) as usize;
+                for c in r.components.iter() {
+                    self.add_rifmux_entry(c, w,  0, None, &r.groups);
+                }
+                self.write("\n");
+                // Add RIF definition for all RIF type used
+                for (idx, rif) in rif_list.iter().enumerate() {
+                    self.add_rif(rif, idx+1, true)?;
+                }
+            },
+            Comp::Rif(r) => {
+                top_name = &r.type_name;
+                self.add_rif(r, 1, false)?;
+            },
+            // Nothing todo for external RIF
+            Comp::External(_) => return Ok(()),
+        }
+
+        // Write file
+        self.save(&format!("{top_name}.md"))
+    }
+
+    // Add row in table, composed of 4 column:
+    // Address, Type name, Instance name and short description
+    fn add_rifmux_entry (&mut self, comp: &CompInst, w: usize, offset: u64, top_name: Option<&str>, groups: &[RifmuxGroupInst]) {
+        let rif_name = remove_rif(comp.inst.get_name()).to_casing(self.base_settings.casing);
+        let instname = if let Some(top) = top_name {format!("{}.{}", top, rif_name)} else {rif_name};
+        let addr = comp.full_addr(groups) + offset;
+        match &comp.inst {
+            Comp::Rifmux(c) => {
+                for sub in c.components.iter() {
+                    self.add_rifmux_entry(sub, w, addr, Some(&instname), &c.groups);
+                }
+            },
+            Comp::Rif(c) => {
+                let tn = remove_rif(&c.type_name);
+                self.write(&format!("| 0x{addr:0w$X} | [{}](#compName__{tn}) | {} | {} |\n",
+                    escape(tn), escape(&instname), escape(c.description.get_short())));
+            }
+            Comp::External(c) => {
+                self.write(&format!("| 0x{addr:0w$X} | Memory | {} | {} |\n",
+                    escape(&instname), escape(c.description.get_short())));
+            }
+        }
+    }
+
+    //
+    fn add_rif(&mut self, rif: &RifInst, idx: usize, has_top: bool) -> Result<(),String> {
+        let rif_name = remove_rif(&rif.type_name);
+        self.write(&format!("<a id=\"compName__{rif_name}\"></a>\n\n# {idx}. "));
+        let desc = rif.base_description.get_split();
+        if desc.0.is_empty() {
+            self.write(&Casing::Title.format(rif_name));
+        } else {
+            self.write(desc.0);
+        }
+        self.write("\n\n");
+        if let Some(desc_detail) = desc.1 {
+            self.write(&format!("{}\n\n", sanitize(desc_detail)));
+        }
+        let inst_dict = self.add_reg_summary(rif);
+        if has_top {
+            self.write("[return to top](#rifSummary)\n\n");
+        }
+        self.add_reg_detail(rif, &inst_dict, idx)
+    }
+
+    /// Register must be documented
+    fn is_visible(&self, reg: &RifRegInst) -> bool {
+        !(self.base_settings.privacy.is_public() && reg.visibility.is_hidden())
+    }
+
+    //
+    fn add_reg_summary(&mut self, rif: &RifInst) -> InstDict {
+        let rif_name = remove_rif(&rif.type_name);
+        let addr_w = ((rif.addr_width+3)>>2) as usize;
+        let data_w = ((rif.data_width+3)>>2) as usize;
+        let mut dict : InstDict = HashMap::new();
+        for page in rif.pages.iter() {
+            self.write(&format!("<a id=\"regSummary__{rif_name}_{page_name}\"></a>\n\n## {page_name} Summary\n\n", page_name=page.name));
+            self.write("| Offset | Instance Name | Reset | Description | Register Type |\n");
+            self.write("|--------|---------------|-------|-------------|---------------|\n");
+            for (idx,reg) in page.regs.iter().enumerate() {
+                if !self.is_visible(reg) {
+                    continue;
+                }
+                let arr_idx = if reg.array.dim() > 0 {format!("[{}]",reg.array.idx())} else {"".to_owned()};
+                let reg_type = reg.expanded_type_name().to_casing(self.base_settings.casing);
+                self.write(&format!("| 0x{addr:0addr_w$X} | {}{arr_idx} | 0x{:0data_w$X} | {} | [{}](#regName__{rif_name}_{reg_type}) |\n",
+                    escape(&reg.reg_name.to_casing(self.base_settings.casing)),
+                    reg.reset,
+                    escape(reg.description.get_short()),
+                    escape(&reg_type),
+                    addr=page.addr+reg.addr,
+                ));
+                dict.entry(reg_type).or_default().push(idx as u16);
+            }
+            self.write("\n");
+        }
+        dict
+    }
+
+    fn add_reg_detail(&mut self, rif: &RifInst, inst_dict: &InstDict, idx_c: usize)  -> Result<(),String> {
+        let rifname = remove_rif(&rif.type_name);
+        let mut idx_p = 0;
+        let addr_w = ((rif.addr_width+3)>>2) as usize;
+        let data_w = ((rif.data_width+3)>>2) as usize;
+        let is_public = self.base_settings.privacy.is_public();
+        for page in rif.pages.iter() {
+            idx_p += 1;
+            // Page heading keeps the register headings one level below
+            self.write(&format!("<a id=\"pageName__{rifname}_{page_name}\"></a>\n\n## {idx_c}.{idx_p} {page_name}\n\n", page_name=page.name));
+            let mut idx_r = 0;
+            for (idx_ri,reg) in page.regs.iter().enumerate() {
+                if !self.is_visible(reg) {
+                    continue;
+                }
+                // Only the first instance of each register type is detailed
+                let reg_type = reg.expanded_type_name().to_casing(self.base_settings.casing);
+                let Some(instances) = inst_dict.get(&reg_type) else {
+                    return Err(format!("Unable to find register type {} in instance dict: {:?}", reg_type, inst_dict.keys().collect::<Vec<&String>>()))
+                };
+                if instances.first() != Some(&(idx_ri as u16)) {
+                    continue;
+                }
+                let reg_impl = rif.get_hw_reg(&reg.group_type);
+                // Title
+                idx_r += 1;
+                self.write(&format!("<a id=\"regName__{rifname}_{reg_type}\"></a>\n\n### {idx_c}.{idx_p}.{idx_r} "));
+                if reg.base_description.is_empty() {
+                    self.write(&format!("{reg_type}\n\n"));
+                } else {
+                    let desc = reg.base_description.get_split();
+                    self.write(&format!("{} ({reg_type})\n\n",desc.0));
+                    if let Some(desc_detail) = desc.1 {
+                        self.write(&format!("{}\n\n", sanitize(desc_detail)));
+                    }
+                }
+                // Register instance summary : Name, offset, reset, Description
+                if instances.len() > 1 {
+                    self.write("| Instance Name | Offset | Reset | Description |\n");
+                    self.write("|---------------|--------|-------|-------------|\n");
+                } else {
+                    self.write("| Instance Name | Offset | Reset |\n");
+                    self.write("|---------------|--------|-------|\n");
+                }
+                for inst_idx in instances.iter() {
+                    let Some(inst) = page.regs.get(*inst_idx as usize) else {
+                        return Err(format!("Instance index {} out of range (max {}) !!!", inst_idx, page.regs.len()));
+                    };
+                    let arr_idx = if inst.array.dim() > 0 {format!("[{}]",inst.array.idx())} else {"".to_owned()};
+                    self.write(&format!("| {}{arr_idx} | 0x{addr:0addr_w$X} | 0x{rst:0data_w$X} |",
+                        escape(&inst.reg_name.to_casing(self.base_settings.casing)),
+                        addr=page.addr+inst.addr,
+                        rst=inst.reset
+                    ));
+                    if instances.len() > 1 {
+                        self.write(&format!(" {} |", escape(inst.description.get_short())));
+                    }
+                    self.write("\n");
+                }
+                self.write("\n");
+
+                // Fields Mapping: one column per field or reserved range
+                let mut cols : Vec<(String,String,String)> = Vec::new();
+                let mut last_pos = rif.data_width;
+                for f in reg.fields.iter().rev().filter(|f| !(f.visibility.is_hidden() && is_public)) {
+                    // Insert reserved in unused bits
+                    if f.msb()+1 < last_pos {
+                        cols.push((bit_range(last_pos-1, f.msb()+1), "-".to_owned(), bit_reset(reg.reset, last_pos-1, f.msb()+1)));
+                    }
+                    cols.push((bit_range(f.msb(), f.lsb), escape(&self.get_field_name(reg, f)), bit_reset(reg.reset, f.msb(), f.lsb)));
+                    last_pos = f.lsb;
+                }
+                if last_pos!=0 {
+                    cols.push((bit_range(last_pos-1, 0), "-".to_owned(), bit_reset(reg.reset, last_pos-1, 0)));
+                }
+                self.write(&format!("| Bit | {} |\n", cols.iter().map(|c| c.0.as_str()).collect::<Vec<&str>>().join(" | ")));
+                self.write(&format!("|-----|{}\n", "---|".repeat(cols.len())));
+                self.write(&format!("| Field | {} |\n", cols.iter().map(|c| c.1.as_str()).collect::<Vec<&str>>().join(" | ")));
+                self.write(&format!("| Reset | {} |\n\n", cols.iter().map(|c| c.2.as_str()).collect::<Vec<&str>>().join(" | ")));
+
+                let is_intr_derived = reg.intr_info.0.is_derived();
+                if !is_intr_derived {
+                    // Fields Details
+                    self.write("| Bits | Name | Access | Reset | Description |\n");
+                    self.write("|------|------|--------|-------|-------------|\n");
+                    for f in reg.fields.iter().rev().filter(|f| !(f.visibility.is_hidden() && is_public)) {
+                        let fieldname = self.get_field_name(reg, f);
+                        // Position
+                        if f.width==1 {
+                            self.write(&format!("| {} ",f.lsb));
+                        } else {
+                            self.write(&format!("| {}:{} ",f.msb(), f.lsb));
+                        }
+                        // Name, Access, Reset
+                        let access = match f.sw_kind {
+                            FieldSwKind::ReadWrite   => "RW",
+                            FieldSwKind::ReadOnly    => "RO",
+                            FieldSwKind::WriteOnly   => "WO",
+                            FieldSwKind::ReadClr     => "RCLR",
+                            FieldSwKind::W1Clr       => "W1CLR",
+                            FieldSwKind::W0Clr       => "W0CLR",
+                            FieldSwKind::W1Set       => "W1SET",
+                            FieldSwKind::W1Tgl       => "W1TGL",
+                            FieldSwKind::W1Pulse(_,_) => "Pulse",
+                            FieldSwKind::Password(_) => "WO",
+                        };
+                        self.write(&format!("| {} | {access} | ", escape(&fieldname)));
+                        // Check if the field reset is the same in all register instance
+                        // If not display a dash character
+                        let mut is_single_reset = true;
+                        for inst_idx in instances.iter().skip(1) {
+                            let reg_inst = page.regs.get(*inst_idx as usize).unwrap(); // Case were this does not exist already checked before
+                            let Some(f_inst) = reg_inst.fields.iter().find(|fi| fi.name==f.name) else {
+                                return Err(format!("Unable to find field {}.{} !", reg.reg_name, f.name));
+                            };
+                            if f_inst.reset != f.reset {
+                                is_single_reset = false;
+                                self.write("-");
+                                break;
+                            }
+                        }
+                        if is_single_reset {
+                            let reset = f.reset.to_u128(f.width);
+                            let w = (f.width>>2) as usize;
+                            self.write(&format!("0x{reset:0w$X}"))
+                        }
+                        self.write(" | ");
+                        // Description: removed for reserved field in public documentation
+                        if f.is_reserved() && is_public {
+                            self.write("Reserved");
+                        } else {
+                            self.write(&sanitize(f.description.get()));
+                            if let Some(enum_name) = f.enum_kind.name() {
+                                let name = if let Some(pkg) = &reg_impl.pkg {
+                                    if enum_name.contains(':') {enum_name.to_owned()}
+                                    else {format!("{pkg}_pkg::{enum_name}")}
+                                } else {
+                                    enum_name.to_owned()
+                                };
+                                let enum_def = rif.get_enum_def(&name)?;
+                                for e in enum_def.iter() {
+                                    self.write(&format!("<br>`{:#0x}`: {}", e.value, escape(e.description.get_short())));
+                                }
+                            }
+                        }
+                        self.write(" |\n");
+                    }
+                    self.write("\n");
+                }
+                self.write(&format!("[return to summary](#regSummary__{rifname}_{page_name})", page_name=page.name));
+                if is_intr_derived {
+                    self.write(&format!(" - [Fields details](#regName__{rifname}_{regtype})", regtype=reg.reg_type.to_casing(self.base_settings.casing)));
+                }
+                self.write("\n\n");
+            }
+        }
+        Ok(())
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat().to_casing(self.base_settings.casing)
+        } else {
+            f.name.to_casing(self.base_settings.casing)
+        }
+    }
+
+}
+
+/// Escape characters interpreted inside a table cell
+fn escape(txt: &str) -> String {
+    txt.replace('|', "\\|").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+/// Sanitize a multi-line description for a table cell: lines are separated by a line break
+fn sanitize(desc: &str) -> String {
+    desc.lines()
+        .map(|l| escape(l.trim()))
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<String>>()
+        .join("<br>")
+}
+
+fn bit_range(msb: u8, lsb: u8) -> String {
+    if msb==lsb {format!("{msb}")} else {format!("{msb}:{lsb}")}
+}
+
+/// Reset value of a bit range in binary
+fn bit_reset(reset: u128, msb: u8, lsb: u8) -> String {
+    let w = (msb - lsb + 1) as usize;
+    format!("{:0w$b}", (reset >> lsb) & ((1_u128 << w) - 1))
+}
@@ -0,0 +1,438 @@
+use std::{fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, EnumKind, FieldSwKind, ResetVal}};
+
+use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RifList}};
+
+
+/// Support library for the register object model
+const RIF_MODEL_LIB: &str = include_str!("py/rif_model.py");
+
+pub struct GeneratorPy {
+    base_settings: GeneratorBaseSetting,
+    /// Generate also the hierarchical register object model
+    model: bool,
+    txt: String,
+    stash: String,
+}
+
+impl GeneratorPy {
+
+    pub fn new(args: GeneratorBaseSetting, model: bool) -> Self {
+        GeneratorPy {
+            base_settings: args,
+            model,
+            txt: String::with_capacity(10000),
+            stash: String::with_capacity(1000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn push_stash(&mut self, string: &str) {
+        self.stash.push_str(string);
+    }
+
+    fn pop_stash(&mut self) {
+        self.txt.push_str(&self.stash);
+        self.stash.clear();
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        if self.model && !matches!(obj, Comp::External(_)) {
+            self.write(RIF_MODEL_LIB);
+            self.save("rif_model.py")?;
+        }
+        match obj {
+            Comp::Rif(rif) => {
+                self.gen_rif_module(rif)?;
+                if self.model {
+                    self.gen_rif_model(rif)?;
+                }
+            }
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_module(rifmux, &rif_list)?;
+                if self.model {
+                    self.gen_rifmux_model(rifmux, &rif_list)?;
+                }
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_module(rif)?;
+                        if self.model {
+                            self.gen_rif_model(rif)?;
+                        }
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    /// Write a module docstring
+    fn write_docstring(&mut self, title: &str, desc: &str) {
+        self.write(&format!("\"\"\"{title}\n"));
+        if !desc.trim().is_empty() {
+            self.write("\n");
+            for l in desc.trim().lines() {
+                self.write(&format!("{}\n", l.trim().replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"")));
+            }
+        }
+        self.write("\"\"\"\n\n");
+    }
+
+    /// Python module for each RIF
+    ///  - one IntEnum class per enum
+    ///  - position/mask of each field for every register type
+    ///  - offset/reset of each register instance
+    fn gen_rif_module(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname_uc = rif.type_name.to_uppercase();
+        let w = rif.data_width as usize;
+        let hw = w / 4;
+        let is_public = self.base_settings.privacy.is_public();
+
+        // Write header
+        self.write("# File generated automatically: DO NOT EDIT.\n");
+        self.write_docstring(&format!("Register definition for {rifname_uc}"), rif.description.get());
+        if rif.enum_defs.iter().any(|d| !d.name.starts_with("doc:")) {
+            self.write("from enum import IntEnum\n\n");
+        }
+        self.write(&format!("ADDR_WIDTH = {}\n", rif.addr_width));
+        self.write(&format!("DATA_WIDTH = {}\n\n", rif.data_width));
+
+        // Add enum declaration
+        for def in rif.enum_defs.iter() {
+            if def.name.starts_with("doc:") {
+                continue;
+            }
+            self.write(&format!("\nclass {}(IntEnum):\n", Self::enum_name(&def.name)));
+            if !def.description.is_empty() {
+                self.write(&format!("    \"\"\"{}\"\"\"\n", def.description.replace('"', "'")));
+            }
+            for entry in def.iter() {
+                self.write(&format!("    {} = {} # {}\n",
+                    entry.name.to_uppercase(),
+                    entry.value,
+                    entry.description.get_short()
+                ));
+            }
+            self.write("\n");
+        }
+
+        self.push_stash("\n# Offset of all registers: name -> (offset, reset)\n");
+        self.push_stash("REGISTERS = {\n");
+        for page in rif.pages.iter() {
+            if page.is_external() {
+                continue;
+            }
+            let prefix =
+                if rif.pages.len() > 1 {format!("{}_", page.name.to_uppercase())}
+                else {"".to_owned()};
+            if rif.pages.len() > 1 {
+                self.write(&format!("\n# Page {}\n", page.name));
+                self.write(&format!("{prefix}PAGE_OFFSET = 0x{:04X}\n", page.addr));
+            }
+
+            // Add field position and mask of each register type
+            for reg in page.iter_reg_type() {
+                // Check if register is hidden/reserved in all instances
+                if reg.sw_access == Access::NA {
+                    continue;
+                }
+                let reg_type = reg.reg_type.to_uppercase();
+                self.write(&format!("\n# {} register bitfields\n", reg.reg_type.to_casing(Casing::Title)));
+                for l in reg.base_description.get().lines() {
+                    self.write(&format!("# {}\n", l.trim()));
+                }
+                for f in reg.fields.iter() {
+                    if is_public && f.visibility.is_hidden() {
+                        continue;
+                    }
+                    let name = format!("{prefix}{reg_type}_{}", self.get_field_name(reg, f).to_uppercase());
+                    let mask = (1_u128<<f.width)-1;
+                    self.write(&format!("{name}_POS = {}\n", f.lsb));
+                    self.write(&format!("{name}_MASK = 0x{mask:0hw$X}\n"));
+                    self.write(&format!("{name}_SMASK = 0x{:0hw$X}\n", mask << f.lsb));
+                }
+            }
+
+            // Add offset and reset of each register instance
+            self.write("\n# Register offsets and reset values\n");
+            for reg in page.regs.iter() {
+                if reg.sw_access == Access::NA || (is_public && reg.visibility.is_hidden()) {
+                    continue;
+                }
+                let name = format!("{prefix}{}", reg.name().to_uppercase());
+                let addr = page.addr + reg.addr;
+                self.write(&format!("{name}_OFFSET = 0x{addr:04X} # {}\n", reg.description.get_short()));
+                self.write(&format!("{name}_RESET = 0x{:0hw$X}\n", reg.reset));
+                self.push_stash(&format!("    \"{}\": ({name}_OFFSET, {name}_RESET),\n", format!("{prefix}{}", reg.name()).to_lowercase()));
+            }
+        }
+        self.push_stash("}\n");
+        self.pop_stash();
+
+        // Write file
+        self.save(&format!("{}.py",rif.name(false).to_lowercase()))
+    }
+
+    /// Python class name of an enum: remove package and e_ prefix
+    fn enum_name(name: &str) -> String {
+        let mut etn = match name.rfind("::") {
+            Some(pos) => &name[pos+2..],
+            None => name,
+        };
+        if etn.starts_with("e_") {
+            etn = &etn[2..];
+        }
+        etn.to_casing(Casing::Pascal)
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat()
+        } else {
+            f.name.to_owned()
+        }
+    }
+
+    /// Python module for the rifmux: base address of every instance
+    fn gen_rifmux_module(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        self.txt.clear();
+        // Write header
+        self.write("# File generated automatically: DO NOT EDIT.\n");
+        self.write_docstring(
+            &format!("Register File mapping for {}", rifname.to_uppercase()),
+            &format!("{}\n\nAll addresses are relative to the base address of {rifname}.", rifmux.description.get().trim()),
+        );
+
+        // Imports
+        for rif in rif_list.iter() {
+            self.write(&format!("import {}\n", rif.name(false).to_lowercase()));
+        }
+        self.write("\n");
+        self.write(&format!("ADDR_WIDTH = {}\n", rifmux.addr_width));
+        self.write(&format!("DATA_WIDTH = {}\n\n", rifmux.data_width));
+
+        // Groups
+        for group in rifmux.groups.iter() {
+            self.write(&format!("{}_GROUP_ADDR = 0x{:08X}\n", group.name.to_uppercase(), group.addr));
+        }
+        if !rifmux.groups.is_empty() {
+            self.write("\n");
+        }
+
+        // Mapping
+        self.push_stash("# All instances: name -> (base address, register module)\n");
+        self.push_stash("INSTANCES = {\n");
+        self.add_rifmux_inst(rifmux, "", 0);
+        self.push_stash("}\n");
+        self.pop_stash();
+
+        // Write file
+        self.save(&format!("{rifname}.py"))
+    }
+
+    fn add_rifmux_inst(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64) {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}{}", remove_rif(comp.get_name())).to_lowercase();
+            let name_uc = name.to_uppercase();
+            match &comp.inst {
+                Comp::Rifmux(r) => {
+                    self.write(&format!("# {name}: {}\n", r.description.get_short()));
+                    self.write(&format!("{name_uc}_BASE_ADDR = 0x{addr:08X}\n"));
+                    self.add_rifmux_inst(r, &format!("{name}_"), addr)
+                }
+                Comp::Rif(r) => {
+                    self.write(&format!("# {name}: {}\n", r.description.get_short()));
+                    self.write(&format!("{name_uc}_BASE_ADDR = 0x{addr:08X}\n"));
+                    if r.pages.len() > 1 {
+                        for page in r.pages.iter() {
+                            self.write(&format!("{name_uc}_{}_BASE_ADDR = 0x{:08X}\n", page.name.to_uppercase(), addr + page.addr));
+                        }
+                    }
+                    self.push_stash(&format!("    \"{name}\": ({name_uc}_BASE_ADDR, {}),\n", r.name(false).to_lowercase()));
+                }
+                Comp::External(r) => {
+                    self.write(&format!("# {name}: {}\n", r.description.get_short()));
+                    self.write(&format!("{name_uc}_BASE_ADDR = 0x{addr:08X}\n"));
+                    self.push_stash(&format!("    \"{name}\": ({name_uc}_BASE_ADDR, None),\n"));
+                }
+            }
+            self.write("\n");
+        }
+    }
+
+    //-----------------------------------------------------------------------------
+    // Hierarchical register object model
+    //-----------------------------------------------------------------------------
+
+    /// Python class with one Register attribute per register instance
+    /// (grouped in a sub-block per page when the RIF has more than one page)
+    fn gen_rif_model(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let modname = rif.name(false).to_lowercase();
+        let is_public = self.base_settings.privacy.is_public();
+        let hw = (rif.data_width / 4) as usize;
+
+        self.write("# File generated automatically: DO NOT EDIT.\n");
+        self.write_docstring(&format!("Register model for {}", modname.to_uppercase()), rif.description.get());
+        self.write("from rif_model import Block, Register, Field\n");
+        self.write(&format!("import {modname}\n\n\n"));
+
+        self.write(&format!("class {}(Block):\n\n", Self::class_name(&modname)));
+        self.write(&format!("    def __init__(self, backend, base_addr=0, name=\"{}\"):\n", remove_rif(&modname)));
+        self.write(&format!("        super().__init__(backend, base_addr, name, {})\n", rif.data_width));
+        for page in rif.pages.iter() {
+            if page.is_external() {
+                continue;
+            }
+            // One sub-block per page only when there is more than one
+            let (parent, page_addr) = if rif.pages.len() > 1 {
+                let pname = page.name.to_lowercase();
+                self.write(&format!("        # {}\n", page.description.get_short()));
+                self.write(&format!("        self.{pname} = Block(backend, base_addr + 0x{:04X}, \"{pname}\", {})\n", page.addr, rif.data_width));
+                (format!("self.{pname}"), 0)
+            } else {
+                ("self".to_owned(), page.addr)
+            };
+            let mut arrays : Vec<(String, Vec<String>)> = Vec::new();
+            for reg in page.regs.iter() {
+                if reg.sw_access == Access::NA || (is_public && reg.visibility.is_hidden()) {
+                    continue;
+                }
+                let name = reg.name().to_lowercase();
+                self.write(&format!("        {parent}.{name} = Register({parent}, \"{name}\", 0x{:04X}, 0x{:0hw$X}, [\n", page_addr + reg.addr, reg.reset));
+                for f in reg.fields.iter() {
+                    if is_public && f.visibility.is_hidden() {
+                        continue;
+                    }
+                    let decl = self.field_decl(rif, reg, f, &modname)?;
+                    self.write(&format!("            {decl},\n"));
+                }
+                self.write(&format!("        ], \"{}\")\n", reg.description.get_short().replace('"', "'")));
+                // Keep track of register arrays to allow indexed access
+                if reg.array.dim() > 0 {
+                    let arr_name = reg.reg_name.to_lowercase();
+                    match arrays.iter_mut().find(|(n,_)| *n == arr_name) {
+                        Some((_,l)) => l.push(format!("{parent}.{name}")),
+                        None => arrays.push((arr_name, vec![format!("{parent}.{name}")])),
+                    }
+                }
+            }
+            for (name, regs) in arrays {
+                self.write(&format!("        {parent}.{name} = [{}]\n", regs.join(", ")));
+            }
+        }
+
+        // Write file
+        self.save(&format!("{modname}_model.py"))
+    }
+
+    /// Field declaration with optional reset, enum and signedness
+    fn field_decl(&self, rif: &RifInst, reg: &RifRegInst, f: &RifFieldInst, modname: &str) -> Result<String, String> {
+        let mut decl = format!("Field(\"{}\", {}, {}, \"{}\"",
+            self.get_field_name(reg, f).to_lowercase(), f.lsb, f.width, f.sw_kind.keyword());
+        match f.reset {
+            ResetVal::Signed(v) if v != 0 => decl.push_str(&format!(", reset={v}")),
+            ResetVal::Unsigned(v) if v != 0 => decl.push_str(&format!(", reset=0x{v:X}")),
+            _ => {},
+        }
+        if let EnumKind::Type(enum_name) = &f.enum_kind {
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                if enum_name.contains(':') {enum_name.to_owned()}
+                else {format!("{pkg}_pkg::{enum_name}")}
+            } else {
+                enum_name.to_owned()
+            };
+            // Ensure enum exists
+            rif.get_enum_def(&enum_name)?;
+            decl.push_str(&format!(", enum={modname}.{}", Self::enum_name(&enum_name)));
+        }
+        if f.is_signed() {
+            decl.push_str(", signed=True");
+        }
+        if matches!(f.sw_kind, FieldSwKind::W1Pulse(_,true)) {
+            decl.push_str(", wo=True");
+        }
+        decl.push(')');
+        Ok(decl)
+    }
+
+    fn class_name(name: &str) -> String {
+        name.to_casing(Casing::Pascal)
+    }
+
+    /// Python module for the rifmux model: one class per rifmux (including sub-rifmux)
+    fn gen_rifmux_model(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        self.write("# File generated automatically: DO NOT EDIT.\n");
+        self.write_docstring(&format!("Register model for {}", rifname.to_uppercase()), rifmux.description.get());
+        self.write("from rif_model import Block\n");
+        for rif in rif_list.iter() {
+            let modname = rif.name(false).to_lowercase();
+            self.write(&format!("from {modname}_model import {}\n", Self::class_name(&modname)));
+        }
+        let mut done = Vec::new();
+        self.add_rifmux_class(rifmux, &mut done);
+
+        // Write file
+        self.save(&format!("{rifname}_model.py"))
+    }
+
+    fn add_rifmux_class(&mut self, rifmux: &RifmuxInst, done: &mut Vec<String>) {
+        // Sub-rifmux class must be declared first
+        for comp in rifmux.components.iter() {
+            if let Comp::Rifmux(r) = &comp.inst {
+                if !done.contains(&r.type_name) {
+                    self.add_rifmux_class(r, done);
+                }
+            }
+        }
+        done.push(rifmux.type_name.to_owned());
+        self.write(&format!("\n\nclass {}(Block):\n", Self::class_name(&rifmux.type_name)));
+        if !rifmux.description.is_empty() {
+            self.write(&format!("    \"\"\"{}\"\"\"\n", rifmux.description.get_short().replace('"', "'")));
+        }
+        self.write(&format!("\n    def __init__(self, backend, base_addr=0, name=\"{}\"):\n", rifmux.inst_name));
+        self.write(&format!("        super().__init__(backend, base_addr, name, {})\n", rifmux.data_width));
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups);
+            let name = remove_rif(comp.get_name()).to_lowercase();
+            let class_name = match &comp.inst {
+                Comp::Rifmux(r) => Self::class_name(&r.type_name),
+                Comp::Rif(r) => Self::class_name(&r.name(false).to_lowercase()),
+                // No register model for external component
+                Comp::External(_) => "Block".to_owned(),
+            };
+            let width = if let Comp::External(_) = &comp.inst {format!(", {}", rifmux.data_width)} else {"".to_owned()};
+            self.write(&format!("        self.{name} = {class_name}(backend, base_addr + 0x{addr:08X}, \"{name}\"{width})\n"));
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::{format, fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, FieldSwKind, InterruptRegKind}};
+
+use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RegSwMasks, RifList}};
+
+
+pub struct GeneratorQemu {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorQemu {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorQemu {
+            base_settings: args,
+            txt: String::with_capacity(10000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_model(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_header(rifmux, &rif_list)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_model(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    /// QEMU sysbus device skeleton for each RIF (header + source):
+    ///  - register storage with reset value
+    ///  - software access semantic of each field handled by a table of masks
+    ///  - hardware behaviour left to stub hooks called on read and write
+    fn gen_rif_model(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = rif.name(false).to_lowercase();
+        let rifname_uc = rifname.to_uppercase();
+        let state = format!("{}State", rifname.to_casing(Casing::Pascal));
+        let w = rif.data_width;
+        let nb_byte = (w >> 3) as u64;
+        let hw = (w >> 2) as usize;
+        let type_reg = format!("uint{w}_t");
+
+        // List all registers visible to software
+        let regs : Vec<(u64, &RifRegInst)> = rif.pages.iter()
+            .filter(|p| !p.is_external())
+            .flat_map(|p| p.regs.iter().map(|r| (p.addr + r.addr, r)))
+            .filter(|(_,r)| r.sw_access != Access::NA)
+            .collect();
+        let nb_regs = regs.iter().map(|(a,_)| a / nb_byte + 1).max().unwrap_or(1);
+        let has_irq = regs.iter().any(|(_,r)| r.is_intr());
+
+        // Header: device state
+        self.write("/* File generated automatically: DO NOT EDIT. */\n");
+        self.write(&format!("/* QEMU model of {rifname_uc} */\n"));
+        self.write(&format!("#ifndef HW_{rifname_uc}_H\n"));
+        self.write(&format!("#define HW_{rifname_uc}_H\n\n"));
+        self.write("#include \"hw/sysbus.h\"\n");
+        self.write("#include \"qom/object.h\"\n\n");
+        self.write(&format!("#define TYPE_{rifname_uc} \"{rifname}\"\n"));
+        self.write(&format!("OBJECT_DECLARE_SIMPLE_TYPE({state}, {rifname_uc})\n\n"));
+        self.write(&format!("#define {rifname_uc}_NB_REGS {nb_regs}\n\n"));
+        self.write(&format!("struct {state} {{\n"));
+        self.write("    SysBusDevice parent_obj;\n");
+        self.write("    MemoryRegion iomem;\n");
+        if has_irq {
+            self.write("    qemu_irq irq;\n");
+        }
+        self.write(&format!("    {type_reg} regs[{rifname_uc}_NB_REGS];\n"));
+        self.write("};\n\n");
+        self.write(&format!("#endif /* HW_{rifname_uc}_H */\n"));
+        self.save(&format!("{rifname}.h"))?;
+
+        // Source
+        self.write("/* File generated automatically from the register description.\n");
+        self.write(" * Hardware behaviour must be implemented in the hooks marked TODO. */\n\n");
+        self.write("#include \"qemu/osdep.h\"\n");
+        self.write("#include \"qemu/log.h\"\n");
+        self.write("#include \"qemu/module.h\"\n");
+        self.write("#include \"hw/irq.h\"\n");
+        self.write("#include \"hw/qdev-properties.h\"\n");
+        self.write("#include \"migration/vmstate.h\"\n");
+        self.write(&format!("#include \"{rifname}.h\"\n\n"));
+
+        // Register offsets
+        self.write("/* Register offsets */\n");
+        for (addr, reg) in regs.iter() {
+            self.write(&format!("#define A_{} 0x{addr:04X}\n", reg.name().to_uppercase()));
+        }
+        self.write("\n");
+
+        // Register table
+        self.write("/* Software access semantic of each register */\n");
+        self.write(&format!("typedef struct {rifname}_reg_info {{\n"));
+        self.write("    const char *name;\n");
+        self.write(&format!("    {type_reg} reset;\n"));
+        self.write(&format!("    {type_reg} rw;    /* Bits storing the written value */\n"));
+        self.write(&format!("    {type_reg} w1c;   /* Bits cleared when writing 1 */\n"));
+        self.write(&format!("    {type_reg} w0c;   /* Bits cleared when writing 0 */\n"));
+        self.write(&format!("    {type_reg} w1s;   /* Bits set when writing 1 */\n"));
+        self.write(&format!("    {type_reg} w1t;   /* Bits toggled when writing 1 */\n"));
+        self.write(&format!("    {type_reg} pulse; /* Bits forwarded to the hardware without storage */\n"));
+        self.write(&format!("    {type_reg} rclr;  /* Bits cleared by a read */\n"));
+        self.write(&format!("    {type_reg} rz;    /* Bits read as zero */\n"));
+        self.write(&format!("}} {rifname}_reg_info;\n\n"));
+        self.write(&format!("static const {rifname}_reg_info {rifname}_regs[{rifname_uc}_NB_REGS] = {{\n"));
+        for (_, reg) in regs.iter() {
+            let m = RegSwMasks::new(reg, w);
+            self.write(&format!("    [A_{} / {nb_byte}] = {{\"{}\", 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}}},\n",
+                reg.name().to_uppercase(), reg.name(), reg.reset, m.rw, m.w1c, m.w0c, m.w1s, m.w1t, m.pulse, m.rclr, m.rz));
+        }
+        self.write("};\n\n");
+
+        // Interrupt
+        if has_irq {
+            self.add_irq_update(&regs, &rifname, &state, nb_byte);
+        }
+
+        // Hardware hooks
+        self.write("/* Hardware side: update registers value before a software read */\n");
+        self.write(&format!("static void {rifname}_hw_read({state} *s, hwaddr addr)\n{{\n"));
+        self.write("    switch (addr) {\n");
+        for (_, reg) in regs.iter() {
+            let fields : Vec<String> = reg.fields.iter()
+                .filter(|f| Self::is_hw_write(f))
+                .map(|f| f.name_flat())
+                .collect();
+            // Pending registers are updated with the interrupt line
+            if !fields.is_empty() && reg.sw_access.is_readable() && !reg.is_intr_derived() {
+                self.write(&format!("    case A_{}:\n", reg.name().to_uppercase()));
+                self.write(&format!("        /* TODO: update {} */\n", fields.join(", ")));
+                self.write("        break;\n");
+            }
+        }
+        self.write("    default:\n        break;\n    }\n}\n\n");
+        self.write("/* Hardware side: react to a software write of the register with its new value (pulse contains the written pulse/password bits) */\n");
+        self.write(&format!("static void {rifname}_hw_write({state} *s, hwaddr addr, {type_reg} value, {type_reg} pulse)\n{{\n"));
+        self.write("    switch (addr) {\n");
+        for (_, reg) in regs.iter() {
+            if !reg.sw_access.is_writable() {
+                continue;
+            }
+            let pulses : Vec<String> = reg.fields.iter()
+                .filter(|f| matches!(f.sw_kind, FieldSwKind::W1Pulse(_,_) | FieldSwKind::Password(_)))
+                .map(|f| f.name_flat())
+                .collect();
+            self.write(&format!("    case A_{}:\n", reg.name().to_uppercase()));
+            if pulses.is_empty() {
+                self.write(&format!("        /* TODO: {} */\n", reg.name()));
+            } else {
+                self.write(&format!("        /* TODO: handle {} */\n", pulses.join(", ")));
+            }
+            self.write("        break;\n");
+        }
+        self.write("    default:\n        break;\n    }\n}\n\n");
+
+        // Memory region operations
+        let irq_update = if has_irq {format!("    {rifname}_update_irq(s);\n")} else {"".to_owned()};
+        self.write(&format!("static uint64_t {rifname}_read(void *opaque, hwaddr addr, unsigned size)\n{{\n"));
+        self.write(&format!("    {state} *s = {rifname_uc}(opaque);\n"));
+        self.write(&format!("    const {rifname}_reg_info *info = &{rifname}_regs[addr / {nb_byte}];\n"));
+        self.write(&format!("    {type_reg} value;\n\n"));
+        self.write("    if (addr % size || !info->name) {\n");
+        self.write(&format!("        qemu_log_mask(LOG_GUEST_ERROR, \"%s: bad read offset 0x%\" HWADDR_PRIx \"\\n\", TYPE_{rifname_uc}, addr);\n"));
+        self.write("        return 0;\n    }\n");
+        self.write(&format!("    {rifname}_hw_read(s, addr);\n"));
+        self.write(&format!("    value = s->regs[addr / {nb_byte}] & ~info->rz;\n"));
+        self.write(&format!("    s->regs[addr / {nb_byte}] &= ~info->rclr;\n"));
+        if has_irq {
+            self.write("    if (info->rclr) {\n");
+            self.write(&format!("        {rifname}_update_irq(s);\n"));
+            self.write("    }\n");
+        }
+        self.write("    return value;\n}\n\n");
+        self.write(&format!("static void {rifname}_write(void *opaque, hwaddr addr, uint64_t value, unsigned size)\n{{\n"));
+        self.write(&format!("    {state} *s = {rifname_uc}(opaque);\n"));
+        self.write(&format!("    const {rifname}_reg_info *info = &{rifname}_regs[addr / {nb_byte}];\n"));
+        self.write(&format!("    {type_reg} v = value;\n"));
+        self.write(&format!("    {type_reg} r;\n\n"));
+        self.write("    if (addr % size || !info->name) {\n");
+        self.write(&format!("        qemu_log_mask(LOG_GUEST_ERROR, \"%s: bad write offset 0x%\" HWADDR_PRIx \"\\n\", TYPE_{rifname_uc}, addr);\n"));
+        self.write("        return;\n    }\n");
+        self.write(&format!("    r = s->regs[addr / {nb_byte}];\n"));
+        self.write("    r = (r & ~info->rw) | (v & info->rw);\n");
+        self.write("    r &= ~(v & info->w1c);\n");
+        self.write("    r &= ~(~v & info->w0c);\n");
+        self.write("    r |= v & info->w1s;\n");
+        self.write("    r ^= v & info->w1t;\n");
+        self.write(&format!("    s->regs[addr / {nb_byte}] = r;\n"));
+        self.write(&format!("    {rifname}_hw_write(s, addr, r, v & info->pulse);\n"));
+        self.write(&irq_update);
+        self.write("}\n\n");
+        self.write(&format!("static const MemoryRegionOps {rifname}_ops = {{\n"));
+        self.write(&format!("    .read = {rifname}_read,\n"));
+        self.write(&format!("    .write = {rifname}_write,\n"));
+        self.write("    .endianness = DEVICE_LITTLE_ENDIAN,\n");
+        self.write(&format!("    .valid.min_access_size = {nb_byte},\n"));
+        self.write(&format!("    .valid.max_access_size = {nb_byte},\n"));
+        self.write("};\n\n");
+
+        // Reset, migration and type registration
+        self.write(&format!("static void {rifname}_reset(DeviceState *dev)\n{{\n"));
+        self.write(&format!("    {state} *s = {rifname_uc}(dev);\n\n"));
+        self.write(&format!("    for (int i = 0; i < {rifname_uc}_NB_REGS; i++) {{\n"));
+        self.write(&format!("        s->regs[i] = {rifname}_regs[i].reset;\n"));
+        self.write("    }\n");
+        self.write(&irq_update);
+        self.write("}\n\n");
+        self.write(&format!("static void {rifname}_init(Object *obj)\n{{\n"));
+        self.write(&format!("    {state} *s = {rifname_uc}(obj);\n\n"));
+        self.write(&format!("    memory_region_init_io(&s->iomem, obj, &{rifname}_ops, s, TYPE_{rifname_uc}, 0x{:X});\n", nb_regs * nb_byte));
+        self.write("    sysbus_init_mmio(SYS_BUS_DEVICE(obj), &s->iomem);\n");
+        if has_irq {
+            self.write("    sysbus_init_irq(SYS_BUS_DEVICE(obj), &s->irq);\n");
+        }
+        self.write("}\n\n");
+        self.write(&format!("static const VMStateDescription vmstate_{rifname} = {{\n"));
+        self.write(&format!("    .name = TYPE_{rifname_uc},\n"));
+        self.write("    .version_id = 1,\n");
+        self.write("    .minimum_version_id = 1,\n");
+        self.write("    .fields = (const VMStateField[]) {\n");
+        self.write(&format!("        VMSTATE_UINT{w}_ARRAY(regs, {state}, {rifname_uc}_NB_REGS),\n"));
+        self.write("        VMSTATE_END_OF_LIST()\n");
+        self.write("    }\n};\n\n");
+        self.write(&format!("static void {rifname}_class_init(ObjectClass *klass, void *data)\n{{\n"));
+        self.write("    DeviceClass *dc = DEVICE_CLASS(klass);\n\n");
+        self.write(&format!("    device_class_set_legacy_reset(dc, {rifname}_reset);\n"));
+        self.write(&format!("    dc->vmsd = &vmstate_{rifname};\n"));
+        self.write("}\n\n");
+        self.write(&format!("static const TypeInfo {rifname}_info = {{\n"));
+        self.write(&format!("    .name = TYPE_{rifname_uc},\n"));
+        self.write("    .parent = TYPE_SYS_BUS_DEVICE,\n");
+        self.write(&format!("    .instance_size = sizeof({state}),\n"));
+        self.write(&format!("    .instance_init = {rifname}_init,\n"));
+        self.write(&format!("    .class_init = {rifname}_class_init,\n"));
+        self.write("};\n\n");
+        self.write(&format!("static void {rifname}_register_types(void)\n{{\n"));
+        self.write(&format!("    type_register_static(&{rifname}_info);\n"));
+        self.write("}\n\n");
+        self.write(&format!("type_init({rifname}_register_types)\n"));
+
+        // Write file
+        self.save(&format!("{rifname}.c"))
+    }
+
+    /// Update pending registers and interrupt line:
+    /// pending is the interrupt status masked by the optional mask register
+    fn add_irq_update(&mut self, regs: &[(u64, &RifRegInst)], rifname: &str, state: &str, nb_byte: u64) {
+        self.write("/* Update pending registers and interrupt line */\n");
+        self.write(&format!("static void {rifname}_update_irq({state} *s)\n{{\n"));
+        self.write("    bool level = false;\n\n");
+        for (_, reg) in regs.iter().filter(|(_,r)| r.is_intr()) {
+            // Find derived registers of the same interrupt
+            let find = |kind: InterruptRegKind| regs.iter()
+                .find(|(_,r)| r.intr_info.0 == kind && r.group_name() == reg.group_name() && r.array.idx() == reg.array.idx())
+                .map(|(_,r)| format!("s->regs[A_{} / {nb_byte}]", r.name().to_uppercase()));
+            let status = format!("s->regs[A_{} / {nb_byte}]", reg.name().to_uppercase());
+            let masked = if let Some(mask) = find(InterruptRegKind::Mask) {
+                format!("({status} & {mask})")
+            } else {
+                status
+            };
+            self.write(&format!("    /* {} */\n", reg.name()));
+            if let Some(pending) = find(InterruptRegKind::Pending) {
+                self.write(&format!("    {pending} = {masked};\n"));
+                self.write(&format!("    level |= {pending} != 0;\n"));
+            } else {
+                self.write(&format!("    level |= {masked} != 0;\n"));
+            }
+        }
+        self.write("    qemu_set_irq(s->irq, level);\n");
+        self.write("}\n\n");
+    }
+
+    /// Field value updated by the hardware
+    fn is_hw_write(f: &RifFieldInst) -> bool {
+        f.hw_access.is_writable() || f.is_hw_write()
+    }
+
+    /// Header for the rifmux: address of each device and helper to create them
+    fn gen_rifmux_header(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        let rifname_uc = rifname.to_uppercase();
+        self.write("/* File generated automatically: DO NOT EDIT. */\n");
+        self.write("/* Register File mapping */\n");
+        self.write(&format!("#ifndef HW_{rifname_uc}_H\n"));
+        self.write(&format!("#define HW_{rifname_uc}_H\n\n"));
+        for rif in rif_list.iter() {
+            self.write(&format!("#include \"{}.h\"\n", rif.name(false).to_lowercase()));
+        }
+        self.write("\n");
+        let mut devices = Vec::new();
+        self.add_rifmux_offsets(rifmux, &rifname_uc, 0, &mut devices);
+        self.write("\n/* Create all devices of the register file mapping */\n");
+        self.write(&format!("static inline void {rifname}_create(hwaddr base)\n{{\n"));
+        for (name, type_name) in devices {
+            self.write(&format!("    sysbus_create_simple(TYPE_{}, base + {name}_OFFSET, NULL);\n", type_name.to_uppercase()));
+        }
+        self.write("}\n");
+        self.write(&format!("\n#endif /* HW_{rifname_uc}_H */\n"));
+        // Write file
+        self.save(&format!("{rifname}.h"))
+    }
+
+    fn add_rifmux_offsets(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64, devices: &mut Vec<(String,String)>) {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}_{}", remove_rif(comp.get_name()).to_uppercase());
+            self.write(&format!("#define {name}_OFFSET 0x{addr:08X} /* {} */\n", comp.get_desc_short()));
+            match &comp.inst {
+                Comp::Rifmux(r) => self.add_rifmux_offsets(r, &name, addr, devices),
+                Comp::Rif(r) => devices.push((name, r.name(false))),
+                Comp::External(_) => {},
+            }
+        }
+    }
+}
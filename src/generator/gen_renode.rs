@@ -0,0 +1,343 @@
+use std::{format, fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, EnumKind, FieldSwKind, InterruptRegKind}};
+
+use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RifList}};
+
+
+/// Namespace of generated peripherals (relative to Antmicro.Renode.Peripherals in platform description)
+const NAMESPACE: &str = "Miscellaneous";
+
+pub struct GeneratorRenode {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorRenode {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorRenode {
+            base_settings: args,
+            txt: String::with_capacity(10000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_peripheral(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_platform(rifmux)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_peripheral(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    /// C# peripheral for each RIF based on the register collection of Renode:
+    /// all register instances are defined with their reset value and fields,
+    /// and interrupt registers drive the IRQ line
+    fn gen_rif_peripheral(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let class_name = rif.name(false).to_casing(Casing::Pascal);
+        let w = rif.data_width;
+        let nb_byte = (w >> 3) as u64;
+        let hw = (w >> 2) as usize;
+        let (base_class, reg_class) = match w {
+            8  => ("BasicBytePeripheral", "ByteRegister"),
+            16 => ("BasicWordPeripheral", "WordRegister"),
+            32 => ("BasicDoubleWordPeripheral", "DoubleWordRegister"),
+            _ => return Err(format!("Renode peripheral does not support data width of {w} bits").into()),
+        };
+
+        // List all registers visible to software
+        let regs : Vec<(u64, &RifRegInst)> = rif.pages.iter()
+            .filter(|p| !p.is_external())
+            .flat_map(|p| p.regs.iter().map(|r| (p.addr + r.addr, r)))
+            .filter(|(_,r)| r.sw_access != Access::NA)
+            .collect();
+        let size = regs.iter().map(|(a,_)| a + nb_byte).max().unwrap_or(nb_byte);
+        // Registers referenced to compute the interrupt line
+        let intr_regs : Vec<&RifRegInst> = regs.iter()
+            .filter(|(_,r)| r.intr_info.0 != InterruptRegKind::None)
+            .map(|(_,r)| *r)
+            .collect();
+        let has_irq = intr_regs.iter().any(|r| r.is_intr());
+
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        for l in rif.description.get().trim().lines() {
+            self.write(&format!("// {}\n", l.trim()));
+        }
+        self.write("using Antmicro.Renode.Core;\n");
+        self.write("using Antmicro.Renode.Core.Structure.Registers;\n");
+        self.write("using Antmicro.Renode.Peripherals.Bus;\n\n");
+        self.write(&format!("namespace Antmicro.Renode.Peripherals.{NAMESPACE}\n{{\n"));
+        self.write(&format!("    public class {class_name} : {base_class}, IKnownSize\n    {{\n"));
+        self.write(&format!("        public {class_name}(IMachine machine) : base(machine)\n        {{\n"));
+        if has_irq {
+            self.write("            IRQ = new GPIO();\n");
+        }
+        self.write("            DefineRegisters();\n");
+        self.write("        }\n\n");
+        self.write("        public override void Reset()\n        {\n");
+        self.write("            base.Reset();\n");
+        if has_irq {
+            self.write("            UpdateInterrupts();\n");
+        }
+        self.write("        }\n\n");
+        self.write(&format!("        public long Size => 0x{size:X};\n\n"));
+        if has_irq {
+            self.write("        public GPIO IRQ { get; }\n\n");
+        }
+
+        // Enum declaration
+        for def in rif.enum_defs.iter() {
+            if def.name.starts_with("doc:") {
+                continue;
+            }
+            if !def.description.is_empty() {
+                self.write(&format!("        // {}\n", def.description));
+            }
+            self.write(&format!("        public enum {}\n        {{\n", Self::enum_name(&def.name)));
+            for e in def.iter() {
+                self.write(&format!("            {} = {},", Self::variant_name(&e.name), e.value));
+                if !e.description.is_empty() {
+                    self.write(&format!(" // {}", e.description.get_short()));
+                }
+                self.write("\n");
+            }
+            self.write("        }\n\n");
+        }
+
+        // Register definitions
+        self.write("        private void DefineRegisters()\n        {\n");
+        for (_, reg) in regs.iter() {
+            let reg_name = reg.name().to_casing(Casing::Pascal);
+            let desc = reg.description.get_short();
+            if !desc.is_empty() {
+                self.write(&format!("            // {desc}\n"));
+            }
+            if Self::is_intr_var(reg) {
+                self.write(&format!("            {} = ", Self::reg_var(reg)));
+            } else {
+                self.write("            ");
+            }
+            self.write(&format!("Registers.{reg_name}.Define(this, 0x{:0hw$X}, name: \"{}\")\n", reg.reset, reg.name()));
+            let mut pos = 0;
+            for f in reg.fields.iter() {
+                if f.lsb > pos {
+                    self.write(&format!("                .WithReservedBits({pos}, {})\n", f.lsb - pos));
+                }
+                pos = f.lsb + f.width;
+                if self.base_settings.privacy.is_public() && (f.is_reserved() || f.visibility.is_hidden()) {
+                    self.write(&format!("                .WithReservedBits({}, {})\n", f.lsb, f.width));
+                    continue;
+                }
+                self.add_field(rif, reg, f, reg_class, &intr_regs)?;
+            }
+            if pos < w {
+                self.write(&format!("                .WithReservedBits({pos}, {})\n", w - pos));
+            }
+            self.write("            ;\n\n");
+        }
+        self.write("        }\n");
+
+        // Interrupt
+        if has_irq {
+            self.write("\n        private void UpdateInterrupts()\n        {\n");
+            self.write("            var state = false;\n");
+            for reg in intr_regs.iter().filter(|r| r.is_intr()) {
+                let status = format!("{}.Value", Self::reg_var(reg));
+                match Self::find_intr_reg(&intr_regs, reg, InterruptRegKind::Mask) {
+                    Some(mask) => self.write(&format!("            state |= ({status} & {}.Value) != 0;\n", Self::reg_var(mask))),
+                    None => self.write(&format!("            state |= {status} != 0;\n")),
+                }
+            }
+            self.write("            IRQ.Set(state);\n");
+            self.write("        }\n");
+        }
+
+        // Register variables for interrupt
+        if has_irq {
+            self.write("\n");
+            for reg in intr_regs.iter().filter(|r| Self::is_intr_var(r)) {
+                self.write(&format!("        private {reg_class} {};\n", Self::reg_var(reg)));
+            }
+        }
+
+        // Register offsets
+        self.write("\n        private enum Registers : long\n        {\n");
+        for (addr, reg) in regs.iter() {
+            self.write(&format!("            {} = 0x{addr:X},\n", reg.name().to_casing(Casing::Pascal)));
+        }
+        self.write("        }\n");
+        self.write("    }\n}\n");
+
+        // Write file
+        self.save(&format!("{class_name}.cs"))
+    }
+
+    /// Add field definition to a register
+    fn add_field(&mut self, rif: &RifInst, reg: &RifRegInst, f: &RifFieldInst, reg_class: &str, intr_regs: &[&RifRegInst]) -> Result<(), String> {
+        let name = self.get_field_name(reg, f);
+        let mode = Self::field_mode(f);
+        let mask = (1_u128 << f.width) - 1;
+        let enum_type = if let EnumKind::Type(enum_name) = &f.enum_kind {
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                if enum_name.contains(':') {enum_name.to_owned()}
+                else {format!("{pkg}_pkg::{enum_name}")}
+            } else {
+                enum_name.to_owned()
+            };
+            // Ensure enum exists
+            rif.get_enum_def(&enum_name)?;
+            Some(Self::enum_name(&enum_name))
+        } else {
+            None
+        };
+        // Interrupt: pending value is computed from status and mask,
+        // and any change on status/enable/mask updates the IRQ line
+        let mut callback = "".to_owned();
+        if reg.intr_info.0 == InterruptRegKind::Pending {
+            let status = Self::find_intr_reg(intr_regs, reg, InterruptRegKind::None);
+            let mask_reg = Self::find_intr_reg(intr_regs, reg, InterruptRegKind::Mask);
+            if let Some(status) = status {
+                let mut value = format!("{}.Value", Self::reg_var(status));
+                if let Some(mask_reg) = mask_reg {
+                    value = format!("({value} & {}.Value)", Self::reg_var(mask_reg));
+                }
+                callback = if f.width == 1 {
+                    format!(", valueProviderCallback: _ => ({value} & 0x{:X}) != 0", 1_u128 << f.lsb)
+                } else {
+                    format!(", valueProviderCallback: _ => (ulong)(({value} >> {}) & 0x{mask:X})", f.lsb)
+                };
+            }
+        } else if reg.intr_info.0 != InterruptRegKind::None {
+            callback = ", changeCallback: (_, __) => UpdateInterrupts()".to_owned();
+        }
+        if let Some(enum_type) = enum_type {
+            self.write(&format!("                .WithEnumField<{reg_class}, {enum_type}>({}, {}, {mode}{callback}, name: \"{name}\")\n", f.lsb, f.width));
+        } else if f.width == 1 {
+            self.write(&format!("                .WithFlag({}, {mode}{callback}, name: \"{name}\")\n", f.lsb));
+        } else {
+            self.write(&format!("                .WithValueField({}, {}, {mode}{callback}, name: \"{name}\")\n", f.lsb, f.width));
+        }
+        Ok(())
+    }
+
+    /// Find register of an interrupt (None for the base register)
+    fn find_intr_reg<'a>(intr_regs: &[&'a RifRegInst], reg: &RifRegInst, kind: InterruptRegKind) -> Option<&'a RifRegInst> {
+        let kind = if kind == InterruptRegKind::None {InterruptRegKind::Base} else {kind};
+        intr_regs.iter()
+            .find(|r| r.intr_info.0 == kind && r.group_name() == reg.group_name() && r.array.idx() == reg.array.idx())
+            .copied()
+    }
+
+    /// Renode field mode from software access kind
+    fn field_mode(f: &RifFieldInst) -> &'static str {
+        match f.sw_kind {
+            FieldSwKind::ReadWrite    => "FieldMode.Read | FieldMode.Write",
+            FieldSwKind::ReadOnly     => "FieldMode.Read",
+            FieldSwKind::WriteOnly    => "FieldMode.Write",
+            FieldSwKind::ReadClr      => "FieldMode.Read | FieldMode.ReadToClear",
+            FieldSwKind::W1Clr        => "FieldMode.Read | FieldMode.WriteOneToClear",
+            FieldSwKind::W0Clr        => "FieldMode.Read | FieldMode.WriteZeroToClear",
+            FieldSwKind::W1Set        => "FieldMode.Read | FieldMode.Set",
+            FieldSwKind::W1Tgl        => "FieldMode.Read | FieldMode.Toggle",
+            // Pulse are never stored and always read as 0
+            FieldSwKind::W1Pulse(_,_) => "FieldMode.Write",
+            FieldSwKind::Password(_)  => "FieldMode.Write",
+        }
+    }
+
+    /// Interrupt status and mask registers are kept to compute the IRQ line and pending value
+    fn is_intr_var(reg: &RifRegInst) -> bool {
+        matches!(reg.intr_info.0, InterruptRegKind::Base | InterruptRegKind::Mask)
+    }
+
+    /// Name of the variable holding a register
+    fn reg_var(reg: &RifRegInst) -> String {
+        format!("{}Reg", reg.name().to_casing(Casing::Camel))
+    }
+
+    /// C# enum name: remove package and e_ prefix
+    fn enum_name(name: &str) -> String {
+        let mut etn = match name.rfind("::") {
+            Some(pos) => &name[pos+2..],
+            None => name,
+        };
+        if etn.starts_with("e_") {
+            etn = &etn[2..];
+        }
+        etn.to_casing(Casing::Pascal)
+    }
+
+    fn variant_name(name: &str) -> String {
+        let name = name.to_casing(Casing::Pascal);
+        if name.starts_with(|c: char| c.is_ascii_digit()) {format!("V{name}")} else {name}
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat()
+        } else {
+            f.name.to_owned()
+        }
+    }
+
+    /// Renode platform description for the rifmux: one peripheral per RIF instance
+    /// with addresses relative to the rifmux base address
+    fn gen_rifmux_platform(&mut self, rifmux: &RifmuxInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        self.write(&format!("// Register File mapping {rifname}: addresses relative to the base address\n\n"));
+        self.add_rifmux_peripherals(rifmux, "", 0);
+        // Write file
+        self.save(&format!("{rifname}.repl"))
+    }
+
+    fn add_rifmux_peripherals(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64) {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}{}", remove_rif(comp.get_name()).to_lowercase());
+            match &comp.inst {
+                Comp::Rifmux(r) => self.add_rifmux_peripherals(r, &format!("{name}_"), addr),
+                Comp::Rif(r) => {
+                    self.write(&format!("// {}\n", comp.get_desc_short()));
+                    self.write(&format!("{name}: {NAMESPACE}.{} @ sysbus 0x{addr:08X}\n\n", r.name(false).to_casing(Casing::Pascal)));
+                }
+                Comp::External(_) => {},
+            }
+        }
+    }
+}
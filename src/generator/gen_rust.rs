@@ -0,0 +1,433 @@
+use std::{collections::HashSet, fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, EnumKind, FieldSwKind}};
+
+use super::{casing::{Casing, ToCasing}, gen_common::{GeneratorBaseSetting, RegSwMasks, RifList}};
+
+
+/// Support library for register access
+const RIF_REG_LIB: &str = include_str!("rust/rif_reg.rs");
+
+pub struct GeneratorRust {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+    stash: String,
+}
+
+impl GeneratorRust {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorRust {
+            base_settings: args,
+            txt: String::with_capacity(10000),
+            stash: String::with_capacity(1000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn push_stash(&mut self, string: &str) {
+        self.stash.push_str(string);
+    }
+
+    fn pop_stash(&mut self) {
+        self.txt.push_str(&self.stash);
+        self.stash.clear();
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    /// Generate one no_std module per RIF/Rifmux: all modules are expected
+    /// to be declared as siblings of the rif_reg support module
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        if !matches!(obj, Comp::External(_)) {
+            self.write(RIF_REG_LIB);
+            self.save("rif_reg.rs")?;
+        }
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_module(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_module(rifmux, &rif_list)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_module(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    /// Write module documentation
+    fn write_doc(&mut self, title: &str, desc: &str) {
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        self.write(&format!("//! {title}\n"));
+        if !desc.trim().is_empty() {
+            self.write("//!\n");
+            for l in desc.trim().lines() {
+                self.write(&format!("//! {}\n", l.trim()).replace("//! \n", "//!\n"));
+            }
+        }
+        self.write("\n");
+    }
+
+    /// Rust module for each RIF
+    ///  - one enum per enum definition
+    ///  - one value type per register type with field accessors
+    ///  - one struct giving access to every register instance from a base address
+    fn gen_rif_module(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let modname = rif.name(false).to_lowercase();
+        let raw = format!("u{}", rif.data_width);
+        let hw = (rif.data_width / 4) as usize;
+
+        self.write_doc(&format!("Register definition for {}", modname.to_uppercase()), rif.description.get());
+        // Import only the access markers used by the register block
+        let mut markers = vec!["Reg", "RegValue"];
+        for page in rif.pages.iter().filter(|p| !p.is_external()) {
+            for reg in page.regs.iter().filter(|r| r.sw_access != Access::NA) {
+                let m = Self::access_marker(reg);
+                if !markers.contains(&m) {
+                    markers.push(m);
+                }
+            }
+        }
+        self.write(&format!("use super::rif_reg::{{{}}};\n", markers.join(", ")));
+
+        // Enum declaration
+        for def in rif.enum_defs.iter() {
+            if def.name.starts_with("doc:") {
+                continue;
+            }
+            let name = Self::enum_name(&def.name);
+            self.write("\n");
+            if !def.description.is_empty() {
+                self.write(&format!("/// {}\n", def.description));
+            }
+            self.write("#[repr(u8)]\n#[derive(Clone, Copy, Debug, PartialEq, Eq)]\n");
+            self.write(&format!("pub enum {name} {{\n"));
+            for entry in def.iter() {
+                if !entry.description.is_empty() {
+                    self.write(&format!("    /// {}\n", entry.description.get_short()));
+                }
+                self.write(&format!("    {} = {},\n", Self::variant_name(&entry.name), entry.value));
+            }
+            self.write("}\n\n");
+            self.write(&format!("impl {name} {{\n"));
+            self.write("    /// Convert a raw value, returning None for undefined values\n");
+            self.write("    pub const fn from_bits(value: u8) -> Option<Self> {\n");
+            self.write("        match value {\n");
+            for entry in def.iter() {
+                self.write(&format!("            {} => Some(Self::{}),\n", entry.value, Self::variant_name(&entry.name)));
+            }
+            self.write("            _ => None,\n");
+            self.write("        }\n    }\n}\n");
+        }
+
+        // Register value types
+        let mut types_done : HashSet<String> = HashSet::new();
+        for page in rif.pages.iter().filter(|p| !p.is_external()) {
+            for reg in page.regs.iter() {
+                // Check if register is hidden/reserved in all instances
+                if reg.sw_access == Access::NA {
+                    continue;
+                }
+                let type_name = reg.expanded_type_name().to_casing(Casing::Pascal);
+                if !types_done.insert(type_name.clone()) {
+                    continue;
+                }
+                self.write("\n");
+                for l in reg.base_description.get().lines() {
+                    self.write(&format!("/// {}\n", l.trim()));
+                }
+                self.write("#[derive(Clone, Copy, Debug, PartialEq, Eq)]\n");
+                self.write(&format!("pub struct {type_name}(pub {raw});\n\n"));
+                self.write(&format!("impl RegValue for {type_name} {{\n"));
+                self.write(&format!("    type Raw = {raw};\n"));
+                self.write(&format!("    fn from_raw(raw: {raw}) -> Self {{ {type_name}(raw) }}\n"));
+                self.write(&format!("    fn to_raw(self) -> {raw} {{ self.0 }}\n"));
+                // Fields with a write side-effect must not be written back by a read-modify-write
+                let masks = RegSwMasks::new(reg, rif.data_width);
+                if masks.w1c | masks.w0c | masks.w1s | masks.w1t | masks.pulse != 0 {
+                    self.write(&format!("    fn neutral(self) -> Self {{ {type_name}((self.0 & 0x{:0hw$X}) | 0x{:0hw$X}) }}\n", masks.rw & !masks.rz, masks.w0c));
+                }
+                self.write("}\n\n");
+                self.write(&format!("impl {type_name} {{\n"));
+                for f in reg.fields.iter() {
+                    self.add_field_accessors(rif, reg, f)?;
+                }
+                self.write("}\n");
+            }
+        }
+
+        // Register block
+        let block_name = modname.to_casing(Casing::Pascal);
+        self.write("\n");
+        for l in rif.description.get().lines() {
+            self.write(&format!("/// {}\n", l.trim()));
+        }
+        self.write(&format!("pub struct {block_name} {{\n    base: usize,\n}}\n\n"));
+        self.write(&format!("impl {block_name} {{\n"));
+        self.write("    /// Create a register block\n");
+        self.write("    ///\n    /// # Safety\n");
+        self.write(&format!("    /// The base address must point to a {} instance\n", modname.to_uppercase()));
+        self.write(&format!("    pub const unsafe fn new(base: usize) -> Self {{\n        {block_name} {{base}}\n    }}\n\n"));
+        self.write("    /// Base address of the register block\n");
+        self.write("    pub const fn base(&self) -> usize {\n        self.base\n    }\n");
+        for page in rif.pages.iter().filter(|p| !p.is_external()) {
+            let mut regs = page.regs.iter().filter(|r| r.sw_access != Access::NA).peekable();
+            while let Some(reg) = regs.next() {
+                let type_name = reg.expanded_type_name().to_casing(Casing::Pascal);
+                let access = Self::access_marker(reg);
+                let name = reg.reg_name.to_lowercase();
+                let name_uc = name.to_uppercase();
+                let addr = page.addr + reg.addr;
+                // Register array: one accessor with an index
+                if reg.array.dim() > 0 {
+                    if reg.array.idx() > 0 {
+                        continue;
+                    }
+                    let dim = reg.array.dim() as usize;
+                    let stride = regs.peek().map(|r| page.addr + r.addr - addr).unwrap_or((rif.data_width >> 3) as u64);
+                    let desc = reg.base_description.get_short();
+                    self.push_stash(&format!("    pub const {name_uc}_OFFSET: usize = 0x{addr:04X};\n"));
+                    self.push_stash(&format!("    pub const {name_uc}_STRIDE: usize = 0x{stride:X};\n"));
+                    self.push_stash(&format!("    pub const {name_uc}_RESET: [{type_name}; {dim}] = ["));
+                    let resets : Vec<String> = page.regs.iter()
+                        .filter(|r| r.reg_name == reg.reg_name)
+                        .map(|r| format!("{type_name}(0x{:0hw$X})", r.reset))
+                        .collect();
+                    self.push_stash(&format!("{}];\n", resets.join(", ")));
+                    self.write(&format!("\n    /// {desc}\n"));
+                    self.write("    #[inline(always)]\n");
+                    self.write(&format!("    pub fn {}(&self, idx: usize) -> Reg<{type_name}, {access}> {{\n", Self::ident(&name)));
+                    self.write(&format!("        assert!(idx < {dim});\n"));
+                    self.write(&format!("        unsafe {{ Reg::new(self.base + Self::{name_uc}_OFFSET + idx * Self::{name_uc}_STRIDE) }}\n"));
+                    self.write("    }\n");
+                } else {
+                    self.push_stash(&format!("    pub const {name_uc}_OFFSET: usize = 0x{addr:04X};\n"));
+                    self.push_stash(&format!("    pub const {name_uc}_RESET: {type_name} = {type_name}(0x{:0hw$X});\n", reg.reset));
+                    self.write(&format!("\n    /// {}\n", reg.description.get_short()));
+                    self.write("    #[inline(always)]\n");
+                    self.write(&format!("    pub fn {}(&self) -> Reg<{type_name}, {access}> {{\n", Self::ident(&name)));
+                    self.write(&format!("        unsafe {{ Reg::new(self.base + Self::{name_uc}_OFFSET) }}\n"));
+                    self.write("    }\n");
+                }
+            }
+        }
+        self.write("}\n\n");
+        self.write("/// Offset and reset value of all registers\n");
+        self.write(&format!("impl {block_name} {{\n"));
+        self.pop_stash();
+        self.write("}\n");
+
+        // Write file
+        self.save(&format!("{modname}.rs"))
+    }
+
+    /// Getter for readable fields and setter for writable fields
+    fn add_field_accessors(&mut self, rif: &RifInst, reg: &RifRegInst, f: &RifFieldInst) -> Result<(), String> {
+        let name = self.get_field_name(reg, f).to_lowercase();
+        let mask = (1_u128 << f.width) - 1;
+        let lsb = f.lsb;
+        let desc = f.base_description.get_short();
+        // Type of the field value
+        let enum_type = if let EnumKind::Type(enum_name) = &f.enum_kind {
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                if enum_name.contains(':') {enum_name.to_owned()}
+                else {format!("{pkg}_pkg::{enum_name}")}
+            } else {
+                enum_name.to_owned()
+            };
+            // Ensure enum exists
+            rif.get_enum_def(&enum_name)?;
+            Some(Self::enum_name(&enum_name))
+        } else {
+            None
+        };
+        let raw = format!("u{}", rif.data_width);
+        let int_width = f.width.next_power_of_two().max(8);
+        let (val_type, getter, setter) = match &enum_type {
+            Some(e) => (
+                e.to_owned(),
+                format!("{e}::from_bits(((self.0 >> {lsb}) & 0x{mask:X}) as u8)"),
+                format!("value as u8 as {raw}"),
+            ),
+            None if f.width == 1 => (
+                "bool".to_owned(),
+                format!("(self.0 >> {lsb}) & 1 != 0"),
+                format!("value as {raw}"),
+            ),
+            None if f.is_signed() && int_width == f.width => (
+                format!("i{int_width}"),
+                format!("((self.0 >> {lsb}) & 0x{mask:X}) as i{int_width}"),
+                format!("value as {raw}"),
+            ),
+            None if f.is_signed() => (
+                format!("i{int_width}"),
+                format!("(((self.0 >> {lsb}) & 0x{mask:X}) as i{int_width}) << {0} >> {0}", int_width - f.width),
+                format!("value as {raw}"),
+            ),
+            None => (
+                format!("u{int_width}"),
+                format!("((self.0 >> {lsb}) & 0x{mask:X}) as u{int_width}"),
+                format!("value as {raw}"),
+            ),
+        };
+        if !f.sw_kind.is_wo() {
+            let ret_type = if enum_type.is_some() {format!("Option<{val_type}>")} else {val_type.to_owned()};
+            self.write(&format!("    /// {desc}\n"));
+            self.write("    #[inline(always)]\n");
+            self.write(&format!("    pub fn {}(&self) -> {ret_type} {{\n        {getter}\n    }}\n", Self::ident(&name)));
+        }
+        if !matches!(f.sw_kind, FieldSwKind::ReadOnly | FieldSwKind::ReadClr) {
+            self.write(&format!("    /// {desc}\n"));
+            self.write("    #[inline(always)]\n");
+            self.write(&format!("    pub fn set_{name}(&mut self, value: {val_type}) -> &mut Self {{\n"));
+            self.write(&format!("        self.0 = (self.0 & !(0x{mask:X} << {lsb})) | ((({setter}) & 0x{mask:X}) << {lsb});\n"));
+            self.write("        self\n    }\n");
+        }
+        Ok(())
+    }
+
+    /// Access marker of a register
+    fn access_marker(reg: &RifRegInst) -> &'static str {
+        match reg.sw_access {
+            Access::RO => "RO",
+            Access::WO => "WO",
+            _ => "RW",
+        }
+    }
+
+    /// Rust enum name: remove package and e_ prefix
+    fn enum_name(name: &str) -> String {
+        let mut etn = match name.rfind("::") {
+            Some(pos) => &name[pos+2..],
+            None => name,
+        };
+        if etn.starts_with("e_") {
+            etn = &etn[2..];
+        }
+        etn.to_casing(Casing::Pascal)
+    }
+
+    fn variant_name(name: &str) -> String {
+        let name = name.to_casing(Casing::Pascal);
+        if name.starts_with(|c: char| c.is_ascii_digit()) {format!("V{name}")} else {name}
+    }
+
+    /// Escape identifiers which are Rust keywords
+    fn ident(name: &str) -> String {
+        match name {
+            "self" | "super" | "crate" | "Self" => format!("{name}_"),
+            "as" | "break" | "const" | "continue" | "else" | "enum" | "extern" | "false" | "fn" |
+            "for" | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move" | "mut" |
+            "pub" | "ref" | "return" | "static" | "struct" | "trait" | "true" | "type" |
+            "unsafe" | "use" | "where" | "while" | "async" | "await" | "dyn" | "abstract" |
+            "become" | "box" | "do" | "final" | "macro" | "override" | "priv" | "typeof" |
+            "unsized" | "virtual" | "yield" | "try" => format!("r#{name}"),
+            _ => name.to_owned(),
+        }
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat()
+        } else {
+            f.name.to_owned()
+        }
+    }
+
+    /// Rust module for the rifmux: one struct per rifmux (including sub-rifmux)
+    /// giving access to the register block of each instance
+    fn gen_rifmux_module(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        self.write_doc(
+            &format!("Register File mapping for {}", rifname.to_uppercase()),
+            &format!("{}\n\nAll addresses are relative to the base address of {rifname}.", rifmux.description.get().trim()),
+        );
+        for rif in rif_list.iter() {
+            self.write(&format!("use super::{};\n", rif.name(false).to_lowercase()));
+        }
+        let mut done = Vec::new();
+        self.add_rifmux_struct(rifmux, &mut done);
+
+        // Write file
+        self.save(&format!("{rifname}.rs"))
+    }
+
+    fn add_rifmux_struct(&mut self, rifmux: &RifmuxInst, done: &mut Vec<String>) {
+        // Sub-rifmux struct are declared first
+        for comp in rifmux.components.iter() {
+            if let Comp::Rifmux(r) = &comp.inst {
+                if !done.contains(&r.type_name) {
+                    self.add_rifmux_struct(r, done);
+                }
+            }
+        }
+        done.push(rifmux.type_name.to_owned());
+        let struct_name = rifmux.type_name.to_casing(Casing::Pascal);
+        self.write("\n");
+        if !rifmux.description.is_empty() {
+            self.write(&format!("/// {}\n", rifmux.description.get_short()));
+        }
+        self.write(&format!("pub struct {struct_name} {{\n    base: usize,\n}}\n\n"));
+        self.write(&format!("impl {struct_name} {{\n"));
+        self.write("    /// Create the register file mapping\n");
+        self.write("    ///\n    /// # Safety\n");
+        self.write(&format!("    /// The base address must point to a {} instance\n", rifmux.type_name.to_uppercase()));
+        self.write(&format!("    pub const unsafe fn new(base: usize) -> Self {{\n        {struct_name} {{base}}\n    }}\n\n"));
+        self.write("    /// Base address of the register file mapping\n");
+        self.write("    pub const fn base(&self) -> usize {\n        self.base\n    }\n\n");
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups);
+            let name = remove_rif(comp.get_name()).to_lowercase();
+            let name_uc = name.to_uppercase();
+            self.write(&format!("    /// {}: 0x{addr:08X}\n", comp.get_desc_short()));
+            self.write(&format!("    pub const {name_uc}_OFFSET: usize = 0x{addr:08X};\n"));
+            let block = match &comp.inst {
+                Comp::Rifmux(r) => r.type_name.to_casing(Casing::Pascal),
+                Comp::Rif(r) => {
+                    let modname = r.name(false).to_lowercase();
+                    format!("{modname}::{}", modname.to_casing(Casing::Pascal))
+                }
+                // No register definition for external component: only the address
+                Comp::External(_) => {
+                    self.push_stash(&format!("\n    /// {}\n", comp.get_desc_short()));
+                    self.push_stash(&format!("    pub const fn {name}_addr(&self) -> usize {{\n"));
+                    self.push_stash(&format!("        self.base + Self::{name_uc}_OFFSET\n    }}\n"));
+                    continue;
+                }
+            };
+            self.push_stash(&format!("\n    /// {}\n", comp.get_desc_short()));
+            self.push_stash("    #[inline(always)]\n");
+            self.push_stash(&format!("    pub fn {}(&self) -> {block} {{\n", Self::ident(&name)));
+            self.push_stash(&format!("        unsafe {{ {block}::new(self.base + Self::{name_uc}_OFFSET) }}\n    }}\n"));
+        }
+        self.pop_stash();
+        self.write("}\n");
+    }
+}
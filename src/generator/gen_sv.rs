@@ -6,7 +6,7 @@ use std::{
 
 use crate::{
     comp::{
-        comp_inst::{ArrayIdx, Comp, CompInst, RifFieldInst, RifInst, RifmuxInst},
+        comp_inst::{ArrayIdx, Comp, CompInst, RifFieldInst, RifInst, RifRegInst, RifmuxInst},
         hw_info::{PortDir, PortInfo, PortWidth, RifIntfPorts, SignalInfo}},
     rifgen::{
         order_dict::OrderDict, Access, ClkEn, ClockingInfo, CounterInfo, CounterKind, EnumKind, ExternalKind, FieldHwKind, FieldSwKind, Interface, InterruptClr, InterruptRegKind, InterruptTrigger, LimitValue, RegPulseKind, ResetDef
@@ -15,11 +15,13 @@ use crate::{
 
 use super::{
     casing::{Casing::{Snake, Title}, ToCasing},
-    gen_common::{GeneratorBaseSetting, RifList}
+    gen_common::{bridge_rst_n, GeneratorBaseSetting, RifList}
 };
 
 pub struct GeneratorSv {
     base_settings: GeneratorBaseSetting,
+    /// Generate also a bindable assertion module for each RIF
+    sva: bool,
     txt: String,
     stash: [String; 2],
     names: Vec<String>,
@@ -27,9 +29,10 @@ pub struct GeneratorSv {
 
 impl GeneratorSv {
 
-    pub fn new(args: GeneratorBaseSetting) -> Self {
+    pub fn new(args: GeneratorBaseSetting, sva: bool) -> Self {
         GeneratorSv {
             base_settings: args,
+            sva,
             txt: String::with_capacity(10000),
             stash: [String::with_capacity(1000), String::with_capacity(1000)],
             names: Vec::new()
@@ -73,6 +76,9 @@ impl GeneratorSv {
             Comp::Rif(rif) => {
                 self.gen_pkg(rif)?;
                 self.gen_rif(rif)?;
+                if self.sva {
+                    self.gen_rif_sva(rif)?;
+                }
             }
             Comp::Rifmux(rifmux) => {
                 self.gen_rifmux_pkg(rifmux)?;
@@ -86,6 +92,9 @@ impl GeneratorSv {
                         }
                         self.gen_pkg(rif)?;
                         self.gen_rif(rif)?;
+                        if self.sva {
+                            self.gen_rif_sva(rif)?;
+                        }
                     }
                 }
                 // Generate Top
@@ -414,7 +423,11 @@ impl GeneratorSv {
         self.write("------------------------------------------------------------------------------*/\n",);
         self.write(&format!("   logic [{}:0] rif_addr_l;\n", rif.addr_width - 1 - addr_shift));
         self.write(&format!("   logic [{}:0] rif_read_data_l;\n", rif.data_width - 1));
-        self.write("   logic rif_err_addr_l, rif_err_access_l, rif_done_next;\n\n");
+        self.write("   logic rif_err_addr_l, rif_err_access_l, rif_done_next;\n");
+        if rif.has_external_reg() {
+            self.write("   logic rif_pending; // High while an access waits for an external register\n");
+        }
+        self.write("\n");
 
         // Declare local clock enable
         self.names.clear();
@@ -555,19 +568,23 @@ impl GeneratorSv {
         }
 
         // Add interface bridge when not default
-        self.add_intf_bridge(&rif.interface, rif.addr_width, rif.data_width, &rif.sw_clocking.clk, &rif.sw_clocking.rst.name);
+        self.add_intf_bridge(&rif.interface, rif.addr_width, rif.data_width, &rif.sw_clocking.clk, &rif.sw_clocking.rst)?;
 
         // Interface handline
         self.write("\n/*------------------------------------------------------------------------------\n",);
         self.write("--  Interface handling\n");
         self.write("------------------------------------------------------------------------------*/\n",);
         // TODO: handle option pipe==0
-        let signals: Vec<SignalInfo> = vec![
+        let mut signals: Vec<SignalInfo> = vec![
             SignalInfo::new("if_rif.err_addr"  , 1, "1'b0", "rif_err_addr_l   & if_rif.en"),
             SignalInfo::new("if_rif.err_access", 1, "1'b0", "rif_err_access_l & if_rif.en"),
             SignalInfo::new("if_rif.done", 1, "1'b0", "rif_done_next"),
             SignalInfo::new_with_en("if_rif.rd_data", rif.data_width, &format!("{}'b0", rif.data_width), "rif_read_data_l", "rif_done_next & if_rif.rd_wrn"),
         ];
+        // Access stays enabled until done: external registers are only started on the first cycle
+        if rif.has_external_reg() {
+            signals.push(SignalInfo::new("rif_pending", 1, "1'b0", "if_rif.en & ~rif_done_next"));
+        }
         self.gen_process(
             &rif.sw_clocking.clk,
             &rif.sw_clocking.rst,
@@ -608,13 +625,9 @@ impl GeneratorSv {
             let page_en = page_en.join(" | ");
             self.write(&format!("      rif_err_addr_l   = ~({});\n", page_en));
             self.write(&format!("      rif_err_access_l = ~({});\n", page_en));
-            self.write(&format!("      rif_done_next = (if_rif.en & ~({}))\n", page_en));
-            for (name,_,_) in ext_pages.iter() {
-                self.write(&format!(
-                    "\n         | (if_page_{name}.en & if_page_{name}.done)",
-                ));
-            }
-            self.write(";\n");
+            // Page enable is released when the page is done: the access completes
+            // with the answer of the page selected in the default branch
+            self.write(&format!("      rif_done_next    = if_rif.en & ~({});\n", page_en));
         }
         for page in rif.pages.iter().filter(|p| p.external.is_none()) {
             for reg in page.regs.iter() {
@@ -682,6 +695,8 @@ impl GeneratorSv {
                         self.write("_");
                     }
                     self.write("done;\n");
+                    // Access is held until done: only the first cycle accesses the register
+                    self.write(&format!("            if(rif_pending) {name_flat}__decode = 1'b0;\n"));
                 }
                 self.write("         end\n");
             }
@@ -710,7 +725,7 @@ impl GeneratorSv {
             self.write(&format!("   assign if_page_{name}.rd_wrn  = if_rif.rd_wrn ;\n"));
             self.write(&format!("   assign if_page_{name}.wr_data = if_rif.wr_data;\n"));
             // self.write(&format!("   assign if_page_{}.wr_mask = if_rif.wr_mask;\n",page));
-            self.write(&format!("   assign if_page_{name}.en      = if_rif.en && if_rif.addr[{}:{}]=={};\n",
+            self.write(&format!("   assign if_page_{name}.en      = if_rif.en && !if_page_{name}.done && if_rif.addr[{}:{}]=={};\n",
                 rif.addr_width-1, width, addr >> width));
         }
 
@@ -1548,17 +1563,21 @@ impl GeneratorSv {
 
     }
 
-    fn add_intf_bridge(&mut self, intf: &Interface, addr_w: u8, data_w: u8, sw_clk: &str, sw_rst: &str) {
+    /// Instantiate the internal register interface and the bridge from the bus.
+    /// The bridges use an asynchronous active low reset: an active high reset is inverted
+    fn add_intf_bridge(&mut self, intf: &Interface, addr_w: u8, data_w: u8, sw_clk: &str, sw_rst: &ResetDef) -> Result<(), String> {
         if intf.is_default() {
-            return;
+            return Ok(());
         }
         self.write("\n/*------------------------------------------------------------------------------\n");
         self.write("--  Bridge to the internal register interface\n");
         self.write("------------------------------------------------------------------------------*/\n");
-        self.write(&format!("   rif_if#({addr_w}, {data_w}) if_rif({sw_clk}, {sw_rst});\n"));
+        let rst_n = bridge_rst_n(sw_rst, "~")?;
+        self.write(&format!("   rif_if#({addr_w}, {data_w}) if_rif({sw_clk}, {rst_n});\n"));
         self.write("\n");
         let name = intf.name();
         self.write(&format!("   bridge_{name}_rif#({addr_w}, {data_w}) i_bridge(.*);\n"));
+        Ok(())
     }
 
 
@@ -1719,6 +1738,283 @@ impl GeneratorSv {
     }
 
 
+    //-----------------------------------------------------------------------------
+    // RIF assertions: bindable module checking the register behaviour
+    //-----------------------------------------------------------------------------
+
+    /// SVA module bound to the RIF module, checking:
+    ///  - the protocol on the internal interface (done, address and access error)
+    ///  - the software behaviour of every field not modified by the hardware
+    ///  - the rejection of writes outside the limit of a field
+    ///  - the interrupt pending value and the duration of pulses
+    fn gen_rif_sva(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let addr_shift = (rif.data_width as f32).log2().ceil() as u8 - 3;
+        let rif_name = rif.name(false).to_casing(Snake);
+        let rif_pkg_name = rif.name(true).to_casing(Snake);
+        let clocking = &rif.sw_clocking;
+        let has_ext_page = rif.pages.iter().any(|p| p.external.is_some());
+        let addr_range = format!("[{}:{}]", rif.addr_width - 1, addr_shift);
+        let addr_w = rif.addr_width - addr_shift;
+
+        let mut ports = vec![
+            format!("input var logic {}", clocking.clk),
+            format!("input var logic {}", clocking.rst.name),
+        ];
+        if !clocking.clear.is_empty() {
+            ports.push(format!("input var logic {}", clocking.clear));
+        }
+        // Local signals and assertions are built in the two stashes and added once ports are known
+        let mut struct_ports : Vec<String> = Vec::new();
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            self.push_stash(0, &format!("   // Page {}\n", page.name));
+            for reg in page.regs.iter().filter(|r| r.sw_access != Access::NA) {
+                let reg_impl = rif.get_hw_reg(&reg.group_type);
+                let reg_name = reg.name().to_casing(Snake);
+                let group_name = reg.group_name().to_casing(Snake);
+                let reg_idx = if let ArrayIdx::Inst(idx,_) = reg.array {format!("{idx}")} else {"".to_owned()};
+                let reg_idxb = if !reg_idx.is_empty() {format!("[{reg_idx}]")} else {"".to_owned()};
+                let intr_suffix = reg.intr_info.0.get_suffix();
+                let rd_data = format!("{reg_name}__read_data");
+                ports.push(format!("input var logic [{}:0] {rd_data}", rif.data_width - 1));
+
+                // Access decoding
+                self.push_stash(0, &format!("   logic {reg_name}__sel, {reg_name}__wr, {reg_name}__rd, {reg_name}__acc_err;\n"));
+                self.push_stash(0, &format!("   assign {reg_name}__sel = if_rif.en && if_rif.addr{addr_range} == {addr_w}'d{};\n",
+                    (reg.addr + page.addr) >> addr_shift));
+                // Limit: check all fields against their allowed values
+                let mut limits = Vec::new();
+                for field in reg.fields.iter().filter(|f| f.has_limit()) {
+                    let check = self.sva_limit_check(rif, reg, field)?;
+                    if field.limit.bypass.is_empty() {
+                        limits.push(format!("({check})"));
+                    } else {
+                        limits.push(format!("({check} || {})", field.limit.bypass));
+                        if !ports.iter().any(|p| p.ends_with(&format!(" {}", field.limit.bypass))) {
+                            ports.push(format!("input var logic {}", field.limit.bypass));
+                        }
+                    }
+                }
+                let wr_ok = if limits.is_empty() {"".to_owned()} else {format!(" & {reg_name}__limit_ok")};
+                if !limits.is_empty() {
+                    self.push_stash(0, &format!("   logic {reg_name}__limit_ok;\n"));
+                    self.push_stash(0, &format!("   assign {reg_name}__limit_ok = {};\n", limits.join(" && ")));
+                }
+                self.push_stash(0, &format!("   assign {reg_name}__wr = {reg_name}__sel & ~if_rif.rd_wrn{wr_ok};\n"));
+                self.push_stash(0, &format!("   assign {reg_name}__rd = {reg_name}__sel & if_rif.rd_wrn;\n"));
+                let acc_err = match reg.sw_access {
+                    Access::RO => "~if_rif.rd_wrn".to_owned(),
+                    Access::WO => "if_rif.rd_wrn".to_owned(),
+                    _ if !limits.is_empty() => format!("~if_rif.rd_wrn & ~{reg_name}__limit_ok"),
+                    _ => "1'b0".to_owned(),
+                };
+                self.push_stash(0, &format!("   assign {reg_name}__acc_err = {acc_err};\n"));
+
+                // Access must complete with the proper error flag
+                self.push_stash(1, &format!("\n   // Register {}\n", reg.name_i()));
+                if !reg.is_external() {
+                    self.push_stash(1, &format!("   a_{reg_name}__access: assert property ({reg_name}__sel |=> if_rif.done && !if_rif.err_addr && if_rif.err_access == $past({reg_name}__acc_err));\n"));
+                }
+
+                // Interrupt pending is the status masked
+                if reg.intr_info.0 == InterruptRegKind::Pending {
+                    let find = |kind: InterruptRegKind| page.regs.iter()
+                        .find(|r| r.intr_info.0 == kind && r.group_name() == reg.group_name() && r.array.idx() == reg.array.idx())
+                        .map(|r| r.name().to_casing(Snake));
+                    if let Some(base) = find(InterruptRegKind::Base) {
+                        let mask = find(InterruptRegKind::Mask).map(|m| format!(" & {m}__read_data")).unwrap_or_default();
+                        self.push_stash(1, &format!("   a_{reg_name}__value: assert property ({rd_data} == ({base}__read_data{mask}));\n"));
+                    }
+                    continue;
+                }
+                if reg.is_external() {
+                    continue;
+                }
+
+                for field in reg.fields.iter() {
+                    let field_impl = reg_impl.get_field(&field.name)?;
+                    if field.is_disabled() || field.partial.0.is_some() || field_impl.is_constant() {
+                        continue;
+                    }
+                    let field_name = field.name().to_casing(Snake);
+                    let field_name_flat = field.name_flat().to_casing(Snake);
+                    let label = format!("a_{reg_name}_{field_name_flat}");
+                    let range = if field.width > 1 {format!("[{}:{}]", field.msb(), field.lsb)} else {format!("[{}]", field.lsb)};
+                    let val = format!("{rd_data}{range}");
+                    let wdata = format!("if_rif.wr_data{range}");
+                    let zero = format!("{}'b0", field.width);
+                    let is_readable = reg.sw_access.is_readable() && !field.sw_kind.is_wo();
+                    let is_locked = field_impl.lock.is_some();
+                    let is_sw_clk = reg_impl.clk.as_ref().map(|c| *c == clocking.clk).unwrap_or(true);
+                    let is_sw_only = !field.is_hw_write() && !field.hw_access.is_writable() && !reg.is_intr() && !is_locked && is_sw_clk;
+                    let is_pulse = matches!(field.sw_kind, FieldSwKind::W1Pulse(_,_));
+
+                    // Writes rejected by a limit do not update the field
+                    if !limits.is_empty() && is_readable && is_sw_only && !is_pulse {
+                        self.push_stash(1, &format!("   {label}__limit: assert property ({reg_name}__sel & ~if_rif.rd_wrn & ~{reg_name}__limit_ok |=> {val} == $past({val}));\n"));
+                    }
+
+                    // Pulse: high only the cycle of the write (combinatorial) or the cycle after
+                    if let FieldSwKind::W1Pulse(is_reg,_) = field.sw_kind {
+                        if is_locked {
+                            continue;
+                        }
+                        let pulse = if field_impl.is_local() && field.has_write_mod() {
+                            let name = format!("{group_name}{intr_suffix}{reg_idx}_{field_name_flat}__reg");
+                            ports.push(format!("input var logic [{}:0] {name}", field.width - 1));
+                            name
+                        } else {
+                            let hw_name = format!("{group_name}{intr_suffix}");
+                            if !struct_ports.contains(&hw_name) {
+                                struct_ports.push(hw_name.clone());
+                            }
+                            format!("rif_{hw_name}{reg_idxb}.{field_name}")
+                        };
+                        let (op, wdata) = if is_reg {("|=>", format!("$past({wdata})"))} else {("|->", wdata)};
+                        self.push_stash(1, &format!("   {label}__pulse: assert property ({reg_name}__wr {op} {pulse} == {wdata});\n"));
+                        self.push_stash(1, &format!("   {label}__pulse_len: assert property (!{reg_name}__wr {op} {pulse} == {zero});\n"));
+                        continue;
+                    }
+                    if !is_readable {
+                        continue;
+                    }
+
+                    // Interrupt status: bits are only cleared by software
+                    if reg.is_intr() && !is_locked && field.hw_kind.iter().all(|k| k.is_interrupt()) {
+                        let cleared = match field.sw_kind {
+                            FieldSwKind::W1Clr => Some(format!("($past({reg_name}__wr) ? $past({wdata}) : {zero})")),
+                            FieldSwKind::W0Clr => Some(format!("($past({reg_name}__wr) ? ~$past({wdata}) : {zero})")),
+                            FieldSwKind::ReadClr => Some(format!("{{{}{{$past({reg_name}__rd)}}}}", field.width)),
+                            _ => None,
+                        };
+                        if let Some(cleared) = cleared {
+                            self.push_stash(1, &format!("   {label}__clear: assert property (1'b1 |=> ($past({val}) & ~{cleared} & ~{val}) == {zero});\n"));
+                        }
+                        continue;
+                    }
+
+                    // Read clear: counters are allowed since software access has priority
+                    if field.sw_kind == FieldSwKind::ReadClr && !is_locked && !field.hw_access.is_writable()
+                        && field.hw_kind.iter().all(|k| k.is_counter()) {
+                        self.push_stash(1, &format!("   {label}__rclr: assert property ({reg_name}__rd |=> {val} == {zero});\n"));
+                    }
+                    if !is_sw_only {
+                        continue;
+                    }
+                    let next = match field.sw_kind {
+                        FieldSwKind::ReadWrite => Some(format!("$past({wdata})")),
+                        FieldSwKind::W1Clr => Some(format!("$past({val} & ~{wdata})")),
+                        FieldSwKind::W0Clr => Some(format!("$past({val} & {wdata})")),
+                        FieldSwKind::W1Set => Some(format!("$past({val} | {wdata})")),
+                        FieldSwKind::W1Tgl => Some(format!("$past({val} ^ {wdata})")),
+                        _ => None,
+                    };
+                    if let Some(next) = next {
+                        let kind = field.sw_kind.keyword();
+                        self.push_stash(1, &format!("   {label}__{kind}: assert property ({reg_name}__wr |=> {val} == {next});\n"));
+                    }
+                    // Value is stable without access
+                    let access = if field.sw_kind == FieldSwKind::ReadClr {format!("{reg_name}__sel")} else {format!("{reg_name}__wr")};
+                    self.push_stash(1, &format!("   {label}__stable: assert property (!{access} |=> $stable({val}));\n"));
+                }
+            }
+        }
+
+        // Register structure used by pulse fields
+        for (name, hw_reg) in rif.hw_regs.items() {
+            let name = name.to_casing(Snake);
+            if !struct_ports.contains(&name) {
+                continue;
+            }
+            let hw_reg_def = rif.get_hw_reg(&hw_reg.group);
+            let pkg_name = if let Some(pkg) = &hw_reg_def.pkg {pkg} else {&rif_pkg_name};
+            let pkg_name = pkg_name.to_casing(Snake);
+            let dim = if hw_reg.dim > 0 {format!("[{}]", hw_reg.dim)} else {"".to_owned()};
+            ports.push(format!("input var {pkg_name}_pkg::t_{}_sw rif_{name}{dim}", hw_reg.group.to_casing(Snake)));
+        }
+        ports.push("rif_if.rif if_rif".to_owned());
+
+        // Module declaration
+        self.write("// File generated automatically: DO NOT EDIT.\n\n");
+        self.write(&format!("module {rif_name}_sva (\n"));
+        self.write(&format!("   {}\n", ports.join(",\n   ")));
+        self.write(");\n\n");
+        self.write(&format!("   default clocking cb @(posedge {}); endclocking\n", clocking.clk));
+        let rst = format!("{}{}", if clocking.rst.active_high {""} else {"!"}, clocking.rst.name);
+        if clocking.clear.is_empty() {
+            self.write(&format!("   default disable iff ({rst});\n\n"));
+        } else {
+            self.write(&format!("   default disable iff ({rst} || {});\n\n", clocking.clear));
+        }
+
+        self.write("/*------------------------------------------------------------------------------\n");
+        self.write("--  Access decoding\n");
+        self.write("------------------------------------------------------------------------------*/\n");
+        self.pop_stash(0);
+
+        self.write("\n/*------------------------------------------------------------------------------\n");
+        self.write("--  Interface protocol\n");
+        self.write("------------------------------------------------------------------------------*/\n");
+        self.write("   a_err_addr_done: assert property (if_rif.err_addr |-> if_rif.done);\n");
+        self.write("   a_err_access_done: assert property (if_rif.err_access |-> if_rif.done);\n");
+        // With external pages the answer can be delayed
+        if !has_ext_page {
+            self.write("   a_done: assert property (if_rif.en |=> if_rif.done);\n");
+            self.write("   a_done_req: assert property (if_rif.done |-> $past(if_rif.en));\n");
+            let regs : Vec<String> = rif.pages.iter()
+                .flat_map(|p| p.regs.iter().filter(|r| r.sw_access != Access::NA))
+                .map(|r| format!("{}__sel", r.name().to_casing(Snake)))
+                .collect();
+            self.write(&format!("   a_err_addr: assert property (if_rif.en && !({}) |=> if_rif.err_addr);\n",
+                if regs.is_empty() {"1'b0".to_owned()} else {regs.join(" || ")}));
+        }
+
+        self.write("\n/*------------------------------------------------------------------------------\n");
+        self.write("--  Registers\n");
+        self.write("------------------------------------------------------------------------------*/");
+        self.pop_stash(1);
+
+        self.write(&format!("\nendmodule : {rif_name}_sva\n\n"));
+        self.write(&format!("bind {rif_name} {rif_name}_sva i_{rif_name}_sva (.*);\n"));
+
+        // Write file
+        self.save(&format!("{}_sva.sv", rif.name(false).to_lowercase()))?;
+        Ok(())
+    }
+
+    /// Expression checking a written value is inside the limit of a field
+    fn sva_limit_check(&self, rif: &RifInst, reg: &RifRegInst, field: &RifFieldInst) -> Result<String, String> {
+        let mut val = if field.width > 1 {format!("if_rif.wr_data[{}:{}]", field.msb(), field.lsb)} else {format!("if_rif.wr_data[{}]", field.lsb)};
+        if field.is_signed() {
+            val = format!("$signed({val})");
+        }
+        let to_str = |v: u128| Self::value_to_str(v, field.width.into(), field.is_signed(), false);
+        let check = match &field.limit.value {
+            LimitValue::Min(min) => format!("{val} >= {}", to_str(min.to_u128(field.width))),
+            LimitValue::Max(max) => format!("{val} <= {}", to_str(max.to_u128(field.width))),
+            LimitValue::MinMax(min, max) => format!("{val} >= {} && {val} <= {}", to_str(min.to_u128(field.width)), to_str(max.to_u128(field.width))),
+            LimitValue::List(l) => l.iter()
+                .map(|e| format!("{val} == {}", to_str(e.to_u128(field.width))))
+                .collect::<Vec<String>>()
+                .join(" || "),
+            LimitValue::Enum => {
+                let Some(enum_name) = field.enum_kind.name() else {
+                    return Err(format!("Using `limit enum` on non-enum field {}.{}!", reg.name(), field.name()));
+                };
+                let reg_impl = rif.get_hw_reg(&reg.group_type);
+                let enum_type = match &reg_impl.pkg {
+                    Some(pkg) if !enum_name.contains(':') => format!("{pkg}_pkg::{enum_name}"),
+                    _ => enum_name.to_owned(),
+                };
+                rif.get_enum_def(&enum_type)?.iter()
+                    .map(|e| format!("{val} == {}", to_str(e.value as u128)))
+                    .collect::<Vec<String>>()
+                    .join(" || ")
+            }
+            LimitValue::None => "1'b1".to_owned(),
+        };
+        Ok(check)
+    }
+
     //-----------------------------------------------------------------------------
     // RIF Mux implementation: Address decoding, registers , ...
     //-----------------------------------------------------------------------------
@@ -1751,7 +2047,7 @@ impl GeneratorSv {
         self.write("   logic addr_invalid_next; // Combinatorial version of addr_invalid\n");
 
         // Add interface bridge when not default
-        self.add_intf_bridge(&rifmux.interface, rifmux.addr_width, rifmux.data_width, &rifmux.sw_clocking.clk, &rifmux.sw_clocking.rst.name);
+        self.add_intf_bridge(&rifmux.interface, rifmux.addr_width, rifmux.data_width, &rifmux.sw_clocking.clk, &rifmux.sw_clocking.rst)?;
 
         // Address demultiplexing
         self.write("\n/*------------------------------------------------------------------------------\n");
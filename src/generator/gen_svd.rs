@@ -0,0 +1,268 @@
+use std::{collections::HashMap, fs::create_dir_all, path::PathBuf};
+
+use crate::{
+    comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst},
+    parser::remove_rif,
+    rifgen::{Access, FieldSwKind, LimitValue, ResetVal},
+};
+
+use super::gen_common::GeneratorBaseSetting;
+
+pub struct GeneratorSvd {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+    /// First peripheral name of each RIF type, used to derive other instances
+    peripherals: HashMap<String,String>,
+}
+
+impl GeneratorSvd {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorSvd {
+            base_settings: args,
+            txt: String::with_capacity(10000),
+            peripherals: HashMap::new(),
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    /// Write a simple XML element on a single line
+    fn write_elt(&mut self, ind: usize, tag: &str, value: &str) {
+        self.write(&format!("{:ind$}<{tag}>{value}</{tag}>\n", ""));
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        self.peripherals.clear();
+        // One device per file: a rifmux is flattened into a list of peripheral
+        let (name, desc, data_width) = match obj {
+            Comp::Rifmux(rifmux) => (&rifmux.inst_name, rifmux.description.get(), rifmux.data_width),
+            Comp::Rif(rif) => (&rif.type_name, rif.description.get(), rif.data_width),
+            // Nothing to do for external RIF
+            Comp::External(_) => return Ok(()),
+        };
+        let version = if let Comp::Rif(rif) = obj {rif.info.get("version").map(|s| s.as_str())} else {None};
+        self.write("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        self.write("<!-- File generated automatically: DO NOT EDIT. -->\n");
+        self.write("<device schemaVersion=\"1.3\" xmlns:xs=\"http://www.w3.org/2001/XMLSchema-instance\" xs:noNamespaceSchemaLocation=\"CMSIS-SVD.xsd\">\n");
+        self.write_elt(2, "name", &name.to_uppercase());
+        self.write_elt(2, "version", version.unwrap_or("1.0"));
+        self.write_elt(2, "description", &Self::sanitize(desc, name));
+        self.write_elt(2, "addressUnitBits", "8");
+        self.write_elt(2, "width", &format!("{data_width}"));
+        self.write_elt(2, "size", &format!("{data_width}"));
+        self.write_elt(2, "access", "read-write");
+        self.write_elt(2, "resetValue", "0x0");
+        self.write_elt(2, "resetMask", &format!("{:#X}", (1_u128 << data_width) - 1));
+        self.write("  <peripherals>\n");
+        match obj {
+            Comp::Rifmux(rifmux) => self.add_rifmux(rifmux, "", 0)?,
+            Comp::Rif(rif) => self.add_peripheral(rif, &remove_rif(&rif.type_name).to_uppercase(), 0)?,
+            Comp::External(_) => {},
+        }
+        self.write("  </peripherals>\n");
+        self.write("</device>\n");
+
+        // Write file
+        self.save(&format!("{}.svd", name.to_lowercase()))
+    }
+
+    /// Add all components of a rifmux as peripherals (recursively for sub-rifmux)
+    fn add_rifmux(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64) -> Result<(), Box<dyn std::error::Error>> {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}{}", remove_rif(comp.get_name())).to_uppercase();
+            match &comp.inst {
+                Comp::Rifmux(r) => self.add_rifmux(r, &format!("{name}_"), addr)?,
+                Comp::Rif(r) => self.add_peripheral(r, &name, addr)?,
+                Comp::External(r) => {
+                    self.write("    <peripheral>\n");
+                    self.write_elt(6, "name", &name);
+                    self.write_elt(6, "description", &Self::sanitize(r.description.get(), &r.inst_name));
+                    self.write_elt(6, "baseAddress", &format!("{addr:#010X}"));
+                    self.add_address_block(r.addr_width, "buffer");
+                    self.write("    </peripheral>\n");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn add_address_block(&mut self, addr_width: u8, usage: &str) {
+        self.write("      <addressBlock>\n");
+        self.write_elt(8, "offset", "0x0");
+        self.write_elt(8, "size", &format!("{:#X}", 1_u64 << addr_width));
+        self.write_elt(8, "usage", usage);
+        self.write("      </addressBlock>\n");
+    }
+
+    /// Add a RIF instance as a peripheral
+    fn add_peripheral(&mut self, rif: &RifInst, name: &str, addr: u64) -> Result<(), Box<dyn std::error::Error>> {
+        // Other instances of the same RIF type simply derive from the first one
+        if let Some(base) = self.peripherals.get(&rif.type_name) {
+            self.write(&format!("    <peripheral derivedFrom=\"{base}\">\n"));
+            self.write_elt(6, "name", name);
+            self.write_elt(6, "description", &Self::sanitize(rif.description.get(), &rif.type_name));
+            self.write_elt(6, "baseAddress", &format!("{addr:#010X}"));
+            self.write("    </peripheral>\n");
+            return Ok(());
+        }
+        self.peripherals.insert(rif.type_name.to_owned(), name.to_owned());
+
+        self.write("    <peripheral>\n");
+        self.write_elt(6, "name", name);
+        self.write_elt(6, "description", &Self::sanitize(rif.description.get(), &rif.type_name));
+        self.write_elt(6, "groupName", &remove_rif(&rif.type_name).to_uppercase());
+        self.write_elt(6, "baseAddress", &format!("{addr:#010X}"));
+        self.add_address_block(rif.addr_width, "registers");
+        self.write("      <registers>\n");
+        let is_public = self.base_settings.privacy.is_public();
+        for page in rif.pages.iter().filter(|p| !p.is_external()) {
+            // Prefix register name by the page only when there is more than one
+            let prefix = if rif.pages.len() > 1 {format!("{}_", page.name.to_uppercase())} else {"".to_owned()};
+            for reg in page.regs.iter() {
+                if reg.sw_access == Access::NA || (is_public && reg.visibility.is_hidden()) {
+                    continue;
+                }
+                self.add_register(rif, reg, &prefix, page.addr)?;
+            }
+        }
+        self.write("      </registers>\n");
+        self.write("    </peripheral>\n");
+        Ok(())
+    }
+
+    fn add_register(&mut self, rif: &RifInst, reg: &RifRegInst, prefix: &str, offset: u64) -> Result<(), Box<dyn std::error::Error>> {
+        let name = format!("{prefix}{}", reg.name().to_uppercase());
+        self.write("        <register>\n");
+        self.write_elt(10, "name", &name);
+        self.write_elt(10, "description", &Self::sanitize(reg.description.get(), &name));
+        self.write_elt(10, "addressOffset", &format!("{:#X}", offset + reg.addr));
+        self.write_elt(10, "size", &format!("{}", rif.data_width));
+        self.write_elt(10, "access", Self::access_str(&reg.sw_access));
+        self.write_elt(10, "resetValue", &format!("{:#X}", reg.reset));
+        self.write("          <fields>\n");
+        let is_public = self.base_settings.privacy.is_public();
+        for field in reg.fields.iter() {
+            if is_public && (field.visibility.is_hidden() || field.is_reserved()) {
+                continue;
+            }
+            self.add_field(rif, reg, field)?;
+        }
+        self.write("          </fields>\n");
+        self.write("        </register>\n");
+        Ok(())
+    }
+
+    fn add_field(&mut self, rif: &RifInst, reg: &RifRegInst, field: &RifFieldInst) -> Result<(), Box<dyn std::error::Error>> {
+        let name = field.name_flat().to_uppercase();
+        self.write("            <field>\n");
+        self.write_elt(14, "name", &name);
+        self.write_elt(14, "description", &Self::sanitize(field.description.get(), &name));
+        self.write_elt(14, "bitOffset", &format!("{}", field.lsb));
+        self.write_elt(14, "bitWidth", &format!("{}", field.width));
+        // Access type with optional write modifier and read side-effect
+        let (access, write_mod, read_action) = match field.sw_kind {
+            FieldSwKind::ReadWrite    => ("read-write", None, None),
+            FieldSwKind::ReadOnly     => ("read-only" , None, None),
+            FieldSwKind::WriteOnly    => ("write-only", None, None),
+            FieldSwKind::ReadClr      => ("read-only" , None, Some("clear")),
+            FieldSwKind::W1Clr        => ("read-write", Some("oneToClear"), None),
+            FieldSwKind::W0Clr        => ("read-write", Some("zeroToClear"), None),
+            FieldSwKind::W1Set        => ("read-write", Some("oneToSet"), None),
+            FieldSwKind::W1Tgl        => ("read-write", Some("oneToToggle"), None),
+            // Pulse is only high for one cycle after the write: always read as 0
+            FieldSwKind::W1Pulse(_,_) => ("write-only", None, None),
+            FieldSwKind::Password(_)  => ("write-only", None, None),
+        };
+        self.write_elt(14, "access", access);
+        if let Some(v) = write_mod {
+            self.write_elt(14, "modifiedWriteValues", v);
+        }
+        // Write constraint: only unsigned range or enum can be described
+        let max_val = (1_u128 << field.width) - 1;
+        let range = match &field.limit.value {
+            LimitValue::Min(min) => Some((min, None)),
+            LimitValue::Max(max) => Some((&ResetVal::Unsigned(0), Some(max))),
+            LimitValue::MinMax(min, max) => Some((min, Some(max))),
+            _ => None,
+        };
+        if let Some((min, max)) = range.filter(|_| !field.is_signed()) {
+            self.write("              <writeConstraint>\n");
+            self.write("                <range>\n");
+            self.write_elt(18, "minimum", &format!("{}", min.to_u128(field.width)));
+            self.write_elt(18, "maximum", &format!("{}", max.map(|v| v.to_u128(field.width)).unwrap_or(max_val)));
+            self.write("                </range>\n");
+            self.write("              </writeConstraint>\n");
+        } else if field.limit.value == LimitValue::Enum {
+            self.write("              <writeConstraint>\n");
+            self.write_elt(16, "useEnumeratedValues", "true");
+            self.write("              </writeConstraint>\n");
+        }
+        if let Some(v) = read_action {
+            self.write_elt(14, "readAction", v);
+        }
+        // Enumerated values
+        if let Some(enum_name) = field.enum_kind.name() {
+            let reg_impl = rif.get_hw_reg(&reg.group_type);
+            let enum_name = if let Some(pkg) = &reg_impl.pkg {
+                if enum_name.contains(':') {enum_name.to_owned()}
+                else {format!("{pkg}_pkg::{enum_name}")}
+            } else {
+                enum_name.to_owned()
+            };
+            let enum_def = rif.get_enum_def(&enum_name)?;
+            self.write("              <enumeratedValues>\n");
+            for e in enum_def.iter() {
+                self.write("                <enumeratedValue>\n");
+                self.write_elt(18, "name", &e.name);
+                self.write_elt(18, "description", &Self::sanitize(e.description.get(), &e.name));
+                self.write_elt(18, "value", &format!("{:#X}", e.value));
+                self.write("                </enumeratedValue>\n");
+            }
+            self.write("              </enumeratedValues>\n");
+        }
+        self.write("            </field>\n");
+        Ok(())
+    }
+
+    fn access_str(access: &Access) -> &'static str {
+        match access {
+            Access::RW => "read-write",
+            Access::RO => "read-only",
+            Access::WO => "write-only",
+            Access::NA => "read-only",
+        }
+    }
+
+    /// Sanitize description for XML: escape special characters and join lines
+    /// Use the default value when the description is empty
+    fn sanitize(desc: &str, default: &str) -> String {
+        let desc = desc.trim();
+        let desc = if desc.is_empty() {default} else {desc};
+        desc.lines()
+            .map(|l| l.trim())
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    }
+
+}
@@ -0,0 +1,112 @@
+use std::{fs::create_dir_all, path::PathBuf};
+
+use crate::comp::{comp_inst::Comp, hw_info::{PortDir, PortWidth, RIF_IF_SIGNALS}};
+
+use super::gen_common::GeneratorBaseSetting;
+
+/// Bridge from each supported bus to the internal register interface: interface name and source
+const SV_BRIDGES: [(&str, &str); 6] = [
+    ("apb"     , include_str!("sv/bridge_apb_rif.sv")),
+    ("uaux"    , include_str!("sv/bridge_uaux_rif.sv")),
+    ("axi4lite", include_str!("sv/bridge_axi4lite_rif.sv")),
+    ("ahb"     , include_str!("sv/bridge_ahb_rif.sv")),
+    ("wishbone", include_str!("sv/bridge_wishbone_rif.sv")),
+    ("avalon"  , include_str!("sv/bridge_avalon_rif.sv")),
+];
+
+pub struct GeneratorSvlib {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorSvlib {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorSvlib {
+            base_settings: args,
+            txt: String::with_capacity(4000),
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    /// Support library does not depend on the RIF content:
+    /// simply write the register interface and all the bridges
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Nothing to do for external RIF
+        if matches!(obj, Comp::External(_)) {
+            return Ok(());
+        }
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        self.gen_rif_if()?;
+        for (name, src) in SV_BRIDGES.iter() {
+            self.write(src);
+            self.save(&format!("bridge_{name}_rif.sv"))?;
+        }
+        Ok(())
+    }
+
+    /// Internal register interface with one modport for the RIF and one for the controller
+    fn gen_rif_if(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        self.write("// Internal register interface:\n");
+        self.write("//  - en starts an access and stays high, with rd_wrn, addr and wr_data stable,\n");
+        self.write("//    until done_next is high: one clock cycle for internal registers, and as\n");
+        self.write("//    many as needed by external registers and pages\n");
+        self.write("//  - done is high during one clock cycle, the cycle after done_next, when the\n");
+        self.write("//    access is complete, qualified by rd_data, err_addr and err_access\n");
+        self.write("//  - *_next signals are the combinatorial version of done and errors\n");
+        self.write("// Clock and reset (asynchronous, active low) are only used by the bridges.\n\n");
+
+        self.write("interface rif_if #(\n");
+        self.write("   parameter int W_ADDR = 16,\n");
+        self.write("   parameter int W_DATA = 32\n");
+        self.write(") (\n");
+        self.write("   input logic clk,  // Software clock\n");
+        self.write("   input logic rst_n // Software reset (asynchronous, active low)\n");
+        self.write(");\n\n");
+
+        let len = RIF_IF_SIGNALS.iter().map(|(n,_,_,_)| n.len()).max().unwrap_or(0);
+        for (name, _, width, desc) in RIF_IF_SIGNALS.iter() {
+            let range = match width {
+                PortWidth::Address => "[W_ADDR-1:0]",
+                PortWidth::Data    => "[W_DATA-1:0]",
+                _ => "",
+            };
+            self.write(&format!("   logic {range:<12} {name:<len$}; // {desc}\n"));
+        }
+
+        // Modport rif uses the direction seen by the register interface, reversed for the controller
+        for (modport, dir_in) in [("rif", PortDir::In), ("ctrl", PortDir::Out)] {
+            let names = |is_in: bool| RIF_IF_SIGNALS.iter()
+                .filter(|(_,d,_,_)| (d == &dir_in) == is_in)
+                .map(|(n,_,_,_)| *n)
+                .collect::<Vec<&str>>();
+            let (inputs, outputs) = (names(true), names(false));
+            self.write(&format!("\n   modport {modport} (\n"));
+            self.write("      input  clk, rst_n,\n");
+            self.write(&format!("      input  {},\n", inputs.join(", ")));
+            self.write(&format!("      output {}\n", outputs.join(", ")));
+            self.write("   );\n");
+        }
+
+        self.write("\nendinterface\n");
+        self.save("rif_if.sv")
+    }
+
+}
@@ -0,0 +1,502 @@
+use std::{format, fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, FieldHwKind, FieldSwKind, InterruptRegKind}};
+
+use super::gen_common::{GeneratorBaseSetting, RegSwMasks, RifList};
+
+
+pub struct GeneratorSystemc {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+}
+
+impl GeneratorSystemc {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorSystemc {
+            base_settings: args,
+            txt: String::with_capacity(10000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_module(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_header(rifmux, &rif_list)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_module(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    /// SystemC module for each RIF (header + source):
+    ///  - TLM-2.0 target socket with blocking and debug transport
+    ///  - register storage with software access semantic of each field
+    ///  - hardware side through field accessors and callbacks (sampled values and pulses)
+    fn gen_rif_module(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let name = rif.name(false).to_lowercase();
+        let name_uc = name.to_uppercase();
+        let w = rif.data_width;
+        let nb_byte = (w >> 3) as u64;
+        let hw = (w >> 2) as usize;
+        let public = self.base_settings.privacy.is_public();
+
+        // List all registers visible to software
+        let regs : Vec<(u64, &RifRegInst)> = rif.pages.iter()
+            .filter(|p| !p.is_external())
+            .flat_map(|p| p.regs.iter().map(|r| (p.addr + r.addr, r)))
+            .filter(|(_,r)| r.sw_access != Access::NA)
+            .collect();
+        let has_irq = regs.iter().any(|(_,r)| r.is_intr());
+
+        // Header: module declaration
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        self.write(&format!("// SystemC/TLM-2.0 model of {name_uc}\n"));
+        for l in rif.description.get().trim().lines() {
+            self.write(&format!("// {}\n", l.trim()));
+        }
+        self.write(&format!("#ifndef __{name_uc}_H__\n"));
+        self.write(&format!("#define __{name_uc}_H__\n\n"));
+        self.write("#include <cstdint>\n");
+        self.write("#include <functional>\n");
+        self.write("#include <systemc>\n");
+        self.write("#include <tlm>\n");
+        self.write("#include <tlm_utils/simple_target_socket.h>\n\n");
+        self.write(&format!("class {name} : public sc_core::sc_module {{\npublic:\n"));
+        self.write(&format!("    using reg_t = uint{w}_t;\n\n"));
+
+        // Register offsets and storage index
+        let len_name = regs.iter().map(|(_,r)| r.name().len()).max().unwrap_or(0);
+        self.write("    // Register offsets\n");
+        self.write("    enum : uint64_t {\n");
+        for (addr, reg) in regs.iter() {
+            self.write(&format!("        A_{:<len_name$} = 0x{addr:04X},", reg.name().to_uppercase()));
+            let desc = reg.description.get_short();
+            if !desc.is_empty() {
+                self.write(&format!(" // {desc}"));
+            }
+            self.write("\n");
+        }
+        self.write("    };\n\n");
+        self.write("    // Register index in storage\n");
+        self.write("    enum : unsigned {\n");
+        for (_, reg) in regs.iter() {
+            self.write(&format!("        R_{},\n", reg.name().to_uppercase()));
+        }
+        self.write("        NB_REGS\n");
+        self.write("    };\n\n");
+
+        self.write(&format!("    tlm_utils::simple_target_socket<{name}, {w}> socket;\n"));
+        if has_irq {
+            self.write("    sc_core::sc_out<bool> irq;\n");
+        }
+        self.write("    // Delay added to each bus access\n");
+        self.write("    sc_core::sc_time latency;\n\n");
+
+        // Callbacks
+        let sampled : Vec<(&RifRegInst, &RifFieldInst)> = regs.iter()
+            .filter(|(_,r)| r.sw_access.is_readable() && !r.is_intr_derived())
+            .flat_map(|(_,r)| r.fields.iter().filter(|f| Self::is_visible(f, public) && Self::is_hw_write(f)).map(move |f| (*r,f)))
+            .collect();
+        let pulses : Vec<(&RifRegInst, &RifFieldInst)> = regs.iter()
+            .filter(|(_,r)| r.sw_access.is_writable())
+            .flat_map(|(_,r)| r.fields.iter().filter(|f| Self::is_visible(f, public) && Self::is_pulse(f)).map(move |f| (*r,f)))
+            .collect();
+        if !sampled.is_empty() {
+            self.write("    // Hardware callbacks: field value sampled before a software read of the register\n");
+            for (reg, f) in sampled.iter() {
+                self.write(&format!("    std::function<{}()> sample_{};\n", Self::field_type(f), Self::field_id(reg, f)));
+            }
+            self.write("\n");
+        }
+        if !pulses.is_empty() {
+            self.write("    // Pulse callbacks: called when software writes a pulse or password field\n");
+            for (reg, f) in pulses.iter() {
+                self.write(&format!("    std::function<void({})> on_{};\n", Self::field_type(f), Self::field_id(reg, f)));
+            }
+            self.write("\n");
+        }
+
+        self.write(&format!("    explicit {name}(sc_core::sc_module_name name);\n\n"));
+        self.write("    // Restore reset value of all registers\n");
+        self.write("    void reset();\n\n");
+
+        // Hardware accessors
+        self.write("    // Hardware side access to the fields\n");
+        for (_, reg) in regs.iter() {
+            if reg.is_intr_derived() {
+                continue;
+            }
+            for f in reg.fields.iter().filter(|f| Self::is_visible(f, public)) {
+                let id = Self::field_id(reg, f);
+                let ft = Self::field_type(f);
+                if f.hw_access.is_readable() && !Self::is_pulse(f) {
+                    self.write(&format!("    {ft} get_{id}() const;\n"));
+                }
+                if Self::is_hw_write(f) {
+                    self.write(&format!("    void set_{id}({ft} value);\n"));
+                }
+            }
+        }
+        self.write("\n");
+
+        self.write("private:\n");
+        self.write("    reg_t regs[NB_REGS];\n\n");
+        self.write("    void b_transport(tlm::tlm_generic_payload& trans, sc_core::sc_time& delay);\n");
+        self.write("    unsigned int transport_dbg(tlm::tlm_generic_payload& trans);\n");
+        self.write("    tlm::tlm_response_status access(tlm::tlm_generic_payload& trans, bool debug);\n");
+        self.write("    reg_t reg_read(unsigned idx);\n");
+        self.write("    void reg_write(unsigned idx, reg_t value);\n");
+        if has_irq {
+            self.write("    void update_irq();\n");
+            self.write("    void start_of_simulation() override;\n");
+        }
+        self.write("};\n\n");
+        self.write(&format!("#endif /* __{name_uc}_H__ */\n"));
+        self.save(&format!("{name}.h"))?;
+
+        // Source
+        self.write("// File generated automatically from the register description: DO NOT EDIT.\n\n");
+        self.write("#include <cstring>\n");
+        self.write(&format!("#include \"{name}.h\"\n\n"));
+        self.write("namespace {\n\n");
+        self.write("// Software access semantic of each register\n");
+        self.write("struct reg_info {\n");
+        self.write("    const char* name;\n");
+        self.write(&format!("    {name}::reg_t reset;\n"));
+        self.write(&format!("    {name}::reg_t rw;    // Bits storing the written value\n"));
+        self.write(&format!("    {name}::reg_t w1c;   // Bits cleared when writing 1\n"));
+        self.write(&format!("    {name}::reg_t w0c;   // Bits cleared when writing 0\n"));
+        self.write(&format!("    {name}::reg_t w1s;   // Bits set when writing 1\n"));
+        self.write(&format!("    {name}::reg_t w1t;   // Bits toggled when writing 1\n"));
+        self.write(&format!("    {name}::reg_t rclr;  // Bits cleared by a read\n"));
+        self.write(&format!("    {name}::reg_t rz;    // Bits read as zero\n"));
+        self.write("};\n\n");
+        self.write(&format!("const reg_info reg_infos[{name}::NB_REGS] = {{\n"));
+        for (_, reg) in regs.iter() {
+            let m = RegSwMasks::new(reg, w);
+            self.write(&format!("    {{\"{}\", 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}, 0x{:0hw$X}}},\n",
+                reg.name(), reg.reset, m.rw, m.w1c, m.w0c, m.w1s, m.w1t, m.rclr, m.rz));
+        }
+        self.write("};\n\n");
+        self.write("// Register index from its offset (NB_REGS if not mapped)\n");
+        self.write("unsigned reg_index(uint64_t addr)\n{\n");
+        self.write("    switch (addr) {\n");
+        for (_, reg) in regs.iter() {
+            let rn = reg.name().to_uppercase();
+            self.write(&format!("    case {name}::A_{rn}: return {name}::R_{rn};\n"));
+        }
+        self.write(&format!("    default: return {name}::NB_REGS;\n"));
+        self.write("    }\n}\n\n");
+        self.write("} // namespace\n\n");
+
+        // Constructor and reset
+        self.write(&format!("{name}::{name}(sc_core::sc_module_name name)\n"));
+        self.write("    : sc_core::sc_module(name)\n");
+        self.write("    , socket(\"socket\")\n");
+        if has_irq {
+            self.write("    , irq(\"irq\")\n");
+        }
+        self.write("    , latency(sc_core::SC_ZERO_TIME)\n{\n");
+        self.write(&format!("    socket.register_b_transport(this, &{name}::b_transport);\n"));
+        self.write(&format!("    socket.register_transport_dbg(this, &{name}::transport_dbg);\n"));
+        self.write("    for (unsigned i = 0; i < NB_REGS; i++) {\n");
+        self.write("        regs[i] = reg_infos[i].reset;\n");
+        self.write("    }\n}\n\n");
+        self.write(&format!("void {name}::reset()\n{{\n"));
+        self.write("    for (unsigned i = 0; i < NB_REGS; i++) {\n");
+        self.write("        regs[i] = reg_infos[i].reset;\n");
+        self.write("    }\n");
+        if has_irq {
+            self.write("    update_irq();\n");
+        }
+        self.write("}\n\n");
+
+        // Hardware accessors
+        for (_, reg) in regs.iter() {
+            if reg.is_intr_derived() {
+                continue;
+            }
+            let rn = reg.name().to_uppercase();
+            let is_intr = has_irq && reg.is_intr();
+            // Interrupt events are only recorded when enabled
+            let enable = regs.iter()
+                .find(|(_,r)| r.intr_info.0 == InterruptRegKind::Enable && r.group_name() == reg.group_name() && r.array.idx() == reg.array.idx())
+                .map(|(_,r)| format!("R_{}", r.name().to_uppercase()));
+            for f in reg.fields.iter().filter(|f| Self::is_visible(f, public)) {
+                let id = Self::field_id(reg, f);
+                let ft = Self::field_type(f);
+                let mask = ((1_u128 << f.width) - 1) << f.lsb;
+                if f.hw_access.is_readable() && !Self::is_pulse(f) {
+                    self.write(&format!("{ft} {name}::get_{id}() const\n{{\n"));
+                    if f.width == 1 {
+                        self.write(&format!("    return (regs[R_{rn}] >> {}) & 1;\n", f.lsb));
+                    } else {
+                        self.write(&format!("    return static_cast<{ft}>((regs[R_{rn}] & 0x{mask:0hw$X}) >> {});\n", f.lsb));
+                    }
+                    self.write("}\n\n");
+                }
+                if Self::is_hw_write(f) {
+                    self.write(&format!("void {name}::set_{id}({ft} value)\n{{\n"));
+                    self.write(&format!("    reg_t v = (static_cast<reg_t>(value) << {}) & 0x{mask:0hw$X};\n", f.lsb));
+                    if f.hw_kind.iter().any(|k| matches!(k, FieldHwKind::Interrupt(_))) {
+                        // Interrupt are sticky: hardware can only set them
+                        if let Some(en) = &enable {
+                            self.write(&format!("    regs[R_{rn}] |= v & regs[{en}];\n"));
+                        } else {
+                            self.write(&format!("    regs[R_{rn}] |= v;\n"));
+                        }
+                    } else {
+                        self.write(&format!("    regs[R_{rn}] = (regs[R_{rn}] & ~reg_t(0x{mask:0hw$X})) | v;\n"));
+                    }
+                    if is_intr {
+                        self.write("    update_irq();\n");
+                    }
+                    self.write("}\n\n");
+                }
+            }
+        }
+
+        // Transport
+        self.write(&format!("void {name}::b_transport(tlm::tlm_generic_payload& trans, sc_core::sc_time& delay)\n{{\n"));
+        self.write("    trans.set_response_status(access(trans, false));\n");
+        self.write("    delay += latency;\n");
+        self.write("}\n\n");
+        self.write(&format!("unsigned int {name}::transport_dbg(tlm::tlm_generic_payload& trans)\n{{\n"));
+        self.write("    return access(trans, true) == tlm::TLM_OK_RESPONSE ? trans.get_data_length() : 0;\n");
+        self.write("}\n\n");
+        self.write(&format!("tlm::tlm_response_status {name}::access(tlm::tlm_generic_payload& trans, bool debug)\n{{\n"));
+        self.write("    if (trans.get_byte_enable_ptr()) {\n");
+        self.write("        return tlm::TLM_BYTE_ENABLE_ERROR_RESPONSE;\n");
+        self.write("    }\n");
+        self.write(&format!("    if (trans.get_data_length() != {nb_byte} || trans.get_streaming_width() < {nb_byte}) {{\n"));
+        self.write("        return tlm::TLM_BURST_ERROR_RESPONSE;\n");
+        self.write("    }\n");
+        self.write("    unsigned idx = reg_index(trans.get_address());\n");
+        self.write("    if (idx == NB_REGS) {\n");
+        self.write("        return tlm::TLM_ADDRESS_ERROR_RESPONSE;\n");
+        self.write("    }\n");
+        self.write("    reg_t value;\n");
+        self.write("    if (trans.is_read()) {\n");
+        self.write("        value = debug ? regs[idx] & ~reg_infos[idx].rz : reg_read(idx);\n");
+        self.write("        std::memcpy(trans.get_data_ptr(), &value, sizeof(value));\n");
+        self.write("    } else if (trans.is_write()) {\n");
+        self.write("        std::memcpy(&value, trans.get_data_ptr(), sizeof(value));\n");
+        self.write("        if (debug) {\n");
+        self.write("            regs[idx] = value;\n");
+        self.write("        } else {\n");
+        self.write("            reg_write(idx, value);\n");
+        self.write("        }\n");
+        self.write("    }\n");
+        self.write("    return tlm::TLM_OK_RESPONSE;\n");
+        self.write("}\n\n");
+
+        // Software read
+        self.write(&format!("{name}::reg_t {name}::reg_read(unsigned idx)\n{{\n"));
+        if !sampled.is_empty() {
+            self.write("    switch (idx) {\n");
+            let mut prev = "".to_owned();
+            for (reg, f) in sampled.iter() {
+                let rn = reg.name().to_uppercase();
+                if rn != prev {
+                    if !prev.is_empty() {
+                        self.write("        break;\n");
+                    }
+                    self.write(&format!("    case R_{rn}:\n"));
+                    prev = rn;
+                }
+                let id = Self::field_id(reg, f);
+                self.write(&format!("        if (sample_{id}) {{\n"));
+                self.write(&format!("            set_{id}(sample_{id}());\n"));
+                self.write("        }\n");
+            }
+            self.write("        break;\n");
+            self.write("    default:\n        break;\n    }\n");
+        }
+        self.write("    reg_t value = regs[idx] & ~reg_infos[idx].rz;\n");
+        self.write("    regs[idx] &= ~reg_infos[idx].rclr;\n");
+        if has_irq {
+            self.write("    if (reg_infos[idx].rclr) {\n");
+            self.write("        update_irq();\n");
+            self.write("    }\n");
+        }
+        self.write("    return value;\n");
+        self.write("}\n\n");
+
+        // Software write
+        self.write(&format!("void {name}::reg_write(unsigned idx, reg_t value)\n{{\n"));
+        self.write("    const reg_info& info = reg_infos[idx];\n");
+        self.write("    reg_t r = regs[idx];\n");
+        self.write("    r = (r & ~info.rw) | (value & info.rw);\n");
+        self.write("    r &= ~(value & info.w1c);\n");
+        self.write("    r &= ~(~value & info.w0c);\n");
+        self.write("    r |= value & info.w1s;\n");
+        self.write("    r ^= value & info.w1t;\n");
+        self.write("    regs[idx] = r;\n");
+        if !pulses.is_empty() {
+            self.write("    switch (idx) {\n");
+            let mut prev = "".to_owned();
+            for (reg, f) in pulses.iter() {
+                let rn = reg.name().to_uppercase();
+                if rn != prev {
+                    if !prev.is_empty() {
+                        self.write("        break;\n");
+                    }
+                    self.write(&format!("    case R_{rn}:\n"));
+                    prev = rn;
+                }
+                let id = Self::field_id(reg, f);
+                let mask = ((1_u128 << f.width) - 1) << f.lsb;
+                // Password value is always forwarded, pulse only when non-zero
+                if f.is_password() {
+                    self.write(&format!("        if (on_{id}) {{\n"));
+                } else {
+                    self.write(&format!("        if (on_{id} && (value & 0x{mask:0hw$X})) {{\n"));
+                }
+                self.write(&format!("            on_{id}(static_cast<{}>((value & 0x{mask:0hw$X}) >> {}));\n", Self::field_type(f), f.lsb));
+                self.write("        }\n");
+            }
+            self.write("        break;\n");
+            self.write("    default:\n        break;\n    }\n");
+        }
+        if has_irq {
+            self.write("    update_irq();\n");
+        }
+        self.write("}\n");
+
+        // Interrupt
+        if has_irq {
+            self.add_irq_update(&regs, &name);
+        }
+
+        // Write file
+        self.save(&format!("{name}.cpp"))
+    }
+
+    /// Update pending registers and interrupt line:
+    /// pending is the interrupt status masked by the optional mask register
+    fn add_irq_update(&mut self, regs: &[(u64, &RifRegInst)], name: &str) {
+        self.write(&format!("\nvoid {name}::start_of_simulation()\n{{\n"));
+        self.write("    update_irq();\n");
+        self.write("}\n\n");
+        self.write("// Update pending registers and interrupt line\n");
+        self.write(&format!("void {name}::update_irq()\n{{\n"));
+        self.write("    bool level = false;\n");
+        for (_, reg) in regs.iter().filter(|(_,r)| r.is_intr()) {
+            // Find derived registers of the same interrupt
+            let find = |kind: InterruptRegKind| regs.iter()
+                .find(|(_,r)| r.intr_info.0 == kind && r.group_name() == reg.group_name() && r.array.idx() == reg.array.idx())
+                .map(|(_,r)| format!("regs[R_{}]", r.name().to_uppercase()));
+            let status = format!("regs[R_{}]", reg.name().to_uppercase());
+            let masked = if let Some(mask) = find(InterruptRegKind::Mask) {
+                format!("({status} & {mask})")
+            } else {
+                status
+            };
+            self.write(&format!("    // {}\n", reg.name()));
+            if let Some(pending) = find(InterruptRegKind::Pending) {
+                self.write(&format!("    {pending} = {masked};\n"));
+                self.write(&format!("    level |= {pending} != 0;\n"));
+            } else {
+                self.write(&format!("    level |= {masked} != 0;\n"));
+            }
+        }
+        self.write("    irq.write(level);\n");
+        self.write("}\n");
+    }
+
+    /// Field are accessible unless reserved, or hidden in public mode
+    fn is_visible(f: &RifFieldInst, public: bool) -> bool {
+        !(f.is_reserved() || public && f.visibility.is_hidden())
+    }
+
+    /// Field identifier: register name followed by field name
+    fn field_id(r: &RifRegInst, f: &RifFieldInst) -> String {
+        let fname = if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat()
+        } else {
+            f.name.to_owned()
+        };
+        format!("{}_{}", r.name(), fname).to_lowercase()
+    }
+
+    /// Type of a field value: bool or smallest unsigned integer type
+    fn field_type(f: &RifFieldInst) -> String {
+        if f.width == 1 {
+            "bool".to_owned()
+        } else {
+            format!("uint{}_t", f.width.next_power_of_two().max(8))
+        }
+    }
+
+    /// Field value updated by the hardware
+    fn is_hw_write(f: &RifFieldInst) -> bool {
+        f.hw_access.is_writable() || f.is_hw_write()
+    }
+
+    /// Field without storage, forwarded to the hardware on write
+    fn is_pulse(f: &RifFieldInst) -> bool {
+        matches!(f.sw_kind, FieldSwKind::W1Pulse(_,_) | FieldSwKind::Password(_))
+    }
+
+    /// Header for the rifmux: offset of each module and includes
+    fn gen_rifmux_header(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        let rifname_uc = rifname.to_uppercase();
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        self.write("// Register File mapping\n");
+        self.write(&format!("#ifndef __{rifname_uc}_H__\n"));
+        self.write(&format!("#define __{rifname_uc}_H__\n\n"));
+        for rif in rif_list.iter() {
+            self.write(&format!("#include \"{}.h\"\n", rif.name(false).to_lowercase()));
+        }
+        self.write("\n");
+        self.write(&format!("namespace {rifname} {{\n\n"));
+        self.add_rifmux_offsets(rifmux, "", 0);
+        self.write(&format!("\n}} // namespace {rifname}\n"));
+        self.write(&format!("\n#endif /* __{rifname_uc}_H__ */\n"));
+        // Write file
+        self.save(&format!("{rifname}.h"))
+    }
+
+    fn add_rifmux_offsets(&mut self, rifmux: &RifmuxInst, prefix: &str, offset: u64) {
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups) + offset;
+            let name = format!("{prefix}{}", remove_rif(comp.get_name()).to_uppercase());
+            self.write(&format!("constexpr uint64_t {name}_OFFSET = 0x{addr:08X}; // {}\n", comp.get_desc_short()));
+            if let Comp::Rifmux(r) = &comp.inst {
+                self.add_rifmux_offsets(r, &format!("{name}_"), addr);
+            }
+        }
+    }
+}
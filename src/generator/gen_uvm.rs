@@ -0,0 +1,345 @@
+use std::{collections::HashSet, fs::create_dir_all, path::PathBuf};
+
+use crate::{comp::comp_inst::{ArrayIdx, Comp, RifFieldInst, RifInst, RifRegInst, RifmuxInst}, parser::remove_rif, rifgen::{Access, FieldSwKind, ResetVal}};
+
+use super::{casing::{Casing::Snake, ToCasing}, gen_common::{GeneratorBaseSetting, RifList}};
+
+
+pub struct GeneratorUvm {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+    stash: String,
+}
+
+impl GeneratorUvm {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorUvm {
+            base_settings: args,
+            txt: String::with_capacity(10000),
+            stash: String::with_capacity(1000)
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn push_stash(&mut self, string: &str) {
+        self.stash.push_str(string);
+    }
+
+    fn pop_stash(&mut self) {
+        self.txt.push_str(&self.stash);
+        self.stash.clear();
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp ) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => self.gen_rif_pkg(rif)?,
+            Comp::Rifmux(rifmux) => {
+                let rif_list = RifList::new(rifmux);
+                self.gen_rifmux_pkg(rifmux, &rif_list)?;
+                if !self.base_settings.gen_inc.is_empty() {
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_rif_pkg(rif)?;
+                    }
+                }
+            },
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    fn write_header(&mut self, pkg_name: &str, desc: &str) {
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        for l in desc.trim().lines() {
+            self.write(&format!("// {}\n", l.trim()));
+        }
+        self.write(&format!("\npackage {pkg_name};\n\n"));
+        self.write("   import uvm_pkg::*;\n");
+        self.write("   `include \"uvm_macros.svh\"\n\n");
+    }
+
+    /// UVM register model package for each RIF
+    ///  - one uvm_reg class per register type
+    ///  - one uvm_reg_block with all register instances mapped in the default map
+    ///    and HDL path slices matching the signals of the SystemVerilog implementation
+    fn gen_rif_pkg(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = rif.name(false).to_casing(Snake);
+        let is_public = self.base_settings.privacy.is_public();
+        let w = rif.data_width;
+
+        self.write_header(&format!("{rifname}_ral_pkg"), rif.description.get());
+
+        // Register classes: registers using the same type can differ only by their reset value
+        let mut types_done : HashSet<String> = HashSet::new();
+        for page in rif.pages.iter().filter(|p| !p.is_external()) {
+            for reg in page.regs.iter() {
+                if reg.sw_access == Access::NA || (is_public && reg.visibility.is_hidden()) {
+                    continue;
+                }
+                let reg_type = reg.expanded_type_name().to_casing(Snake);
+                if !types_done.insert(reg_type.clone()) {
+                    continue;
+                }
+                let cls = format!("{rifname}_{reg_type}_reg");
+                self.write(&format!("   // {}\n", reg.base_description.get_short()));
+                self.write(&format!("   class {cls} extends uvm_reg;\n"));
+                self.write(&format!("      `uvm_object_utils({cls})\n\n"));
+                for f in reg.fields.iter() {
+                    if is_public && f.visibility.is_hidden() {
+                        continue;
+                    }
+                    let name = self.get_field_name(reg, f);
+                    self.write(&format!("      rand uvm_reg_field {name}; // {}\n", f.base_description.get_short()));
+                }
+                self.write(&format!("\n      function new(string name = \"{reg_type}\");\n"));
+                self.write(&format!("         super.new(name, {w}, UVM_NO_COVERAGE);\n"));
+                self.write("      endfunction\n\n");
+                self.write("      virtual function void build();\n");
+                for f in reg.fields.iter() {
+                    if is_public && f.visibility.is_hidden() {
+                        continue;
+                    }
+                    let name = self.get_field_name(reg, f);
+                    let access = Self::access(&f.sw_kind);
+                    self.write(&format!("         {name} = uvm_reg_field::type_id::create(\"{name}\",,get_full_name());\n"));
+                    self.write(&format!("         {name}.configure(this, {}, {}, \"{access}\", {}, {}, 1, {}, 0);\n",
+                        f.width,
+                        f.lsb,
+                        Self::is_volatile(f) as u8,
+                        Self::reset_str(f),
+                        !matches!(access, "RO" | "RC") as u8,
+                    ));
+                }
+                self.write("      endfunction\n\n");
+                self.write("   endclass\n\n");
+            }
+        }
+
+        // Register block
+        let cls = format!("{rifname}_reg_block");
+        self.write(&format!("   // {}\n", rif.description.get_short()));
+        self.write(&format!("   class {cls} extends uvm_reg_block;\n"));
+        self.write(&format!("      `uvm_object_utils({cls})\n\n"));
+        self.push_stash(&format!("\n      function new(string name = \"{}\");\n", remove_rif(&rifname)));
+        self.push_stash("         super.new(name, UVM_NO_COVERAGE);\n");
+        self.push_stash("      endfunction\n\n");
+        self.push_stash("      virtual function void build();\n");
+        self.push_stash(&format!("         default_map = create_map(\"default_map\", 0, {}, UVM_LITTLE_ENDIAN, 1);\n", w >> 3));
+        for page in rif.pages.iter().filter(|p| !p.is_external()) {
+            for reg in page.regs.iter() {
+                if reg.sw_access == Access::NA || (is_public && reg.visibility.is_hidden()) {
+                    continue;
+                }
+                let reg_type = reg.expanded_type_name().to_casing(Snake);
+                let reg_cls = format!("{rifname}_{reg_type}_reg");
+                let reg_name = reg.reg_name.to_casing(Snake);
+                // Register array are declared once and indexed
+                let inst = if reg.array.dim() > 0 {
+                    if reg.array.idx() == 0 {
+                        self.write(&format!("      rand {reg_cls} {reg_name}[{}];\n", reg.array.dim()));
+                    }
+                    format!("{reg_name}[{}]", reg.array.idx())
+                } else {
+                    self.write(&format!("      rand {reg_cls} {reg_name};\n"));
+                    reg_name.to_owned()
+                };
+                let name = reg.name().to_casing(Snake);
+                self.push_stash(&format!("         // {}\n", reg.description.get_short()));
+                self.push_stash(&format!("         {inst} = {reg_cls}::type_id::create(\"{name}\",,get_full_name());\n"));
+                self.push_stash(&format!("         {inst}.configure(this, null, \"\");\n"));
+                self.push_stash(&format!("         {inst}.build();\n"));
+                // Backdoor access
+                self.add_hdl_path(rif, reg, &inst)?;
+                // Override reset value when different from the register type
+                let reg_ref = rif.pages.iter()
+                    .filter(|p| !p.is_external())
+                    .flat_map(|p| p.regs.iter())
+                    .find(|r| r.sw_access != Access::NA && r.expanded_type_name() == reg.expanded_type_name())
+                    .unwrap_or(reg);
+                for (f, f_ref) in reg.fields.iter().zip(reg_ref.fields.iter()) {
+                    if (is_public && f.visibility.is_hidden()) || Self::reset_str(f) == Self::reset_str(f_ref) {
+                        continue;
+                    }
+                    self.push_stash(&format!("         {inst}.{}.set_reset({});\n", self.get_field_name(reg, f), Self::reset_str(f)));
+                }
+                self.push_stash(&format!("         default_map.add_reg({inst}, 'h{:X}, \"{}\");\n", page.addr + reg.addr, Self::map_rights(reg)));
+            }
+        }
+        self.push_stash("      endfunction\n\n");
+        self.push_stash("   endclass\n\n");
+        self.pop_stash();
+        self.write("endpackage\n");
+
+        // Write file
+        self.save(&format!("{rifname}_ral_pkg.sv"))
+    }
+
+    /// Add HDL path slices of all fields in a register
+    /// Path follows the naming used in the read data logic of the SystemVerilog implementation
+    fn add_hdl_path(&mut self, rif: &RifInst, reg: &RifRegInst, inst: &str) -> Result<(), String> {
+        let is_public = self.base_settings.privacy.is_public();
+        let reg_impl = rif.get_hw_reg(&reg.group_type);
+        let group_name = reg.group_name().to_casing(Snake);
+        let reg_idx    = if let ArrayIdx::Inst(idx,_) = reg.array {format!("{idx}")} else {"".to_owned()};
+        let reg_idxb   = if !reg_idx.is_empty() {format!("[{reg_idx}]")} else {"".to_owned()};
+        let intr_suffix = reg.intr_info.0.get_suffix();
+        for field in reg.fields.iter() {
+            // Password fields do not store any value
+            if field.is_password() || (is_public && field.visibility.is_hidden()) {
+                continue;
+            }
+            let field_impl = reg_impl.get_field(&field.name)?;
+            let mut path =
+                if !reg.is_external() && field_impl.is_local() && field.has_write_mod() {
+                    format!("{group_name}{intr_suffix}{reg_idx}_{}__reg", field.name_flat().to_casing(Snake))
+                } else {
+                    let prefix = if !reg.is_external() && (field_impl.is_sw_write() || field.is_hw_write() || field_impl.is_constant()) {"rif_"} else {""};
+                    format!("{prefix}{group_name}{intr_suffix}{reg_idxb}.{}", field.name().to_casing(Snake))
+                };
+            if let Some(partial_pos) = field.partial.0 {
+                if field.width > 1 {
+                    path.push_str(&format!("[{}:{}]", partial_pos + field.width as u16 - 1, partial_pos));
+                } else {
+                    path.push_str(&format!("[{partial_pos}]"));
+                }
+            }
+            self.push_stash(&format!("         {inst}.add_hdl_path_slice(\"{path}\", {}, {});\n", field.lsb, field.width));
+        }
+        Ok(())
+    }
+
+    fn get_field_name(&self, r: &RifRegInst, f: &RifFieldInst) -> String {
+        if f.is_reserved() && self.base_settings.privacy.is_public() {
+            format!("rsvd{}",f.lsb)
+        } else if f.array.dim() > 1 || r.array.dim()==0 || r.array.is_inst() {
+            f.name_flat().to_casing(Snake)
+        } else {
+            f.name.to_casing(Snake)
+        }
+    }
+
+    /// UVM access policy of a field
+    fn access(kind: &FieldSwKind) -> &'static str {
+        match kind {
+            FieldSwKind::ReadWrite => "RW",
+            FieldSwKind::ReadOnly  => "RO",
+            FieldSwKind::WriteOnly => "WO",
+            FieldSwKind::ReadClr   => "RC",
+            FieldSwKind::W1Clr     => "W1C",
+            FieldSwKind::W0Clr     => "W0C",
+            FieldSwKind::W1Set     => "W1S",
+            FieldSwKind::W1Tgl     => "W1T",
+            // Pulse is only high for one cycle after the write: always read as 0
+            FieldSwKind::W1Pulse(_,_) => "WO",
+            FieldSwKind::Password(_) => "WO",
+        }
+    }
+
+    /// Field value can change without software access
+    fn is_volatile(f: &RifFieldInst) -> bool {
+        f.hw_access.is_writable() || f.is_hw_write() || matches!(f.sw_kind, FieldSwKind::ReadClr | FieldSwKind::W1Pulse(_,_))
+    }
+
+    fn reset_str(f: &RifFieldInst) -> String {
+        let mask = (1_u128 << f.width) - 1;
+        let v = match f.reset {
+            ResetVal::Unsigned(v) => v & mask,
+            ResetVal::Signed(v) => (v as u128) & mask,
+            ResetVal::Param(_) => 0,
+        };
+        format!("{}'h{v:X}", f.width)
+    }
+
+    /// Register access rights in the address map
+    fn map_rights(reg: &RifRegInst) -> &'static str {
+        match reg.sw_access {
+            Access::RO => "RO",
+            Access::WO => "WO",
+            _ => "RW",
+        }
+    }
+
+    /// UVM register model package for the rifmux: one block per rifmux (including sub-rifmux)
+    /// with the default map of each RIF block added as sub-map at the component address
+    fn gen_rifmux_pkg(&mut self, rifmux: &RifmuxInst, rif_list: &RifList) -> Result<(), Box<dyn std::error::Error>> {
+        let rifname = &rifmux.inst_name;
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        for l in rifmux.description.get().trim().lines() {
+            self.write(&format!("// {}\n", l.trim()));
+        }
+        self.write(&format!("\npackage {rifname}_ral_pkg;\n\n"));
+        self.write("   import uvm_pkg::*;\n");
+        for rif in rif_list.iter() {
+            self.write(&format!("   import {}_ral_pkg::*;\n", rif.name(false).to_casing(Snake)));
+        }
+        self.write("   `include \"uvm_macros.svh\"\n\n");
+        let mut done = Vec::new();
+        self.add_rifmux_block(rifmux, &mut done);
+        self.write("endpackage\n");
+
+        // Write file
+        self.save(&format!("{rifname}_ral_pkg.sv"))
+    }
+
+    fn add_rifmux_block(&mut self, rifmux: &RifmuxInst, done: &mut Vec<String>) {
+        // Sub-rifmux class must be declared first
+        for comp in rifmux.components.iter() {
+            if let Comp::Rifmux(r) = &comp.inst {
+                if !done.contains(&r.type_name) {
+                    self.add_rifmux_block(r, done);
+                }
+            }
+        }
+        done.push(rifmux.type_name.to_owned());
+        let cls = format!("{}_reg_block", rifmux.type_name.to_casing(Snake));
+        self.write(&format!("   // {}\n", rifmux.description.get_short()));
+        self.write(&format!("   class {cls} extends uvm_reg_block;\n"));
+        self.write(&format!("      `uvm_object_utils({cls})\n\n"));
+        self.push_stash(&format!("\n      function new(string name = \"{}\");\n", rifmux.inst_name));
+        self.push_stash("         super.new(name, UVM_NO_COVERAGE);\n");
+        self.push_stash("      endfunction\n\n");
+        self.push_stash("      virtual function void build();\n");
+        self.push_stash(&format!("         default_map = create_map(\"default_map\", 0, {}, UVM_LITTLE_ENDIAN, 1);\n", rifmux.data_width >> 3));
+        for comp in rifmux.components.iter() {
+            let addr = comp.full_addr(&rifmux.groups);
+            let name = remove_rif(comp.get_name()).to_casing(Snake);
+            let block_cls = match &comp.inst {
+                Comp::Rifmux(r) => format!("{}_reg_block", r.type_name.to_casing(Snake)),
+                Comp::Rif(r) => format!("{}_reg_block", r.name(false).to_casing(Snake)),
+                // No register model for external component
+                Comp::External(_) => continue,
+            };
+            self.write(&format!("      rand {block_cls} {name};\n"));
+            self.push_stash(&format!("         // {}\n", comp.get_desc_short()));
+            self.push_stash(&format!("         {name} = {block_cls}::type_id::create(\"{name}\",,get_full_name());\n"));
+            self.push_stash(&format!("         {name}.configure(this, \"\");\n"));
+            self.push_stash(&format!("         {name}.build();\n"));
+            self.push_stash(&format!("         default_map.add_submap({name}.default_map, 'h{addr:X});\n"));
+        }
+        self.push_stash("      endfunction\n\n");
+        self.push_stash("   endclass\n\n");
+        self.pop_stash();
+    }
+}
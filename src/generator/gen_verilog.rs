@@ -0,0 +1,1898 @@
+use std::{
+    collections::HashSet,
+    fs::create_dir_all,
+    path::PathBuf
+};
+
+use crate::{
+    comp::{
+        comp_inst::{ArrayIdx, Comp, RifFieldInst, RifInst, RifmuxInst},
+        hw_info::{PortInfo, PortWidth, RifIntfPorts, SignalInfo, RIF_IF_SIGNALS},
+        reg_impl::RegImpl},
+    rifgen::{
+        order_dict::OrderDict, Access, ClkEn, ClockingInfo, CounterInfo, CounterKind, ExternalKind, FieldHwKind, FieldSwKind, Interface, InterruptClr, InterruptRegKind, InterruptTrigger, LimitValue, RegPulseKind, ResetDef
+    }
+};
+
+use super::{
+    casing::{Casing::{Snake, Title}, ToCasing},
+    gen_common::{bridge_rst_n, GeneratorBaseSetting, RifList},
+    gen_sv::GeneratorSv
+};
+
+/// Bridge from each supported bus to the internal register interface: interface name and source
+const VERILOG_BRIDGES: [(&str, &str); 6] = [
+    ("apb"     , include_str!("verilog/bridge_apb_rif.v")),
+    ("uaux"    , include_str!("verilog/bridge_uaux_rif.v")),
+    ("axi4lite", include_str!("verilog/bridge_axi4lite_rif.v")),
+    ("ahb"     , include_str!("verilog/bridge_ahb_rif.v")),
+    ("wishbone", include_str!("verilog/bridge_wishbone_rif.v")),
+    ("avalon"  , include_str!("verilog/bridge_avalon_rif.v")),
+];
+
+/// Member of a register structure, flattened in Verilog into one signal per member
+struct StructMember {
+    name: String,
+    width: u16,
+    signed: bool,
+    array: u16,
+    desc: String,
+}
+
+impl StructMember {
+    fn new(name: String, width: u16, signed: bool, desc: String) -> Self {
+        StructMember { name, width, signed, array: 0, desc }
+    }
+
+    /// Name of all signals for this member given the name of the structure
+    fn signals(&self, base: &str) -> Vec<String> {
+        if self.array > 0 {
+            (0..self.array).map(|i| format!("{base}_{}{i}", self.name)).collect()
+        } else {
+            vec![format!("{base}_{}", self.name)]
+        }
+    }
+}
+
+pub struct GeneratorVerilog {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+    names: Vec<String>,
+    /// Signals assigned inside a process, to be declared as reg
+    regs: HashSet<String>,
+}
+
+impl GeneratorVerilog {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorVerilog {
+            base_settings: args,
+            txt: String::with_capacity(10000),
+            names: Vec::new(),
+            regs: HashSet::new(),
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => {
+                self.gen_pkg(rif)?;
+                self.gen_rif(rif)?;
+            }
+            Comp::Rifmux(rifmux) => {
+                self.gen_rifmux_pkg(rifmux)?;
+                self.gen_rifmux(rifmux)?;
+                // Generate include file
+                if !self.base_settings.gen_inc.is_empty() {
+                    let rif_list = RifList::new(rifmux);
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_pkg(rif)?;
+                        self.gen_rif(rif)?;
+                    }
+                }
+                // RIF top is only available in SystemVerilog for now
+            }
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    //-----------------------------------------------------------------------------
+    // RIF header: constants and enum values
+    //-----------------------------------------------------------------------------
+
+    /// Verilog has no package: constants are localparam in a file to include inside a module
+    fn gen_pkg(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rif_name = rif.name(true).to_casing(Snake);
+        self.write("// File generated automatically by rifgen: DO NOT EDIT.\n");
+        self.write(&format!("// Constants of {rif_name}: include inside the module using them\n\n"));
+        let rif_name_uc = rif.type_name.to_uppercase();
+        self.write(&format!("localparam C_{rif_name_uc}_ADDR_W = {:2};\n", rif.addr_width));
+        self.write(&format!("localparam C_{rif_name_uc}_DATA_W = {:2};\n", rif.data_width));
+        for (k, &v) in rif.params.items() {
+            self.write(&format!("localparam C_{rif_name_uc}_{} = {v};\n", k.to_uppercase()));
+        }
+
+        // Enums: one localparam per value
+        let nb_enum = rif.enum_defs.iter().filter(|e| e.is_local_type()).count();
+        if nb_enum > 0 {
+            self.write("\n// Enums\n");
+            for enum_def in rif.enum_defs.iter().filter(|e| e.is_local_type()) {
+                let msb = usize::BITS - (enum_def.len()-1).leading_zeros() - 1;
+                self.write(&format!("// {}", enum_def.name));
+                if !enum_def.description.is_empty() {
+                    self.write(&format!(": {}", enum_def.description));
+                }
+                self.write("\n");
+                for v in enum_def.iter() {
+                    self.write("localparam ");
+                    if msb > 0 {
+                        self.write(&format!("[{msb}:0] "));
+                    }
+                    self.write(&format!("{} = {};", v.name, v.value));
+                    if !v.description.is_empty() {
+                        self.write(&format!(" // {}", v.description.get_short()));
+                    }
+                    self.write("\n");
+                }
+            }
+        }
+
+        // Write file
+        self.save(&format!("{rif_name}_pkg.vh"))?;
+        Ok(())
+    }
+
+    /// Members of the hardware (index 0) and software (index 1) structures of a register
+    fn reg_members(&mut self, hw_reg: &RegImpl) -> Result<[Vec<StructMember>; 2], String> {
+        let mut members: [Vec<StructMember>; 2] = [Vec::new(), Vec::new()];
+        self.names.clear();
+        // Iterate over all register fields to add them in the structs
+        for f in hw_reg.fields.iter() {
+            let width = if f.sw_kind.is_password() {1} else {f.width};
+            let name = f.name.to_casing(Snake);
+            let Some(ctrl) = hw_reg.regs_ctrl.get(f.ctrl_idx) else {
+                return Err(format!("Field {}.{name} points to ctrl {} but max is {}",hw_reg.name, f.ctrl_idx, hw_reg.regs_ctrl.len()))
+            };
+            // Suffix password by _locked
+            let member = StructMember {
+                name: if f.sw_kind.is_password() {format!("{name}_locked")} else {name.clone()},
+                width,
+                signed: f.signed,
+                array: f.array,
+                desc: f.description.get_short().to_owned(),
+            };
+            // Add field to SW structure writable by firmware or readable by hardware
+            if (!f.is_local() || ctrl.external.is_rw()) && (f.is_sw_write() || f.is_constant() || f.is_counter() || f.hw_acc.is_readable()) {
+                members[1].push(StructMember { desc: member.desc.clone(), name: member.name.clone(), ..member });
+            }
+            // Add field to HW structure if written by hardware
+            if f.has_hw_value() || ctrl.external.is_rw() {
+                members[0].push(member);
+            }
+            // Add special fields
+            for kind in f.hw_kind.iter() {
+                // Write modifiers: Write Enable, clr/set/tgl
+                if kind.has_write_mod() {
+                    self.add_special_field(&mut members[0], kind, &hw_reg.name, &name);
+                }
+                // Counter need multiple extra fields
+                else if let FieldHwKind::Counter(info) = kind {
+                    if info.clr {
+                        members[0].push(StructMember::new(format!("{name}_clr"), 1, false, format!("Clear counter {name}")));
+                    }
+                    if info.kind == CounterKind::Up || info.kind == CounterKind::UpDown {
+                        members[0].push(StructMember::new(format!("{name}_incr_en"), 1, false, format!("Increment counter {name}")));
+                    }
+                    if info.kind == CounterKind::Down || info.kind == CounterKind::UpDown {
+                        members[0].push(StructMember::new(format!("{name}_decr_en"), 1, false, format!("Decrement counter {name}")));
+                    }
+                    if info.event || info.sat {
+                        members[1].push(StructMember::new(format!("{name}_event"), 1, false, format!("Pulse high when {name} wrap/saturate")));
+                    }
+                    if info.incr_val > 1 {
+                        members[0].push(StructMember::new(format!("{name}_incr_val"), info.incr_val.into(), f.signed, format!("Increment value for counter {name}")));
+                    }
+                    if info.decr_val > 1 {
+                        members[0].push(StructMember::new(format!("{name}_decr_val"), info.decr_val.into(), f.signed, format!("Decrement value for counter {name}")));
+                    }
+                }
+            }
+            if let FieldSwKind::Password(info) = &f.sw_kind {
+                if info.has_hold() {
+                    members[1].push(StructMember::new(format!("{name}_hold"), 1, false, format!("High when {name}_locked is not changed by register access")));
+                }
+            }
+            // Clear
+            if let Some(clr_sig) = &f.clear {
+                let clr_name = if clr_sig.is_empty() {format!("this.{name}_clr")} else {clr_sig.to_owned()};
+                let kind = FieldHwKind::Clear(Some(clr_name));
+                self.add_special_field(&mut members[0], &kind, &hw_reg.name, &name);
+            }
+            // Lock signal from hardware
+            if let Some(lock) = f.lock.local_name() {
+                if !lock.is_empty() && !self.names.iter().rev().any(|n| n==lock) {
+                    self.names.push(lock.to_owned());
+                    members[0].push(StructMember::new(lock.to_owned(), 1, false, "High to lock some field write access".to_owned()));
+                }
+            }
+        }
+        // Add fields for register pulse and external access
+        let is_multi_pulse = hw_reg.is_multi_pulse();
+        for ctrl in hw_reg.regs_ctrl.iter() {
+            let (sep,name) = if is_multi_pulse {("_",&*ctrl.name)} else {("","")};
+            for pulse in ctrl.pulse.iter() {
+                let (suffix, desc) = match pulse {
+                    RegPulseKind::Write(_)  => ("write", "written"),
+                    RegPulseKind::Read(_)   => ("read", "read"),
+                    RegPulseKind::Access(_) => ("acc", "accessed"),
+                };
+                members[1].push(StructMember::new(format!("p_{name}{sep}{suffix}"), 1, false, format!("Pulse high when register {} is {desc}", ctrl.name)));
+            }
+            if ctrl.external != ExternalKind::None {
+                members[0].push(StructMember::new(format!("ext_{name}{sep}done"), 1, false, format!("Pulse high when read/write operation on register {} is complete", ctrl.name)));
+                if matches!(ctrl.external, ExternalKind::ReadWrite | ExternalKind::Write) {
+                    members[1].push(StructMember::new(format!("ext_{name}{sep}write"), 1, false, format!("Pulse high to start a write operation on register {}", ctrl.name)));
+                }
+                if matches!(ctrl.external, ExternalKind::ReadWrite | ExternalKind::Read) {
+                    members[1].push(StructMember::new(format!("ext_{name}{sep}read"), 1, false, format!("Pulse high to start a read operation on register {}", ctrl.name)));
+                }
+            }
+        }
+        Ok(members)
+    }
+
+    fn add_special_field(&mut self, members: &mut Vec<StructMember>, kind: &FieldHwKind, regname: &str, fieldname: &str) {
+        let name = if let Some(path) = kind.get_signal() {
+            let mut parts = path.split('.');
+            match (parts.next(),parts.next()) {
+                (Some(f),None) => f.to_owned(),
+                (Some(r),Some(f)) if r == regname || r == "this" || r == "self" => f.to_owned(),
+                _ => "".to_owned()
+            }
+        } else {
+            format!("{}{}", fieldname, kind.get_suffix())
+        };
+        //
+        if !name.is_empty() && !self.names.iter().rev().any(|n| n==&name) {
+            members.push(StructMember::new(name.clone(), 1, false, kind.get_comment(fieldname)));
+            self.names.push(name);
+        }
+    }
+
+
+    //-----------------------------------------------------------------------------
+    // RIF implementation: Address decoding, registers , ...
+    //-----------------------------------------------------------------------------
+
+    fn gen_rif(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+
+        let addr_shift = (rif.data_width as f32).log2().ceil() as u8 - 3; // Min data width is 8 bits
+        let rif_name = rif.name(false).to_casing(Snake);
+        self.regs.clear();
+
+        // Collect external pages
+        let ext_pages: Vec<(String, u64, &u8)> = rif.pages.iter()
+            .filter_map(|p| p.external.as_ref().map(|w| (p.name.to_lowercase(), p.addr, w)))
+            .collect();
+
+        // The implementation is generated first to know which signals are assigned in a process
+        self.gen_rif_impl(rif, &ext_pages, addr_shift)?;
+        let implementation = std::mem::take(&mut self.txt);
+
+        // Header (TODO: support external template)
+        self.write("// File generated automatically: DO NOT EDIT.\n\n");
+        self.write(&format!("module {rif_name}"));
+
+        // Collect all ports: declaration and comment
+        let mut ports : Vec<(String,String)> = Vec::new();
+        // Clocks/Reset/Clear
+        let mut list_clocking = HashSet::with_capacity(2);
+        Self::add_clocking_port(&mut ports, &rif.sw_clocking, &mut list_clocking, false);
+        for hw_clk in rif.hw_clocking.iter() {
+            Self::add_clocking_port(&mut ports, hw_clk, &mut list_clocking, true);
+        }
+        // Clock enables and control signals
+        for port in rif.ports.clk_ens.iter().chain(rif.ports.ctrls.iter()) {
+            ports.push(self.port_decl(port, "", false, 0, 0));
+        }
+        // Limit bypass signals
+        let mut bypass_names : Vec<String> = Vec::new();
+        for page in rif.pages.iter().filter(|p| !p.is_external()) {
+            for field in page.regs.iter().flat_map(|r| r.fields.iter()) {
+                let name = Self::flat(&field.limit.bypass);
+                if !name.is_empty() && !bypass_names.contains(&name) && !rif.ports.ctrls.iter().any(|p| p.name == name) {
+                    ports.push((format!("input  wire {name}"), "Limit bypass".to_owned()));
+                    bypass_names.push(name);
+                }
+            }
+        }
+        // Registers: one port per structure member
+        let mut out_ports = Vec::new();
+        for (group_name, hw_reg) in rif.hw_regs.items() {
+            let hw_reg_def = rif.get_hw_reg(&hw_reg.group);
+            let group_name = group_name.to_casing(Snake);
+            let members = self.reg_members(hw_reg_def)?;
+            for idx_u16 in 0..hw_reg.dim.max(1) {
+                let idx = if hw_reg.dim > 0 {format!("{idx_u16}")} else {"".to_owned()};
+                if hw_reg.port.is_in() {
+                    for m in members[0].iter() {
+                        for name in m.signals(&format!("{group_name}{idx}")) {
+                            ports.push((format!("input  wire {}{name}", Self::range(m.width, m.signed)), m.desc.clone()));
+                        }
+                    }
+                }
+                if hw_reg.port.is_out() {
+                    let kind = if hw_reg.intr_derived {0} else {1};
+                    for m in members[kind].iter() {
+                        for name in m.signals(&format!("rif_{group_name}{idx}")) {
+                            out_ports.push((format!("output {} {}{name}", self.net_kind(&name), Self::range(m.width, m.signed)), m.desc.clone()));
+                        }
+                    }
+                }
+            }
+        }
+        ports.append(&mut out_ports);
+        // Interrupt lines
+        for port in rif.ports.irqs.iter() {
+            ports.push(self.port_decl(port, "", false, 0, 0));
+        }
+        // Add control to external pages
+        for (name,_, _) in ext_pages.iter() {
+            self.add_rif_if_ports(&mut ports, &format!("if_page_{name}"), true, rif.addr_width, rif.data_width);
+        }
+        // Add Main Control interface
+        self.add_intf_ports(&mut ports, &rif.interface, rif.addr_width, rif.data_width)?;
+        self.write_ports(&ports);
+
+        //----------------------
+        // Signals declaration
+        self.write("/*------------------------------------------------------------------------------\n",);
+        self.write("--  Signals declaration\n");
+        self.write("------------------------------------------------------------------------------*/\n",);
+        // Internal register interface when a bridge is used
+        if !rif.interface.is_default() {
+            self.add_rif_if_signals(rif.addr_width, rif.data_width);
+        }
+        self.write(&format!("   wire [{}:0] rif_addr_l;\n", rif.addr_width - 1 - addr_shift));
+        self.write(&format!("   reg  [{}:0] rif_read_data_l;\n", rif.data_width - 1));
+        self.write("   reg  rif_err_addr_l, rif_err_access_l, rif_done_next;\n");
+        if rif.has_external_reg() {
+            self.write("   reg  rif_pending; // High while an access waits for an external register\n");
+        }
+        self.write("\n");
+
+        // Declare local clock enable
+        self.names.clear();
+        for hw_clk in rif.hw_clocking.iter() {
+            if !hw_clk.en.is_empty() && !self.names.contains(&hw_clk.en){
+                self.write(&format!("   wire {}_l;\n",hw_clk.en));
+                self.names.push(hw_clk.en.to_owned());
+            }
+        }
+        // Declare Decode pulse / readback value per register
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                let name = reg.name().to_casing(Snake);
+                self.write(&format!("   reg  {name}__decode;\n"));
+                self.write(&format!("   wire [{}:0] {name}__read_data;\n", rif.data_width - 1));
+            }
+        }
+        self.write("\n");
+        // Declare local signal per register group
+        for (inst_name, hw_reg) in rif.hw_regs.items().filter(|(_,r)| !r.intr_derived) {
+            let group_name = inst_name.to_casing(Snake);
+            let hw_reg_def = rif.get_hw_reg(&hw_reg.group);
+            let reg_dim = hw_reg.dim;
+            let members = self.reg_members(hw_reg_def)?;
+            for idx_u16 in 0..reg_dim.max(1) {
+                let idx = if reg_dim > 0 {format!("{idx_u16}")} else {"".to_owned()};
+                // Local register
+                if hw_reg_def.is_local() {
+                    self.declare_members(&members[1], &format!("rif_{group_name}{idx}"));
+                }
+                // Interrupt register
+                if hw_reg_def.is_interrupt() {
+                    for intr_info in hw_reg_def.interrupt.iter() {
+                        let name = if intr_info.name.is_empty() {&group_name} else {&intr_info.name};
+                        if !hw_reg.port.is_out() {
+                            self.declare_members(&members[1], &format!("rif_{name}{idx}"));
+                        }
+                        self.declare_members(&members[0], &format!("{name}{idx}_l"));
+                        // Add delay register for fields triggered on edges
+                        let edge_members : Vec<&StructMember> = members[0].iter()
+                            .filter(|m| hw_reg_def.fields.iter().any(|f|
+                                f.name.to_casing(Snake) == m.name &&
+                                matches!(f.hw_kind.first(), Some(FieldHwKind::Interrupt(t)) if !t.is_level())))
+                            .collect();
+                        for m in edge_members {
+                            for sig in m.signals(&format!("{name}{idx}_d1")) {
+                                self.write(&format!("   {} {}{sig};\n", self.net_kind(&sig), Self::range(m.width, m.signed)));
+                            }
+                        }
+                        // Add optional enable/mask register
+                        if intr_info.enable.is_some() {
+                            let n = format!("{inst_name}_en");
+                            if let Some(hw_reg_en) = rif.hw_regs.get(&n) {
+                                if !hw_reg_en.port.is_out() {
+                                    self.declare_members(&members[0], &format!("rif_{name}{idx}_en"));
+                                }
+                            }
+                        }
+                        if intr_info.mask.is_some() {
+                            let n = format!("{inst_name}_mask");
+                            if let Some(hw_reg_mask) = rif.hw_regs.get(&n) {
+                                if !hw_reg_mask.port.is_out() {
+                                    self.declare_members(&members[0], &format!("rif_{name}{idx}_mask"));
+                                }
+                            }
+                        }
+                        // Internal pending signal is always present (used to generate the irq output)
+                        self.declare_members(&members[0], &format!("rif_{name}{idx}_pending"));
+                        self.write(&format!("   wire clk_en_intr_{name}{idx};\n"));
+                        // Add next signal for each field
+                        for f in hw_reg_def.fields.iter() {
+                            let f_name = f.name.to_casing(Snake);
+                            let range = Self::range(f.width, false);
+                            self.write(&format!("   wire {range}{name}{idx}_{f_name}__next;\n"));
+                            if intr_info.enable.is_some() {
+                                self.write(&format!("   wire {range}{name}{idx}_en_{f_name}__next;\n"));
+                            }
+                            if intr_info.mask.is_some() {
+                                self.write(&format!("   wire {range}{name}{idx}_mask_{f_name}__next;\n"));
+                            }
+                        }
+                    }
+                    continue;
+                }
+                // Field combinatorial next value
+                for f in hw_reg_def.fields.iter() {
+                    let f_name = f.name.to_casing(Snake);
+                    // Add signal to handle out-of-limit check
+                    if f.limit.value != LimitValue::None {
+                        self.write(&format!("   wire {group_name}{idx}_{f_name}__check;\n"));
+                    }
+                    // Skip external field
+                    let Some(ctrl) = hw_reg_def.regs_ctrl.get(f.ctrl_idx) else {
+                        return Err(format!("Field {}.{} points to ctrl {} but max is {}", hw_reg_def.name, f.name, f.ctrl_idx, hw_reg_def.regs_ctrl.len()).into())
+                    };
+                    if ctrl.is_external() {
+                        continue;
+                    }
+                    // No next for combinatorial pulse or read only field from hardware with no register
+                    if f.sw_kind.is_pulse_comb() || (f.sw_kind==FieldSwKind::ReadOnly && !f.has_write_mod() && !f.is_counter()) {
+                        continue;
+                    }
+                    let width =
+                        if f.sw_kind.is_password() {2}
+                        else if f.is_counter() {f.width+1}
+                        else {f.width};
+                    let range = Self::range(width, f.signed && f.enum_kind.name().is_none());
+                    if f.array > 0 {
+                        for i in 0..f.array {
+                            self.write(&format!("   wire {range}{group_name}{idx}_{f_name}{i}__next;\n"));
+                        }
+                    } else {
+                        self.write(&format!("   wire {range}{group_name}{idx}_{f_name}__next;\n"));
+                    }
+                    // Add register to store local value (when register is not visible at the output)
+                    if f.is_local() {
+                        let name = format!("{group_name}{idx}_{f_name}__reg");
+                        self.write(&format!("   {} {range}{name};\n", self.net_kind(&name)));
+                    }
+                }
+            }
+        }
+
+        self.write(&implementation);
+        self.write("\nendmodule\n");
+
+        // Write file
+        self.save(&format!("{}.v", rif.name(false).to_lowercase()))?;
+        self.gen_bridge(&rif.interface)
+    }
+
+    /// Module implementation: bridge, interface handling, address decoding and registers
+    fn gen_rif_impl(&mut self, rif: &RifInst, ext_pages: &[(String, u64, &u8)], addr_shift: u8) -> Result<(), Box<dyn std::error::Error>> {
+
+        // Add interface bridge when not default
+        self.add_intf_bridge(&rif.interface, rif.addr_width, rif.data_width, &rif.sw_clocking.clk, &rif.sw_clocking.rst)?;
+
+        // Interface handline
+        self.write("\n/*------------------------------------------------------------------------------\n",);
+        self.write("--  Interface handling\n");
+        self.write("------------------------------------------------------------------------------*/\n",);
+        let mut signals: Vec<SignalInfo> = vec![
+            SignalInfo::new("if_rif_err_addr"  , 1, "1'b0", "rif_err_addr_l   & if_rif_en"),
+            SignalInfo::new("if_rif_err_access", 1, "1'b0", "rif_err_access_l & if_rif_en"),
+            SignalInfo::new("if_rif_done", 1, "1'b0", "rif_done_next"),
+            SignalInfo::new_with_en("if_rif_rd_data", rif.data_width, &format!("{}'b0", rif.data_width), "rif_read_data_l", "rif_done_next & if_rif_rd_wrn"),
+        ];
+        // Access stays enabled until done: external registers are only started on the first cycle
+        if rif.has_external_reg() {
+            signals.push(SignalInfo::new("rif_pending", 1, "1'b0", "if_rif_en & ~rif_done_next"));
+        }
+        self.gen_process(
+            &rif.sw_clocking.clk,
+            &rif.sw_clocking.rst,
+            "proc_if_rif",
+            &signals,
+        );
+        self.write("   assign if_rif_done_next       = rif_done_next   ;\n");
+        self.write("   assign if_rif_err_addr_next   = rif_err_addr_l  ;\n");
+        self.write("   assign if_rif_err_access_next = rif_err_access_l;\n\n");
+        self.write(&format!(
+            "   assign rif_addr_l = if_rif_addr[{}:{}];\n\n",
+            rif.addr_width - 1,
+            addr_shift
+        ));
+
+        // Hardware clock enable: add register access to ensure field can be modify  by firmware
+        self.names.clear();
+        for hw_clk in rif.hw_clocking.iter() {
+            if !hw_clk.en.is_empty() && !self.names.contains(&hw_clk.en){
+                self.write(&format!("   assign {0}_l = {0} || if_rif_en;\n",hw_clk.en));
+                self.names.push(hw_clk.en.to_owned());
+            }
+        }
+
+        // Decode process
+        self.write("   always @* begin : proc_decode\n");
+        self.write(&format!("      rif_read_data_l = {}'b0;\n", rif.data_width));
+        if ext_pages.is_empty() {
+            self.write("      rif_done_next    = if_rif_en;\n");
+            self.write("      rif_err_addr_l   = 1'b1;\n");
+            self.write("      rif_err_access_l = 1'b1;\n");
+        } else {
+            let page_en: Vec<String> = ext_pages
+                .iter()
+                .map(|(n,_,_)| format!("if_page_{}_en", n))
+                .collect();
+            let page_en = page_en.join(" | ");
+            self.write(&format!("      rif_err_addr_l   = ~({});\n", page_en));
+            self.write(&format!("      rif_err_access_l = ~({});\n", page_en));
+            // Page enable is released when the page is done: the access completes
+            // with the answer of the page selected in the default branch
+            self.write(&format!("      rif_done_next    = if_rif_en & ~({});\n", page_en));
+        }
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                self.write(&format!("      {}__decode = 1'b0;\n", reg.name().to_casing(Snake)));
+            }
+        }
+        self.write("      case(rif_addr_l)\n");
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                let name_flat = reg.name().to_casing(Snake);
+                let group_name = reg.group_name.to_casing(Snake);
+                self.write(&format!(
+                    "         {}'d{} : begin\n",
+                    rif.addr_width - addr_shift,
+                    (reg.addr + page.addr) >> addr_shift
+                ));
+                self.write(&format!("            {name_flat}__decode = "));
+                let field_limit: Vec<(String, String)> = reg
+                    .fields
+                    .iter()
+                    .filter(|field| field.limit.value != LimitValue::None)
+                    .map(|field| (field.name.to_owned(), field.limit.bypass.to_owned()))
+                    .collect();
+                if !field_limit.is_empty() {
+                    self.write("if_rif_rd_wrn || (");
+                    for (i, fl) in field_limit.iter().enumerate() {
+                        if i != 0 {
+                            self.write(" && ");
+                        }
+                        if fl.1.is_empty() {
+                            self.write(&format!("{group_name}_{}__check", fl.0));
+                        } else {
+                            self.write(&format!("({group_name}_{}__check || {})", fl.0, Self::flat(&fl.1)));
+                        }
+                    }
+                    self.write(");\n");
+                } else {
+                    self.write("1'b1;\n");
+                }
+                self.write(&format!("            rif_read_data_l   = {name_flat}__read_data;\n"));
+                self.write("            rif_err_addr_l    = 1'b0;\n");
+                // Access error when writing a read-only field, reading a write only field,
+                //  or writing one field outside its set value (when limits are defined)
+                self.write("            rif_err_access_l  = ");
+                match reg.sw_access {
+                    Access::RO => self.write("~if_rif_rd_wrn;\n"),
+                    Access::WO => self.write("if_rif_rd_wrn;\n"),
+                    Access::NA => self.write("1'b1;\n"),
+                    Access::RW => {
+                        if field_limit.is_empty() {
+                            self.write("1'b0;\n");
+                        } else {
+                            self.write(&format!("~{name_flat}__decode;\n"));
+                        }
+                    },
+                }
+                // Handle external register
+                if reg.external!=ExternalKind::None {
+                    let hw_reg_def = rif.get_hw_reg(&reg.group_type);
+                    let idx = if let ArrayIdx::Inst(idx,_)= reg.array {format!("{idx}")} else {"".to_owned()};
+                    self.write(&format!("            rif_done_next = {group_name}{idx}_ext_"));
+                    if hw_reg_def.is_multi_pulse() {
+                        self.write(&name_flat);
+                        self.write("_");
+                    }
+                    self.write("done;\n");
+                    // Access is held until done: only the first cycle accesses the register
+                    self.write(&format!("            if(rif_pending) {name_flat}__decode = 1'b0;\n"));
+                }
+                self.write("         end\n");
+            }
+        }
+        // Handle external pages
+        if !ext_pages.is_empty() {
+            self.write("      default: begin\n");
+            for (i,(name,_,_)) in ext_pages.iter().enumerate() {
+                let name = name.to_casing(Snake);
+                self.write(&format!("            {}if(if_page_{}_done) begin\n",if i!=0 {"else"} else {""},name));
+                self.write(&format!("               rif_read_data_l  = if_page_{name}_rd_data;\n"));
+                self.write(&format!("               rif_err_addr_l   = if_page_{name}_err_addr;\n"));
+                self.write(&format!("               rif_err_access_l = if_page_{name}_err_access;\n"));
+                self.write("            end\n");
+            }
+            self.write("      end\n");
+        }
+
+        self.write("      endcase\n");
+        self.write("   end\n\n");
+
+        // Control the external page interface
+        for (name,addr,&width) in ext_pages.iter() {
+            let name = name.to_casing(Snake);
+            self.write(&format!("   assign if_page_{name}_addr    = if_rif_addr   ;\n"));
+            self.write(&format!("   assign if_page_{name}_rd_wrn  = if_rif_rd_wrn ;\n"));
+            self.write(&format!("   assign if_page_{name}_wr_data = if_rif_wr_data;\n"));
+            self.write(&format!("   assign if_page_{name}_en      = if_rif_en && !if_page_{name}_done && if_rif_addr[{}:{}]=={};\n",
+                rif.addr_width-1, width, addr >> width));
+        }
+
+        // Register Process
+        self.write("/*------------------------------------------------------------------------------\n");
+        self.write("--  Registers\n");
+        self.write("------------------------------------------------------------------------------*/\n\n");
+
+        let mut group_done : HashSet<String> = HashSet::with_capacity(rif.hw_regs.len());
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                let reg_impl = rif.get_hw_reg(&reg.group_type);
+                // Save a few string to be reused
+                let reg_name  = reg.name().to_casing(Snake); // Register Name with index apped after
+                let group_name = reg.group_name().to_casing(Snake); // Group name without index
+                let reg_name_i   = reg.name_i().to_casing(Snake); // Register name with optional index in bracket
+                let reg_idx    = if let ArrayIdx::Inst(idx,_) = reg.array {format!("{idx}")} else {"".to_owned()};
+                let group_name_i = format!("{}{reg_idx}", reg.group_name.to_casing(Snake)); // Group name with optional index
+                let intr_suffix = reg.intr_info.0.get_suffix();
+                self.write(&format!("   // Register {reg_name_i}\n"));
+                // Assign field
+                for field in reg.fields.iter() {
+                    let field_impl = reg_impl.get_field(&field.name)?;
+                    // Handle partial field
+                    let partial_range = if let Some(partial_pos) = field.partial.0 {
+                        if field.width > 1 {
+                            format!("[{}:{}]",partial_pos + field.width as u16 - 1, partial_pos)
+                        } else {
+                            format!("[{}]",partial_pos)
+                        }
+                    } else {
+                        "".to_string()
+                    };
+
+                    let field_name = field.name_flat().to_casing(Snake);
+                    // Local field: for partial field ensure the current one is also local
+                    let field_path = if field_impl.is_local() && field.has_write_mod() && !reg.is_external() {
+                        format!("{group_name}{intr_suffix}{reg_idx}_{field_name}__reg{partial_range}")
+                    } else {
+                        format!("rif_{group_name}{intr_suffix}{reg_idx}_{field_name}{partial_range}")
+                    };
+                    let reg_field_name = format!("{group_name}{intr_suffix}{reg_idx}_{field_name}");
+
+                    let reset_str = Self::field_reset_str(field, false);
+
+                    // Disabled field ? simply assign to its reset value
+                    if field.is_disabled() && (field.sw_kind==FieldSwKind::ReadWrite || field.sw_kind==FieldSwKind::WriteOnly) {
+                        self.write(&format!("   assign {field_path} = {reset_str}; // Disabled\n"));
+                        continue;
+                    }
+
+                    // Constant field
+                    if field_impl.is_constant() {
+                        self.write(&format!("   assign {field_path} = {reset_str}; \n"));
+                        continue;
+                    }
+
+                    // Construct the field value from the bus with bit selection
+                    // For non partial field, add proper casting (signed)
+                    let mut field_val = "if_rif_wr_data".to_string();
+                    if field.width > 1 {
+                        field_val.push_str(&format!("[{}:{}]",field.msb(), field.lsb));
+                    } else {
+                        field_val.push_str(&format!("[{}]",field.lsb));
+                    };
+                    if !field_impl.is_partial {
+                        field_val = Self::add_cast(&field_val, field);
+                    }
+
+                    // Add logic for field with limit
+                    if field.has_limit() {
+                        self.write(&format!("   assign {reg_field_name}__check = "));
+                        match &field.limit.value {
+                            LimitValue::Min(min) => {
+                                let min = GeneratorSv::value_to_str(min.to_u128(field.width), field.width.into(), field.is_signed(), false);
+                                self.write(&format!("{} >= {min}",field_val));
+                            }
+                            LimitValue::Max(max) => {
+                                let max = GeneratorSv::value_to_str(max.to_u128(field.width), field.width.into(), field.is_signed(), false);
+                                self.write(&format!("{} <= {max}",field_val));
+                            }
+                            LimitValue::MinMax(min, max) => {
+                                let min = GeneratorSv::value_to_str(min.to_u128(field.width), field.width.into(), field.is_signed(), false);
+                                let max = GeneratorSv::value_to_str(max.to_u128(field.width), field.width.into(), field.is_signed(), false);
+                                self.write(&format!("{0} >= {min} && {0} <= {max} ",field_val));
+                            }
+                            LimitValue::List(l) => {
+                                for (i,e) in l.iter().enumerate() {
+                                    let e_val = GeneratorSv::value_to_str(e.to_u128(field.width), field.width.into(), field.is_signed(), false);
+                                    self.write(&format!("{field_val} == {e_val}"));
+                                    if i < l.len()-1 {
+                                        self.write(" || ");
+                                    }
+                                }
+                            },
+                            LimitValue::Enum => {
+                                let Some(enum_name) = field.enum_kind.name() else {
+                                    return Err(format!("Using `limit enum` on non-enum field {reg_field_name}!").into());
+                                };
+                                let enum_type = if let Some(pkg) = &reg_impl.pkg {
+                                    if enum_name.contains(':') {enum_name.to_owned()}
+                                    else {format!("{pkg}_pkg::{enum_name}")}
+                                } else {
+                                    enum_name.to_owned()
+                                };
+                                let enum_def = rif.get_enum_def(&enum_type)?;
+                                for (i,e) in enum_def.iter().enumerate() {
+                                    self.write(&format!("{} == {:?}",field_val, e.value));
+                                    if i < enum_def.len()-1 {
+                                        self.write(" || ");
+                                    }
+                                }
+                            },
+                            // No limit -> nothing to do
+                            LimitValue::None => {},
+                        }
+                        self.write(";\n");
+                    }
+
+                    // For external register combinatorial assign from the interface bus
+                    if reg.is_external() && field.is_sw_write() {
+                        self.write(&format!("   assign {} = {};\n", field_path, field_val));
+                        continue;
+                    }
+
+                    // Combinatorial pulse : direct assign
+                    if field.sw_kind.is_pulse_comb() {
+                        self.write(&format!("   assign {field_path} = "));
+                        self.write(&format!("{reg_name}__decode & if_rif_en & ~if_rif_rd_wrn ? "));
+                        self.write(&format!("{field_val} : {}'b0;\n", field.width));
+                        continue;
+                    }
+
+                    // Counter event (counter kind is exclusive so take fist one)
+                    if let Some(FieldHwKind::Counter(info)) = field.hw_kind.first() {
+                        if info.sat || info.event {
+                            let msb = field.width-1;
+                            self.write(&format!("   assign {}_event = ", field_path));
+                            if field.is_sw_write() {
+                                let pol = if field.sw_kind==FieldSwKind::ReadClr {"~"} else {""};
+                                self.write(&format!("(~{reg_name}__decode | ~if_rif_en | {pol}if_rif_rd_wrn) & "));
+                            }
+                            self.write("(\n");
+                            if field.is_signed() {
+                                self.write(&format!("      {reg_field_name}__next[{}] ^ {reg_field_name}__next[{msb}]",
+                                    field.width));
+                            } else {
+                                if info.is_up() {
+                                    self.write(&format!("      (~{reg_field_name}__next[{msb}] & {field_path}[{msb}] & {}_incr_en)", &field_path[4..]));
+                                }
+                                if info.is_down() {
+                                    if info.is_up() {
+                                        self.write(" |\n");
+                                    }
+                                    self.write(&format!("      ({reg_field_name}__next[{msb}] & ~{field_path}[{msb}] & {}_decr_en)", &field_path[4..]));
+                                }
+                            }
+                            self.write(");\n");
+                        }
+                    }
+
+                    // Generate intermediate signal for interrupt
+                    if reg.is_intr() {
+                        let intr_info = reg_impl.intr_info(reg)?;
+                        // Local signal where interrupt vector is and with the optional enable signals
+                        self.write(&format!("   assign {0}_l_{1} = {0}_{1}", group_name, field_name));
+                        if intr_info.enable.is_some() {
+                            self.write(&format!(" & rif_{}_en_{}", group_name, field_name));
+                        }
+                        self.write(";\n");
+                        // Next
+                        self.write(&format!("   assign {reg_field_name}__next{partial_range} = "));
+                        if let Some(FieldHwKind::Interrupt(intr_trig)) = field.hw_kind.first() {
+                            match intr_trig {
+                                InterruptTrigger::High    => self.write(&format!("{group_name}_l_{}", field_name)),
+                                InterruptTrigger::Low     => self.write(&format!("~{group_name}_l_{}", field_name)),
+                                InterruptTrigger::Rising  => self.write(&format!("({0}_l_{1} & ~{0}_d1_{1})", group_name, field_name)),
+                                InterruptTrigger::Falling => self.write(&format!("(!{0}_l_{1} & ~{0}_d1_{1})", group_name, field_name)),
+                                InterruptTrigger::Edge    => self.write(&format!("({0}_l_{1} != {0}_d1_{1})", group_name, field_name)),
+                            }
+                        }
+                        self.write(&format!(" |\n      ({reg_name}__decode & if_rif_en & "));
+                        match intr_info.clear {
+                            InterruptClr::Read => self.write(&format!("if_rif_rd_wrn ? {}'b0", field.width)),
+                            InterruptClr::Write0 => self.write(&format!("~if_rif_rd_wrn ? ({} & {})", field_val, field_path)),
+                            InterruptClr::Write1 => self.write(&format!("~if_rif_rd_wrn ? (~{} & {})", field_val, field_path)),
+                            InterruptClr::Hw => return Err(format!("Interrupt clear by hardware is not supported on {reg_field_name}").into()),
+                        }
+                        self.write(&format!(" : {});\n", field_path));
+                        continue;
+                    }
+
+                    // Register derived from interrupt (i.e. enable/mask)
+                    // Basic read/write register
+                    if reg.is_intr_derived() && reg.intr_info.0 !=InterruptRegKind::Pending {
+                        self.write(&format!("   assign {reg_field_name}__next{partial_range} = \n      "));
+                        self.write(&format!("{reg_name}__decode & if_rif_en & ~if_rif_rd_wrn ? {field_val} :\n      "));
+                        self.write(&format!("{field_path};\n"));
+                        continue;
+                    }
+
+                    // Generate next value
+                    if field.is_hw_write() || field.is_sw_write() {
+
+                        // Generate __next signal
+                        self.write(&format!("   assign {reg_field_name}__next{partial_range} = \n      "));
+
+                        let mut cnt_info : Option<&CounterInfo> = None;
+                        let idx = if let Some(partial_pos) = field.partial.0 {format!("_{}",partial_pos)} else {"".to_owned()};
+
+                        // Handle hardware access
+                        if field.is_hw_write() {
+                            for kind in field.hw_kind.iter() {
+                                let suffix = kind.get_suffix();
+                                let sig = |info| Self::flat(&GeneratorSv::get_signal_name(info, suffix, &reg.group_type, &group_name, &reg_idx, &field_name, &idx));
+                                match kind {
+                                    FieldHwKind::WriteEn(info) => {
+                                        self.write(&format!("{} ? {group_name_i}_{field_name}{partial_range}", sig(info)));
+                                    },
+                                    FieldHwKind::WriteEnL(info) => {
+                                        self.write(&format!("~{} ? {group_name_i}_{field_name}{partial_range}", sig(info)));
+                                    },
+                                    FieldHwKind::Set(info) => {
+                                        self.write(&format!("{} ? ", sig(info)));
+                                        if field.width == 1 {
+                                            self.write("1'b1");
+                                        } else {
+                                            self.write(&format!("{field_path} | {group_name_i}_{field_name}{partial_range}"));
+                                        }
+                                    },
+                                    FieldHwKind::Clear(info) => {
+                                        self.write(&format!("{} ? ", sig(info)));
+                                        if field.width == 1 {
+                                            self.write("1'b0");
+                                        } else {
+                                            self.write(&format!("{field_path} & ~{group_name_i}_{field_name}{partial_range}"));
+                                        }
+                                    },
+                                    FieldHwKind::Toggle(info) => {
+                                        self.write(&format!("{} ? ", sig(info)));
+                                        if field.width == 1 {
+                                            self.write(&format!("~{field_path}"));
+                                        } else {
+                                            self.write(&format!("{field_path} ^ {group_name_i}_{field_name}{partial_range}"));
+                                        }
+                                    },
+                                    // Counter : save the info for later implementation (counter has less prevalence than software access)
+                                    FieldHwKind::Counter(info) => {
+                                        cnt_info = Some(info);
+                                    },
+                                    // Nothing todo for other HwKind (already handled for interrupt)
+                                    FieldHwKind::ReadOnly => {},
+                                    FieldHwKind::Interrupt(_) => {},
+                                }
+                                if !matches!(kind, FieldHwKind::Counter(_)) {
+                                    self.write(" :\n      ");
+                                }
+                            }
+                        }
+
+                        if field.is_sw_write() {
+                            self.write(&format!("{reg_name}__decode & if_rif_en "));
+                            // Handle Software access
+                            match &field.sw_kind {
+                                FieldSwKind::ReadWrite |
+                                FieldSwKind::WriteOnly => self.write(&format!("& ~if_rif_rd_wrn ? {field_val}")),
+                                FieldSwKind::ReadClr   => self.write(&format!("& if_rif_rd_wrn ? {}'b0", field.width)),
+                                FieldSwKind::W1Clr => {
+                                    self.write("& ~if_rif_rd_wrn ");
+                                    if field.width == 1 {
+                                        self.write(&format!("& {field_val} ? 1'b0"));
+                                    } else {
+                                        self.write(&format!("? {field_path} & ~{field_val}"));
+                                    }
+                                }
+                                FieldSwKind::W0Clr => {
+                                    self.write("& ~if_rif_rd_wrn ");
+                                    if field.width == 1 {
+                                        self.write(&format!("& ~{field_val} ? 1'b0"));
+                                    } else {
+                                        self.write(&format!("? {field_path} & {field_val}"));
+                                    }
+                                }
+                                FieldSwKind::W1Set |
+                                FieldSwKind::W1Pulse(_,_) => {
+                                    self.write("& ~if_rif_rd_wrn ? ");
+                                    if field.width > 1 {
+                                        self.write(&format!("{field_path} | "));
+                                    }
+                                    self.write(&field_val);
+                                }
+                                FieldSwKind::W1Tgl => {
+                                    self.write("& ~if_rif_rd_wrn ? ");
+                                    if field.width == 1 {
+                                        self.write(&format!("~{field_path}", ));
+                                    } else {
+                                        self.write(&format!("{field_path} ^ {field_val}"));
+                                    }
+                                }
+                                FieldSwKind::Password(info) => {
+                                    self.write("& ~if_rif_rd_wrn");
+                                    if info.protect || (info.once.is_some() && info.hold.is_some()) {
+                                        self.write(&format!(" & ({field_path}_hold | ~{field_path}_locked)"));
+                                    }
+                                    self.write(" ? (");
+                                    if let Some(v) = &info.once {
+                                        self.write(&format!("{}=={} ? 2'd0 : ", field_val, GeneratorSv::value_to_str(v.to_u128(field.width), field.width.into(), false, true)));
+                                    }
+                                    if let Some(v) = &info.hold {
+                                        self.write(&format!("{}=={} ? 2'd2 : ", field_val, GeneratorSv::value_to_str(v.to_u128(field.width), field.width.into(), false, true)));
+                                    }
+                                    if info.protect {
+                                        self.write(&format!("{field_val}!=0 ? 2'd3 : "));
+                                    }
+                                    self.write("2'd1)");
+                                    // For once password, reset to 1 when writing on any other register
+                                    if info.once.is_some() {
+                                        self.write(" :\n      if_rif_en & ~if_rif_rd_wrn");
+                                        if info.hold.is_some() {
+                                            self.write(&format!(" & ~{field_path}_hold"));
+                                        }
+                                        self.write(" ? 2'd1");
+                                    }
+                                },
+                                // Read Only case should be impossible due to the is_sw_write check earlier
+                                FieldSwKind::ReadOnly => {},
+                            }
+                            self.write(" :\n      ");
+                        }
+
+                        // Handle Counter
+                        if let Some(info) = cnt_info {
+                            let hw_path = &field_path[4..];
+                            if info.clr {
+                                self.write(&format!("{hw_path}_clr ? {reset_str} :\n      "));
+                            }
+                            if info.is_up() {
+                                self.write(&format!("{hw_path}_incr_en ? {field_path} + "));
+                                if info.incr_val <= 1 {
+                                    self.write(&GeneratorSv::value_to_str(1, field.width.into(), field.is_signed(), false));
+                                } else {
+                                    self.write(&format!("{hw_path}_incr_val"));
+                                }
+                                self.write(" :\n      ");
+                            }
+                            if info.is_down() {
+                                self.write(&format!("{hw_path}_decr_en ? {field_path} - "));
+                                if info.decr_val <= 1 {
+                                    self.write(&GeneratorSv::value_to_str(1, field.width.into(), field.is_signed(), false));
+                                } else {
+                                    self.write(&format!("{hw_path}_decr_val"));
+                                }
+                                self.write(" :\n      ");
+                            }
+                        }
+
+                        // Default next to current value
+                        match &field.sw_kind {
+                            FieldSwKind::W1Pulse(_,_) => self.write("1'b0;\n"),
+                            FieldSwKind::Password(info) => {
+                                if info.hold.is_some() {
+                                    self.write(&format!("{{{field_path}_hold,"))
+                                } else {
+                                    self.write("{1'b0,");
+                                }
+                                self.write(&format!("{field_path}_locked}};\n"))
+                            }
+                            _ => self.write(&format!("{field_path};\n"))
+                        }
+                    }
+                    // Handle case of partial field where one part is read-only
+                    else if field_impl.has_write_mod() {
+                        self.write(&format!("   assign {reg_field_name}__next{partial_range} = {}'b0; // unused\n", field.width));
+                    }
+
+                }
+                // External register
+                if reg.is_external() {
+                    let mut sig_name = format!("rif_{group_name_i}_ext");
+                    if reg_impl.regs_ctrl.len() > 1 {
+                        sig_name.push_str(&format!("_{}",reg.reg_name));
+                    }
+                    if reg.sw_access.is_writable() {
+                        self.write(&format!("   assign {sig_name}_write = {reg_name}__decode && if_rif_en && ~if_rif_rd_wrn;\n"));
+                    }
+                    if reg.sw_access.is_readable() {
+                        self.write(&format!("   assign {sig_name}_read = {reg_name}__decode && if_rif_en && if_rif_rd_wrn;\n"));
+                    }
+                }
+                // Sequential process
+                else if reg.has_proc() {
+                   // Get a default clock for the register
+                    let hw_clk = &rif.hw_clocking.first().unwrap_or(&rif.sw_clocking);
+                    let reg_clk =
+                        if let Some(n) = &reg_impl.clk {n}
+                        else if reg.sw_access.is_writable() && !reg.is_intr() {&rif.sw_clocking.clk}
+                        else {&hw_clk.clk};
+                    // Collect each field signal info in a hashmap indexed by a couple (clk/rst)
+                    let mut signals: OrderDict<(String,String), Vec<SignalInfo> > = OrderDict::new();
+                    for field in reg.fields.iter() {
+                        // Get field implementation
+                        let field_impl = reg_impl.get_field(&field.name)?;
+                        let field_idx = if field.array.dim() > 0 {format!("{}", field.array.idx())} else {"".to_owned()};
+                        // Ignore disabled fields and partial fields after the first one
+                        let partial_pos = field.partial.0.unwrap_or(0);
+                        if field.is_disabled() || partial_pos!=0 || field.sw_kind.is_pulse_comb() {
+                            continue;
+                        }
+                        // Combinatorial pulse and readonly field with no hardware
+                        if field.sw_kind.is_pulse_comb() || (!field_impl.is_hw_write() && !field_impl.is_sw_write()) {
+                            continue;
+                        }
+                        let field_name = field.name_flat().to_casing(Snake);
+                        // Get clock associated with the field
+                        let f_clk =
+                            if let Some(n) = &field_impl.clk {n}
+                            else if let Some(n) = &reg_impl.clk {n}
+                            else if field_impl.is_hw_write() && !reg.is_intr_derived() {&hw_clk.clk}
+                            else {&rif.sw_clocking.clk};
+                        // Get reset associated with the field
+                        let f_rst =
+                            if let Some(n) = &reg_impl.rst {n}
+                            else if f_clk==&hw_clk.clk {&hw_clk.rst.name}
+                            else {&rif.sw_clocking.rst.name};
+                        // Name of the signal
+                        let mut name = if field_impl.is_local() {
+                            format!("{group_name}{intr_suffix}{reg_idx}_{field_name}__reg")
+                        } else {
+                            format!("rif_{group_name}{intr_suffix}{reg_idx}_{field_name}")
+                        };
+                        // Next value
+                        let mut value = format!("{group_name}{intr_suffix}{reg_idx}_{field_name}__next");
+                        // Enable
+                        let mut enable = if reg.is_intr() {
+                            format!("clk_en_intr_{group_name_i}")
+                        } else if let ClkEn::Signal(clk_en) = &field_impl.clk_en {
+                            clk_en.clone()
+                        } else if let ClkEn::Signal(clk_en) = &reg_impl.clk_en {
+                            clk_en.clone()
+                        } else if field.is_hw_write() {
+                            hw_clk.en.clone()
+                        } else {
+                            rif.sw_clocking.en.clone()
+                        };
+                        if !enable.is_empty() && enable == hw_clk.en {
+                            enable.push_str("_l");
+                        }
+                        if field_impl.lock.is_some() {
+                            if !enable.is_empty() {
+                                enable.push_str(" & ");
+                            }
+                            let lock_name = GeneratorSv::get_signal_name(field_impl.lock.name(), "_lock", &reg.group_type,  &group_name, &reg_idx, &field.name, &field_idx);
+                            enable.push_str(&format!("~{}", Self::field_signal(rif, &lock_name)));
+                        }
+                        if let Some(FieldHwKind::Counter(cnt_info)) = field.hw_kind.first() {
+                            if cnt_info.sat && cnt_info.incr_val <= 1 && cnt_info.decr_val <= 1 {
+                                if !enable.is_empty() {
+                                    enable.push_str(" & ");
+                                }
+                                enable.push_str(&format!("~rif_{group_name_i}_{}_event", field.name));
+                            }
+                            if field.array.dim() > 0 {
+                                return Err(format!("Field array of counters is not supported ({reg_name}.{})", field.name).into());
+                            }
+                        }
+                        // Clear
+                        let clear = if reg_impl.clear.is_some() {
+                            Self::flat(&GeneratorSv::get_signal_name(&reg_impl.clear, "reg_clr", &reg.group_type,  &group_name, &reg_idx, "", ""))
+                        } else if field_impl.clear.is_some() {
+                            Self::flat(&GeneratorSv::get_signal_name(&field_impl.clear, "_clr", &reg.group_type,  &group_name, &reg_idx, &field.name, &field_idx))
+                        } else {
+                            "".to_string()
+                        };
+
+                        // Signal Width: field width except for special fields
+                        let width = if field.is_password() {1} else {field.width};
+
+                        let reset = if field.is_password() {
+                                "1'b1".to_owned()
+                            } else if field.partial.0.is_some() {
+                                let rst_val = field_impl.get_reset(reg.group_idx);
+                                GeneratorSv::value_to_str(rst_val, field_impl.width, field_impl.signed, true)
+                            } else {
+                                Self::field_reset_str(field, false)
+                            };
+
+                        // Handle Special cases
+                        if field.is_password() {
+                            name.push_str("_locked");
+                            value.push_str("[0]");
+                        }
+                        else if let Some(FieldHwKind::Counter(cnt_info)) = field.hw_kind.first() {
+                            if cnt_info.sat && (cnt_info.incr_val > 1 || cnt_info.decr_val > 1) {
+                                let sat = if field.is_signed() {
+                                    format!("$signed({{{0}[{1}],{{{2}{{~{0}[{1}]}}}}}})",value, field.width, field.width-1)
+                                } else {
+                                    format!("{{{0}{{~{1}[{0}]}}}}}}",field.width,value)
+                                };
+                                value = format!("rif_{group_name_i}_{}_event ? {sat} : {value}", field.name);
+                            }
+                        }
+
+                        // Add the signal info the hashmap
+                        let k = (f_clk.to_string(),f_rst.to_string());
+                        let field_entry = signals.entry(&k);
+
+                        field_entry.push(
+                            SignalInfo::new_with_en_clr(&name, width, &reset, &value, &enable, &clear)
+                        );
+
+                        // For password protected or with both option once/hold, add another signal
+                        if let FieldSwKind::Password(info) = &field.sw_kind {
+                            if info.has_hold() {
+                                let name = format!("rif_{group_name_i}_{}_hold", field.name);
+                                field_entry.push(
+                                    SignalInfo::new_with_en_clr(
+                                        &name, width,
+                                        "1'b0",
+                                        &format!("{group_name}{reg_idx}_{field_name}__next[1]"),
+                                        &enable, &clear)
+                                );
+                            }
+                        }
+                        // For interrupt on edge, add delay version of the interrupt event
+                        else if let Some(FieldHwKind::Interrupt(info)) = field.hw_kind.first() {
+                            if !info.is_level() {
+                                field_entry.push(
+                                    SignalInfo::new_with_en_clr(
+                                        &format!("{group_name}_d1{reg_idx}_{field_name}"),
+                                        width,
+                                        "1'b0",
+                                        &format!("{group_name}_l{reg_idx}_{field_name}"),
+                                        &enable, &clear)
+                                    );
+                            }
+                        }
+                    }
+                    // Create one process for each pair of clock/reset found in the register field
+                    for ((clk,rst_name),sig_list) in signals.items() {
+                        let mut proc_name = format!("proc_{reg_name}");
+                        // Append clk/rst_name to process if different from the register default
+                        if clk!=reg_clk && signals.len() > 1 {
+                            proc_name.push_str(&format!("_{}",clk));
+                        }
+                        let mut rst = if clk==&rif.sw_clocking.clk || rif.hw_clocking.is_empty() {&rif.sw_clocking.rst} else {&rif.hw_clocking.first().unwrap().rst};
+                        // Find the full reset definition in the sw_clock or hw_clocking
+                        if rst_name!=&rst.name {
+                            proc_name.push_str(&format!("_{}",rst_name));
+                            if rst_name == &rif.sw_clocking.rst.name {
+                                rst = &rif.sw_clocking.rst;
+                            } else {
+                                rst = &rif.hw_clocking.iter()
+                                    .find(|&x| &x.rst.name==rst_name)
+                                    .ok_or(format!("Reset {rst_name} should be amongst the software or hardware reset list !"))?
+                                    .rst;
+                            }
+                        }
+                        //
+                        self.gen_process(clk, rst, &proc_name, sig_list);
+                    }
+                }
+
+                // Create process to generate register pulse access
+                if !group_done.contains(&group_name_i) {
+                    group_done.insert(group_name_i.clone());
+
+                    let mut signals: Vec<SignalInfo> = Vec::new();
+                    let mut reg_clk = "".to_owned();
+                    for ctrl in reg_impl.regs_ctrl.iter() {
+                        let mut base_name = format!("rif_{group_name_i}_p");
+                        let ctrl_name = format!("{}{reg_idx}",ctrl.name.to_casing(Snake));
+                        let base_value = format!("{ctrl_name}__decode & if_rif_en");
+                        if reg_impl.is_multi_pulse() {
+                            base_name.push('_');
+                            base_name.push_str(&ctrl_name);
+                        };
+                        for pulse in ctrl.pulse.iter() {
+                            let mut name = base_name.to_owned();
+                            let mut value = base_value.to_owned();
+                            let p_clk =
+                                match pulse {
+                                    RegPulseKind::Write(clk)  => {
+                                        name.push_str("_write");
+                                        value.push_str(" & ~if_rif_rd_wrn");
+                                        clk
+                                    },
+                                    RegPulseKind::Read(clk)   => {
+                                        name.push_str("_read");
+                                        value.push_str(" & if_rif_rd_wrn");
+                                        clk
+                                    },
+                                    RegPulseKind::Access(clk) => {
+                                        name.push_str("_acc");
+                                        clk
+                                    },
+                                };
+                            // No clock means the pulse is just combinatorial logic
+                            if p_clk.is_empty() {
+                                self.write(&format!("   assign {name} = {value};\n"));
+                            } else {
+                                if reg_clk.is_empty() {
+                                    reg_clk = p_clk.to_owned();
+                                } else if &reg_clk!=p_clk {
+                                    return Err(format!("Only one clock should be used for the register {group_name} pulses").into());
+                                }
+                                signals.push(SignalInfo::new(&name, 1, "1'b0", &value));
+                            }
+                        }
+                    }
+                    if !signals.is_empty() {
+                        let proc_name = format!("proc_{group_name}{reg_idx}_special");
+                        self.gen_process(&reg_clk, &rif.sw_clocking.rst, &proc_name, &signals);
+                    }
+                }
+
+                // Interrupt registers signals : clock enable and IRQ
+                if reg.is_intr() {
+                    let intr_info = reg_impl.intr_info(reg)?;
+                    // Clock enable : or of all interrupts events (only the base one, not the alternate)
+                    if intr_info.name.is_empty() {
+                        self.write(&format!("   assign clk_en_intr_{group_name} ="));
+                        if let ClkEn::Signal(clk_en) = &reg_impl.clk_en {
+                            self.write(&format!(" {clk_en} || "));
+                        } else {
+                            self.write("\n      ");
+                            for field in reg.fields.iter() {
+                                if let Some(FieldHwKind::Interrupt(intr_trig)) = field.hw_kind.first() {
+                                    let field_name = field.name_flat().to_casing(Snake);
+                                    match intr_trig {
+                                        // Level Trigger
+                                        InterruptTrigger::High => self.write(&format!("{group_name}_{field_name}!=0 ||\n      ")),
+                                        InterruptTrigger::Low  => self.write(&format!("~{group_name}_{field_name}!=0 ||\n      ")),
+                                        // Edge trigger : enable on change
+                                        _ => self.write(&format!("{0}_{1}!={0}_d1_{1} ||\n      ", group_name, field_name)),
+                                    }
+                                }
+                            }
+                        }
+                        self.write("if_rif_en;\n\n");
+                    }
+                    // IRQ: or of all interrupts status and-ed with the mask
+                    for field in reg.fields.iter() {
+                        let field_name = field.name_flat().to_casing(Snake);
+                        self.write(&format!("   assign rif_{group_name}_pending_{field_name} = "));
+                        if field.is_disabled() {
+                            self.write(&Self::field_reset_str(field, false));
+                        } else {
+                            self.write(&format!("rif_{group_name_i}_{field_name}"));
+                            if intr_info.mask.is_some() {
+                                self.write(&format!(" & rif_{group_name}_mask_{field_name}"));
+                            }
+                        }
+                        self.write(";\n");
+                    }
+                    self.write(&format!("\n   assign rif_{group_name}_irq = \n"));
+                    self.write(
+                        &reg.fields.iter().filter(|f| !f.is_disabled())
+                            .map(|field| format!("      {}rif_{group_name}_pending_{}",
+                                if field.width > 1 {"|"} else {""},
+                                field.name_flat().to_casing(Snake)))
+                            .collect::<Vec<String>>()
+                            .join(" ||\n")
+                        );
+                    self.write(";\n\n");
+                }
+
+                // Concatenation for Read data
+                self.write(&format!("   assign {reg_name}__read_data = "));
+                let nb_fields = reg.fields.iter().rev().filter(|f| !f.sw_kind.is_wo()).count();
+                let first_field = reg.fields.iter().find(|f| !f.sw_kind.is_wo());
+                let first_width = if let Some(f) = first_field {f.width} else {0};
+                let first_is_signed = if let Some(f) = first_field {f.is_signed()} else {false};
+                let is_single_wide_field = nb_fields==0 || (nb_fields == 1 && first_width==rif.data_width);
+                // Start concatenation of fields if more than one, or cast to unsigned if only one field signed
+                if !is_single_wide_field {
+                    self.write("{");
+                } else if first_is_signed {
+                    self.write("$unsigned(");
+                }
+                let mut prev_lsb = rif.data_width;
+                for field in reg.fields.iter().rev().filter(|f| !f.sw_kind.is_wo()) {
+                    let field_impl = reg_impl.get_field(&field.name)?;
+                    let field_name = field.name_flat().to_casing(Snake);
+                    // Fill register spaces with 0s
+                    let spaces = prev_lsb.saturating_sub(field.msb()+1);
+                    if spaces != 0 {
+                        self.write(&format!("{}'b0,", spaces));
+                    }
+                    if !reg.is_external() && field_impl.is_local() && field.has_write_mod() {
+                        self.write(&format!("{group_name}{intr_suffix}{reg_idx}_{field_name}__reg"));
+                    } else if let FieldSwKind::Password(info) = &field.sw_kind {
+                        if info.has_hold() {
+                            self.write(&format!("{2}'b0,rif_{0}_{1}_hold,rif_{0}_{1}_locked", group_name_i, field.name, field.width-2));
+                        } else {
+                            self.write(&format!("{2}'b0,rif_{0}_{1}_locked", group_name_i, field.name, field.width-1));
+                        }
+                    } else {
+                        if !reg.is_external() && (field_impl.is_sw_write() || field.is_hw_write() || field_impl.is_constant()) {
+                            self.write("rif_");
+                        }
+                        self.write(&format!("{group_name}{intr_suffix}{reg_idx}_{field_name}"));
+                    }
+                    if let Some(partial_pos) = field.partial.0 {
+                        if field.width > 1 {
+                            self.write(&format!("[{}:{}]", partial_pos + field.width as u16 - 1, partial_pos));
+                        } else {
+                            self.write(&format!("[{}]", partial_pos));
+                        }
+                    }
+
+                    prev_lsb = field.lsb;
+                    if prev_lsb!=0 {
+                        self.write(",");
+                    }
+                }
+                // Handle case where the first field does not starts at 0
+                if prev_lsb!=0 {
+                    self.write(&format!("{}'b0", prev_lsb));
+                }
+                // Close concatenation of fields or unsigned cast
+                if !is_single_wide_field {
+                    self.write("}");
+                }  else if first_is_signed {
+                    self.write(")");
+                }
+                self.write(";\n\n");
+            }
+        }
+
+        // Handle case of missing fields in a register implementation
+        for (group_name, hw_reg) in rif.hw_regs.items() {
+            // Skip register if read-only from firmware
+            let reg_impl = rif.get_hw_reg(&hw_reg.group);
+            if !reg_impl.port.is_out() && reg_impl.interrupt.is_empty() {continue;}
+            for (field_name,info) in &hw_reg.missing_fields {
+                let rst = GeneratorSv::value_to_str(info.reset, info.width, info.signed, info.width > 16);
+                self.write(&format!("   assign rif_{group_name}_{field_name} = {rst};\n",));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_clocking_port(
+        ports: &mut Vec<(String,String)>,
+        info: &ClockingInfo,
+        list: &mut HashSet<String>,
+        is_hw: bool,
+    ) {
+        let kind = if is_hw { "Hardware" } else { "Software" };
+        // Clock
+        if !list.contains(&info.clk) {
+            ports.push((format!("input  wire {}", info.clk), format!("{kind} Clock")));
+            list.insert(info.clk.to_owned());
+        }
+        // Reset
+        if !list.contains(&info.rst.name) {
+            ports.push((format!("input  wire {}", info.rst.name), format!("{kind} {}", info.rst.desc())));
+            list.insert(info.rst.name.to_owned());
+        }
+        // Clear
+        if !info.clear.is_empty() && !list.contains(&info.clear) {
+            ports.push((format!("input  wire {}", info.clear), format!("{kind} Clear")));
+            list.insert(info.clear.to_owned());
+        }
+    }
+
+    /// Add ports of the main control interface: internal register interface or the bus signals
+    fn add_intf_ports(&self, ports: &mut Vec<(String,String)>, intf: &Interface, addr_w: u8, data_w: u8) -> Result<(), String> {
+        match intf {
+            Interface::Default => self.add_rif_if_ports(ports, "if_rif", false, addr_w, data_w),
+            Interface::Custom(name) => return Err(format!("Custom interface {name} is not supported in Verilog")),
+            _ => {
+                Self::bridge_src(intf)?;
+                for port in RifIntfPorts::new(intf).iter() {
+                    ports.push(self.port_decl(port, "", false, addr_w, data_w));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add all ports of the internal register interface, with direction reversed for the control side
+    fn add_rif_if_ports(&self, ports: &mut Vec<(String,String)>, prefix: &str, is_ctrl: bool, addr_w: u8, data_w: u8) {
+        for (name, dir, width, desc) in RIF_IF_SIGNALS.iter() {
+            let port = PortInfo::new(name.to_string(), width.clone(), dir.clone(), desc.to_string(), 0);
+            ports.push(self.port_decl(&port, prefix, is_ctrl, addr_w, data_w));
+        }
+    }
+
+    /// Declare the internal register interface as local signals
+    fn add_rif_if_signals(&mut self, addr_w: u8, data_w: u8) {
+        for (name, _, width, _) in RIF_IF_SIGNALS.iter() {
+            let w = match width {
+                PortWidth::Address => addr_w,
+                PortWidth::Data => data_w,
+                _ => 1,
+            };
+            let name = format!("if_rif_{name}");
+            self.write(&format!("   {} {}{name};\n", self.net_kind(&name), Self::range(w.into(), false)));
+        }
+    }
+
+    /// Create a port declaration and its associated comment
+    fn port_decl(&self, port: &PortInfo, prefix: &str, is_ctrl: bool, addr_w: u8, data_w: u8) -> (String, String) {
+        let name = if prefix.is_empty() {port.name.to_casing(Snake)} else {format!("{prefix}_{}", port.name.to_casing(Snake))};
+        let range = Self::range(port.width(addr_w, data_w).into(), false);
+        let decl = if port.dir.is_out() != is_ctrl {
+            format!("output {} {range}{name}", self.net_kind(&name))
+        } else {
+            format!("input  wire {range}{name}")
+        };
+        (decl, port.desc.to_owned())
+    }
+
+    /// Write the port list of a module
+    fn write_ports(&mut self, ports: &[(String,String)]) {
+        self.write(" (\n");
+        let len = ports.iter().map(|(d,_)| d.len()).max().unwrap_or(0) + 1;
+        let mut ports_iter = ports.iter().peekable();
+        while let Some((decl, desc)) = ports_iter.next() {
+            let sep = if ports_iter.peek().is_none() {""} else {","};
+            self.write(&format!("   {:<len$}", format!("{decl}{sep}")));
+            if !desc.is_empty() {
+                self.write(&format!(" // {desc}"));
+            }
+            self.write("\n");
+        }
+        self.write(");\n\n");
+    }
+
+    /// Declare all signals of a flattened structure
+    fn declare_members(&mut self, members: &[StructMember], base: &str) {
+        for m in members.iter() {
+            for name in m.signals(base) {
+                self.write(&format!("   {} {}{name};\n", self.net_kind(&name), Self::range(m.width, m.signed)));
+            }
+        }
+    }
+
+    fn add_intf_bridge(&mut self, intf: &Interface, addr_w: u8, data_w: u8, sw_clk: &str, sw_rst: &ResetDef) -> Result<(), String> {
+        if intf.is_default() {
+            return Ok(());
+        }
+        self.write("\n/*------------------------------------------------------------------------------\n");
+        self.write("--  Bridge to the internal register interface\n");
+        self.write("------------------------------------------------------------------------------*/\n");
+        let name = intf.name();
+        self.write(&format!("   bridge_{name}_rif #(.W_ADDR({addr_w}), .W_DATA({data_w})) i_bridge (\n"));
+        let mut maps = vec![
+            ("clk".to_owned(), sw_clk.to_owned()),
+            ("rst_n".to_owned(), bridge_rst_n(sw_rst, "~")?),
+        ];
+        maps.extend(RifIntfPorts::new(intf).iter().map(|p| (p.name.to_owned(), p.name.to_owned())));
+        maps.extend(RIF_IF_SIGNALS.iter().map(|(n,_,_,_)| (format!("if_rif_{n}"), format!("if_rif_{n}"))));
+        self.write(&maps.iter()
+            .map(|(f,a)| format!("      .{f}({a})"))
+            .collect::<Vec<String>>()
+            .join(",\n"));
+        self.write("\n   );\n");
+        Ok(())
+    }
+
+    /// Write the module of the bridge used by the interface, if any
+    fn gen_bridge(&mut self, intf: &Interface) -> Result<(), Box<dyn std::error::Error>> {
+        if intf.is_default() {
+            return Ok(());
+        }
+        self.write(Self::bridge_src(intf)?);
+        self.save(&format!("bridge_{}_rif.v", intf.name()))
+    }
+
+    /// Source of the bridge module of an interface
+    fn bridge_src(intf: &Interface) -> Result<&'static str, String> {
+        VERILOG_BRIDGES.iter()
+            .find(|(n,_)| *n == intf.name())
+            .map(|(_,src)| *src)
+            .ok_or(format!("Interface {} is not supported in Verilog", intf.name()))
+    }
+
+    /// Net kind of a signal: reg when assigned in a process, wire otherwise
+    fn net_kind(&self, name: &str) -> &'static str {
+        if self.regs.contains(name) {"reg "} else {"wire"}
+    }
+
+    /// Range of a vector with optional signed qualifier
+    fn range(width: u16, signed: bool) -> String {
+        let signed = if signed {"signed "} else {""};
+        if width > 1 {
+            format!("{signed}[{}:0] ", width-1)
+        } else {
+            signed.to_owned()
+        }
+    }
+
+    /// Flatten a structure member access into a signal name: `a[1].b` becomes `a1_b`
+    /// Flat signal name of a reference to a field of another register: software value
+    /// (locked state for password) when the field is controlled by the register access
+    fn field_signal(rif: &RifInst, name: &str) -> String {
+        if let Some((reg_name, field_name)) = name.split_once('.').filter(|_| !name.starts_with('(')) {
+            if let Some(hw_reg) = rif.hw_regs.get(&reg_name.to_owned()) {
+                let reg_impl = rif.get_hw_reg(&hw_reg.group);
+                if let Some(f) = reg_impl.fields.iter().find(|f| f.name == field_name) {
+                    if f.sw_kind.is_password() {
+                        return format!("rif_{reg_name}_{field_name}_locked");
+                    }
+                    if f.is_sw_write() {
+                        return format!("rif_{reg_name}_{field_name}");
+                    }
+                }
+            }
+        }
+        Self::flat(name)
+    }
+
+    fn flat(name: &str) -> String {
+        if name.starts_with('(') {
+            name.replace('.', "_")
+        } else {
+            name.replace('.', "_").replace(['[', ']'], "")
+        }
+    }
+
+    /// Verilog has no enum type: only signed field requires a cast
+    fn add_cast(val: &str, field: &RifFieldInst) -> String {
+        if field.is_signed() && field.enum_kind.name().is_none() {
+            format!("$signed({val})")
+        } else {
+            val.to_owned()
+        }
+    }
+
+    fn field_reset_str(field: &RifFieldInst, is_hexa: bool) -> String {
+        let val = field.reset.to_u128(field.width);
+        let is_signed = field.is_signed() && field.enum_kind.name().is_none();
+        GeneratorSv::value_to_str(val, field.width.into(), is_signed, is_hexa || field.width > 16)
+    }
+
+    /// Generate synchronous process
+    fn gen_process(&mut self, clk: &str, rst: &ResetDef, name: &str, signals: &[SignalInfo]) {
+        // Signals assigned in the process must be declared as reg
+        for signal in signals.iter() {
+            self.regs.insert(signal.name.to_owned());
+        }
+        // Check all signals clear/enable o see if all signals share a condition or not
+        let mut signals_iter = signals.iter();
+        let (clk_en, clr): (Option<&String>, Option<&String>) =
+            if let Some(signal) = signals_iter.next() {
+                (signal.enable.as_ref(), signal.clear.as_ref())
+            } else {
+                (None, None)
+            };
+        let mut clk_en_global = true;
+        let mut clr_global = true;
+        for signal in signals_iter {
+            if clk_en_global && signal.enable.as_ref() != clk_en {
+                clk_en_global = false;
+            }
+            if clr_global && signal.clear.as_ref() != clr {
+                clr_global = false;
+            }
+        }
+        // Declaration
+        self.write(&format!("\n   always @(posedge {clk}"));
+        if !rst.sync {
+            let pol = if rst.active_high { "pos" } else { "neg" };
+            self.write(&format!(" or {pol}edge {}", rst.name));
+        }
+        // Reset
+        self.write(&format!(") begin : {name}\n      if("));
+        if !rst.active_high {
+            self.write("!");
+        }
+        self.write(&format!("{}) begin\n", rst.name));
+        for signal in signals.iter() {
+            self.write(&format!("         {} <= {};\n", signal.name, signal.reset));
+        }
+        self.write("      end else ");
+        // Optional Global Enable
+        if let (true, Some(clk_en)) = (clk_en_global, clk_en) {
+            self.write(&format!("if({clk_en}) "));
+        }
+        self.write("begin\n");
+        // Optional Global clear
+        if let (true, Some(clr)) = (clr_global, clr) {
+            self.write(&format!("      if({clr}) begin\n"));
+            for signal in signals.iter() {
+                self.write(&format!("            {} <= {};\n", signal.name, signal.reset));
+            }
+            self.write("      end else begin\n");
+        }
+        // Set value
+        for signal in signals.iter() {
+            self.write("         ");
+            if let (false, Some(clear)) = (clr_global, &signal.clear) {
+                self.write(&format!("if({clear})\n            {} <= {};\n            else", signal.name, signal.reset));
+            }
+            if let (false, Some(enable)) = (clk_en_global, &signal.enable) {
+                self.write(&format!("if({enable})\n            "));
+            }
+            self.write(&format!("{} <= {};\n", signal.name, signal.value));
+        }
+        //
+        if clr_global && clr.is_some() {
+            self.write("      end\n");
+        }
+        self.write("      end\n   end\n\n");
+    }
+
+
+    //-----------------------------------------------------------------------------
+    // RIF Mux implementation: Address decoding, registers , ...
+    //-----------------------------------------------------------------------------
+
+    fn gen_rifmux(&mut self, rifmux: &RifmuxInst) -> Result<(), Box<dyn std::error::Error>> {
+        let msb = rifmux.addr_width - 1;
+        let name_len = rifmux.components.iter().map(|c| c.get_name().len()).max().unwrap_or(0);
+        let rifmux_name = &rifmux.inst_name;
+        self.regs.clear();
+
+        // Header (TODO: support external template)
+        self.write("// File generated automatically: DO NOT EDIT.\n\n");
+        self.write(&format!("module {rifmux_name}"));
+
+        // Port declaration
+        let mut ports : Vec<(String,String)> = Vec::new();
+        if !rifmux.interface.is_default() {
+            ports.push((format!("input  wire {}", rifmux.sw_clocking.clk), "Bridge clock".to_owned()));
+            ports.push((format!("input  wire {}", rifmux.sw_clocking.rst.name), format!("Bridge reset: {}", rifmux.sw_clocking.rst.desc())));
+        }
+        for comp in rifmux.components.iter() {
+            self.add_rif_if_ports(&mut ports, &format!("if_{}", comp.get_name()), true, comp.get_addr_width(), rifmux.data_width);
+        }
+        self.add_intf_ports(&mut ports, &rifmux.interface, rifmux.addr_width, rifmux.data_width)?;
+        self.write_ports(&ports);
+
+        if !rifmux.interface.is_default() {
+            self.add_rif_if_signals(rifmux.addr_width, rifmux.data_width);
+        }
+        self.write("   wire addr_invalid; // High when address is not in the range of any of the connected RIF\n");
+        self.write("   wire addr_invalid_next; // Combinatorial version of addr_invalid\n");
+
+        // Add interface bridge when not default
+        self.add_intf_bridge(&rifmux.interface, rifmux.addr_width, rifmux.data_width, &rifmux.sw_clocking.clk, &rifmux.sw_clocking.rst)?;
+
+        // Address demultiplexing
+        self.write("\n/*------------------------------------------------------------------------------\n");
+        self.write("--  Demux access\n");
+        self.write("------------------------------------------------------------------------------*/\n\n");
+
+        let mut en_names = Vec::new();
+        for comp in rifmux.components.iter() {
+            let name = comp.get_name();
+            let lsb = comp.get_addr_width();
+            let addr_map = comp.addr >> lsb;
+
+            self.write(&format!("   // {}\n", name.to_casing(Title)));
+            // Enable : high when main enable is high and address match
+            let en = format!("if_{name}_en");
+            self.write(&format!("   assign {en:<0$} = if_rif_en && if_rif_addr[{msb}:{lsb}]=={addr_map};\n",name_len+11));
+            en_names.push(en);
+            // Address : Forced to 0 when address is not matching
+            let addr = format!("if_{name}_addr");
+            self.write(&format!("   assign {addr:<0$} = if_rif_addr[{msb}:{lsb}]=={addr_map} ? if_rif_addr[{1}:0] : {lsb}'b0;\n",name_len+11, lsb-1));
+            // Write data : just copy the main interface
+            let data = format!("if_{name}_wr_data");
+            self.write(&format!("   assign {data:<0$} = if_rif_wr_data;\n",name_len+11));
+            // Read/Write control : just copy the main interface
+            let rd_wrn = format!("if_{name}_rd_wrn");
+            self.write(&format!("   assign {rd_wrn:<0$} = if_rif_rd_wrn;\n\n",name_len+11));
+        }
+
+        // Address demultiplexing
+        self.write("/*------------------------------------------------------------------------------\n");
+        self.write("--  Mux feedback\n");
+        self.write("------------------------------------------------------------------------------*/\n\n");
+
+        self.write("   assign addr_invalid_next = if_rif_en & ~(");
+        self.write(&en_names.join(" | "));
+        self.write(");\n");
+        self.write("   assign addr_invalid = addr_invalid_next;\n\n");
+
+        for suffix in ["", "_next"] {
+            self.write(&format!("   assign if_rif_done{suffix} = addr_invalid{suffix} |\n      "));
+            self.write(&rifmux.components.iter()
+                .map(|c| format!("if_{}_done{suffix}{:<2$}", c.get_name(), "", name_len-c.get_name().len()))
+                .collect::<Vec<String>>()
+                .join(" |\n      "));
+            self.write(" ;\n");
+        }
+        self.write("\n");
+
+        self.add_mux_if(rifmux, "rd_data", name_len, "0");
+        self.add_mux_if(rifmux, "err_addr", name_len, "1'b1");
+        self.add_mux_if(rifmux, "err_access", name_len, "1'b0");
+        self.add_mux_if(rifmux, "err_addr_next", name_len, "1'b1");
+        self.add_mux_if(rifmux, "err_access_next", name_len, "1'b0");
+
+        self.write("endmodule\n");
+
+        // Write file
+        self.save(&format!("{}.v", rifmux.type_name))?;
+        self.gen_bridge(&rifmux.interface)
+    }
+
+    fn add_mux_if(&mut self, rifmux: &RifmuxInst, name: &str, len: usize, err_val: &str) {
+        let suffix = if name.ends_with("_next") {"_next"} else {""};
+        self.write(&format!("   assign if_rif_{name} = addr_invalid{suffix} ? {err_val} :\n"));
+        let pad = "";
+        let comps = &rifmux.components;
+        for (i,comp) in comps.iter().enumerate() {
+            let top = comp.get_name();
+            let nb = len - top.len();
+            if i != comps.len() - 1 {
+                self.write(&format!("      if_{top}_done{suffix}{pad:<nb$} ? if_{top}_{name}{pad:<nb$} :\n"));
+            } else {
+                self.write(&format!("      {pad:<0$}   if_{top}_{name}{pad:<nb$} ;\n\n", len+8+suffix.len()));
+            }
+        }
+    }
+
+    fn gen_rifmux_pkg(&mut self, rifmux: &RifmuxInst) -> Result<(), Box<dyn std::error::Error>> {
+        let name_len = rifmux.components.iter().map(|c| c.get_name().len()).max().unwrap_or(0);
+        self.write("// File generated automatically: DO NOT EDIT.\n");
+        self.write(&format!("// Constants of {}: include inside the module using them\n\n", rifmux.type_name));
+        for comp in rifmux.components.iter() {
+            let w = ((rifmux.addr_width+3)>>2) as usize;
+            let pad = name_len - comp.get_name().len();
+            self.write(&format!("localparam [{}:0] {}_BASE_ADDR{:<pad$} = {}'h{:0w$x};\n",
+                rifmux.addr_width-1,
+                comp.get_name().to_uppercase(),
+                "",
+                rifmux.addr_width,
+                comp.addr
+            ));
+        }
+
+        // Write file
+        self.save(&format!("{}_pkg.vh", rifmux.type_name))
+    }
+
+}
+
+#[cfg(test)]
+mod tests_verilog {
+    use std::collections::HashMap;
+    use super::*;
+    use crate::{comp::comp_inst::Comp, generator::casing::Casing, generator::gen_common::Privacy, parser::{parser_expr::ParamValues, RifGenSrc}, rifgen::SuffixInfo};
+
+    /// Generate the Verilog of a RIF file and return the content of the module
+    fn gen_file(rif_file: &str, name: &str) -> String {
+        let src = RifGenSrc::from_file(rif_file).expect("Parsing failed");
+        let obj = Comp::compile(&src, &HashMap::new(), &ParamValues::new()).expect("Compilation failed");
+        let path = std::env::temp_dir().join("rifgen_tests_verilog");
+        let setting = GeneratorBaseSetting {
+            path: path.to_string_lossy().into_owned(),
+            template: "".to_owned(),
+            suffix: SuffixInfo::new("".to_owned(),false,false),
+            casing: Casing::Snake,
+            privacy: Privacy::Internal,
+            compact: true,
+            gen_inc: Vec::new(),
+        };
+        GeneratorVerilog::new(setting).gen(&obj).expect("Generation failed");
+        std::fs::read_to_string(path.join(format!("{name}.v"))).expect("Module should be generated")
+    }
+
+    /// Names declared as port, wire or reg
+    fn declared(txt: &str) -> HashSet<String> {
+        txt.lines()
+            .map(|l| l.split("//").next().unwrap_or_default().trim())
+            .filter(|l| ["input", "output", "wire", "reg"].iter().any(|k| l.starts_with(k)))
+            .flat_map(|l| l.trim_end_matches([',', ';']).split(',').map(|n| n.split_whitespace().last().unwrap_or_default().to_owned()).collect::<Vec<String>>())
+            .collect()
+    }
+
+    #[test]
+    fn test_model() {
+        let txt = gen_file("test/model.rif", "model_rif");
+        // Decrement
+        assert!(txt.contains("cnt_lvl_decr_en ? rif_cnt_lvl - 8'd1"));
+        // Empty expressions
+        assert!(!txt.contains("& (\n)"));
+        assert!(!txt.contains("if( "));
+        // Edge detection only for edge triggered interrupt
+        assert!(txt.contains("reg  irq_d1_evt0;"));
+        assert!(!txt.contains("irq_d1_evt1"));
+        // Every identifier used in the module body is declared
+        let names = declared(&txt);
+        let keywords = ["module", "endmodule", "input", "output", "wire", "reg", "signed", "assign", "always", "begin", "end",
+            "if", "else", "posedge", "negedge", "or", "case", "endcase", "default"];
+        let body = txt.split_once(");\n").expect("Port list should be closed").1;
+        let mut in_comment = false;
+        for l in body.lines() {
+            // Skip section header
+            if l.starts_with("/*") || in_comment {
+                in_comment = !l.ends_with("*/");
+                continue;
+            }
+            let l = l.split("//").next().unwrap_or_default();
+            // Skip bridge connection and process labels
+            if l.trim_start().starts_with('.') || l.contains("i_bridge") {
+                continue;
+            }
+            let l = l.split(" : proc_").next().unwrap_or_default();
+            let mut chars = l.char_indices().peekable();
+            while let Some((i,c)) = chars.next() {
+                if c.is_ascii_digit() || c == '\'' || c == '$' {
+                    // Skip number, sized literal and system function
+                    while chars.peek().is_some_and(|(_,c)| c.is_ascii_alphanumeric() || *c == '_' || *c == '\'') {
+                        chars.next();
+                    }
+                } else if c.is_ascii_alphabetic() || c == '_' {
+                    let mut end = i + 1;
+                    while let Some((j,_)) = chars.next_if(|(_,c)| c.is_ascii_alphanumeric() || *c == '_') {
+                        end = j + 1;
+                    }
+                    let ident = &l[i..end];
+                    assert!(keywords.contains(&ident) || names.contains(ident), "{ident} is not declared: {l}");
+                }
+            }
+        }
+    }
+}
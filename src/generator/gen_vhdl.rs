@@ -0,0 +1,1794 @@
+use std::{
+    collections::HashSet,
+    fs::create_dir_all,
+    path::PathBuf
+};
+
+use crate::{
+    comp::{
+        comp_inst::{ArrayIdx, Comp, RifFieldInst, RifInst, RifmuxInst},
+        hw_info::{PortInfo, PortWidth, RifIntfPorts, SignalInfo, RIF_IF_SIGNALS}},
+    rifgen::{
+        order_dict::OrderDict, Access, ClkEn, ClockingInfo, CounterKind, ExternalKind, FieldHwKind, FieldSwKind, Interface, InterruptClr, InterruptRegKind, InterruptTrigger, LimitValue, RegPulseKind, ResetDef
+    }
+};
+
+use super::{
+    casing::{Casing::{Snake, Title}, ToCasing},
+    gen_common::{bridge_rst_n, GeneratorBaseSetting, RifList},
+    gen_sv::GeneratorSv
+};
+
+/// Bridge from each supported bus to the internal register interface: interface name and source
+const VHDL_BRIDGES: [(&str, &str); 6] = [
+    ("apb"     , include_str!("vhdl/bridge_apb_rif.vhd")),
+    ("uaux"    , include_str!("vhdl/bridge_uaux_rif.vhd")),
+    ("axi4lite", include_str!("vhdl/bridge_axi4lite_rif.vhd")),
+    ("ahb"     , include_str!("vhdl/bridge_ahb_rif.vhd")),
+    ("wishbone", include_str!("vhdl/bridge_wishbone_rif.vhd")),
+    ("avalon"  , include_str!("vhdl/bridge_avalon_rif.vhd")),
+];
+
+pub struct GeneratorVhdl {
+    base_settings: GeneratorBaseSetting,
+    txt: String,
+    stash: [String; 2],
+    names: Vec<String>,
+}
+
+impl GeneratorVhdl {
+
+    pub fn new(args: GeneratorBaseSetting) -> Self {
+        GeneratorVhdl {
+            base_settings: args,
+            txt: String::with_capacity(10000),
+            stash: [String::with_capacity(1000), String::with_capacity(1000)],
+            names: Vec::new()
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn push_stash(&mut self, idx: usize, string: &str) {
+        self.stash[idx].push_str(string);
+    }
+
+    fn pop_stash(&mut self, idx: usize) {
+        self.txt.push_str(&self.stash[idx]);
+        self.stash[idx].clear();
+    }
+
+    fn stash_is_empty(&self, idx: usize) -> bool {
+        self.stash[idx].is_empty()
+    }
+
+    fn save(&mut self, filename: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path : PathBuf = [
+            self.base_settings.path.clone(),
+            filename.into()
+        ].iter().collect();
+        std::fs::write(path, self.txt.as_bytes())?;
+        self.txt.clear();
+        Ok(())
+    }
+
+    //-----------------------------
+
+    pub fn gen(&mut self, obj: &Comp) -> Result<(), Box<dyn std::error::Error>> {
+        // Create output directory if it does not exist
+        create_dir_all(self.base_settings.path.clone())?;
+        // Call relevant generator (Rif or Rifmux)
+        match obj {
+            Comp::Rif(rif) => {
+                self.gen_pkg(rif)?;
+                self.gen_rif(rif)?;
+            }
+            Comp::Rifmux(rifmux) => {
+                self.gen_rifmux_pkg(rifmux)?;
+                self.gen_rifmux(rifmux)?;
+                // Generate include file
+                if !self.base_settings.gen_inc.is_empty() {
+                    let rif_list = RifList::new(rifmux);
+                    for rif in rif_list.iter() {
+                        if !self.base_settings.gen_inc.contains(&rif.inst_name) && self.base_settings.gen_inc.first()!=Some(&"*".to_owned()) {
+                            continue;
+                        }
+                        self.gen_pkg(rif)?;
+                        self.gen_rif(rif)?;
+                    }
+                }
+                // RIF top is only available in SystemVerilog for now
+            }
+            // Nothing to do for external RIF
+            Comp::External(_) => {},
+        }
+        Ok(())
+    }
+
+    fn write_header(&mut self, pkgs: &[String]) {
+        self.write("-- File generated automatically: DO NOT EDIT.\n\n");
+        self.write("library ieee;\n");
+        self.write("use ieee.std_logic_1164.all;\n");
+        self.write("use ieee.numeric_std.all;\n\n");
+        for pkg in pkgs.iter() {
+            self.write(&format!("use work.{pkg}_pkg.all;\n"));
+        }
+        if !pkgs.is_empty() {
+            self.write("\n");
+        }
+    }
+
+    //-----------------------------------------------------------------------------
+    // RIF Package: enum & record definition
+    //-----------------------------------------------------------------------------
+
+    fn gen_pkg(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+        let rif_name = rif.name(true).to_casing(Snake);
+        self.write_header(&[]);
+        self.write(&format!("package {rif_name}_pkg is\n\n"));
+        // Constants
+        let rif_name_uc = rif.type_name.to_uppercase();
+        self.write(&format!("   constant C_{rif_name_uc}_ADDR_W : integer := {:2};\n", rif.addr_width));
+        self.write(&format!("   constant C_{rif_name_uc}_DATA_W : integer := {:2};\n", rif.data_width));
+        for (k, &v) in rif.params.items() {
+            self.write(&format!(
+                "   constant C_{rif_name_uc}_{} : integer := {v};\n",
+                k.to_uppercase()
+            ));
+        }
+
+        // Enums: subtype with one constant per value
+        self.write("\n");
+        let nb_enum = rif.enum_defs.iter().filter(|e| e.is_local_type()).count();
+        if nb_enum > 0 {
+            self.write("   -- Enums\n");
+            for enum_def in rif.enum_defs.iter().filter(|e| e.is_local_type()) {
+                let max = enum_def.iter().map(|v| v.value).max().unwrap_or(0);
+                let width = (u8::BITS - max.leading_zeros()).max(1) as u16;
+                if !enum_def.description.is_empty() {
+                    self.write(&format!("   -- {}\n", enum_def.description));
+                }
+                self.write(&format!("   subtype {} is {};\n", enum_def.name, Self::type_str(width, false)));
+                for v in enum_def.iter() {
+                    self.write(&format!("   constant {} : {} := {};",
+                        v.name, enum_def.name, Self::bit_lit(v.value.into(), width)));
+                    if !v.description.is_empty() {
+                        self.write(" -- ");
+                        self.write(v.description.get_short());
+                    }
+                    self.write("\n");
+                }
+                self.write("\n");
+            }
+        }
+
+        // Create two records (hardware/software) per register type
+        for hw_reg in rif.reg_impl_defs.values().filter(|r| r.pkg.is_none()) {
+            let reg_name = hw_reg.name.to_casing(Snake);
+            // Array fields requires a dedicated type declared before the record
+            let mut array_types = String::new();
+            self.names.clear();
+            // Iterate over all register fields to add them in the records
+            for f in hw_reg.fields.iter() {
+                let width = if f.sw_kind.is_password() {1} else {f.width};
+                let name = f.name.to_casing(Snake);
+                let Some(ctrl) = hw_reg.regs_ctrl.get(f.ctrl_idx) else {
+                    return Err(format!("Field {}.{name} points to ctrl {} but max is {}",hw_reg.name, f.ctrl_idx, hw_reg.regs_ctrl.len()).into())
+                };
+                let mut t = String::with_capacity(32);
+                t.push_str("      ");
+                t.push_str(&name);
+                // Suffix password by _locked
+                if f.sw_kind.is_password() {
+                    t.push_str("_locked");
+                }
+                t.push_str(" : ");
+                if f.array > 0 {
+                    let array_type = format!("{reg_name}_{name}_arr_t");
+                    if !array_types.contains(&format!(" {array_type} ")) {
+                        array_types.push_str(&format!("   type {array_type} is array (0 to {}) of {};\n", f.array-1, Self::type_str(width, f.signed)));
+                    }
+                    t.push_str(&array_type);
+                } else {
+                    t.push_str(&Self::type_str(width, f.signed));
+                }
+                t.push(';');
+                if !f.description.is_empty() {
+                    t.push_str(&format!(" -- {}", f.description.get_short()));
+                }
+                t.push('\n');
+                // Add field to SW record writable by firmware or readable by hardware
+                if (!f.is_local() || ctrl.external.is_rw()) && (f.is_sw_write() || f.is_constant() || f.is_counter() || f.hw_acc.is_readable()) {
+                    self.push_stash(1, &t);
+                }
+                // Add field to HW record if written by hardware
+                if f.has_hw_value() || ctrl.external.is_rw() {
+                    self.push_stash(0,&t);
+                }
+                // Add special fields
+                for kind in f.hw_kind.iter() {
+                    // Write modifiers: Write Enable, clr/set/tgl
+                    if kind.has_write_mod() {
+                        self.add_special_field(kind, &hw_reg.name, &name);
+                    }
+                    // Counter need multiple extra fields
+                    else if let FieldHwKind::Counter(info) = kind {
+                        if info.clr {
+                            self.push_stash(0, &format!("      {0}_clr : std_logic; -- Clear counter {0}\n",name));
+                        }
+                        if info.kind == CounterKind::Up || info.kind == CounterKind::UpDown {
+                            self.push_stash(0, &format!("      {0}_incr_en : std_logic; -- Increment counter {0}\n", name));
+                        }
+                        if info.kind == CounterKind::Down || info.kind == CounterKind::UpDown {
+                            self.push_stash(0, &format!("      {0}_decr_en : std_logic; -- Decrement counter {0}\n", name));
+                        }
+                        if info.event || info.sat {
+                            self.push_stash(1, &format!("      {0}_event : std_logic; -- Pulse high when {0} wrap/saturate\n", name));
+                        }
+                        if info.incr_val > 1 {
+                            self.push_stash(0, &format!("      {name}_incr_val : {}; -- Increment value for counter {name}\n", Self::type_str(info.incr_val.into(), f.signed)));
+                        }
+                        if info.decr_val > 1 {
+                            self.push_stash(0, &format!("      {name}_decr_val : {}; -- Decrement value for counter {name}\n", Self::type_str(info.decr_val.into(), f.signed)));
+                        }
+                    }
+                }
+                if let FieldSwKind::Password(info) = &f.sw_kind {
+                    if info.has_hold() {
+                        self.push_stash(1, &format!("      {name}_hold : std_logic; -- High when {name}_locked is not changed by register access\n"));
+                    }
+                }
+                // Clear
+                if let Some(clr_sig) = &f.clear {
+                    let clr_name = if clr_sig.is_empty() {format!("this.{name}_clr")} else {clr_sig.to_owned()};
+                    let kind = FieldHwKind::Clear(Some(clr_name));
+                    self.add_special_field(&kind, &hw_reg.name, &name);
+                }
+                // Lock signal from hardware
+                if let Some(lock) = f.lock.local_name() {
+                    if !lock.is_empty() && !self.names.iter().rev().any(|n| n==lock) {
+                        self.names.push(lock.to_owned());
+                        self.push_stash(0, &format!("      {lock} : std_logic; -- High to lock some field write access\n"));
+                    }
+                }
+            }
+            // Add fields for register pulse and external access
+            let is_multi_pulse = hw_reg.is_multi_pulse();
+            for ctrl in hw_reg.regs_ctrl.iter() {
+                let (sep,name) = if is_multi_pulse {("_",&*ctrl.name)} else {("","")};
+                for pulse in ctrl.pulse.iter() {
+                    match pulse {
+                        RegPulseKind::Write(_)  => self.push_stash(1, &format!("      p_{name}{sep}write : std_logic; -- Pulse high when register {} is written\n", ctrl.name)),
+                        RegPulseKind::Read(_)   => self.push_stash(1, &format!("      p_{name}{sep}read : std_logic; -- Pulse high when register {} is read\n", ctrl.name)),
+                        RegPulseKind::Access(_) => self.push_stash(1, &format!("      p_{name}{sep}acc : std_logic; -- Pulse high when register {} is accessed\n", ctrl.name)),
+                    }
+                }
+                if ctrl.external != ExternalKind::None {
+                    self.push_stash(0, &format!("      ext_{name}{sep}done : std_logic; -- Pulse high when read/write operation on register {} is complete\n", ctrl.name));
+                    if matches!(ctrl.external, ExternalKind::ReadWrite | ExternalKind::Write) {
+                        self.push_stash(1, &format!("      ext_{name}{sep}write : std_logic; -- Pulse high to start a write operation on register {}\n", ctrl.name));
+                    }
+                    if matches!(ctrl.external, ExternalKind::ReadWrite | ExternalKind::Read) {
+                        self.push_stash(1, &format!("      ext_{name}{sep}read : std_logic; -- Pulse high to start a read operation on register {}\n", ctrl.name));
+                    }
+                }
+            }
+            self.write(&array_types);
+            // Array of register are declared only when used by one instance
+            let has_array = rif.hw_regs.values().any(|r| r.group==hw_reg.name && r.dim > 0);
+            for (idx, kind) in [(1,"sw"), (0,"hw")] {
+                if self.stash_is_empty(idx) {
+                    continue;
+                }
+                self.write(&format!("   type {reg_name}_{kind}_t is record\n"));
+                self.pop_stash(idx);
+                self.write("   end record;\n");
+                if has_array {
+                    self.write(&format!("   type {reg_name}_{kind}_arr_t is array (natural range <>) of {reg_name}_{kind}_t;\n"));
+                }
+                self.write("\n");
+            }
+        }
+
+        self.write(&format!("end package {rif_name}_pkg;\n"));
+
+        // Write file
+        self.save(&format!("{rif_name}_pkg.vhd"))?;
+        Ok(())
+    }
+
+    fn add_special_field(&mut self, kind: &FieldHwKind, regname: &str, fieldname: &str) {
+        let name = if let Some(path) = kind.get_signal() {
+            let mut parts = path.split('.');
+            match (parts.next(),parts.next()) {
+                (Some(f),None) => f.to_owned(),
+                (Some(r),Some(f)) if r == regname || r == "this" || r == "self" => f.to_owned(),
+                _ => "".to_owned()
+            }
+        } else {
+            format!("{}{}", fieldname, kind.get_suffix())
+        };
+        //
+        if !name.is_empty() && !self.names.iter().rev().any(|n| n==&name) {
+            self.push_stash(0, &format!("      {name} : std_logic; -- {}\n", kind.get_comment(fieldname)));
+            self.names.push(name);
+        }
+    }
+
+
+    //-----------------------------------------------------------------------------
+    // RIF implementation: Address decoding, registers , ...
+    //-----------------------------------------------------------------------------
+
+    fn gen_rif(&mut self, rif: &RifInst) -> Result<(), Box<dyn std::error::Error>> {
+
+        let addr_shift = (rif.data_width as f32).log2().ceil() as u8 - 3; // Min data width is 8 bits
+        let rif_name = rif.name(false).to_casing(Snake);
+        let rif_pkg_name = rif.name(true).to_casing(Snake);
+
+        // Header with all packages used by the register types
+        let mut pkgs = vec![rif_pkg_name.clone()];
+        for hw_reg in rif.hw_regs.values() {
+            if let Some(pkg) = &rif.get_hw_reg(&hw_reg.group).pkg {
+                let pkg = pkg.to_casing(Snake);
+                if !pkgs.contains(&pkg) {
+                    pkgs.push(pkg);
+                }
+            }
+        }
+        self.write_header(&pkgs);
+
+        // Collect all ports: declaration and comment
+        let mut ports : Vec<(String,String)> = Vec::new();
+        // Clocks/Reset/Clear
+        let mut list_clocking = HashSet::with_capacity(2);
+        Self::add_clocking_port(&mut ports, &rif.sw_clocking, &mut list_clocking, false);
+        for hw_clk in rif.hw_clocking.iter() {
+            Self::add_clocking_port(&mut ports, hw_clk, &mut list_clocking, true);
+        }
+        // Clock enables and control signals
+        for port in rif.ports.clk_ens.iter().chain(rif.ports.ctrls.iter()) {
+            ports.push(Self::port_decl(port, "", 0, 0));
+        }
+        // Collect external pages
+        let ext_pages: Vec<(String, u64, &u8)> = rif.pages.iter()
+            .filter_map(|p| p.external.as_ref().map(|w| (p.name.to_lowercase(), p.addr, w)))
+            .collect();
+
+        // Input registers
+        let mut interrupts = Vec::new();
+        let mut out_ports = Vec::new();
+        for (group_name, hw_reg) in rif.hw_regs.items() {
+            let hw_reg_def = rif.get_hw_reg(&hw_reg.group);
+            let group_name = group_name.to_casing(Snake);
+            let group_type = hw_reg.group.to_casing(Snake);
+            let desc = hw_reg_def.description.get_short().to_owned();
+            if hw_reg.port.is_in() {
+                ports.push((format!("{group_name} : in {}", Self::reg_type(&group_type, "hw", hw_reg.dim)), desc.clone()));
+            }
+            if hw_reg.port.is_out() {
+                let kind = if hw_reg.intr_derived {"hw"} else {"sw"};
+                out_ports.push((format!("rif_{group_name} : out {}", Self::reg_type(&group_type, kind, hw_reg.dim)), desc));
+            }
+            // Save interrupts in a Vec for later
+            if !hw_reg_def.interrupt.is_empty() && !hw_reg.intr_derived {
+                interrupts.push(group_name.clone());
+                for info in hw_reg_def.interrupt.iter().skip(1) {
+                    interrupts.push(format!("{group_name}_{}", info.name));
+                }
+            }
+        }
+        // Output registers
+        ports.append(&mut out_ports);
+        // Interrupt lines
+        for irq in interrupts {
+            ports.push((format!("rif_{irq}_irq : out std_logic"), format!("High when one interrupt field of {irq} is asserted")));
+        }
+        // Add control to external pages
+        for (name,_, _) in ext_pages.iter() {
+            Self::add_rif_if_ports(&mut ports, &format!("if_page_{name}"), true, rif.addr_width, rif.data_width);
+        }
+        // Add Main Control interface
+        Self::add_intf_ports(&mut ports, &rif.interface, rif.addr_width, rif.data_width)?;
+
+        self.write(&format!("entity {rif_name} is\n"));
+        self.write_ports(&ports);
+        self.write(&format!("end entity {rif_name};\n\n"));
+
+        self.write(&format!("architecture rtl of {rif_name} is\n\n"));
+
+        //----------------------
+        // Signals declaration
+        self.write("--------------------------------------------------------------------------------\n");
+        self.write("--  Signals declaration\n");
+        self.write("--------------------------------------------------------------------------------\n");
+        // Internal register interface when a bridge is used
+        if !rif.interface.is_default() {
+            self.add_rif_if_signals(rif.addr_width, rif.data_width);
+        }
+        self.write(&format!("   signal rif_addr_l : {};\n", Self::type_str((rif.addr_width - addr_shift).into(), false)));
+        self.write(&format!("   signal rif_read_data_l : {};\n", Self::type_str(rif.data_width.into(), false)));
+        self.write("   signal rif_err_addr_l, rif_err_access_l, rif_done_next : std_logic;\n");
+        if rif.has_external_reg() {
+            self.write("   signal rif_pending : std_logic; -- High while an access waits for an external register\n");
+        }
+        self.write("\n");
+
+        // Declare local clock enable
+        self.names.clear();
+        for hw_clk in rif.hw_clocking.iter() {
+            if !hw_clk.en.is_empty() && !self.names.contains(&hw_clk.en){
+                self.write(&format!("   signal {}_l : std_logic;\n",hw_clk.en));
+                self.names.push(hw_clk.en.to_owned());
+            }
+        }
+        // Declare Decode pulse / readback value per register
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                let name = reg.name().to_casing(Snake);
+                self.write(&format!("   signal {name}_decode : std_logic;\n"));
+                self.write(&format!("   signal {name}_read_data : {};\n", Self::type_str(rif.data_width.into(), false)));
+            }
+        }
+        self.write("\n");
+        // Declare local signal per register group
+        for (inst_name, hw_reg) in rif.hw_regs.items().filter(|(_,r)| !r.intr_derived) {
+            let group_name = inst_name.to_casing(Snake);
+            let hw_reg_def = rif.get_hw_reg(&hw_reg.group);
+            let reg_dim = hw_reg.dim;
+            let group_type = hw_reg.group.to_casing(Snake);
+            // Local register
+            if hw_reg_def.is_local() {
+                self.write(&format!("   signal rif_{group_name} : {};\n", Self::reg_type(&group_type, "sw", reg_dim)));
+            }
+            for idx_u16 in 0..reg_dim.max(1) {
+                let idx = if reg_dim > 0 {format!("{idx_u16}")} else {"".to_owned()};
+                // Interrupt register
+                if hw_reg_def.is_interrupt() {
+                    for intr_info in hw_reg_def.interrupt.iter() {
+                        let name = if intr_info.name.is_empty() {&group_name} else {&intr_info.name};
+                        if !hw_reg.port.is_out() {
+                            self.write(&format!("   signal rif_{name}{idx} : {group_type}_sw_t;\n"));
+                        }
+                        self.write(&format!("   signal {name}{idx}_l : {group_type}_hw_t;\n"));
+                        // Add delay register if trigger works on edges
+                        if intr_info.edge_trigger() {
+                            self.write(&format!("   signal {name}{idx}_d1 : {group_type}_hw_t;\n"));
+                        }
+                        // Add optional enable/mask register
+                        if intr_info.enable.is_some() {
+                            if let Some(hw_reg_en) = rif.hw_regs.get(&format!("{inst_name}_en")) {
+                                if !hw_reg_en.port.is_out() {
+                                    self.write(&format!("   signal rif_{name}{idx}_en : {group_type}_hw_t;\n"));
+                                }
+                            }
+                        }
+                        if intr_info.mask.is_some() {
+                            if let Some(hw_reg_mask) = rif.hw_regs.get(&format!("{inst_name}_mask")) {
+                                if !hw_reg_mask.port.is_out() {
+                                    self.write(&format!("   signal rif_{name}{idx}_mask : {group_type}_hw_t;\n"));
+                                }
+                            }
+                        }
+                        // Internal pending signal is always present (used to generate the irq output)
+                        self.write(&format!("   signal rif_{name}{idx}_pending : {group_type}_hw_t;\n"));
+                        self.write(&format!("   signal clk_en_intr_{name}{idx} : std_logic;\n"));
+                        // Add next signal for each field
+                        for f in hw_reg_def.fields.iter() {
+                            let f_name = f.name.to_casing(Snake);
+                            let tn = Self::type_str(f.width, f.signed);
+                            self.write(&format!("   signal {name}{idx}_{f_name}_next : {tn};\n"));
+                            if intr_info.enable.is_some() {
+                                self.write(&format!("   signal {name}{idx}_en_{f_name}_next : {tn};\n"));
+                            }
+                            if intr_info.mask.is_some() {
+                                self.write(&format!("   signal {name}{idx}_mask_{f_name}_next : {tn};\n"));
+                            }
+                        }
+                    }
+                    continue;
+                }
+                // Field combinatorial next value
+                for f in hw_reg_def.fields.iter() {
+                    let f_name = f.name.to_casing(Snake);
+                    // Add signal to handle out-of-limit check
+                    if f.limit.value != LimitValue::None {
+                        self.write(&format!("   signal {group_name}{idx}_{f_name}_check : std_logic;\n"));
+                    }
+                    // Skip external field
+                    let Some(ctrl) = hw_reg_def.regs_ctrl.get(f.ctrl_idx) else {
+                        return Err(format!("Field {}.{} points to ctrl {} but max is {}", hw_reg_def.name, f.name, f.ctrl_idx, hw_reg_def.regs_ctrl.len()).into())
+                    };
+                    if ctrl.is_external() {
+                        continue;
+                    }
+                    // No next for combinatorial pulse or read only field from hardware with no register
+                    if f.sw_kind.is_pulse_comb() || (f.sw_kind==FieldSwKind::ReadOnly && !f.has_write_mod() && !f.is_counter()) {
+                        continue;
+                    }
+                    // Counter next value has one extra bit to detect wrap
+                    let tn_next =
+                        if f.sw_kind.is_password() {Self::type_str(2, false)}
+                        else if f.is_counter() {
+                            let kind = if f.signed && f.width > 1 {"signed"} else {"unsigned"};
+                            format!("{kind}({} downto 0)", f.width)
+                        }
+                        else {Self::type_str(f.width, f.signed)};
+                    if f.array > 0 {
+                        for i in 0..f.array {
+                            self.write(&format!("   signal {group_name}{idx}_{f_name}{i}_next : {tn_next};\n"));
+                        }
+                    } else {
+                        self.write(&format!("   signal {group_name}{idx}_{f_name}_next : {tn_next};\n"));
+                    }
+                    // Add register to store local value (when register is not visible at the output)
+                    if f.is_local() {
+                        self.write(&format!("   signal {group_name}{idx}_{f_name}_reg : {};\n", Self::type_str(f.width, f.signed)));
+                    }
+                }
+            }
+        }
+
+        self.write("\nbegin\n");
+
+        // Add interface bridge when not default
+        self.add_intf_bridge(&rif.interface, rif.addr_width, rif.data_width, &rif.sw_clocking.clk, &rif.sw_clocking.rst)?;
+
+        // Interface handling
+        self.write("\n--------------------------------------------------------------------------------\n");
+        self.write("--  Interface handling\n");
+        self.write("--------------------------------------------------------------------------------\n");
+        let mut signals: Vec<SignalInfo> = vec![
+            SignalInfo::new("if_rif_err_addr"  , 1, "'0'", "rif_err_addr_l and if_rif_en"),
+            SignalInfo::new("if_rif_err_access", 1, "'0'", "rif_err_access_l and if_rif_en"),
+            SignalInfo::new("if_rif_done", 1, "'0'", "rif_done_next"),
+            SignalInfo::new_with_en("if_rif_rd_data", rif.data_width, "(others => '0')", "rif_read_data_l", "rif_done_next and if_rif_rd_wrn"),
+        ];
+        // Access stays enabled until done: external registers are only started on the first cycle
+        if rif.has_external_reg() {
+            signals.push(SignalInfo::new("rif_pending", 1, "'0'", "if_rif_en and not rif_done_next"));
+        }
+        self.gen_process(
+            &rif.sw_clocking.clk,
+            &rif.sw_clocking.rst,
+            "proc_if_rif",
+            &signals,
+        );
+        self.write("   if_rif_done_next       <= rif_done_next;\n");
+        self.write("   if_rif_err_addr_next   <= rif_err_addr_l;\n");
+        self.write("   if_rif_err_access_next <= rif_err_access_l;\n\n");
+        self.write(&format!(
+            "   rif_addr_l <= if_rif_addr({} downto {});\n\n",
+            rif.addr_width - 1,
+            addr_shift
+        ));
+
+        // Hardware clock enable: add register access to ensure field can be modify by firmware
+        self.names.clear();
+        for hw_clk in rif.hw_clocking.iter() {
+            if !hw_clk.en.is_empty() && !self.names.contains(&hw_clk.en){
+                self.write(&format!("   {0}_l <= {0} or if_rif_en;\n",hw_clk.en));
+                self.names.push(hw_clk.en.to_owned());
+            }
+        }
+
+        // Decode process
+        let page_en: Vec<String> = ext_pages.iter().map(|(n,_,_)| format!("if_page_{n}_en")).collect();
+        let page_en = page_en.join(" or ");
+        self.write("   proc_decode : process(all)\n");
+        self.write("   begin\n");
+        self.write("      rif_read_data_l <= (others => '0');\n");
+        if ext_pages.is_empty() {
+            self.write("      rif_done_next    <= if_rif_en;\n");
+            self.write("      rif_err_addr_l   <= '1';\n");
+            self.write("      rif_err_access_l <= '1';\n");
+        } else {
+            self.write(&format!("      rif_err_addr_l   <= not ({page_en});\n"));
+            self.write(&format!("      rif_err_access_l <= not ({page_en});\n"));
+            // Page enable is released when the page is done: the access completes
+            // with the answer of the page selected in the default branch
+            self.write(&format!("      rif_done_next    <= if_rif_en and not ({page_en});\n"));
+        }
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                self.write(&format!("      {}_decode <= '0';\n", reg.name().to_casing(Snake)));
+            }
+        }
+        self.write("      case to_integer(unsigned(rif_addr_l)) is\n");
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                let name_flat = reg.name().to_casing(Snake);
+                let group_name = reg.group_name.to_casing(Snake);
+                self.write(&format!("         when {} =>\n", (reg.addr + page.addr) >> addr_shift));
+                let field_limit: Vec<String> = reg
+                    .fields
+                    .iter()
+                    .filter(|field| field.limit.value != LimitValue::None)
+                    .map(|field| {
+                        if field.limit.bypass.is_empty() {
+                            format!("{group_name}_{}_check", field.name)
+                        } else {
+                            format!("({group_name}_{}_check or {})", field.name, field.limit.bypass)
+                        }
+                    })
+                    .collect();
+                let decode =
+                    if field_limit.is_empty() {"'1'".to_owned()}
+                    else {format!("if_rif_rd_wrn or ({})", field_limit.join(" and "))};
+                // Access is held until done: only the first cycle accesses an external register
+                if reg.external != ExternalKind::None {
+                    let decode_ext = if field_limit.is_empty() {"not rif_pending".to_owned()} else {format!("({decode}) and not rif_pending")};
+                    self.write(&format!("            {name_flat}_decode <= {decode_ext};\n"));
+                } else {
+                    self.write(&format!("            {name_flat}_decode <= {decode};\n"));
+                }
+                self.write(&format!("            rif_read_data_l  <= {name_flat}_read_data;\n"));
+                self.write("            rif_err_addr_l   <= '0';\n");
+                // Access error when writing a read-only field, reading a write only field,
+                //  or writing one field outside its set value (when limits are defined)
+                self.write("            rif_err_access_l <= ");
+                match reg.sw_access {
+                    Access::RO => self.write("not if_rif_rd_wrn;\n"),
+                    Access::WO => self.write("if_rif_rd_wrn;\n"),
+                    Access::NA => self.write("'1';\n"),
+                    Access::RW => {
+                        if field_limit.is_empty() {
+                            self.write("'0';\n");
+                        } else {
+                            self.write(&format!("not ({decode});\n"));
+                        }
+                    },
+                }
+                // Handle external register
+                if reg.external!=ExternalKind::None {
+                    let hw_reg_def = rif.get_hw_reg(&reg.group_type);
+                    let idx = if let ArrayIdx::Inst(idx,_)= reg.array {format!("({idx})")} else {"".to_owned()};
+                    self.write(&format!("            rif_done_next    <= {group_name}{idx}.ext_"));
+                    if hw_reg_def.is_multi_pulse() {
+                        self.write(&name_flat);
+                        self.write("_");
+                    }
+                    self.write("done;\n");
+                }
+            }
+        }
+        // Handle external pages
+        self.write("         when others =>\n");
+        if ext_pages.is_empty() {
+            self.write("            null;\n");
+        }
+        for (i,(name,_,_)) in ext_pages.iter().enumerate() {
+            let name = name.to_casing(Snake);
+            self.write(&format!("            {}if if_page_{name}_done then\n",if i!=0 {"els"} else {""}));
+            self.write(&format!("               rif_read_data_l  <= if_page_{name}_rd_data;\n"));
+            self.write(&format!("               rif_err_addr_l   <= if_page_{name}_err_addr;\n"));
+            self.write(&format!("               rif_err_access_l <= if_page_{name}_err_access;\n"));
+            if i == ext_pages.len() - 1 {
+                self.write("            end if;\n");
+            }
+        }
+        self.write("      end case;\n");
+        self.write("   end process proc_decode;\n\n");
+
+        // Control the external page interface
+        for (name,addr,&width) in ext_pages.iter() {
+            let name = name.to_casing(Snake);
+            let cmp_w = (rif.addr_width - width) as u16;
+            self.write(&format!("   if_page_{name}_addr    <= if_rif_addr;\n"));
+            self.write(&format!("   if_page_{name}_rd_wrn  <= if_rif_rd_wrn;\n"));
+            self.write(&format!("   if_page_{name}_wr_data <= if_rif_wr_data;\n"));
+            self.write(&format!("   if_page_{name}_en      <= if_rif_en and not if_page_{name}_done and ({} ?= {});\n",
+                Self::slice("if_rif_addr", rif.addr_width as u16 - 1, width as u16),
+                Self::bit_lit((addr >> width).into(), cmp_w)));
+        }
+
+        // Register Process
+        self.write("\n--------------------------------------------------------------------------------\n");
+        self.write("--  Registers\n");
+        self.write("--------------------------------------------------------------------------------\n\n");
+
+        let mut group_done : HashSet<String> = HashSet::with_capacity(rif.hw_regs.len());
+        for page in rif.pages.iter().filter(|p| p.external.is_none()) {
+            for reg in page.regs.iter() {
+                let reg_impl = rif.get_hw_reg(&reg.group_type);
+                // Save a few string to be reused
+                let reg_name  = reg.name().to_casing(Snake); // Register Name with index apped after
+                let group_name = reg.group_name().to_casing(Snake); // Group name without index
+                let reg_name_i   = reg.name_i().to_casing(Snake); // Register name with optional index in bracket
+                let group_name_i = Self::to_vhdl_idx(&reg.group_name_i().to_casing(Snake)); // Group name with optional index in parenthesis
+                let reg_idx    = if let ArrayIdx::Inst(idx,_) = reg.array {format!("{idx}")} else {"".to_owned()};
+                let reg_idxb   = if !reg_idx.is_empty() {format!("({reg_idx})")} else {"".to_owned()};
+                let intr_suffix = reg.intr_info.0.get_suffix();
+                let wr_access = format!("{reg_name}_decode and if_rif_en and not if_rif_rd_wrn");
+                let rd_access = format!("{reg_name}_decode and if_rif_en and if_rif_rd_wrn");
+                self.write(&format!("   -- Register {reg_name_i}\n"));
+                // Assign field
+                for field in reg.fields.iter() {
+                    let field_impl = reg_impl.get_field(&field.name)?;
+                    // Handle partial field
+                    let partial_range = if let Some(partial_pos) = field.partial.0 {
+                        if field.width > 1 {
+                            format!("({} downto {})",partial_pos + field.width as u16 - 1, partial_pos)
+                        } else {
+                            format!("({})",partial_pos)
+                        }
+                    } else {
+                        "".to_string()
+                    };
+                    let is_signed = field_impl.signed && field_impl.width > 1 && field.width > 1;
+
+                    let field_name = Self::to_vhdl_idx(&field.name().to_casing(Snake));
+                    let field_name_flat = field.name_flat().to_casing(Snake);
+                    // Local field: for partial field ensure the current one is also local
+                    let field_path = if field_impl.is_local() && field.has_write_mod() && !reg.is_external() {
+                        format!("{group_name}{intr_suffix}{reg_idx}_{field_name_flat}_reg{partial_range}")
+                    } else {
+                        format!("rif_{group_name}{intr_suffix}{reg_idxb}.{field_name}{partial_range}")
+                    };
+                    // Hardware input path
+                    let hw_path = format!("{group_name_i}.{field_name}{partial_range}");
+                    let reg_field_name = format!("{group_name}{intr_suffix}{reg_idx}_{field_name_flat}");
+
+                    let reset_str = Self::field_reset_str(field);
+
+                    // Disabled field ? simply assign to its reset value
+                    if field.is_disabled() && (field.sw_kind==FieldSwKind::ReadWrite || field.sw_kind==FieldSwKind::WriteOnly) {
+                        self.write(&format!("   {field_path} <= {reset_str}; -- Disabled\n"));
+                        continue;
+                    }
+
+                    // Constant field
+                    if field_impl.is_constant() {
+                        self.write(&format!("   {field_path} <= {reset_str};\n"));
+                        continue;
+                    }
+
+                    // Construct the field value from the bus with bit selection
+                    let mut field_val = Self::slice("if_rif_wr_data", field.msb() as u16, field.lsb as u16);
+                    if is_signed {
+                        field_val = format!("signed({field_val})");
+                    }
+
+                    // Add logic for field with limit
+                    if field.has_limit() {
+                        let num_val = Self::to_num(&field_val, field.width.into(), is_signed);
+                        let num_lit = |v: u128| Self::num_lit(v, field.width.into(), is_signed);
+                        let check = match &field.limit.value {
+                            LimitValue::Min(min) => format!("({num_val} ?>= {})", num_lit(min.to_u128(field.width))),
+                            LimitValue::Max(max) => format!("({num_val} ?<= {})", num_lit(max.to_u128(field.width))),
+                            LimitValue::MinMax(min, max) => format!("({num_val} ?>= {}) and ({num_val} ?<= {})",
+                                num_lit(min.to_u128(field.width)),
+                                num_lit(max.to_u128(field.width))),
+                            LimitValue::List(l) => l.iter()
+                                .map(|e| format!("({num_val} ?= {})", num_lit(e.to_u128(field.width))))
+                                .collect::<Vec<String>>()
+                                .join(" or "),
+                            LimitValue::Enum => {
+                                let Some(enum_name) = field.enum_kind.name() else {
+                                    return Err(format!("Using `limit enum` on non-enum field {reg_field_name}!").into());
+                                };
+                                let enum_type = if let Some(pkg) = &reg_impl.pkg {
+                                    if enum_name.contains(':') {enum_name.to_owned()}
+                                    else {format!("{pkg}_pkg::{enum_name}")}
+                                } else {
+                                    enum_name.to_owned()
+                                };
+                                let enum_def = rif.get_enum_def(&enum_type)?;
+                                enum_def.iter()
+                                    .map(|e| format!("({num_val} ?= {})", num_lit(e.value.into())))
+                                    .collect::<Vec<String>>()
+                                    .join(" or ")
+                            },
+                            // No limit -> nothing to do
+                            LimitValue::None => "'1'".to_owned(),
+                        };
+                        self.write(&format!("   {reg_field_name}_check <= {check};\n"));
+                    }
+
+                    // For external register combinatorial assign from the interface bus
+                    if reg.is_external() && field.is_sw_write() {
+                        self.write(&format!("   {field_path} <= {field_val};\n"));
+                        continue;
+                    }
+
+                    // Combinatorial pulse : direct assign
+                    if field.sw_kind.is_pulse_comb() {
+                        self.write(&format!("   {field_path} <= {field_val} when {wr_access} else {};\n", Self::zero(field.width.into())));
+                        continue;
+                    }
+
+                    // Counter event (counter kind is exclusive so take fist one)
+                    if let Some(FieldHwKind::Counter(info)) = field.hw_kind.first() {
+                        if info.sat || info.event {
+                            let msb = field.width-1;
+                            self.write(&format!("   {field_path}_event <= "));
+                            if field.is_sw_write() {
+                                let pol = if field.sw_kind==FieldSwKind::ReadClr {"not "} else {""};
+                                self.write(&format!("(not {reg_name}_decode or not if_rif_en or {pol}if_rif_rd_wrn) and "));
+                            }
+                            self.write("(\n");
+                            if is_signed {
+                                self.write(&format!("      {reg_field_name}_next({}) xor {reg_field_name}_next({msb})", field.width));
+                            } else {
+                                let mut terms = Vec::new();
+                                if info.is_up() {
+                                    terms.push(format!("      (not {reg_field_name}_next({msb}) and {field_path}({msb}) and {hw_path}_incr_en)"));
+                                }
+                                if info.is_down() {
+                                    terms.push(format!("      ({reg_field_name}_next({msb}) and not {field_path}({msb}) and {hw_path}_decr_en)"));
+                                }
+                                self.write(&terms.join(" or\n"));
+                            }
+                            self.write(");\n");
+                        }
+                    }
+
+                    // Generate intermediate signal for interrupt
+                    if reg.is_intr() {
+                        let intr_info = reg_impl.intr_info(reg)?;
+                        // Local signal where interrupt vector is and with the optional enable signals
+                        self.write(&format!("   {0}_l.{1} <= {0}.{1}", group_name, field_name));
+                        if intr_info.enable.is_some() {
+                            self.write(&format!(" and rif_{}_en.{}", group_name, field_name));
+                        }
+                        self.write(";\n");
+                        // Next
+                        let trig = match field.hw_kind.first() {
+                            Some(FieldHwKind::Interrupt(intr_trig)) => match intr_trig {
+                                InterruptTrigger::High    => format!("{group_name}_l.{field_name}"),
+                                InterruptTrigger::Low     => format!("not {group_name}_l.{field_name}"),
+                                InterruptTrigger::Rising  => format!("({0}_l.{1} and not {0}_d1.{1})", group_name, field_name),
+                                InterruptTrigger::Falling => format!("(not {0}_l.{1} and {0}_d1.{1})", group_name, field_name),
+                                InterruptTrigger::Edge    => format!("({0}_l.{1} xor {0}_d1.{1})", group_name, field_name),
+                            },
+                            _ => Self::zero(field.width.into()),
+                        };
+                        let (cond, clr_val) = match intr_info.clear {
+                            InterruptClr::Read => (&rd_access, trig.clone()),
+                            InterruptClr::Write0 => (&wr_access, format!("{trig} or ({field_val} and {field_path})")),
+                            InterruptClr::Write1 => (&wr_access, format!("{trig} or (not {field_val} and {field_path})")),
+                            InterruptClr::Hw => return Err(format!("Hardware clear of interrupt {reg_field_name} not supported").into()),
+                        };
+                        self.write(&format!("   {reg_field_name}_next{partial_range} <=\n      {clr_val} when {cond} else\n      {trig} or {field_path};\n"));
+                        continue;
+                    }
+
+                    // Register derived from interrupt (i.e. enable/mask)
+                    // Basic read/write register
+                    if reg.is_intr_derived() && reg.intr_info.0 !=InterruptRegKind::Pending {
+                        self.write(&format!("   {reg_field_name}_next{partial_range} <=\n      {field_val} when {wr_access} else\n      {field_path};\n"));
+                        continue;
+                    }
+
+                    // Generate next value
+                    if field.is_hw_write() || field.is_sw_write() {
+                        // Counter next value is one bit larger: extend all values
+                        let cnt_info = field.hw_kind.iter().find_map(|k| if let FieldHwKind::Counter(info) = k {Some(info)} else {None});
+                        let ext = |v: &str| -> String {
+                            if cnt_info.is_none() {v.to_owned()}
+                            else if is_signed {format!("resize({v}, {})", field.width+1)}
+                            else if field.width == 1 {format!("('0' & {v})")}
+                            else {format!("resize(unsigned({v}), {})", field.width+1)}
+                        };
+                        // List of couple (value, condition) in decreasing priority
+                        let mut choices : Vec<(String,String)> = Vec::new();
+                        let idx = if let Some(partial_pos) = field.partial.0 {format!("_{}",partial_pos)} else {"".to_owned()};
+
+                        // Handle hardware access
+                        if field.is_hw_write() {
+                            for kind in field.hw_kind.iter() {
+                                let suffix = kind.get_suffix();
+                                let sig = || Self::to_vhdl_idx(&GeneratorSv::get_signal_name(kind.get_signal(), suffix, &reg.group_type, &group_name, &reg_idxb, &field_name, &idx));
+                                match kind {
+                                    FieldHwKind::WriteEn(_) => choices.push((ext(&hw_path), sig())),
+                                    FieldHwKind::WriteEnL(_) => choices.push((ext(&hw_path), format!("not {}", sig()))),
+                                    FieldHwKind::Set(_) => {
+                                        let v = if field.width == 1 {"'1'".to_owned()} else {format!("({field_path} or {hw_path})")};
+                                        choices.push((ext(&v), sig()));
+                                    },
+                                    FieldHwKind::Clear(_) => {
+                                        let v = if field.width == 1 {"'0'".to_owned()} else {format!("({field_path} and not {hw_path})")};
+                                        choices.push((ext(&v), sig()));
+                                    },
+                                    FieldHwKind::Toggle(_) => {
+                                        let v = if field.width == 1 {format!("not {field_path}")} else {format!("({field_path} xor {hw_path})")};
+                                        choices.push((ext(&v), sig()));
+                                    },
+                                    // Counter has less prevalence than software access: handled later
+                                    // Nothing todo for other HwKind (already handled for interrupt)
+                                    FieldHwKind::Counter(_) |
+                                    FieldHwKind::ReadOnly |
+                                    FieldHwKind::Interrupt(_) => {},
+                                }
+                            }
+                        }
+
+                        if field.is_sw_write() {
+                            let zero = if cnt_info.is_some() {"(others => '0')".to_owned()} else {Self::zero(field.width.into())};
+                            // Handle Software access
+                            match &field.sw_kind {
+                                FieldSwKind::ReadWrite |
+                                FieldSwKind::WriteOnly => choices.push((ext(&field_val), wr_access.clone())),
+                                FieldSwKind::ReadClr   => choices.push((zero, rd_access.clone())),
+                                FieldSwKind::W1Clr => {
+                                    if field.width == 1 {
+                                        choices.push((ext("'0'"), format!("{wr_access} and {field_val}")));
+                                    } else {
+                                        choices.push((ext(&format!("({field_path} and not {field_val})")), wr_access.clone()));
+                                    }
+                                }
+                                FieldSwKind::W0Clr => {
+                                    if field.width == 1 {
+                                        choices.push((ext("'0'"), format!("{wr_access} and not {field_val}")));
+                                    } else {
+                                        choices.push((ext(&format!("({field_path} and {field_val})")), wr_access.clone()));
+                                    }
+                                }
+                                FieldSwKind::W1Set => choices.push((ext(&format!("({field_path} or {field_val})")), wr_access.clone())),
+                                FieldSwKind::W1Pulse(_,_) => choices.push((ext(&field_val), wr_access.clone())),
+                                FieldSwKind::W1Tgl => choices.push((ext(&format!("({field_path} xor {field_val})")), wr_access.clone())),
+                                FieldSwKind::Password(info) => {
+                                    let mut cond = wr_access.clone();
+                                    if info.protect || (info.once.is_some() && info.hold.is_some()) {
+                                        cond.push_str(&format!(" and ({field_path}_hold or not {field_path}_locked)"));
+                                    }
+                                    if let Some(v) = &info.once {
+                                        choices.push(("\"00\"".to_owned(), format!("{cond} and ({field_val} ?= {})", Self::bit_lit(v.to_u128(field.width), field.width.into()))));
+                                    }
+                                    if let Some(v) = &info.hold {
+                                        choices.push(("\"10\"".to_owned(), format!("{cond} and ({field_val} ?= {})", Self::bit_lit(v.to_u128(field.width), field.width.into()))));
+                                    }
+                                    if info.protect {
+                                        choices.push(("\"11\"".to_owned(), format!("{cond} and ({field_val} ?/= {})", Self::bit_lit(0, field.width.into()))));
+                                    }
+                                    choices.push(("\"01\"".to_owned(), cond));
+                                    // For once password, reset to 1 when writing on any other register
+                                    if info.once.is_some() {
+                                        let mut cond = "if_rif_en and not if_rif_rd_wrn".to_owned();
+                                        if info.hold.is_some() {
+                                            cond.push_str(&format!(" and not {field_path}_hold"));
+                                        }
+                                        choices.push(("\"01\"".to_owned(), cond));
+                                    }
+                                },
+                                // Read Only case should be impossible due to the is_sw_write check earlier
+                                FieldSwKind::ReadOnly => {},
+                            }
+                        }
+
+                        // Handle Counter
+                        if let Some(info) = cnt_info {
+                            if info.clr {
+                                let rst = field.reset.to_u128(field.width);
+                                // Sign extension of the reset value
+                                let rst = if is_signed && (rst >> (field.width-1)) & 1 == 1 {rst | (1 << field.width)} else {rst};
+                                choices.push((Self::bit_lit(rst, field.width as u16 + 1), format!("{hw_path}_clr")));
+                            }
+                            if info.is_up() {
+                                let incr = if info.incr_val <= 1 {"1".to_owned()} else if is_signed {format!("{hw_path}_incr_val")} else {format!("unsigned({hw_path}_incr_val)")};
+                                choices.push((format!("{} + {incr}", ext(&field_path)), format!("{hw_path}_incr_en")));
+                            }
+                            if info.is_down() {
+                                let decr = if info.decr_val <= 1 {"1".to_owned()} else if is_signed {format!("{hw_path}_decr_val")} else {format!("unsigned({hw_path}_decr_val)")};
+                                choices.push((format!("{} - {decr}", ext(&field_path)), format!("{hw_path}_decr_en")));
+                            }
+                        }
+
+                        // Default next to current value
+                        let default = match &field.sw_kind {
+                            FieldSwKind::W1Pulse(_,_) => Self::zero(field.width.into()),
+                            FieldSwKind::Password(info) => {
+                                if info.hold.is_some() {
+                                    format!("{field_path}_hold & {field_path}_locked")
+                                } else {
+                                    format!("'0' & {field_path}_locked")
+                                }
+                            }
+                            _ => ext(&field_path)
+                        };
+                        self.write_choices(&format!("{reg_field_name}_next{partial_range}"), &choices, &default);
+                    }
+                    // Handle case of partial field where one part is read-only
+                    else if field_impl.has_write_mod() {
+                        self.write(&format!("   {reg_field_name}_next{partial_range} <= {}; -- unused\n", Self::zero(field.width.into())));
+                    }
+
+                }
+                // External register
+                if reg.is_external() {
+                    let mut sig_name = format!("rif_{group_name_i}.ext");
+                    if reg_impl.regs_ctrl.len() > 1 {
+                        sig_name.push_str(&format!("_{}",reg.reg_name));
+                    }
+                    if reg.sw_access.is_writable() {
+                        self.write(&format!("   {sig_name}_write <= {wr_access};\n"));
+                    }
+                    if reg.sw_access.is_readable() {
+                        self.write(&format!("   {sig_name}_read <= {rd_access};\n"));
+                    }
+                }
+                // Sequential process
+                else if reg.has_proc() {
+                   // Get a default clock for the register
+                    let hw_clk = &rif.hw_clocking.first().unwrap_or(&rif.sw_clocking);
+                    let reg_clk =
+                        if let Some(n) = &reg_impl.clk {n}
+                        else if reg.sw_access.is_writable() && !reg.is_intr() {&rif.sw_clocking.clk}
+                        else {&hw_clk.clk};
+                    // Collect each field signal info in a hashmap indexed by a couple (clk/rst)
+                    let mut signals: OrderDict<(String,String), Vec<SignalInfo> > = OrderDict::new();
+                    for field in reg.fields.iter() {
+                        // Get field implementation
+                        let field_impl = reg_impl.get_field(&field.name)?;
+                        let field_idxb = if field.array.dim() > 0 {format!("({})", field.array.idx())} else {"".to_owned()};
+                        // Ignore disabled fields and partial fields after the first one
+                        let partial_pos = field.partial.0.unwrap_or(0);
+                        if field.is_disabled() || partial_pos!=0 || field.sw_kind.is_pulse_comb() {
+                            continue;
+                        }
+                        // Readonly field with no hardware
+                        if !field_impl.is_hw_write() && !field_impl.is_sw_write() {
+                            continue;
+                        }
+                        let field_name = Self::to_vhdl_idx(&field.name().to_casing(Snake));
+                        let field_name_flat = field.name_flat().to_casing(Snake);
+                        // Get clock associated with the field
+                        let f_clk =
+                            if let Some(n) = &field_impl.clk {n}
+                            else if let Some(n) = &reg_impl.clk {n}
+                            else if field_impl.is_hw_write() && !reg.is_intr_derived() {&hw_clk.clk}
+                            else {&rif.sw_clocking.clk};
+                        // Get reset associated with the field
+                        let f_rst =
+                            if let Some(n) = &reg_impl.rst {n}
+                            else if f_clk==&hw_clk.clk {&hw_clk.rst.name}
+                            else {&rif.sw_clocking.rst.name};
+                        // Name of the signal
+                        let mut name = if field_impl.is_local() {
+                            format!("{group_name}{intr_suffix}{reg_idx}_{field_name_flat}_reg")
+                        } else {
+                            format!("rif_{group_name}{intr_suffix}{reg_idxb}.{field_name}")
+                        };
+                        // Next value
+                        let mut value = format!("{group_name}{intr_suffix}{reg_idx}_{field_name_flat}_next");
+                        // Enable
+                        let mut enable = if reg.is_intr() {
+                            format!("clk_en_intr_{group_name_i}")
+                        } else if let ClkEn::Signal(clk_en) = &field_impl.clk_en {
+                            clk_en.clone()
+                        } else if let ClkEn::Signal(clk_en) = &reg_impl.clk_en {
+                            clk_en.clone()
+                        } else if field.is_hw_write() {
+                            hw_clk.en.clone()
+                        } else {
+                            rif.sw_clocking.en.clone()
+                        };
+                        if !enable.is_empty() && enable == hw_clk.en {
+                            enable.push_str("_l");
+                        }
+                        let mut enable_conds = if enable.is_empty() {vec![]} else {vec![enable]};
+                        if field_impl.lock.is_some() {
+                            let lock_name = GeneratorSv::get_signal_name(field_impl.lock.name(), "_lock", &reg.group_type,  &group_name, &reg_idxb, &field.name, &field_idxb);
+                            enable_conds.push(format!("not {}", Self::to_vhdl_idx(&lock_name)));
+                        }
+                        if let Some(FieldHwKind::Counter(cnt_info)) = field.hw_kind.first() {
+                            if cnt_info.sat && cnt_info.incr_val <= 1 && cnt_info.decr_val <= 1 {
+                                enable_conds.push(format!("not rif_{group_name_i}.{}_event", field.name));
+                            }
+                            if field.array.dim() > 0 {
+                                return Err(format!("Field array of counters not supported ({reg_name}.{field_name})").into());
+                            }
+                        }
+                        let enable = enable_conds.join(" and ");
+                        // Clear
+                        let clear = if reg_impl.clear.is_some() {
+                            GeneratorSv::get_signal_name(&reg_impl.clear, "reg_clr", &reg.group_type,  &group_name, &reg_idxb, "", "")
+                        } else if field_impl.clear.is_some() {
+                            GeneratorSv::get_signal_name(&field_impl.clear, "_clr", &reg.group_type,  &group_name, &reg_idxb, &field.name, &field_idxb)
+                        } else {
+                            "".to_string()
+                        };
+                        let clear = Self::to_vhdl_idx(&clear);
+
+                        // Signal Width: field width except for special fields
+                        let width = if field.is_password() {1} else {field.width};
+
+                        let reset = if field.is_password() {
+                                "'1'".to_owned()
+                            } else if field.partial.0.is_some() {
+                                let rst_val = field_impl.get_reset(reg.group_idx);
+                                Self::bit_lit(rst_val, field_impl.width)
+                            } else {
+                                Self::field_reset_str(field)
+                            };
+
+                        // Handle Special cases
+                        if field.is_password() {
+                            name.push_str("_locked");
+                            value.push_str("(0)");
+                        }
+                        else if let Some(FieldHwKind::Counter(cnt_info)) = field.hw_kind.first() {
+                            let w = field.width;
+                            let is_signed = field_impl.signed && w > 1;
+                            // Remove the extra bit used to detect wrapping
+                            let value_trunc =
+                                if w == 1 {format!("{value}(0)")}
+                                else if is_signed {format!("{value}({} downto 0)", w-1)}
+                                else {format!("std_logic_vector({value}({} downto 0))", w-1)};
+                            if cnt_info.sat && (cnt_info.incr_val > 1 || cnt_info.decr_val > 1) {
+                                let sat =
+                                    if is_signed {format!("({} => {value}({w}), others => not {value}({w}))", w-1)}
+                                    else if cnt_info.kind == CounterKind::Up {"(others => '1')".to_owned()}
+                                    else if cnt_info.kind == CounterKind::Down {"(others => '0')".to_owned()}
+                                    else {format!("(others => {group_name_i}.{}_incr_en)", field.name)};
+                                value = format!("{sat} when rif_{group_name_i}.{}_event else {value_trunc}", field.name);
+                            } else {
+                                value = value_trunc;
+                            }
+                        }
+
+                        // Add the signal info the hashmap
+                        let k = (f_clk.to_string(),f_rst.to_string());
+                        let field_entry = signals.entry(&k);
+
+                        field_entry.push(
+                            SignalInfo::new_with_en_clr(&name, width, &reset, &value, &enable, &clear)
+                        );
+
+                        // For password protected or with both option once/hold, add another signal
+                        if let FieldSwKind::Password(info) = &field.sw_kind {
+                            if info.has_hold() {
+                                let name = format!("rif_{group_name_i}.{}_hold", field.name);
+                                field_entry.push(
+                                    SignalInfo::new_with_en_clr(
+                                        &name, width,
+                                        "'0'",
+                                        &format!("{group_name}{reg_idx}_{field_name_flat}_next(1)"),
+                                        &enable, &clear)
+                                );
+                            }
+                        }
+                        // For interrupt on edge, add delay version of the interrupt event
+                        else if let Some(FieldHwKind::Interrupt(info)) = field.hw_kind.first() {
+                            if !info.is_level() {
+                                field_entry.push(
+                                    SignalInfo::new_with_en_clr(
+                                        &format!("{group_name}_d1{reg_idxb}.{field_name}"),
+                                        width,
+                                        &Self::zero(width.into()),
+                                        &format!("{group_name}_l{reg_idxb}.{field_name}"),
+                                        &enable, &clear)
+                                    );
+                            }
+                        }
+                    }
+                    // Create one process for each pair of clock/reset found in the register field
+                    for ((clk,rst_name),sig_list) in signals.items() {
+                        let mut proc_name = format!("proc_{reg_name}");
+                        // Append clk/rst_name to process if different from the register default
+                        if clk!=reg_clk && signals.len() > 1 {
+                            proc_name.push_str(&format!("_{}",clk));
+                        }
+                        let mut rst = if clk==&rif.sw_clocking.clk || rif.hw_clocking.is_empty() {&rif.sw_clocking.rst} else {&rif.hw_clocking.first().unwrap().rst};
+                        // Find the full reset definition in the sw_clock or hw_clocking
+                        if rst_name!=&rst.name {
+                            proc_name.push_str(&format!("_{}",rst_name));
+                            if rst_name == &rif.sw_clocking.rst.name {
+                                rst = &rif.sw_clocking.rst;
+                            } else {
+                                rst = &rif.hw_clocking.iter()
+                                    .find(|&x| &x.rst.name==rst_name)
+                                    .ok_or(format!("Reset {rst_name} should be amongst the software or hardware reset list !"))?
+                                    .rst;
+                            }
+                        }
+                        //
+                        self.gen_process(clk, rst, &proc_name, sig_list);
+                    }
+                }
+
+                // Create process to generate register pulse access
+                if !group_done.contains(&group_name_i) {
+                    group_done.insert(group_name_i.clone());
+
+                    let mut signals: Vec<SignalInfo> = Vec::new();
+                    let mut reg_clk = "".to_owned();
+                    for ctrl in reg_impl.regs_ctrl.iter() {
+                        let mut base_name = format!("rif_{group_name_i}.p");
+                        let ctrl_name = format!("{}{reg_idx}",ctrl.name.to_casing(Snake));
+                        let base_value = format!("{ctrl_name}_decode and if_rif_en");
+                        if reg_impl.is_multi_pulse() {
+                            base_name.push('_');
+                            base_name.push_str(&ctrl.name.to_casing(Snake));
+                        };
+                        for pulse in ctrl.pulse.iter() {
+                            let mut name = base_name.to_owned();
+                            let mut value = base_value.to_owned();
+                            let p_clk =
+                                match pulse {
+                                    RegPulseKind::Write(clk)  => {
+                                        name.push_str("_write");
+                                        value.push_str(" and not if_rif_rd_wrn");
+                                        clk
+                                    },
+                                    RegPulseKind::Read(clk)   => {
+                                        name.push_str("_read");
+                                        value.push_str(" and if_rif_rd_wrn");
+                                        clk
+                                    },
+                                    RegPulseKind::Access(clk) => {
+                                        name.push_str("_acc");
+                                        clk
+                                    },
+                                };
+                            // No clock means the pulse is just combinatorial logic
+                            if p_clk.is_empty() {
+                                self.write(&format!("   {name} <= {value};\n"));
+                            } else {
+                                if reg_clk.is_empty() {
+                                    reg_clk = p_clk.to_owned();
+                                } else if &reg_clk!=p_clk {
+                                    return Err(format!("Only one clock should be used for the register {group_name} pulses").into());
+                                }
+                                signals.push(SignalInfo::new(&name, 1, "'0'", &value));
+                            }
+                        }
+                    }
+                    if !signals.is_empty() {
+                        let proc_name = format!("proc_{group_name}{reg_idx}_special");
+                        self.gen_process(&reg_clk, &rif.sw_clocking.rst, &proc_name, &signals);
+                    }
+                }
+
+                // Interrupt registers signals : clock enable and IRQ
+                if reg.is_intr() {
+                    let intr_info = reg_impl.intr_info(reg)?;
+                    // Clock enable : or of all interrupts events (only the base one, not the alternate)
+                    if intr_info.name.is_empty() {
+                        self.write(&format!("   clk_en_intr_{group_name} <="));
+                        if let ClkEn::Signal(clk_en) = &reg_impl.clk_en {
+                            self.write(&format!(" {clk_en} or "));
+                        } else {
+                            self.write("\n      ");
+                            for field in reg.fields.iter() {
+                                if let Some(FieldHwKind::Interrupt(intr_trig)) = field.hw_kind.first() {
+                                    let field_name = Self::to_vhdl_idx(&field.name().to_casing(Snake));
+                                    let w : u16 = field.width.into();
+                                    let event = match intr_trig {
+                                        // Level Trigger
+                                        InterruptTrigger::High => Self::or_reduce(&format!("{group_name}.{field_name}"), w),
+                                        InterruptTrigger::Low  => Self::or_reduce(&format!("not {group_name}.{field_name}"), w),
+                                        // Edge trigger : enable on change
+                                        _ => Self::or_reduce(&format!("({0}.{1} xor {0}_d1.{1})", group_name, field_name), w),
+                                    };
+                                    self.write(&format!("{event} or\n      "));
+                                }
+                            }
+                        }
+                        self.write("if_rif_en;\n\n");
+                    }
+                    // IRQ: or of all interrupts status and-ed with the mask
+                    for field in reg.fields.iter() {
+                        let field_name = Self::to_vhdl_idx(&field.name().to_casing(Snake));
+                        self.write(&format!("   rif_{group_name}_pending.{field_name} <= "));
+                        if field.is_disabled() {
+                            self.write(&Self::field_reset_str(field));
+                        } else {
+                            self.write(&format!("rif_{group_name_i}.{field_name}"));
+                            if intr_info.mask.is_some() {
+                                self.write(&format!(" and rif_{group_name}_mask.{field_name}"));
+                            }
+                        }
+                        self.write(";\n");
+                    }
+                    self.write(&format!("\n   rif_{group_name}_irq <=\n"));
+                    self.write(
+                        &reg.fields.iter().filter(|f| !f.is_disabled())
+                            .map(|field| format!("      {}",
+                                Self::or_reduce(&format!("rif_{group_name}_pending.{}", Self::to_vhdl_idx(&field.name().to_casing(Snake))), field.width.into())))
+                            .collect::<Vec<String>>()
+                            .join(" or\n")
+                        );
+                    self.write(";\n\n");
+                }
+
+                // Concatenation for Read data
+                self.write(&format!("   {reg_name}_read_data <= "));
+                let nb_fields = reg.fields.iter().filter(|f| !f.sw_kind.is_wo()).count();
+                let mut parts : Vec<String> = Vec::with_capacity(2*nb_fields+1);
+                let mut prev_lsb = rif.data_width;
+                for field in reg.fields.iter().rev().filter(|f| !f.sw_kind.is_wo()) {
+                    let field_impl = reg_impl.get_field(&field.name)?;
+                    let field_name = Self::to_vhdl_idx(&field.name().to_casing(Snake));
+                    // Fill register spaces with 0s
+                    if field.msb() >= prev_lsb {println!("ERROR : Field {} ({:?}) has range [{}:{}] while previous LSB is {prev_lsb}", field.name, field.array, field.msb(), field.lsb);}
+                    let spaces = prev_lsb.saturating_sub(field.msb()+1);
+                    if spaces != 0 {
+                        parts.push(Self::bit_lit(0, spaces.into()));
+                    }
+                    let mut part =
+                        if !reg.is_external() && field_impl.is_local() && field.has_write_mod() {
+                            format!("{group_name}{intr_suffix}{reg_idx}_{}_reg", field.name_flat().to_casing(Snake))
+                        } else if let FieldSwKind::Password(info) = &field.sw_kind {
+                            if info.has_hold() {
+                                if field.width > 2 {
+                                    parts.push(Self::bit_lit(0, field.width as u16 - 2));
+                                }
+                                parts.push(format!("rif_{group_name_i}.{}_hold", field.name));
+                            } else if field.width > 1 {
+                                parts.push(Self::bit_lit(0, field.width as u16 - 1));
+                            }
+                            format!("rif_{group_name_i}.{}_locked", field.name)
+                        } else {
+                            let prefix = if !reg.is_external() && (field_impl.is_sw_write() || field.is_hw_write() || field_impl.is_constant()) {"rif_"} else {""};
+                            format!("{prefix}{group_name}{intr_suffix}{reg_idxb}.{field_name}")
+                        };
+                    if let Some(partial_pos) = field.partial.0 {
+                        if field.width > 1 {
+                            part.push_str(&format!("({} downto {})", partial_pos + field.width as u16 - 1, partial_pos));
+                        } else {
+                            part.push_str(&format!("({})", partial_pos));
+                        }
+                    }
+                    if field_impl.signed && field_impl.width > 1 && field.width > 1 && !field.is_password() {
+                        part = format!("std_logic_vector({part})");
+                    }
+                    parts.push(part);
+                    prev_lsb = field.lsb;
+                }
+                // Handle case where the first field does not starts at 0
+                if prev_lsb!=0 {
+                    parts.push(Self::bit_lit(0, prev_lsb.into()));
+                }
+                self.write(&parts.join(" & "));
+                self.write(";\n\n");
+            }
+        }
+
+        // Handle case of missing fields in a register implementation
+        for (group_name, hw_reg) in rif.hw_regs.items() {
+            // Skip register if read-only from firmware
+            let reg_impl = rif.get_hw_reg(&hw_reg.group);
+            if !reg_impl.port.is_out() && reg_impl.interrupt.is_empty() {continue;}
+            for (field_name,info) in &hw_reg.missing_fields {
+                let rst = Self::bit_lit(info.reset, info.width);
+                self.write(&format!("   rif_{group_name}.{field_name} <= {rst};\n",));
+            }
+        }
+
+        self.write("\nend architecture rtl;\n");
+
+        // Write file
+        self.save(&format!("{}.vhd", rif.name(false).to_lowercase()))?;
+        self.gen_bridge(&rif.interface)
+    }
+
+    fn add_clocking_port(
+        ports: &mut Vec<(String,String)>,
+        info: &ClockingInfo,
+        list: &mut HashSet<String>,
+        is_hw: bool,
+    ) {
+        let kind = if is_hw { "Hardware" } else { "Software" };
+        // Clock
+        if !list.contains(&info.clk) {
+            ports.push((format!("{} : in std_logic", info.clk), format!("{kind} Clock")));
+            list.insert(info.clk.to_owned());
+        }
+        // Reset
+        if !list.contains(&info.rst.name) {
+            ports.push((format!("{} : in std_logic", info.rst.name), format!("{kind} {}", info.rst.desc())));
+            list.insert(info.rst.name.to_owned());
+        }
+        // Clear
+        if !info.clear.is_empty() && !list.contains(&info.clear) {
+            ports.push((format!("{} : in std_logic", info.clear), format!("{kind} Clear")));
+            list.insert(info.clear.to_owned());
+        }
+    }
+
+    /// Add ports of the main control interface: internal register interface or the bus signals
+    fn add_intf_ports(ports: &mut Vec<(String,String)>, intf: &Interface, addr_w: u8, data_w: u8) -> Result<(), String> {
+        match intf {
+            Interface::Default => Self::add_rif_if_ports(ports, "if_rif", false, addr_w, data_w),
+            Interface::Custom(name) => return Err(format!("Custom interface {name} is not supported in VHDL")),
+            _ => {
+                Self::bridge_src(intf)?;
+                for port in RifIntfPorts::new(intf).iter() {
+                    ports.push(Self::port_decl(port, "", addr_w, data_w));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add all ports of the internal register interface, with direction reversed for the control side
+    fn add_rif_if_ports(ports: &mut Vec<(String,String)>, prefix: &str, is_ctrl: bool, addr_w: u8, data_w: u8) {
+        for (name, dir, width, desc) in RIF_IF_SIGNALS.iter() {
+            let port = PortInfo::new(name.to_string(), width.clone(), dir.clone(), desc.to_string(), 0);
+            let (decl, desc) = Self::port_decl(&port, prefix, addr_w, data_w);
+            let decl = if is_ctrl {
+                decl.replace(" : in ", " : tmp ").replace(" : out ", " : in ").replace(" : tmp ", " : out ")
+            } else {
+                decl
+            };
+            ports.push((decl, desc));
+        }
+    }
+
+    /// Declare the internal register interface as local signals
+    fn add_rif_if_signals(&mut self, addr_w: u8, data_w: u8) {
+        for (name, _, width, _) in RIF_IF_SIGNALS.iter() {
+            let w = match width {
+                PortWidth::Address => addr_w,
+                PortWidth::Data => data_w,
+                _ => 1,
+            };
+            self.write(&format!("   signal if_rif_{name} : {};\n", Self::type_str(w.into(), false)));
+        }
+    }
+
+    /// Create a port declaration and its associated comment
+    fn port_decl(port: &PortInfo, prefix: &str, addr_w: u8, data_w: u8) -> (String, String) {
+        let dir = if port.dir.is_out() {"out"} else {"in"};
+        let name = if prefix.is_empty() {port.name.to_casing(Snake)} else {format!("{prefix}_{}", port.name.to_casing(Snake))};
+        let tn = match &port.width {
+            PortWidth::Custom(type_name) => type_name.to_owned(),
+            _ => Self::type_str(port.width(addr_w, data_w).into(), false),
+        };
+        (format!("{name} : {dir} {tn}"), port.desc.to_owned())
+    }
+
+    /// Write the port list of an entity
+    fn write_ports(&mut self, ports: &[(String,String)]) {
+        self.write("   port (\n");
+        let len = ports.iter().map(|(d,_)| d.len()).max().unwrap_or(0) + 1;
+        let mut ports_iter = ports.iter().peekable();
+        while let Some((decl, desc)) = ports_iter.next() {
+            let sep = if ports_iter.peek().is_none() {""} else {";"};
+            self.write(&format!("      {:<len$}", format!("{decl}{sep}")));
+            if !desc.is_empty() {
+                self.write(&format!(" -- {desc}"));
+            }
+            self.write("\n");
+        }
+        self.write("   );\n");
+    }
+
+    fn add_intf_bridge(&mut self, intf: &Interface, addr_w: u8, data_w: u8, sw_clk: &str, sw_rst: &ResetDef) -> Result<(), String> {
+        if intf.is_default() {
+            return Ok(());
+        }
+        self.write("\n--------------------------------------------------------------------------------\n");
+        self.write("--  Bridge to the internal register interface\n");
+        self.write("--------------------------------------------------------------------------------\n");
+        let name = intf.name();
+        self.write(&format!("   i_bridge : entity work.bridge_{name}_rif\n"));
+        self.write(&format!("      generic map (W_ADDR => {addr_w}, W_DATA => {data_w})\n"));
+        self.write("      port map (\n");
+        let mut maps = vec![
+            ("clk".to_owned(), sw_clk.to_owned()),
+            ("rst_n".to_owned(), bridge_rst_n(sw_rst, "not ")?),
+        ];
+        maps.extend(RifIntfPorts::new(intf).iter().map(|p| (p.name.to_owned(), p.name.to_owned())));
+        maps.extend(RIF_IF_SIGNALS.iter().map(|(n,_,_,_)| (format!("if_rif_{n}"), format!("if_rif_{n}"))));
+        self.write(&maps.iter()
+            .map(|(f,a)| format!("         {f} => {a}"))
+            .collect::<Vec<String>>()
+            .join(",\n"));
+        self.write("\n      );\n");
+        Ok(())
+    }
+
+    /// Write the entity of the bridge used by the interface, if any
+    fn gen_bridge(&mut self, intf: &Interface) -> Result<(), Box<dyn std::error::Error>> {
+        if intf.is_default() {
+            return Ok(());
+        }
+        self.write(Self::bridge_src(intf)?);
+        self.save(&format!("bridge_{}_rif.vhd", intf.name()))
+    }
+
+    /// Source of the bridge entity of an interface
+    fn bridge_src(intf: &Interface) -> Result<&'static str, String> {
+        VHDL_BRIDGES.iter()
+            .find(|(n,_)| *n == intf.name())
+            .map(|(_,src)| *src)
+            .ok_or(format!("Interface {} is not supported in VHDL", intf.name()))
+    }
+
+    /// Write a conditional assignment from a list of value/condition by decreasing priority
+    fn write_choices(&mut self, target: &str, choices: &[(String,String)], default: &str) {
+        if choices.is_empty() {
+            self.write(&format!("   {target} <= {default};\n"));
+            return;
+        }
+        self.write(&format!("   {target} <=\n"));
+        for (value, cond) in choices.iter() {
+            self.write(&format!("      {value} when {cond} else\n"));
+        }
+        self.write(&format!("      {default};\n"));
+    }
+
+    /// Convert SystemVerilog index (square bracket) to VHDL index (parenthesis)
+    pub fn to_vhdl_idx(name: &str) -> String {
+        name.replace('[', "(").replace(']', ")")
+    }
+
+    /// VHDL type of a signal based on its width and signess
+    pub fn type_str(width: u16, signed: bool) -> String {
+        match (width, signed) {
+            (1, _) => "std_logic".to_owned(),
+            (w, true) => format!("signed({} downto 0)", w-1),
+            (w, false) => format!("std_logic_vector({} downto 0)", w-1),
+        }
+    }
+
+    /// Record type of a register, with optional array
+    fn reg_type(group_type: &str, kind: &str, dim: u16) -> String {
+        if dim > 0 {
+            format!("{group_type}_{kind}_arr_t(0 to {})", dim-1)
+        } else {
+            format!("{group_type}_{kind}_t")
+        }
+    }
+
+    /// Bit selection of a vector
+    fn slice(name: &str, msb: u16, lsb: u16) -> String {
+        if msb == lsb {
+            format!("{name}({lsb})")
+        } else {
+            format!("{name}({msb} downto {lsb})")
+        }
+    }
+
+    /// Bit string literal: character for single bit, hexadecimal when possible, binary otherwise
+    pub fn bit_lit(val: u128, width: u16) -> String {
+        let val = if width < 128 {val & ((1u128 << width) - 1)} else {val};
+        let w = width as usize;
+        if width == 1 {
+            format!("'{val}'")
+        } else if width.is_multiple_of(4) && width >= 8 {
+            format!("x\"{val:0w$x}\"", w=w/4)
+        } else {
+            format!("\"{val:0w$b}\"")
+        }
+    }
+
+    /// Value of zero for a signal of a given width
+    fn zero(width: u16) -> String {
+        if width == 1 {"'0'".to_owned()} else {"(others => '0')".to_owned()}
+    }
+
+    /// Convert a std_logic_vector expression to a numeric type for comparison
+    fn to_num(val: &str, width: u16, is_signed: bool) -> String {
+        if width == 1 || is_signed {val.to_owned()} else {format!("unsigned({val})")}
+    }
+
+    /// Literal for comparison with a numeric expression
+    fn num_lit(val: u128, width: u16, is_signed: bool) -> String {
+        let lit = Self::bit_lit(val, width);
+        match (width, is_signed) {
+            (1, _) => lit,
+            (_, true) => format!("signed'({lit})"),
+            (_, false) => format!("unsigned'({lit})"),
+        }
+    }
+
+    /// OR reduction of an expression (std_logic for single bit)
+    fn or_reduce(val: &str, width: u16) -> String {
+        if width == 1 {val.to_owned()} else {format!("(or {val})")}
+    }
+
+    pub fn field_reset_str(field: &RifFieldInst) -> String {
+        let val = field.reset.to_u128(field.width);
+        Self::bit_lit(val, field.width.into())
+    }
+
+    /// Generate synchronous process
+    fn gen_process(&mut self, clk: &str, rst: &ResetDef, name: &str, signals: &[SignalInfo]) {
+        // Check all signals clear/enable o see if all signals share a condition or not
+        let clk_en = signals.first().and_then(|s| s.enable.as_ref());
+        let clr = signals.first().and_then(|s| s.clear.as_ref());
+        let clk_en_global = signals.iter().all(|s| s.enable.as_ref()==clk_en);
+        let clr_global = signals.iter().all(|s| s.clear.as_ref()==clr);
+        let rst_val = if rst.active_high {"'1'"} else {"'0'"};
+        // Declaration
+        let mut ind = "      ".to_owned();
+        if rst.sync {
+            self.write(&format!("\n   {name} : process({clk})\n   begin\n"));
+            self.write(&format!("      if rising_edge({clk}) then\n"));
+            self.write(&format!("         if {} = {rst_val} then\n", rst.name));
+            ind.push_str("   ");
+        } else {
+            self.write(&format!("\n   {name} : process({clk}, {})\n   begin\n", rst.name));
+            self.write(&format!("      if {} = {rst_val} then\n", rst.name));
+        }
+        // Reset
+        for signal in signals.iter() {
+            self.write(&format!("{ind}   {} <= {};\n", signal.name, signal.reset));
+        }
+        if rst.sync {
+            self.write(&format!("{ind}else\n"));
+        } else {
+            self.write(&format!("{ind}elsif rising_edge({clk}) then\n"));
+        }
+        // Optional Global Enable
+        if let Some(en) = clk_en.filter(|_| clk_en_global) {
+            self.write(&format!("{ind}   if {en} then\n"));
+            ind.push_str("   ");
+        }
+        // Optional Global clear
+        if let Some(clr) = clr.filter(|_| clr_global) {
+            self.write(&format!("{ind}   if {clr} then\n"));
+            for signal in signals.iter() {
+                self.write(&format!("{ind}      {} <= {};\n", signal.name, signal.reset));
+            }
+            self.write(&format!("{ind}   else\n"));
+            ind.push_str("   ");
+        }
+        // Set value
+        for signal in signals.iter() {
+            let clear = signal.clear.as_ref().filter(|_| !clr_global);
+            let enable = signal.enable.as_ref().filter(|_| !clk_en_global);
+            match (clear, enable) {
+                (None, None) => self.write(&format!("{ind}   {} <= {};\n", signal.name, signal.value)),
+                (Some(c), e) => {
+                    self.write(&format!("{ind}   if {c} then\n"));
+                    self.write(&format!("{ind}      {} <= {};\n", signal.name, signal.reset));
+                    if let Some(e) = e {
+                        self.write(&format!("{ind}   elsif {e} then\n"));
+                    } else {
+                        self.write(&format!("{ind}   else\n"));
+                    }
+                    self.write(&format!("{ind}      {} <= {};\n", signal.name, signal.value));
+                    self.write(&format!("{ind}   end if;\n"));
+                }
+                (None, Some(e)) => {
+                    self.write(&format!("{ind}   if {e} then\n"));
+                    self.write(&format!("{ind}      {} <= {};\n", signal.name, signal.value));
+                    self.write(&format!("{ind}   end if;\n"));
+                }
+            }
+        }
+        //
+        if clr_global && clr.is_some() {
+            ind.truncate(ind.len()-3);
+            self.write(&format!("{ind}   end if;\n"));
+        }
+        if clk_en_global && clk_en.is_some() {
+            ind.truncate(ind.len()-3);
+            self.write(&format!("{ind}   end if;\n"));
+        }
+        if rst.sync {
+            self.write("         end if;\n");
+        }
+        self.write(&format!("      end if;\n   end process {name};\n\n"));
+    }
+
+
+    //-----------------------------------------------------------------------------
+    // RIF Mux implementation: Address decoding, registers , ...
+    //-----------------------------------------------------------------------------
+
+    fn gen_rifmux(&mut self, rifmux: &RifmuxInst) -> Result<(), Box<dyn std::error::Error>> {
+        let msb = rifmux.addr_width as u16 - 1;
+        let rifmux_name = &rifmux.inst_name;
+
+        self.write_header(&[]);
+
+        // Port declaration
+        let mut ports : Vec<(String,String)> = Vec::new();
+        if !rifmux.interface.is_default() {
+            ports.push((format!("{} : in std_logic", rifmux.sw_clocking.clk), "Bridge clock".to_owned()));
+            ports.push((format!("{} : in std_logic", rifmux.sw_clocking.rst.name), format!("Bridge reset: {}", rifmux.sw_clocking.rst.desc())));
+        }
+        for comp in rifmux.components.iter() {
+            Self::add_rif_if_ports(&mut ports, &format!("if_{}", comp.get_name()), true, comp.get_addr_width(), rifmux.data_width);
+        }
+        Self::add_intf_ports(&mut ports, &rifmux.interface, rifmux.addr_width, rifmux.data_width)?;
+
+        self.write(&format!("entity {rifmux_name} is\n"));
+        self.write_ports(&ports);
+        self.write(&format!("end entity {rifmux_name};\n\n"));
+
+        self.write(&format!("architecture rtl of {rifmux_name} is\n\n"));
+        if !rifmux.interface.is_default() {
+            self.add_rif_if_signals(rifmux.addr_width, rifmux.data_width);
+        }
+        self.write("   signal addr_invalid : std_logic; -- High when address is not in the range of any of the connected RIF\n");
+        self.write("   signal addr_invalid_next : std_logic; -- Combinatorial version of addr_invalid\n");
+        self.write("\nbegin\n");
+
+        // Add interface bridge when not default
+        self.add_intf_bridge(&rifmux.interface, rifmux.addr_width, rifmux.data_width, &rifmux.sw_clocking.clk, &rifmux.sw_clocking.rst)?;
+
+        // Address demultiplexing
+        self.write("\n--------------------------------------------------------------------------------\n");
+        self.write("--  Demux access\n");
+        self.write("--------------------------------------------------------------------------------\n\n");
+
+        let mut en_names = Vec::new();
+        for comp in rifmux.components.iter() {
+            let name = comp.get_name();
+            let lsb = comp.get_addr_width() as u16;
+            let addr_match = format!("({} ?= {})",
+                Self::slice("if_rif_addr", msb, lsb),
+                Self::bit_lit((comp.addr >> lsb).into(), msb + 1 - lsb));
+
+            self.write(&format!("   -- {}\n", name.to_casing(Title)));
+            // Enable : high when main enable is high and address match
+            self.write(&format!("   if_{name}_en      <= if_rif_en and {addr_match};\n"));
+            en_names.push(format!("if_{name}_en"));
+            // Address : Forced to 0 when address is not matching
+            self.write(&format!("   if_{name}_addr    <= if_rif_addr({} downto 0) when {addr_match} else (others => '0');\n", lsb-1));
+            // Write data and Read/Write control: just copy the main interface
+            self.write(&format!("   if_{name}_wr_data <= if_rif_wr_data;\n"));
+            self.write(&format!("   if_{name}_rd_wrn  <= if_rif_rd_wrn;\n\n"));
+        }
+
+        // Mux feedback
+        self.write("--------------------------------------------------------------------------------\n");
+        self.write("--  Mux feedback\n");
+        self.write("--------------------------------------------------------------------------------\n\n");
+
+        self.write(&format!("   addr_invalid_next <= if_rif_en and not ({});\n", en_names.join(" or ")));
+        self.write("   addr_invalid <= addr_invalid_next;\n\n");
+
+        for suffix in ["", "_next"] {
+            self.write(&format!("   if_rif_done{suffix} <= addr_invalid{suffix} or\n      "));
+            self.write(&rifmux.components.iter()
+                .map(|c| format!("if_{}_done{suffix}", c.get_name()))
+                .collect::<Vec<String>>()
+                .join(" or\n      "));
+            self.write(";\n\n");
+        }
+
+        self.add_mux_if(rifmux, "rd_data", "(others => '0')");
+        self.add_mux_if(rifmux, "err_addr", "'1'");
+        self.add_mux_if(rifmux, "err_access", "'0'");
+        self.add_mux_if(rifmux, "err_addr_next", "'1'");
+        self.add_mux_if(rifmux, "err_access_next", "'0'");
+
+        self.write("end architecture rtl;\n");
+
+        // Write file
+        self.save(&format!("{}.vhd", rifmux.type_name))?;
+        self.gen_bridge(&rifmux.interface)
+    }
+
+    fn add_mux_if(&mut self, rifmux: &RifmuxInst, name: &str, err_val: &str) {
+        let suffix = if name.ends_with("_next") {"_next"} else {""};
+        self.write(&format!("   if_rif_{name} <=\n      {err_val} when addr_invalid{suffix} else\n"));
+        for (i,comp) in rifmux.components.iter().enumerate() {
+            let top = comp.get_name();
+            if i != rifmux.components.len() - 1 {
+                self.write(&format!("      if_{top}_{name} when if_{top}_done{suffix} else\n"));
+            } else {
+                self.write(&format!("      if_{top}_{name};\n\n"));
+            }
+        }
+    }
+
+    fn gen_rifmux_pkg(&mut self, rifmux: &RifmuxInst) -> Result<(), Box<dyn std::error::Error>> {
+        let name_len = rifmux.components.iter().map(|c| c.get_name().len()).max().unwrap_or(0);
+        self.write_header(&[]);
+        self.write(&format!("package {}_pkg is\n\n", rifmux.type_name));
+        for comp in rifmux.components.iter() {
+            let pad = name_len - comp.get_name().len();
+            self.write(&format!("   constant {}_BASE_ADDR{:<pad$} : {} := {};\n",
+                comp.get_name().to_uppercase(),
+                "",
+                Self::type_str(rifmux.addr_width.into(), false),
+                Self::bit_lit(comp.addr.into(), rifmux.addr_width.into())
+            ));
+        }
+        self.write(&format!("\nend package {}_pkg;\n", rifmux.type_name));
+
+        // Write file
+        self.save(&format!("{}_pkg.vhd", rifmux.type_name))
+    }
+
+}
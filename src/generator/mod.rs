@@ -2,4 +2,21 @@ pub mod casing;
 pub mod gen_common;
 pub mod gen_html;
 pub mod gen_sv;
-pub mod gen_c;
\ No newline at end of file
+pub mod gen_vhdl;
+pub mod gen_c;
+pub mod gen_svd;
+pub mod gen_json;
+pub mod gen_py;
+pub mod gen_ipxact;
+pub mod gen_uvm;
+pub mod gen_latex;
+pub mod gen_md;
+pub mod gen_rust;
+pub mod gen_cpp;
+pub mod gen_linux;
+pub mod gen_qemu;
+pub mod gen_renode;
+pub mod gen_systemc;
+pub mod gen_cocotb;
+pub mod gen_verilog;
+pub mod gen_svlib;
\ No newline at end of file
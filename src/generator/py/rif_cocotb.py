@@ -0,0 +1,305 @@
+# File generated automatically: DO NOT EDIT.
+"""Cocotb testbench support library
+
+Registers are accessed through a bus driver connected to the ports of the
+generated SystemVerilog module:
+ - RifIfDriver: internal register interface (rif_if)
+ - ApbDriver: AMBA APB ports
+
+The register description allows to access registers and fields by name and
+provides ready-made checks:
+ - check_reset: value of all fields after reset
+ - check_walking_ones: walking ones on every read/write field
+ - check_read_only: write on read-only registers and fields has no effect
+
+Fields driven by the hardware are skipped by the reset and walking ones
+checks, while fields protected by a lock or a limit are skipped by the walking
+ones check. The read-only check expects the hardware inputs to be stable.
+"""
+
+from cocotb.triggers import FallingEdge, RisingEdge
+
+
+class RifBusError(Exception):
+    """Bus access answered with an error (address or access error)"""
+
+
+#------------------------------------------------------------------------------
+# Bus drivers
+#------------------------------------------------------------------------------
+
+class BusDriver:
+    """Abstract bus driver: each access returns the read data and the error flag"""
+
+    def __init__(self, bus, clk, prefix="", timeout=1000):
+        self.bus = bus
+        self.clk = clk
+        self.prefix = prefix
+        self.timeout = timeout
+
+    def sig(self, name):
+        return getattr(self.bus, self.prefix + name)
+
+    async def access(self, addr, rd_wrn, data=0):
+        raise NotImplementedError
+
+    async def read(self, addr):
+        return await self.access(addr, True)
+
+    async def write(self, addr, data):
+        _, err = await self.access(addr, False, data)
+        return err
+
+
+class RifIfDriver(BusDriver):
+    """Driver for the internal register interface (rif_if):
+    `en` is held with address, direction and data until `done_next`,
+    and the access is answered by `done` with the read data and the error flags.
+    Use the prefix for a wrapper exposing the interface signals as ports.
+    """
+
+    def __init__(self, bus, clk, prefix="", timeout=1000):
+        super().__init__(bus, clk, prefix, timeout)
+        self.sig("en").value = 0
+
+    async def access(self, addr, rd_wrn, data=0):
+        self.sig("addr").value = addr
+        self.sig("rd_wrn").value = int(rd_wrn)
+        self.sig("wr_data").value = data
+        self.sig("en").value = 1
+        for _ in range(self.timeout):
+            await FallingEdge(self.clk)
+            if int(self.sig("done_next").value):
+                await RisingEdge(self.clk)
+                self.sig("en").value = 0
+                await FallingEdge(self.clk)
+                rd_data = int(self.sig("rd_data").value) if rd_wrn else 0
+                err = bool(int(self.sig("err_addr").value) | int(self.sig("err_access").value))
+                return rd_data, err
+        self.sig("en").value = 0
+        raise TimeoutError(f"No answer from register interface at address 0x{addr:x}")
+
+
+class ApbDriver(BusDriver):
+    """Driver for the APB ports (paddr, psel, penable, pwrite, pwdata, prdata, pready, pslverr)"""
+
+    def __init__(self, bus, clk, prefix="", timeout=1000):
+        super().__init__(bus, clk, prefix, timeout)
+        self.sig("psel").value = 0
+        self.sig("penable").value = 0
+
+    async def access(self, addr, rd_wrn, data=0):
+        # Setup phase
+        self.sig("paddr").value = addr
+        self.sig("pwrite").value = int(not rd_wrn)
+        self.sig("pwdata").value = data
+        self.sig("psel").value = 1
+        self.sig("penable").value = 0
+        await RisingEdge(self.clk)
+        # Access phase: wait for ready
+        self.sig("penable").value = 1
+        for _ in range(self.timeout):
+            await FallingEdge(self.clk)
+            if int(self.sig("pready").value):
+                rd_data = int(self.sig("prdata").value) if rd_wrn else 0
+                err = bool(int(self.sig("pslverr").value))
+                await RisingEdge(self.clk)
+                self.sig("psel").value = 0
+                self.sig("penable").value = 0
+                return rd_data, err
+        raise TimeoutError(f"No answer from APB at address 0x{addr:x}")
+
+
+#------------------------------------------------------------------------------
+# Register description
+#------------------------------------------------------------------------------
+
+# Field kind which always read as 0
+READ_ZERO = ("wo", "password", "pulse", "pulsereg")
+
+
+class Field:
+    """Register field:
+     - hw: value can be modified by the hardware
+     - fixed: software write can be prevented (lock, limit, disabled field)
+    """
+
+    def __init__(self, name, lsb, width, sw_kind, reset=0, hw=False, fixed=False):
+        self.name = name
+        self.lsb = lsb
+        self.width = width
+        self.sw_kind = sw_kind
+        self.reset = reset
+        self.hw = hw
+        self.fixed = fixed
+        self.mask = ((1 << width) - 1) << lsb
+        self.readable = sw_kind not in READ_ZERO
+        self.writable = sw_kind not in ("ro", "rclr")
+
+    def decode(self, reg_value):
+        return (reg_value & self.mask) >> self.lsb
+
+    def encode(self, value):
+        return (int(value) << self.lsb) & self.mask
+
+    def __repr__(self):
+        return f"Field({self.name}, [{self.lsb + self.width - 1}:{self.lsb}], {self.sw_kind})"
+
+
+class Register:
+    """Register instance: access is rw, ro or wo"""
+
+    def __init__(self, name, addr, reset, access, fields):
+        self.name = name
+        self.addr = addr
+        self.reset = reset
+        self.access = access
+        self.fields = {f.name: f for f in fields}
+
+    @property
+    def readable(self):
+        return self.access != "wo"
+
+    @property
+    def writable(self):
+        return self.access != "ro"
+
+    def relocate(self, prefix, offset):
+        """Copy of the register with a prefixed name at an offset address"""
+        return Register(prefix + self.name, self.addr + offset, self.reset, self.access, self.fields.values())
+
+    def neutral(self, value):
+        """Value to write to keep all fields unchanged given the current read value"""
+        neutral = 0
+        for f in self.fields.values():
+            if f.sw_kind == "rw":
+                neutral |= value & f.mask
+            elif f.sw_kind == "wo":
+                neutral |= f.encode(f.reset)
+            elif f.sw_kind == "w0clr":
+                neutral |= f.mask
+        return neutral
+
+    def __repr__(self):
+        return f"Register({self.name} @ 0x{self.addr:x})"
+
+
+class RifTb:
+    """Access to registers and fields by name through a bus driver"""
+
+    def __init__(self, driver, registers, base_addr=0, width=32):
+        self.driver = driver
+        self.base_addr = base_addr
+        self.width = width
+        self.regs = {r.name: r for r in registers}
+
+    def reg(self, name):
+        try:
+            return self.regs[name]
+        except KeyError:
+            raise KeyError(f"Unknown register {name}") from None
+
+    async def read_addr(self, addr, check=True):
+        data, err = await self.driver.read(self.base_addr + addr)
+        if err and check:
+            raise RifBusError(f"Read error at address 0x{addr:x}")
+        return data
+
+    async def write_addr(self, addr, data, check=True):
+        err = await self.driver.write(self.base_addr + addr, data)
+        if err and check:
+            raise RifBusError(f"Write error at address 0x{addr:x}")
+        return err
+
+    async def read(self, reg):
+        """Read a full register"""
+        return await self.read_addr(self.reg(reg).addr)
+
+    async def write(self, reg, value):
+        """Write a full register"""
+        await self.write_addr(self.reg(reg).addr, value)
+
+    async def get(self, reg, field):
+        """Read one field"""
+        return self.reg(reg).fields[field].decode(await self.read(reg))
+
+    async def set(self, reg, field, value):
+        """Write one field, keeping value of the other fields"""
+        r = self.reg(reg)
+        f = r.fields[field]
+        current = await self.read(reg) if r.readable else r.reset
+        await self.write(reg, (r.neutral(current) & ~f.mask) | f.encode(value))
+
+
+#------------------------------------------------------------------------------
+# Ready-made checks
+#------------------------------------------------------------------------------
+
+def _report(errors):
+    for e in errors:
+        print(e)
+    assert not errors, f"{len(errors)} error(s) found"
+
+
+async def check_reset(tb):
+    """Compare all readable fields with their reset value"""
+    errors = []
+    for r in tb.regs.values():
+        fields = [f for f in r.fields.values() if f.readable and not f.hw]
+        if not r.readable or not fields:
+            continue
+        value = await tb.read(r.name)
+        for f in fields:
+            if f.decode(value) != f.reset:
+                errors.append(f"{r.name}.{f.name}: reset value 0x{f.decode(value):x} instead of 0x{f.reset:x}")
+    _report(errors)
+
+
+async def check_walking_ones(tb):
+    """Write a walking one on every bit of read/write fields and read it back"""
+    errors = []
+    for r in tb.regs.values():
+        fields = [f for f in r.fields.values() if f.sw_kind == "rw" and not f.hw and not f.fixed]
+        if not r.readable or not r.writable or not fields:
+            continue
+        init = await tb.read(r.name)
+        for f in fields:
+            for bit in range(f.width):
+                value = (r.neutral(init) & ~f.mask) | f.encode(1 << bit)
+                await tb.write(r.name, value)
+                rd = f.decode(await tb.read(r.name))
+                if rd != 1 << bit:
+                    errors.append(f"{r.name}.{f.name}: read 0x{rd:x} after writing 0x{1 << bit:x}")
+        await tb.write(r.name, r.neutral(init))
+    _report(errors)
+
+
+async def check_read_only(tb):
+    """Write inverted value on read-only registers and fields, expecting no change.
+    Write on a read-only register must also return an error.
+    """
+    errors = []
+    full = (1 << tb.width) - 1
+    for r in tb.regs.values():
+        fields = [f for f in r.fields.values() if f.readable and f.sw_kind == "ro"]
+        if not r.readable:
+            continue
+        if not r.writable:
+            fields = [f for f in r.fields.values() if f.readable and f.sw_kind != "rclr"]
+        if not fields:
+            continue
+        init = await tb.read(r.name)
+        if r.writable:
+            value = r.neutral(init)
+            for f in fields:
+                value = (value & ~f.mask) | (~init & f.mask)
+            await tb.write(r.name, value)
+        else:
+            err = await tb.write_addr(r.addr, ~init & full, check=False)
+            if not err:
+                errors.append(f"{r.name}: no error on write to read-only register")
+        value = await tb.read(r.name)
+        for f in fields:
+            if f.decode(value) != f.decode(init):
+                errors.append(f"{r.name}.{f.name}: value changed from 0x{f.decode(init):x} to 0x{f.decode(value):x}")
+    _report(errors)
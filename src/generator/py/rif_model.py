@@ -0,0 +1,288 @@
+# File generated automatically: DO NOT EDIT.
+"""Register model support library
+
+Registers are accessed through a backend providing read/write of a full
+register at an absolute address. Field accesses are done with
+read-modify-write and respect the software kind of every field:
+ - write on a read-only field (ro, rclr) raises RifAccessError
+ - read of a write-only field (wo, password) raises RifAccessError
+ - fields with a write side-effect (w1clr, w1set, toggle, pulse) are written
+   with 0 (1 for w0clr) when another field of the register is modified
+ - write-only fields keep the last value written
+ - reset only restores read/write and write-only fields: password and
+   side-effect fields are written with their neutral value
+
+Note that a register is read only when it contains read/write fields to
+preserve: a read-modify-write on a register with rclr fields will clear them.
+"""
+
+import socket
+
+
+class RifAccessError(Exception):
+    """Access not allowed by the software kind of a field"""
+
+
+#------------------------------------------------------------------------------
+# Access backends
+#------------------------------------------------------------------------------
+
+class Backend:
+    """Abstract access backend: read/write a register at an absolute address"""
+
+    def read(self, addr):
+        raise NotImplementedError
+
+    def write(self, addr, value):
+        raise NotImplementedError
+
+
+class DictBackend(Backend):
+    """Mock backend storing register values in a dictionary"""
+
+    def __init__(self, init=None):
+        self.mem = dict(init or {})
+
+    def read(self, addr):
+        return self.mem.get(addr, 0)
+
+    def write(self, addr, value):
+        self.mem[addr] = value
+
+
+class FileBackend(Backend):
+    """Backend accessing a file or a device (/dev/mem, UIO, ...) at a given offset"""
+
+    def __init__(self, path, offset=0, width=32):
+        self.file = open(path, "r+b", buffering=0)
+        self.offset = offset
+        self.nb_byte = width // 8
+
+    def read(self, addr):
+        self.file.seek(self.offset + addr)
+        return int.from_bytes(self.file.read(self.nb_byte), "little")
+
+    def write(self, addr, value):
+        self.file.seek(self.offset + addr)
+        self.file.write(value.to_bytes(self.nb_byte, "little"))
+
+    def close(self):
+        self.file.close()
+
+
+class SocketBackend(Backend):
+    """Backend sending text commands on a TCP socket, with values in hexadecimal:
+     - 'R <addr>' answered by '<value>'
+     - 'W <addr> <value>' answered by 'OK'
+    """
+
+    def __init__(self, host, port):
+        self.sock = socket.create_connection((host, port))
+        self.rsp = self.sock.makefile("r")
+
+    def _cmd(self, cmd):
+        self.sock.sendall((cmd + "\n").encode())
+        return self.rsp.readline().strip()
+
+    def read(self, addr):
+        return int(self._cmd(f"R {addr:x}"), 16)
+
+    def write(self, addr, value):
+        rsp = self._cmd(f"W {addr:x} {value:x}")
+        if rsp != "OK":
+            raise IOError(f"Write at 0x{addr:x} failed: {rsp}")
+
+    def close(self):
+        self.sock.close()
+
+
+#------------------------------------------------------------------------------
+# Register model
+#------------------------------------------------------------------------------
+
+# Field kind with a side-effect on write of 1 or 0
+WRITE_1_EFFECT = ("w1clr", "w1set", "toggle", "pulse", "pulsereg")
+WRITE_0_EFFECT = ("w0clr",)
+# Field kind storing the value written
+WRITE_STORE = ("rw", "wo")
+
+
+class Field:
+    """Register field: position, software kind and optional enum"""
+
+    def __init__(self, name, lsb, width, sw_kind, reset=0, enum=None, signed=False, wo=False):
+        self.name = name
+        self.lsb = lsb
+        self.width = width
+        self.sw_kind = sw_kind
+        self.reset = reset
+        self.enum = enum
+        self.signed = signed
+        self.mask = ((1 << width) - 1) << lsb
+        self.readable = not wo and sw_kind not in ("wo", "password")
+        self.writable = sw_kind not in ("ro", "rclr")
+
+    def decode(self, reg_value):
+        """Extract field value from a register value"""
+        value = (reg_value & self.mask) >> self.lsb
+        if self.signed and value >> (self.width - 1):
+            value -= 1 << self.width
+        if self.enum is not None:
+            try:
+                return self.enum(value)
+            except ValueError:
+                pass
+        return value
+
+    def encode(self, value):
+        """Convert a field value to its position in the register"""
+        value = int(value)
+        lo, hi = (-(1 << (self.width - 1)), (1 << (self.width - 1)) - 1) if self.signed else (0, (1 << self.width) - 1)
+        if not lo <= value <= hi:
+            raise ValueError(f"Value {value} out of range for field {self.name} ({lo}..{hi})")
+        return (value << self.lsb) & self.mask
+
+    def __repr__(self):
+        return f"Field({self.name}, [{self.lsb + self.width - 1}:{self.lsb}], {self.sw_kind})"
+
+
+class Register:
+    """Register instance: fields are accessed as attributes"""
+
+    def __init__(self, parent, name, offset, reset, fields, description=""):
+        self.__dict__.update(
+            _parent = parent,
+            _name = name,
+            _addr = parent._base_addr + offset,
+            _offset = offset,
+            _reset = reset,
+            _fields = {f.name: f for f in fields},
+            _desc = description,
+            _shadow = reset,
+        )
+
+    @property
+    def addr(self):
+        return self._addr
+
+    @property
+    def fields(self):
+        return self._fields
+
+    def read(self):
+        """Read the full register value"""
+        return self._parent._backend.read(self._addr)
+
+    def write(self, value):
+        """Write the full register value without any check"""
+        self.__dict__["_shadow"] = value
+        self._parent._backend.write(self._addr, value)
+
+    def get(self, name):
+        """Read one field"""
+        field = self._field(name)
+        if not field.readable:
+            raise RifAccessError(f"{self._name}.{name} is not readable ({field.sw_kind})")
+        return field.decode(self.read())
+
+    def set(self, name, value):
+        """Write one field with a read-modify-write"""
+        self.update(**{name: value})
+
+    def update(self, **kwargs):
+        """Write multiple fields at once with a single read-modify-write"""
+        targets = [self._field(name) for name in kwargs]
+        for field in targets:
+            if not field.writable:
+                raise RifAccessError(f"{self._name}.{field.name} is not writable ({field.sw_kind})")
+        # Keep value of read/write fields, write neutral value for others
+        keep = 0
+        value = 0
+        for field in self._fields.values():
+            if field in targets:
+                continue
+            if field.sw_kind == "rw":
+                keep |= field.mask
+            elif field.sw_kind in WRITE_0_EFFECT:
+                value |= field.mask
+            elif not field.readable:
+                value |= self._shadow & field.mask
+        if keep:
+            value |= self.read() & keep
+        for field in targets:
+            value |= field.encode(kwargs[field.name])
+        self.write(value)
+
+    def reset(self):
+        """Write the reset value of the stored fields (rw, wo) and the neutral
+        value of the others: password and side-effect fields are not triggered.
+        Nothing is written when the register has no stored field.
+        """
+        value = 0
+        store = False
+        for field in self._fields.values():
+            if field.sw_kind in WRITE_STORE:
+                value |= self._reset & field.mask
+                store = True
+            elif field.sw_kind in WRITE_0_EFFECT:
+                value |= field.mask
+        if store:
+            self.write(value)
+
+    def dump(self):
+        """Read register and return a dictionary with the value of all readable fields"""
+        value = self.read()
+        return {f.name: f.decode(value) for f in self._fields.values() if f.readable}
+
+    def _field(self, name):
+        try:
+            return self._fields[name]
+        except KeyError:
+            raise AttributeError(f"Register {self._name} has no field {name}") from None
+
+    def __getattr__(self, name):
+        if name.startswith("_"):
+            raise AttributeError(name)
+        return self.get(name)
+
+    def __setattr__(self, name, value):
+        if name in self._fields:
+            self.set(name, value)
+        else:
+            raise AttributeError(f"Register {self._name} has no field {name}")
+
+    def __dir__(self):
+        return list(self._fields) + ["addr", "fields", "read", "write", "get", "set", "update", "reset", "dump"]
+
+    def __repr__(self):
+        return f"Register({self._name} @ 0x{self._addr:x})"
+
+
+class Block:
+    """Group of registers or blocks (RIF, page or rifmux) at a base address"""
+
+    def __init__(self, backend, base_addr=0, name="", width=32):
+        self._backend = backend
+        self._base_addr = base_addr
+        self._name = name
+        self._width = width
+
+    @property
+    def base_addr(self):
+        return self._base_addr
+
+    def registers(self):
+        """Iterate over all registers, including sub-blocks"""
+        for item in vars(self).values():
+            if isinstance(item, Register):
+                yield item
+            elif isinstance(item, Block):
+                yield from item.registers()
+
+    def reset(self):
+        """Write reset value of all registers with stored fields"""
+        for reg in self.registers():
+            reg.reset()
+
+    def __repr__(self):
+        return f"{type(self).__name__}({self._name} @ 0x{self._base_addr:x})"
@@ -0,0 +1,89 @@
+// File generated automatically: DO NOT EDIT.
+//! Register access support for generated register modules
+//!
+//! A register is accessed through a `Reg<T, A>` giving volatile read/write of its
+//! value type `T` at a fixed address. The access marker `A` restricts the
+//! available operations:
+//!  - `RO`: `read` only
+//!  - `WO`: `write` only
+//!  - `RW`: `read`, `write` and `modify`
+//!
+//! A `modify` writes back the value read with fields having a write side-effect
+//! (clear, set, toggle, pulse, password) replaced by their neutral value.
+
+use core::marker::PhantomData;
+use core::ptr::{read_volatile, write_volatile};
+
+/// Value of a register: conversion from/to the raw bus word
+pub trait RegValue: Copy {
+    type Raw: Copy;
+    fn from_raw(raw: Self::Raw) -> Self;
+    fn to_raw(self) -> Self::Raw;
+    /// Base value of a read-modify-write: fields with a write side-effect are set to their neutral value
+    fn neutral(self) -> Self { self }
+}
+
+/// Access marker of registers which can be read
+pub trait Readable {}
+/// Access marker of registers which can be written
+pub trait Writable {}
+
+/// Read-only register
+pub struct RO;
+/// Write-only register
+pub struct WO;
+/// Read/Write register
+pub struct RW;
+
+impl Readable for RO {}
+impl Readable for RW {}
+impl Writable for WO {}
+impl Writable for RW {}
+
+/// Register at a fixed address
+pub struct Reg<T: RegValue, A> {
+    addr: usize,
+    _marker: PhantomData<(T, A)>,
+}
+
+impl<T: RegValue, A> Reg<T, A> {
+
+    /// Create a register handle
+    ///
+    /// # Safety
+    /// The address must point to a valid register of type `T`
+    pub const unsafe fn new(addr: usize) -> Self {
+        Reg {addr, _marker: PhantomData}
+    }
+
+    /// Absolute address of the register
+    pub const fn addr(&self) -> usize {
+        self.addr
+    }
+}
+
+impl<T: RegValue, A: Readable> Reg<T, A> {
+    /// Read the register
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        T::from_raw(unsafe { read_volatile(self.addr as *const T::Raw) })
+    }
+}
+
+impl<T: RegValue, A: Writable> Reg<T, A> {
+    /// Write the register
+    #[inline(always)]
+    pub fn write(&self, value: T) {
+        unsafe { write_volatile(self.addr as *mut T::Raw, value.to_raw()) }
+    }
+}
+
+impl<T: RegValue> Reg<T, RW> {
+    /// Read-modify-write the register
+    #[inline(always)]
+    pub fn modify<F: FnOnce(&mut T)>(&self, f: F) {
+        let mut value = self.read().neutral();
+        f(&mut value);
+        self.write(value);
+    }
+}
@@ -0,0 +1,110 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from AMBA AHB-Lite to the internal register interface:
+//  - a read starts during the address phase when no access is pending,
+//    giving no wait state for internal registers. Otherwise it starts in the
+//    data phase, like a write which needs hwdata
+//  - hreadyout stays low until the access is done, adding wait states for
+//    external registers. The access is held on the register interface with
+//    the address of the data phase until done
+//  - address or access error is answered with the two cycles ERROR response
+//  - write smaller than the data width is not supported: it is answered with
+//    an ERROR response without accessing the registers
+// Clock and reset are taken from the register interface.
+
+module bridge_ahb_rif #(
+   parameter int W_ADDR = 16,
+   parameter int W_DATA = 32
+) (
+   input  logic              hsel     , // AHB Select
+   input  logic [W_ADDR-1:0] haddr    , // AHB Address
+   input  logic [1:0]        htrans   , // AHB Transfer type
+   input  logic              hwrite   , // AHB Write
+   input  logic [2:0]        hsize    , // AHB Transfer size
+   input  logic [W_DATA-1:0] hwdata   , // AHB Write Data
+   input  logic              hready   , // AHB Ready from the bus
+   output logic [W_DATA-1:0] hrdata   , // AHB Read Data
+   output logic              hreadyout, // AHB Ready
+   output logic              hresp    , // AHB Error Response
+   rif_if.ctrl               if_rif     // Internal register interface
+);
+
+   localparam logic [2:0] SIZE_FULL = 3'($clog2(W_DATA/8));
+
+   logic              dp_valid   ; // Transfer in data phase
+   logic              dp_write   ; // Transfer in data phase is a write
+   logic [W_ADDR-1:0] dp_addr    ; // Address of the transfer in data phase
+   logic              dp_err     ; // Transfer in data phase is not supported
+   logic              dp_started ; // Access of the transfer in data phase has started
+   logic              pending    ; // Access started, waiting for done
+   logic              rd_early   ; // Start a read in the address phase
+   logic              late_start ; // Start an access in the data phase
+   logic              done_cur   ; // Access of the transfer in data phase is done
+   logic              done_next  ; // Access started in the address phase is already done
+   logic              done_l     ; // Access done before the end of the data phase
+   logic              err_l      ; // Error of an access done before the end of the data phase
+   logic [W_DATA-1:0] rd_data_l  ; // Read data of an access done before the end of the data phase
+   logic              err_2nd    ; // Second cycle of the error response
+   logic              resp_done  ; // Response of the data phase available
+   logic              resp_err   ; // Response of the data phase is an error
+
+   // Start the register access
+   assign late_start = dp_valid & ~dp_started & ~dp_err & ~pending;
+   assign rd_early   = hsel & htrans[1] & hready & ~hwrite & ~pending & ~late_start;
+
+   assign if_rif.en      = rd_early | late_start | (pending & ~if_rif.done);
+   assign if_rif.rd_wrn  = rd_early | ~dp_write;
+   assign if_rif.addr    = rd_early ? haddr : dp_addr;
+   assign if_rif.wr_data = hwdata;
+
+   // Access done: an access started in the address phase belongs to the next data phase
+   assign done_cur  = if_rif.done & (pending | late_start);
+   assign done_next = if_rif.done & rd_early;
+
+   // Data phase response
+   assign resp_done = done_l | done_cur | dp_err;
+   assign resp_err  = dp_err | (done_l ? err_l : if_rif.err_addr | if_rif.err_access);
+   assign hresp     = dp_valid & resp_done & resp_err;
+   assign hreadyout = ~dp_valid | (resp_done & (~resp_err | err_2nd));
+   assign hrdata    = done_l ? rd_data_l : if_rif.rd_data;
+
+   always_ff @(posedge if_rif.clk or negedge if_rif.rst_n) begin : proc_ahb
+      if(!if_rif.rst_n) begin
+         dp_valid   <= 1'b0;
+         dp_write   <= 1'b0;
+         dp_addr    <= '0;
+         dp_err     <= 1'b0;
+         dp_started <= 1'b0;
+         pending    <= 1'b0;
+         done_l     <= 1'b0;
+         err_l      <= 1'b0;
+         rd_data_l  <= '0;
+         err_2nd    <= 1'b0;
+      end else begin
+         // Address phase
+         if(hready) begin
+            dp_valid   <= hsel & htrans[1];
+            dp_write   <= hwrite;
+            dp_addr    <= haddr;
+            dp_err     <= hwrite & (hsize != SIZE_FULL);
+            dp_started <= rd_early;
+         end else if(late_start) begin
+            dp_started <= 1'b1;
+         end
+         // Register access
+         if(if_rif.en & ~if_rif.done)
+            pending <= 1'b1;
+         else if(if_rif.done)
+            pending <= 1'b0;
+         // Keep the access result until the end of the data phase
+         if(done_next | (done_cur & ~hreadyout)) begin
+            done_l    <= 1'b1;
+            err_l     <= if_rif.err_addr | if_rif.err_access;
+            rd_data_l <= if_rif.rd_data;
+         end else if(dp_valid & hreadyout) begin
+            done_l    <= 1'b0;
+         end
+         err_2nd <= hresp & ~hreadyout;
+      end
+   end
+
+endmodule
@@ -0,0 +1,34 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from AMBA APB to the internal register interface:
+//  - the register access is started during the setup phase (psel & ~penable)
+//    and held until pready: APB keeps address, direction and data stable
+//  - pready is high once the access is done: no wait state for internal
+//    registers, and as many as needed for external registers
+//  - pslverr is high on address or access error
+// Clock and reset are taken from the register interface.
+
+module bridge_apb_rif #(
+   parameter int W_ADDR = 16,
+   parameter int W_DATA = 32
+) (
+   input  logic [W_ADDR-1:0] paddr  , // APB Address
+   input  logic              psel   , // APB Select
+   input  logic              penable, // APB Enable
+   input  logic              pwrite , // APB Write
+   input  logic [W_DATA-1:0] pwdata , // APB Write Data
+   output logic [W_DATA-1:0] prdata , // APB Read Data
+   output logic              pready , // APB Ready
+   output logic              pslverr, // APB Slave Error
+   rif_if.ctrl               if_rif   // Internal register interface
+);
+
+   assign if_rif.en      = psel & ~(penable & if_rif.done);
+   assign if_rif.rd_wrn  = ~pwrite;
+   assign if_rif.addr    = paddr;
+   assign if_rif.wr_data = pwdata;
+
+   assign pready  = if_rif.done;
+   assign prdata  = if_rif.rd_data;
+   assign pslverr = if_rif.err_addr | if_rif.err_access;
+
+endmodule
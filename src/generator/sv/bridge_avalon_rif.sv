@@ -0,0 +1,84 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from Intel Avalon-MM to the internal register interface:
+//  - avs_address is a byte address (addressUnits SYMBOLS)
+//  - a command is accepted when avs_read or avs_write is high with avs_waitrequest low
+//  - only one register access is done at a time: avs_waitrequest is high until
+//    the access is done, which covers the latency of external registers
+//  - address, direction and write data are latched when the command is
+//    accepted and held on the register interface until done
+//  - read data is returned with avs_readdatavalid, write completion with
+//    avs_writeresponsevalid, and avs_response is SLVERR on access error and
+//    DECODEERROR on address error
+//  - write with partial byte enable is not supported: it is answered with
+//    SLVERR without accessing the registers
+// Clock and reset are taken from the register interface.
+
+module bridge_avalon_rif #(
+   parameter int W_ADDR = 16,
+   parameter int W_DATA = 32
+) (
+   input  logic [W_ADDR-1:0]   avs_address           , // Avalon Address
+   input  logic                avs_read              , // Avalon Read
+   input  logic                avs_write             , // Avalon Write
+   input  logic [W_DATA-1:0]   avs_writedata         , // Avalon Write Data
+   input  logic [W_DATA/8-1:0] avs_byteenable        , // Avalon Byte Enable
+   output logic [W_DATA-1:0]   avs_readdata          , // Avalon Read Data
+   output logic                avs_waitrequest       , // Avalon Wait Request
+   output logic                avs_readdatavalid     , // Avalon Read Data Valid
+   output logic                avs_writeresponsevalid, // Avalon Write Response Valid
+   output logic [1:0]          avs_response          , // Avalon Response
+   rif_if.ctrl                 if_rif                  // Internal register interface
+);
+
+   localparam logic [1:0] OKAY        = 2'b00;
+   localparam logic [1:0] SLVERR      = 2'b10;
+   localparam logic [1:0] DECODEERROR = 2'b11;
+
+   logic              req       ; // Command accepted
+   logic              partial   ; // Write with partial byte enable
+   logic              pending   ; // Access started, waiting for done
+   logic              pending_rd; // Pending access is a read
+   logic [W_ADDR-1:0] acc_addr  ; // Address of the pending access
+   logic [W_DATA-1:0] acc_data  ; // Write data of the pending access
+   logic              done_cur  ; // Pending access is done
+   logic              partial_l ; // Partial write rejected, answered on the next cycle
+   logic [1:0]        resp      ; // Response of the access
+
+   assign req     = (avs_read | avs_write) & ~avs_waitrequest;
+   assign partial = avs_write & ~&avs_byteenable;
+
+   assign if_rif.en      = (req & ~partial) | (pending & ~if_rif.done);
+   assign if_rif.rd_wrn  = pending ? pending_rd : avs_read;
+   assign if_rif.addr    = pending ? acc_addr   : avs_address;
+   assign if_rif.wr_data = pending ? acc_data   : avs_writedata;
+
+   assign avs_waitrequest = pending;
+   assign done_cur        = if_rif.done & pending;
+   assign resp            = if_rif.err_addr ? DECODEERROR : if_rif.err_access ? SLVERR : OKAY;
+
+   always_ff @(posedge if_rif.clk or negedge if_rif.rst_n) begin : proc_avalon
+      if(!if_rif.rst_n) begin
+         pending    <= 1'b0;
+         pending_rd <= 1'b0;
+         acc_addr   <= '0;
+         acc_data   <= '0;
+         partial_l  <= 1'b0;
+      end else begin
+         if(if_rif.done) begin
+            pending    <= 1'b0;
+         end else if(req & ~partial) begin
+            pending    <= 1'b1;
+            pending_rd <= avs_read;
+            acc_addr   <= avs_address;
+            acc_data   <= avs_writedata;
+         end
+         partial_l <= req & partial;
+      end
+   end
+
+   assign avs_readdatavalid      = done_cur & pending_rd;
+   assign avs_writeresponsevalid = (done_cur & ~pending_rd) | partial_l;
+   assign avs_response           = partial_l ? SLVERR : resp;
+   assign avs_readdata           = if_rif.rd_data;
+
+endmodule
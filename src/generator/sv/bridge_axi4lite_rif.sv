@@ -0,0 +1,150 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from AMBA AXI4-Lite to the internal register interface:
+//  - write address, write data and read address are each stored in a one
+//    entry buffer, so all channels are independent
+//  - only one register access is done at a time: when both a read and a write
+//    are ready, priority alternates between them. The access is held from the
+//    buffers on the register interface until done
+//  - a new access only starts once the response of the previous access of the
+//    same kind has been accepted (bready/rready backpressure)
+//  - address error is answered with DECERR and access error with SLVERR
+//  - write with partial strobe is not supported: it is answered with SLVERR
+//    without accessing the registers
+// Clock and reset are taken from the register interface.
+
+module bridge_axi4lite_rif #(
+   parameter int W_ADDR = 16,
+   parameter int W_DATA = 32
+) (
+   input  logic [W_ADDR-1:0]   awaddr , // AXI Write Address
+   input  logic [2:0]          awprot , // AXI Write Protection (unused)
+   input  logic                awvalid, // AXI Write Address Valid
+   output logic                awready, // AXI Write Address Ready
+   input  logic [W_DATA-1:0]   wdata  , // AXI Write Data
+   input  logic [W_DATA/8-1:0] wstrb  , // AXI Write Strobe
+   input  logic                wvalid , // AXI Write Data Valid
+   output logic                wready , // AXI Write Data Ready
+   output logic [1:0]          bresp  , // AXI Write Response
+   output logic                bvalid , // AXI Write Response Valid
+   input  logic                bready , // AXI Write Response Ready
+   input  logic [W_ADDR-1:0]   araddr , // AXI Read Address
+   input  logic [2:0]          arprot , // AXI Read Protection (unused)
+   input  logic                arvalid, // AXI Read Address Valid
+   output logic                arready, // AXI Read Address Ready
+   output logic [W_DATA-1:0]   rdata  , // AXI Read Data
+   output logic [1:0]          rresp  , // AXI Read Response
+   output logic                rvalid , // AXI Read Data Valid
+   input  logic                rready , // AXI Read Data Ready
+   rif_if.ctrl                 if_rif   // Internal register interface
+);
+
+   localparam logic [1:0] OKAY   = 2'b00;
+   localparam logic [1:0] SLVERR = 2'b10;
+   localparam logic [1:0] DECERR = 2'b11;
+
+   logic              aw_full   ; // Write address buffer is full
+   logic [W_ADDR-1:0] aw_addr   ; // Write address buffer
+   logic              w_full    ; // Write data buffer is full
+   logic [W_DATA-1:0] w_data    ; // Write data buffer
+   logic              w_partial ; // Write strobe does not cover the full data
+   logic              ar_full   ; // Read address buffer is full
+   logic [W_ADDR-1:0] ar_addr   ; // Read address buffer
+   logic              pending   ; // Access started, waiting for done
+   logic              pending_rd; // Pending access is a read
+   logic              last_rd   ; // Last access was a read: write has priority
+   logic              wr_ok     ; // Write can start
+   logic              rd_ok     ; // Read can start
+   logic              wr_start  ; // Start a write
+   logic              rd_start  ; // Start a read
+   logic              done      ; // Access done
+   logic              acc_rd    ; // Current access is a read
+   logic [1:0]        resp      ; // Response of the access
+
+   assign awready = ~aw_full;
+   assign wready  = ~w_full;
+   assign arready = ~ar_full;
+
+   // Arbitration between read and write
+   assign wr_ok    = aw_full & w_full & ~bvalid;
+   assign rd_ok    = ar_full & ~rvalid;
+   assign rd_start = ~pending & rd_ok & ~(wr_ok & last_rd);
+   assign wr_start = ~pending & wr_ok & ~rd_start;
+
+   assign done    = if_rif.done & pending;
+   assign acc_rd  = pending ? pending_rd : rd_start;
+   assign resp    = if_rif.err_addr ? DECERR : if_rif.err_access ? SLVERR : OKAY;
+
+   // Partial write is answered directly without accessing the registers
+   assign if_rif.en      = rd_start | (wr_start & ~w_partial) | (pending & ~if_rif.done);
+   assign if_rif.rd_wrn  = acc_rd;
+   assign if_rif.addr    = acc_rd ? ar_addr : aw_addr;
+   assign if_rif.wr_data = w_data;
+
+   always_ff @(posedge if_rif.clk or negedge if_rif.rst_n) begin : proc_axi
+      if(!if_rif.rst_n) begin
+         aw_full    <= 1'b0;
+         aw_addr    <= '0;
+         w_full     <= 1'b0;
+         w_data     <= '0;
+         w_partial  <= 1'b0;
+         ar_full    <= 1'b0;
+         ar_addr    <= '0;
+         pending    <= 1'b0;
+         pending_rd <= 1'b0;
+         last_rd    <= 1'b0;
+         bvalid     <= 1'b0;
+         bresp      <= OKAY;
+         rvalid     <= 1'b0;
+         rresp      <= OKAY;
+         rdata      <= '0;
+      end else begin
+         // Request channels
+         if(awvalid & awready) begin
+            aw_full <= 1'b1;
+            aw_addr <= awaddr;
+         end
+         if(wvalid & wready) begin
+            w_full    <= 1'b1;
+            w_data    <= wdata;
+            w_partial <= ~&wstrb;
+         end
+         if(arvalid & arready) begin
+            ar_full <= 1'b1;
+            ar_addr <= araddr;
+         end
+         // Register access
+         if(if_rif.done) begin
+            pending    <= 1'b0;
+         end else if(rd_start | (wr_start & ~w_partial)) begin
+            pending    <= 1'b1;
+            pending_rd <= rd_start;
+         end
+         if(rd_start | wr_start)
+            last_rd <= rd_start;
+         // Read response channel
+         if(done & acc_rd) begin
+            ar_full <= 1'b0;
+            rvalid  <= 1'b1;
+            rresp   <= resp;
+            rdata   <= if_rif.rd_data;
+         end else if(rready) begin
+            rvalid  <= 1'b0;
+         end
+         // Write response channel
+         if(done & ~acc_rd) begin
+            aw_full <= 1'b0;
+            w_full  <= 1'b0;
+            bvalid  <= 1'b1;
+            bresp   <= resp;
+         end else if(wr_start & w_partial) begin
+            aw_full <= 1'b0;
+            w_full  <= 1'b0;
+            bvalid  <= 1'b1;
+            bresp   <= SLVERR;
+         end else if(bready) begin
+            bvalid  <= 1'b0;
+         end
+      end
+   end
+
+endmodule
@@ -0,0 +1,78 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from Wishbone B4 pipelined to the internal register interface:
+//  - a request is accepted when wb_cyc_i & wb_stb_i & ~wb_stall_o
+//  - only one register access is done at a time: wb_stall_o is high until the
+//    access is done, which covers the latency of external registers
+//  - address, direction and write data are latched when the request is
+//    accepted and held on the register interface until done
+//  - wb_ack_o or wb_err_o is high during one cycle per accepted request,
+//    wb_err_o on address or access error
+//  - write with partial byte select is not supported: it is answered with
+//    wb_err_o without accessing the registers
+// Clock and reset are taken from the register interface.
+
+module bridge_wishbone_rif #(
+   parameter int W_ADDR = 16,
+   parameter int W_DATA = 32
+) (
+   input  logic [W_ADDR-1:0]   wb_adr_i  , // Wishbone Address
+   input  logic [W_DATA-1:0]   wb_dat_i  , // Wishbone Write Data
+   output logic [W_DATA-1:0]   wb_dat_o  , // Wishbone Read Data
+   input  logic [W_DATA/8-1:0] wb_sel_i  , // Wishbone Byte Select
+   input  logic                wb_we_i   , // Wishbone Write Enable
+   input  logic                wb_cyc_i  , // Wishbone Cycle
+   input  logic                wb_stb_i  , // Wishbone Strobe
+   output logic                wb_ack_o  , // Wishbone Acknowledge
+   output logic                wb_err_o  , // Wishbone Error
+   output logic                wb_stall_o, // Wishbone Stall
+   rif_if.ctrl                 if_rif      // Internal register interface
+);
+
+   logic              req      ; // Request accepted
+   logic              partial  ; // Write with partial byte select
+   logic              pending  ; // Access started, waiting for done
+   logic              acc_rd   ; // Pending access is a read
+   logic [W_ADDR-1:0] acc_addr ; // Address of the pending access
+   logic [W_DATA-1:0] acc_data ; // Write data of the pending access
+   logic              done_cur ; // Pending access is done
+   logic              partial_l; // Partial write rejected, answered on the next cycle
+   logic              err      ; // Error of the pending access
+
+   assign req     = wb_cyc_i & wb_stb_i & ~wb_stall_o;
+   assign partial = wb_we_i & ~&wb_sel_i;
+
+   assign if_rif.en      = (req & ~partial) | (pending & ~if_rif.done);
+   assign if_rif.rd_wrn  = pending ? acc_rd   : ~wb_we_i;
+   assign if_rif.addr    = pending ? acc_addr : wb_adr_i;
+   assign if_rif.wr_data = pending ? acc_data : wb_dat_i;
+
+   assign wb_stall_o = pending;
+   assign done_cur   = if_rif.done & pending;
+   assign err        = if_rif.err_addr | if_rif.err_access;
+
+   always_ff @(posedge if_rif.clk or negedge if_rif.rst_n) begin : proc_wishbone
+      if(!if_rif.rst_n) begin
+         pending   <= 1'b0;
+         acc_rd    <= 1'b0;
+         acc_addr  <= '0;
+         acc_data  <= '0;
+         partial_l <= 1'b0;
+      end else begin
+         if(if_rif.done) begin
+            pending  <= 1'b0;
+         end else if(req & ~partial) begin
+            pending  <= 1'b1;
+            acc_rd   <= ~wb_we_i;
+            acc_addr <= wb_adr_i;
+            acc_data <= wb_dat_i;
+         end
+         partial_l <= req & partial;
+      end
+   end
+
+   // Response is dropped when the cycle is aborted
+   assign wb_ack_o = wb_cyc_i & done_cur & ~err;
+   assign wb_err_o = wb_cyc_i & ((done_cur & err) | partial_l);
+   assign wb_dat_o = if_rif.rd_data;
+
+endmodule
@@ -0,0 +1,134 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from AMBA AHB-Lite to the internal register interface:
+//  - a read starts during the address phase when no access is pending,
+//    giving no wait state for internal registers. Otherwise it starts in the
+//    data phase, like a write which needs hwdata
+//  - hreadyout stays low until the access is done, adding wait states for
+//    external registers. The access is held on the register interface with
+//    the address of the data phase until done
+//  - address or access error is answered with the two cycles ERROR response
+//  - write smaller than the data width is not supported: it is answered with
+//    an ERROR response without accessing the registers
+// Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+module bridge_ahb_rif #(
+   parameter W_ADDR = 16,
+   parameter W_DATA = 32
+) (
+   input  wire              clk                   , // Software clock
+   input  wire              rst_n                 , // Software reset
+   input  wire              hsel                  , // AHB Select
+   input  wire [W_ADDR-1:0] haddr                 , // AHB Address
+   input  wire [1:0]        htrans                , // AHB Transfer type
+   input  wire              hwrite                , // AHB Write
+   input  wire [2:0]        hsize                 , // AHB Transfer size
+   input  wire [W_DATA-1:0] hwdata                , // AHB Write Data
+   input  wire              hready                , // AHB Ready from the bus
+   output wire [W_DATA-1:0] hrdata                , // AHB Read Data
+   output wire              hreadyout             , // AHB Ready
+   output wire              hresp                 , // AHB Error Response
+   output wire              if_rif_en             , // Access enable, held until done_next
+   output wire              if_rif_rd_wrn         , // Read (high) / Write (low)
+   output wire [W_ADDR-1:0] if_rif_addr           , // Address
+   output wire [W_DATA-1:0] if_rif_wr_data        , // Write data
+   input  wire [W_DATA-1:0] if_rif_rd_data        , // Read data
+   input  wire              if_rif_done           , // Access done
+   input  wire              if_rif_err_addr       , // Address error
+   input  wire              if_rif_err_access     , // Access error
+   input  wire              if_rif_done_next      , // Access done (combinatorial)
+   input  wire              if_rif_err_addr_next  , // Address error (combinatorial)
+   input  wire              if_rif_err_access_next  // Access error (combinatorial)
+);
+
+   // Transfer size (log2 of the number of bytes) using the full data width
+   function [2:0] full_size;
+      input integer w;
+      integer i;
+      begin
+         full_size = 3'd7;
+         for(i = 6; i >= 0; i = i - 1)
+            if((8 << i) >= w)
+               full_size = i;
+      end
+   endfunction
+
+   localparam [2:0] SIZE_FULL = full_size(W_DATA);
+
+   reg               dp_valid   ; // Transfer in data phase
+   reg               dp_write   ; // Transfer in data phase is a write
+   reg  [W_ADDR-1:0] dp_addr    ; // Address of the transfer in data phase
+   reg               dp_err     ; // Transfer in data phase is not supported
+   reg               dp_started ; // Access of the transfer in data phase has started
+   reg               pending    ; // Access started, waiting for done
+   wire              rd_early   ; // Start a read in the address phase
+   wire              late_start ; // Start an access in the data phase
+   wire              done_cur   ; // Access of the transfer in data phase is done
+   wire              done_next  ; // Access started in the address phase is already done
+   reg               done_l     ; // Access done before the end of the data phase
+   reg               err_l      ; // Error of an access done before the end of the data phase
+   reg  [W_DATA-1:0] rd_data_l  ; // Read data of an access done before the end of the data phase
+   reg               err_2nd    ; // Second cycle of the error response
+   wire              resp_done  ; // Response of the data phase available
+   wire              resp_err   ; // Response of the data phase is an error
+
+   // Start the register access
+   assign late_start = dp_valid & ~dp_started & ~dp_err & ~pending;
+   assign rd_early   = hsel & htrans[1] & hready & ~hwrite & ~pending & ~late_start;
+
+   assign if_rif_en      = rd_early | late_start | (pending & ~if_rif_done);
+   assign if_rif_rd_wrn  = rd_early | ~dp_write;
+   assign if_rif_addr    = rd_early ? haddr : dp_addr;
+   assign if_rif_wr_data = hwdata;
+
+   // Access done: an access started in the address phase belongs to the next data phase
+   assign done_cur  = if_rif_done & (pending | late_start);
+   assign done_next = if_rif_done & rd_early;
+
+   // Data phase response
+   assign resp_done = done_l | done_cur | dp_err;
+   assign resp_err  = dp_err | (done_l ? err_l : if_rif_err_addr | if_rif_err_access);
+   assign hresp     = dp_valid & resp_done & resp_err;
+   assign hreadyout = ~dp_valid | (resp_done & (~resp_err | err_2nd));
+   assign hrdata    = done_l ? rd_data_l : if_rif_rd_data;
+
+   always @(posedge clk or negedge rst_n) begin : proc_ahb
+      if(!rst_n) begin
+         dp_valid   <= 1'b0;
+         dp_write   <= 1'b0;
+         dp_addr    <= {W_ADDR{1'b0}};
+         dp_err     <= 1'b0;
+         dp_started <= 1'b0;
+         pending    <= 1'b0;
+         done_l     <= 1'b0;
+         err_l      <= 1'b0;
+         rd_data_l  <= {W_DATA{1'b0}};
+         err_2nd    <= 1'b0;
+      end else begin
+         // Address phase
+         if(hready) begin
+            dp_valid   <= hsel & htrans[1];
+            dp_write   <= hwrite;
+            dp_addr    <= haddr;
+            dp_err     <= hwrite & (hsize != SIZE_FULL);
+            dp_started <= rd_early;
+         end else if(late_start) begin
+            dp_started <= 1'b1;
+         end
+         // Register access
+         if(if_rif_en & ~if_rif_done)
+            pending <= 1'b1;
+         else if(if_rif_done)
+            pending <= 1'b0;
+         // Keep the access result until the end of the data phase
+         if(done_next | (done_cur & ~hreadyout)) begin
+            done_l    <= 1'b1;
+            err_l     <= if_rif_err_addr | if_rif_err_access;
+            rd_data_l <= if_rif_rd_data;
+         end else if(dp_valid & hreadyout) begin
+            done_l    <= 1'b0;
+         end
+         err_2nd <= hresp & ~hreadyout;
+      end
+   end
+
+endmodule
@@ -0,0 +1,46 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from AMBA APB to the internal register interface:
+//  - the register access is started during the setup phase (psel & ~penable)
+//    and held until pready: APB keeps address, direction and data stable
+//  - pready is high once the access is done: no wait state for internal
+//    registers, and as many as needed for external registers
+//  - pslverr is high on address or access error
+// Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+module bridge_apb_rif #(
+   parameter W_ADDR = 16,
+   parameter W_DATA = 32
+) (
+   input  wire              clk                   , // Software clock
+   input  wire              rst_n                 , // Software reset
+   input  wire [W_ADDR-1:0] paddr                 , // APB Address
+   input  wire              psel                  , // APB Select
+   input  wire              penable               , // APB Enable
+   input  wire              pwrite                , // APB Write
+   input  wire [W_DATA-1:0] pwdata                , // APB Write Data
+   output wire [W_DATA-1:0] prdata                , // APB Read Data
+   output wire              pready                , // APB Ready
+   output wire              pslverr               , // APB Slave Error
+   output wire              if_rif_en             , // Access enable, held until done_next
+   output wire              if_rif_rd_wrn         , // Read (high) / Write (low)
+   output wire [W_ADDR-1:0] if_rif_addr           , // Address
+   output wire [W_DATA-1:0] if_rif_wr_data        , // Write data
+   input  wire [W_DATA-1:0] if_rif_rd_data        , // Read data
+   input  wire              if_rif_done           , // Access done
+   input  wire              if_rif_err_addr       , // Address error
+   input  wire              if_rif_err_access     , // Access error
+   input  wire              if_rif_done_next      , // Access done (combinatorial)
+   input  wire              if_rif_err_addr_next  , // Address error (combinatorial)
+   input  wire              if_rif_err_access_next  // Access error (combinatorial)
+);
+
+   assign if_rif_en      = psel & ~(penable & if_rif_done);
+   assign if_rif_rd_wrn  = ~pwrite;
+   assign if_rif_addr    = paddr;
+   assign if_rif_wr_data = pwdata;
+
+   assign pready  = if_rif_done;
+   assign prdata  = if_rif_rd_data;
+   assign pslverr = if_rif_err_addr | if_rif_err_access;
+
+endmodule
@@ -0,0 +1,96 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from Intel Avalon-MM to the internal register interface:
+//  - avs_address is a byte address (addressUnits SYMBOLS)
+//  - a command is accepted when avs_read or avs_write is high with avs_waitrequest low
+//  - only one register access is done at a time: avs_waitrequest is high until
+//    the access is done, which covers the latency of external registers
+//  - address, direction and write data are latched when the command is
+//    accepted and held on the register interface until done
+//  - read data is returned with avs_readdatavalid, write completion with
+//    avs_writeresponsevalid, and avs_response is SLVERR on access error and
+//    DECODEERROR on address error
+//  - write with partial byte enable is not supported: it is answered with
+//    SLVERR without accessing the registers
+// Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+module bridge_avalon_rif #(
+   parameter W_ADDR = 16,
+   parameter W_DATA = 32
+) (
+   input  wire                clk                   , // Software clock
+   input  wire                rst_n                 , // Software reset
+   input  wire [W_ADDR-1:0]   avs_address           , // Avalon Address
+   input  wire                avs_read              , // Avalon Read
+   input  wire                avs_write             , // Avalon Write
+   input  wire [W_DATA-1:0]   avs_writedata         , // Avalon Write Data
+   input  wire [W_DATA/8-1:0] avs_byteenable        , // Avalon Byte Enable
+   output wire [W_DATA-1:0]   avs_readdata          , // Avalon Read Data
+   output wire                avs_waitrequest       , // Avalon Wait Request
+   output wire                avs_readdatavalid     , // Avalon Read Data Valid
+   output wire                avs_writeresponsevalid, // Avalon Write Response Valid
+   output wire [1:0]          avs_response          , // Avalon Response
+   output wire                if_rif_en             , // Access enable, held until done_next
+   output wire                if_rif_rd_wrn         , // Read (high) / Write (low)
+   output wire [W_ADDR-1:0]   if_rif_addr           , // Address
+   output wire [W_DATA-1:0]   if_rif_wr_data        , // Write data
+   input  wire [W_DATA-1:0]   if_rif_rd_data        , // Read data
+   input  wire                if_rif_done           , // Access done
+   input  wire                if_rif_err_addr       , // Address error
+   input  wire                if_rif_err_access     , // Access error
+   input  wire                if_rif_done_next      , // Access done (combinatorial)
+   input  wire                if_rif_err_addr_next  , // Address error (combinatorial)
+   input  wire                if_rif_err_access_next  // Access error (combinatorial)
+);
+
+   localparam [1:0] OKAY        = 2'b00;
+   localparam [1:0] SLVERR      = 2'b10;
+   localparam [1:0] DECODEERROR = 2'b11;
+
+   wire              req       ; // Command accepted
+   wire              partial   ; // Write with partial byte enable
+   reg               pending   ; // Access started, waiting for done
+   reg               pending_rd; // Pending access is a read
+   reg  [W_ADDR-1:0] acc_addr  ; // Address of the pending access
+   reg  [W_DATA-1:0] acc_data  ; // Write data of the pending access
+   wire              done_cur  ; // Pending access is done
+   reg               partial_l ; // Partial write rejected, answered on the next cycle
+   wire [1:0]        resp      ; // Response of the access
+
+   assign req     = (avs_read | avs_write) & ~avs_waitrequest;
+   assign partial = avs_write & ~&avs_byteenable;
+
+   assign if_rif_en      = (req & ~partial) | (pending & ~if_rif_done);
+   assign if_rif_rd_wrn  = pending ? pending_rd : avs_read;
+   assign if_rif_addr    = pending ? acc_addr   : avs_address;
+   assign if_rif_wr_data = pending ? acc_data   : avs_writedata;
+
+   assign avs_waitrequest = pending;
+   assign done_cur        = if_rif_done & pending;
+   assign resp            = if_rif_err_addr ? DECODEERROR : if_rif_err_access ? SLVERR : OKAY;
+
+   always @(posedge clk or negedge rst_n) begin : proc_avalon
+      if(!rst_n) begin
+         pending    <= 1'b0;
+         pending_rd <= 1'b0;
+         acc_addr   <= {W_ADDR{1'b0}};
+         acc_data   <= {W_DATA{1'b0}};
+         partial_l  <= 1'b0;
+      end else begin
+         if(if_rif_done) begin
+            pending    <= 1'b0;
+         end else if(req & ~partial) begin
+            pending    <= 1'b1;
+            pending_rd <= avs_read;
+            acc_addr   <= avs_address;
+            acc_data   <= avs_writedata;
+         end
+         partial_l <= req & partial;
+      end
+   end
+
+   assign avs_readdatavalid      = done_cur & pending_rd;
+   assign avs_writeresponsevalid = (done_cur & ~pending_rd) | partial_l;
+   assign avs_response           = partial_l ? SLVERR : resp;
+   assign avs_readdata           = if_rif_rd_data;
+
+endmodule
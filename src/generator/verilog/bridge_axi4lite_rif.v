@@ -0,0 +1,172 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from AMBA AXI4-Lite to the internal register interface:
+//  - write address, write data and read address are each stored in a one
+//    entry buffer, so all channels are independent
+//  - only one register access is done at a time: when both a read and a write
+//    are ready, priority alternates between them. The access is held from the
+//    buffers on the register interface until done
+//  - a new access only starts once the response of the previous access of the
+//    same kind has been accepted (bready/rready backpressure)
+//  - address error is answered with DECERR and access error with SLVERR
+//  - write with partial strobe is not supported: it is answered with SLVERR
+//    without accessing the registers
+// Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+module bridge_axi4lite_rif #(
+   parameter W_ADDR = 16,
+   parameter W_DATA = 32
+) (
+   input  wire                clk                   , // Software clock
+   input  wire                rst_n                 , // Software reset
+   input  wire [W_ADDR-1:0]   awaddr                , // AXI Write Address
+   input  wire [2:0]          awprot                , // AXI Write Protection (unused)
+   input  wire                awvalid               , // AXI Write Address Valid
+   output wire                awready               , // AXI Write Address Ready
+   input  wire [W_DATA-1:0]   wdata                 , // AXI Write Data
+   input  wire [W_DATA/8-1:0] wstrb                 , // AXI Write Strobe
+   input  wire                wvalid                , // AXI Write Data Valid
+   output wire                wready                , // AXI Write Data Ready
+   output wire [1:0]          bresp                 , // AXI Write Response
+   output wire                bvalid                , // AXI Write Response Valid
+   input  wire                bready                , // AXI Write Response Ready
+   input  wire [W_ADDR-1:0]   araddr                , // AXI Read Address
+   input  wire [2:0]          arprot                , // AXI Read Protection (unused)
+   input  wire                arvalid               , // AXI Read Address Valid
+   output wire                arready               , // AXI Read Address Ready
+   output wire [W_DATA-1:0]   rdata                 , // AXI Read Data
+   output wire [1:0]          rresp                 , // AXI Read Response
+   output wire                rvalid                , // AXI Read Data Valid
+   input  wire                rready                , // AXI Read Data Ready
+   output wire                if_rif_en             , // Access enable, held until done_next
+   output wire                if_rif_rd_wrn         , // Read (high) / Write (low)
+   output wire [W_ADDR-1:0]   if_rif_addr           , // Address
+   output wire [W_DATA-1:0]   if_rif_wr_data        , // Write data
+   input  wire [W_DATA-1:0]   if_rif_rd_data        , // Read data
+   input  wire                if_rif_done           , // Access done
+   input  wire                if_rif_err_addr       , // Address error
+   input  wire                if_rif_err_access     , // Access error
+   input  wire                if_rif_done_next      , // Access done (combinatorial)
+   input  wire                if_rif_err_addr_next  , // Address error (combinatorial)
+   input  wire                if_rif_err_access_next  // Access error (combinatorial)
+);
+
+   localparam [1:0] OKAY   = 2'b00;
+   localparam [1:0] SLVERR = 2'b10;
+   localparam [1:0] DECERR = 2'b11;
+
+   reg               aw_full   ; // Write address buffer is full
+   reg  [W_ADDR-1:0] aw_addr   ; // Write address buffer
+   reg               w_full    ; // Write data buffer is full
+   reg  [W_DATA-1:0] w_data    ; // Write data buffer
+   reg               w_partial ; // Write strobe does not cover the full data
+   reg               ar_full   ; // Read address buffer is full
+   reg  [W_ADDR-1:0] ar_addr   ; // Read address buffer
+   reg               pending   ; // Access started, waiting for done
+   reg               pending_rd; // Pending access is a read
+   reg               last_rd   ; // Last access was a read: write has priority
+   wire              wr_ok     ; // Write can start
+   wire              rd_ok     ; // Read can start
+   wire              wr_start  ; // Start a write
+   wire              rd_start  ; // Start a read
+   wire              done      ; // Access done
+   wire              acc_rd    ; // Current access is a read
+   wire [1:0]        resp      ; // Response of the access
+   reg               bvalid_l  ; // Write response valid
+   reg  [1:0]        bresp_l   ; // Write response
+   reg               rvalid_l  ; // Read data valid
+   reg  [1:0]        rresp_l   ; // Read response
+   reg  [W_DATA-1:0] rdata_l   ; // Read data
+
+   assign awready = ~aw_full;
+   assign wready  = ~w_full;
+   assign arready = ~ar_full;
+   assign bvalid  = bvalid_l;
+   assign bresp   = bresp_l;
+   assign rvalid  = rvalid_l;
+   assign rresp   = rresp_l;
+   assign rdata   = rdata_l;
+
+   // Arbitration between read and write
+   assign wr_ok    = aw_full & w_full & ~bvalid_l;
+   assign rd_ok    = ar_full & ~rvalid_l;
+   assign rd_start = ~pending & rd_ok & ~(wr_ok & last_rd);
+   assign wr_start = ~pending & wr_ok & ~rd_start;
+
+   assign done    = if_rif_done & pending;
+   assign acc_rd  = pending ? pending_rd : rd_start;
+   assign resp    = if_rif_err_addr ? DECERR : if_rif_err_access ? SLVERR : OKAY;
+
+   // Partial write is answered directly without accessing the registers
+   assign if_rif_en      = rd_start | (wr_start & ~w_partial) | (pending & ~if_rif_done);
+   assign if_rif_rd_wrn  = acc_rd;
+   assign if_rif_addr    = acc_rd ? ar_addr : aw_addr;
+   assign if_rif_wr_data = w_data;
+
+   always @(posedge clk or negedge rst_n) begin : proc_axi
+      if(!rst_n) begin
+         aw_full    <= 1'b0;
+         aw_addr    <= {W_ADDR{1'b0}};
+         w_full     <= 1'b0;
+         w_data     <= {W_DATA{1'b0}};
+         w_partial  <= 1'b0;
+         ar_full    <= 1'b0;
+         ar_addr    <= {W_ADDR{1'b0}};
+         pending    <= 1'b0;
+         pending_rd <= 1'b0;
+         last_rd    <= 1'b0;
+         bvalid_l   <= 1'b0;
+         bresp_l    <= OKAY;
+         rvalid_l   <= 1'b0;
+         rresp_l    <= OKAY;
+         rdata_l    <= {W_DATA{1'b0}};
+      end else begin
+         // Request channels
+         if(awvalid & ~aw_full) begin
+            aw_full <= 1'b1;
+            aw_addr <= awaddr;
+         end
+         if(wvalid & ~w_full) begin
+            w_full    <= 1'b1;
+            w_data    <= wdata;
+            w_partial <= ~&wstrb;
+         end
+         if(arvalid & ~ar_full) begin
+            ar_full <= 1'b1;
+            ar_addr <= araddr;
+         end
+         // Register access
+         if(if_rif_done) begin
+            pending    <= 1'b0;
+         end else if(rd_start | (wr_start & ~w_partial)) begin
+            pending    <= 1'b1;
+            pending_rd <= rd_start;
+         end
+         if(rd_start | wr_start)
+            last_rd <= rd_start;
+         // Read response channel
+         if(done & acc_rd) begin
+            ar_full  <= 1'b0;
+            rvalid_l <= 1'b1;
+            rresp_l  <= resp;
+            rdata_l  <= if_rif_rd_data;
+         end else if(rready) begin
+            rvalid_l <= 1'b0;
+         end
+         // Write response channel
+         if(done & ~acc_rd) begin
+            aw_full  <= 1'b0;
+            w_full   <= 1'b0;
+            bvalid_l <= 1'b1;
+            bresp_l  <= resp;
+         end else if(wr_start & w_partial) begin
+            aw_full  <= 1'b0;
+            w_full   <= 1'b0;
+            bvalid_l <= 1'b1;
+            bresp_l  <= SLVERR;
+         end else if(bready) begin
+            bvalid_l <= 1'b0;
+         end
+      end
+   end
+
+endmodule
@@ -0,0 +1,109 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from the ARC auxiliary register interface (UAUX) to the internal
+// register interface:
+//  - a read is started in the prepare stage (uaux_en & uaux_read) and stalls
+//    the core with uaux_busy until the access is done
+//  - a write is started when the instruction commits (uaux_cmt_phase & uaux_cmt_valid)
+//    using the address captured in the prepare stage. Any following auxiliary
+//    access is stalled until the write is done. A write committed during a
+//    pending read waits for the end of the read
+//  - the access is held on the register interface until done, with the
+//    address captured when it started
+//  - on read, address error is reported as unimplemented and access error as
+//    read privilege violation. Write errors are not reported.
+// Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+module bridge_uaux_rif #(
+   parameter W_ADDR = 16,
+   parameter W_DATA = 32
+) (
+   input  wire              clk                   , // Software clock
+   input  wire              rst_n                 , // Software reset
+   input  wire [W_ADDR-1:0] uaux_addr             , // AUX Address
+   input  wire              uaux_en               , // AUX Enable
+   input  wire              uaux_cmt_phase        , // AUX Commit status
+   input  wire              uaux_cmt_valid        , // AUX Commit Valid
+   input  wire              uaux_read             , // AUX Read
+   input  wire              uaux_write            , // AUX Write
+   input  wire [W_DATA-1:0] uaux_wdata            , // AUX Write Data
+   output wire [W_DATA-1:0] uaux_rdata            , // AUX Read Data
+   output wire              uaux_busy             , // AUX Busy
+   output wire              uaux_illegal          , // SR/LR illegal
+   output wire              uaux_k_rd             , // AUX read privilege violation
+   output wire              uaux_k_wr             , // AUX write privilege violation
+   output wire              uaux_unimpl           , // AUX unimplemented address
+   output wire              uaux_serial_sr        , // AUX SR group flush
+   output wire              uaux_strict_sr        , // AUX SR single flush
+   output wire              if_rif_en             , // Access enable, held until done_next
+   output wire              if_rif_rd_wrn         , // Read (high) / Write (low)
+   output wire [W_ADDR-1:0] if_rif_addr           , // Address
+   output wire [W_DATA-1:0] if_rif_wr_data        , // Write data
+   input  wire [W_DATA-1:0] if_rif_rd_data        , // Read data
+   input  wire              if_rif_done           , // Access done
+   input  wire              if_rif_err_addr       , // Address error
+   input  wire              if_rif_err_access     , // Access error
+   input  wire              if_rif_done_next      , // Access done (combinatorial)
+   input  wire              if_rif_err_addr_next  , // Address error (combinatorial)
+   input  wire              if_rif_err_access_next  // Access error (combinatorial)
+);
+
+   reg  [W_ADDR-1:0] wr_addr;    // Address of the write captured in the prepare stage
+   reg  [W_DATA-1:0] wr_data;    // Data of the write captured when the instruction commits
+   reg  [W_ADDR-1:0] acc_addr;   // Address of the pending access
+   wire              wr_cmt;     // Write instruction commits
+   reg               wr_wait;    // Write committed during a pending read
+   wire              rd_req;     // Start a read access
+   wire              wr_req;     // Start a write access
+   reg               rd_pending; // Read started, waiting for done
+   reg               wr_pending; // Write started, waiting for done
+   wire              rd_done;    // Read access done
+
+   // The write of an older instruction has priority over a new read
+   assign wr_cmt  = uaux_cmt_valid & uaux_cmt_phase & uaux_write;
+   assign wr_req  = (wr_cmt | wr_wait) & ~rd_pending & ~wr_pending;
+   assign rd_req  = uaux_en & uaux_read & ~rd_pending & ~wr_pending & ~wr_req;
+   assign rd_done = if_rif_done & rd_pending;
+
+   // Access is held until the cycle before done
+   assign if_rif_en      = rd_req | wr_req | ((rd_pending | wr_pending) & ~if_rif_done);
+   assign if_rif_rd_wrn  = rd_req | rd_pending;
+   assign if_rif_addr    = (rd_pending | wr_pending) ? acc_addr : wr_req ? wr_addr : uaux_addr;
+   assign if_rif_wr_data = (wr_wait | wr_pending) ? wr_data : uaux_wdata;
+
+   always @(posedge clk or negedge rst_n) begin : proc_pending
+      if(!rst_n) begin
+         wr_addr    <= {W_ADDR{1'b0}};
+         wr_data    <= {W_DATA{1'b0}};
+         acc_addr   <= {W_ADDR{1'b0}};
+         wr_wait    <= 1'b0;
+         rd_pending <= 1'b0;
+         wr_pending <= 1'b0;
+      end else begin
+         if(uaux_en & uaux_write)
+            wr_addr <= uaux_addr;
+         if(wr_cmt)
+            wr_data <= uaux_wdata;
+         if(rd_req | wr_req)
+            acc_addr <= if_rif_addr;
+         wr_wait <= (wr_cmt | wr_wait) & ~wr_req;
+         if(if_rif_done)
+            rd_pending <= 1'b0;
+         else if(rd_req)
+            rd_pending <= 1'b1;
+         if(if_rif_done)
+            wr_pending <= 1'b0;
+         else if(wr_req)
+            wr_pending <= 1'b1;
+      end
+   end
+
+   assign uaux_busy      = uaux_en & ((uaux_read & ~rd_done) | wr_pending | wr_wait);
+   assign uaux_rdata     = if_rif_rd_data;
+   assign uaux_unimpl    = rd_done & if_rif_err_addr;
+   assign uaux_k_rd      = rd_done & if_rif_err_access;
+   assign uaux_k_wr      = 1'b0;
+   assign uaux_illegal   = 1'b0;
+   assign uaux_serial_sr = 1'b0;
+   assign uaux_strict_sr = 1'b0;
+
+endmodule
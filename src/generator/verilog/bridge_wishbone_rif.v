@@ -0,0 +1,90 @@
+// File generated automatically: DO NOT EDIT.
+// Bridge from Wishbone B4 pipelined to the internal register interface:
+//  - a request is accepted when wb_cyc_i & wb_stb_i & ~wb_stall_o
+//  - only one register access is done at a time: wb_stall_o is high until the
+//    access is done, which covers the latency of external registers
+//  - address, direction and write data are latched when the request is
+//    accepted and held on the register interface until done
+//  - wb_ack_o or wb_err_o is high during one cycle per accepted request,
+//    wb_err_o on address or access error
+//  - write with partial byte select is not supported: it is answered with
+//    wb_err_o without accessing the registers
+// Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+module bridge_wishbone_rif #(
+   parameter W_ADDR = 16,
+   parameter W_DATA = 32
+) (
+   input  wire                clk                   , // Software clock
+   input  wire                rst_n                 , // Software reset
+   input  wire [W_ADDR-1:0]   wb_adr_i              , // Wishbone Address
+   input  wire [W_DATA-1:0]   wb_dat_i              , // Wishbone Write Data
+   output wire [W_DATA-1:0]   wb_dat_o              , // Wishbone Read Data
+   input  wire [W_DATA/8-1:0] wb_sel_i              , // Wishbone Byte Select
+   input  wire                wb_we_i               , // Wishbone Write Enable
+   input  wire                wb_cyc_i              , // Wishbone Cycle
+   input  wire                wb_stb_i              , // Wishbone Strobe
+   output wire                wb_ack_o              , // Wishbone Acknowledge
+   output wire                wb_err_o              , // Wishbone Error
+   output wire                wb_stall_o            , // Wishbone Stall
+   output wire                if_rif_en             , // Access enable, held until done_next
+   output wire                if_rif_rd_wrn         , // Read (high) / Write (low)
+   output wire [W_ADDR-1:0]   if_rif_addr           , // Address
+   output wire [W_DATA-1:0]   if_rif_wr_data        , // Write data
+   input  wire [W_DATA-1:0]   if_rif_rd_data        , // Read data
+   input  wire                if_rif_done           , // Access done
+   input  wire                if_rif_err_addr       , // Address error
+   input  wire                if_rif_err_access     , // Access error
+   input  wire                if_rif_done_next      , // Access done (combinatorial)
+   input  wire                if_rif_err_addr_next  , // Address error (combinatorial)
+   input  wire                if_rif_err_access_next  // Access error (combinatorial)
+);
+
+   wire              req      ; // Request accepted
+   wire              partial  ; // Write with partial byte select
+   reg               pending  ; // Access started, waiting for done
+   reg               acc_rd   ; // Pending access is a read
+   reg  [W_ADDR-1:0] acc_addr ; // Address of the pending access
+   reg  [W_DATA-1:0] acc_data ; // Write data of the pending access
+   wire              done_cur ; // Pending access is done
+   reg               partial_l; // Partial write rejected, answered on the next cycle
+   wire              err      ; // Error of the pending access
+
+   assign req     = wb_cyc_i & wb_stb_i & ~wb_stall_o;
+   assign partial = wb_we_i & ~&wb_sel_i;
+
+   assign if_rif_en      = (req & ~partial) | (pending & ~if_rif_done);
+   assign if_rif_rd_wrn  = pending ? acc_rd   : ~wb_we_i;
+   assign if_rif_addr    = pending ? acc_addr : wb_adr_i;
+   assign if_rif_wr_data = pending ? acc_data : wb_dat_i;
+
+   assign wb_stall_o = pending;
+   assign done_cur   = if_rif_done & pending;
+   assign err        = if_rif_err_addr | if_rif_err_access;
+
+   always @(posedge clk or negedge rst_n) begin : proc_wishbone
+      if(!rst_n) begin
+         pending   <= 1'b0;
+         acc_rd    <= 1'b0;
+         acc_addr  <= {W_ADDR{1'b0}};
+         acc_data  <= {W_DATA{1'b0}};
+         partial_l <= 1'b0;
+      end else begin
+         if(if_rif_done) begin
+            pending  <= 1'b0;
+         end else if(req & ~partial) begin
+            pending  <= 1'b1;
+            acc_rd   <= ~wb_we_i;
+            acc_addr <= wb_adr_i;
+            acc_data <= wb_dat_i;
+         end
+         partial_l <= req & partial;
+      end
+   end
+
+   // Response is dropped when the cycle is aborted
+   assign wb_ack_o = wb_cyc_i & done_cur & ~err;
+   assign wb_err_o = wb_cyc_i & ((done_cur & err) | partial_l);
+   assign wb_dat_o = if_rif_rd_data;
+
+endmodule
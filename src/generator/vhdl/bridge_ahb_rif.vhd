@@ -0,0 +1,148 @@
+-- File generated automatically: DO NOT EDIT.
+-- Bridge from AMBA AHB-Lite to the internal register interface:
+--  - a read starts during the address phase when no access is pending,
+--    giving no wait state for internal registers. Otherwise it starts in the
+--    data phase, like a write which needs hwdata
+--  - hreadyout stays low until the access is done, adding wait states for
+--    external registers. The access is held on the register interface with
+--    the address of the data phase until done
+--  - address or access error is answered with the two cycles ERROR response
+--  - write smaller than the data width is not supported: it is answered with
+--    an ERROR response without accessing the registers
+-- Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+library ieee;
+use ieee.std_logic_1164.all;
+use ieee.numeric_std.all;
+
+entity bridge_ahb_rif is
+   generic (
+      W_ADDR : natural := 16;
+      W_DATA : natural := 32
+   );
+   port (
+      clk                    : in  std_logic;                           -- Software clock
+      rst_n                  : in  std_logic;                           -- Software reset
+      hsel                   : in  std_logic;                           -- AHB Select
+      haddr                  : in  std_logic_vector(W_ADDR-1 downto 0); -- AHB Address
+      htrans                 : in  std_logic_vector(1 downto 0);        -- AHB Transfer type
+      hwrite                 : in  std_logic;                           -- AHB Write
+      hsize                  : in  std_logic_vector(2 downto 0);        -- AHB Transfer size
+      hwdata                 : in  std_logic_vector(W_DATA-1 downto 0); -- AHB Write Data
+      hready                 : in  std_logic;                           -- AHB Ready from the bus
+      hrdata                 : out std_logic_vector(W_DATA-1 downto 0); -- AHB Read Data
+      hreadyout              : out std_logic;                           -- AHB Ready
+      hresp                  : out std_logic;                           -- AHB Error Response
+      if_rif_en              : out std_logic;                           -- Access enable, held until done_next
+      if_rif_rd_wrn          : out std_logic;                           -- Read (high) / Write (low)
+      if_rif_addr            : out std_logic_vector(W_ADDR-1 downto 0); -- Address
+      if_rif_wr_data         : out std_logic_vector(W_DATA-1 downto 0); -- Write data
+      if_rif_rd_data         : in  std_logic_vector(W_DATA-1 downto 0); -- Read data
+      if_rif_done            : in  std_logic;                           -- Access done
+      if_rif_err_addr        : in  std_logic;                           -- Address error
+      if_rif_err_access      : in  std_logic;                           -- Access error
+      if_rif_done_next       : in  std_logic;                           -- Access done (combinatorial)
+      if_rif_err_addr_next   : in  std_logic;                           -- Address error (combinatorial)
+      if_rif_err_access_next : in  std_logic                            -- Access error (combinatorial)
+   );
+end entity bridge_ahb_rif;
+
+architecture rtl of bridge_ahb_rif is
+
+   -- Transfer size (log2 of the number of bytes) using the full data width
+   function full_size return std_logic_vector is
+   begin
+      for i in 0 to 6 loop
+         if 2**(i+3) >= W_DATA then
+            return std_logic_vector(to_unsigned(i, 3));
+         end if;
+      end loop;
+      return "111";
+   end function full_size;
+
+   constant SIZE_FULL : std_logic_vector(2 downto 0) := full_size;
+
+   signal dp_valid   : std_logic;                           -- Transfer in data phase
+   signal dp_write   : std_logic;                           -- Transfer in data phase is a write
+   signal dp_addr    : std_logic_vector(W_ADDR-1 downto 0); -- Address of the transfer in data phase
+   signal dp_err     : std_logic;                           -- Transfer in data phase is not supported
+   signal dp_started : std_logic;                           -- Access of the transfer in data phase has started
+   signal pending    : std_logic;                           -- Access started, waiting for done
+   signal rd_early   : std_logic;                           -- Start a read in the address phase
+   signal late_start : std_logic;                           -- Start an access in the data phase
+   signal en         : std_logic;                           -- Register access enable
+   signal done_cur   : std_logic;                           -- Access of the transfer in data phase is done
+   signal done_next  : std_logic;                           -- Access started in the address phase is already done
+   signal done_l     : std_logic;                           -- Access done before the end of the data phase
+   signal err_l      : std_logic;                           -- Error of an access done before the end of the data phase
+   signal rd_data_l  : std_logic_vector(W_DATA-1 downto 0); -- Read data of an access done before the end of the data phase
+   signal err_2nd    : std_logic;                           -- Second cycle of the error response
+   signal resp_done  : std_logic;                           -- Response of the data phase available
+   signal resp_err   : std_logic;                           -- Response of the data phase is an error
+
+begin
+
+   -- Start the register access
+   late_start <= dp_valid and not dp_started and not dp_err and not pending;
+   rd_early   <= hsel and htrans(1) and hready and not hwrite and not pending and not late_start;
+
+   en             <= rd_early or late_start or (pending and not if_rif_done);
+   if_rif_en      <= en;
+   if_rif_rd_wrn  <= rd_early or not dp_write;
+   if_rif_addr    <= haddr when rd_early else dp_addr;
+   if_rif_wr_data <= hwdata;
+
+   -- Access done: an access started in the address phase belongs to the next data phase
+   done_cur  <= if_rif_done and (pending or late_start);
+   done_next <= if_rif_done and rd_early;
+
+   -- Data phase response
+   resp_done <= done_l or done_cur or dp_err;
+   resp_err  <= dp_err or err_l when done_l else dp_err or if_rif_err_addr or if_rif_err_access;
+   hresp     <= dp_valid and resp_done and resp_err;
+   hreadyout <= not dp_valid or (resp_done and (not resp_err or err_2nd));
+   hrdata    <= rd_data_l when done_l else if_rif_rd_data;
+
+   proc_ahb : process(clk, rst_n)
+   begin
+      if rst_n = '0' then
+         dp_valid   <= '0';
+         dp_write   <= '0';
+         dp_addr    <= (others => '0');
+         dp_err     <= '0';
+         dp_started <= '0';
+         pending    <= '0';
+         done_l     <= '0';
+         err_l      <= '0';
+         rd_data_l  <= (others => '0');
+         err_2nd    <= '0';
+      elsif rising_edge(clk) then
+         -- Address phase
+         if hready then
+            dp_valid   <= hsel and htrans(1);
+            dp_write   <= hwrite;
+            dp_addr    <= haddr;
+            dp_err     <= hwrite and (hsize ?/= SIZE_FULL);
+            dp_started <= rd_early;
+         elsif late_start then
+            dp_started <= '1';
+         end if;
+         -- Register access
+         if en and not if_rif_done then
+            pending <= '1';
+         elsif if_rif_done then
+            pending <= '0';
+         end if;
+         -- Keep the access result until the end of the data phase
+         if done_next or (done_cur and not hreadyout) then
+            done_l    <= '1';
+            err_l     <= if_rif_err_addr or if_rif_err_access;
+            rd_data_l <= if_rif_rd_data;
+         elsif dp_valid and hreadyout then
+            done_l    <= '0';
+         end if;
+         err_2nd <= hresp and not hreadyout;
+      end if;
+   end process proc_ahb;
+
+end architecture rtl;
@@ -0,0 +1,56 @@
+-- File generated automatically: DO NOT EDIT.
+-- Bridge from AMBA APB to the internal register interface:
+--  - the register access is started during the setup phase (psel and not penable)
+--    and held until pready: APB keeps address, direction and data stable
+--  - pready is high once the access is done: no wait state for internal
+--    registers, and as many as needed for external registers
+--  - pslverr is high on address or access error
+-- Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity bridge_apb_rif is
+   generic (
+      W_ADDR : natural := 16;
+      W_DATA : natural := 32
+   );
+   port (
+      clk                    : in  std_logic;                           -- Software clock
+      rst_n                  : in  std_logic;                           -- Software reset
+      paddr                  : in  std_logic_vector(W_ADDR-1 downto 0); -- APB Address
+      psel                   : in  std_logic;                           -- APB Select
+      penable                : in  std_logic;                           -- APB Enable
+      pwrite                 : in  std_logic;                           -- APB Write
+      pwdata                 : in  std_logic_vector(W_DATA-1 downto 0); -- APB Write Data
+      prdata                 : out std_logic_vector(W_DATA-1 downto 0); -- APB Read Data
+      pready                 : out std_logic;                           -- APB Ready
+      pslverr                : out std_logic;                           -- APB Slave Error
+      if_rif_en              : out std_logic;                           -- Access enable, held until done_next
+      if_rif_rd_wrn          : out std_logic;                           -- Read (high) / Write (low)
+      if_rif_addr            : out std_logic_vector(W_ADDR-1 downto 0); -- Address
+      if_rif_wr_data         : out std_logic_vector(W_DATA-1 downto 0); -- Write data
+      if_rif_rd_data         : in  std_logic_vector(W_DATA-1 downto 0); -- Read data
+      if_rif_done            : in  std_logic;                           -- Access done
+      if_rif_err_addr        : in  std_logic;                           -- Address error
+      if_rif_err_access      : in  std_logic;                           -- Access error
+      if_rif_done_next       : in  std_logic;                           -- Access done (combinatorial)
+      if_rif_err_addr_next   : in  std_logic;                           -- Address error (combinatorial)
+      if_rif_err_access_next : in  std_logic                            -- Access error (combinatorial)
+   );
+end entity bridge_apb_rif;
+
+architecture rtl of bridge_apb_rif is
+
+begin
+
+   if_rif_en      <= psel and not (penable and if_rif_done);
+   if_rif_rd_wrn  <= not pwrite;
+   if_rif_addr    <= paddr;
+   if_rif_wr_data <= pwdata;
+
+   pready  <= if_rif_done;
+   prdata  <= if_rif_rd_data;
+   pslverr <= if_rif_err_addr or if_rif_err_access;
+
+end architecture rtl;
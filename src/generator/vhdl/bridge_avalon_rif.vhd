@@ -0,0 +1,107 @@
+-- File generated automatically: DO NOT EDIT.
+-- Bridge from Intel Avalon-MM to the internal register interface:
+--  - avs_address is a byte address (addressUnits SYMBOLS)
+--  - a command is accepted when avs_read or avs_write is high with avs_waitrequest low
+--  - only one register access is done at a time: avs_waitrequest is high until
+--    the access is done, which covers the latency of external registers
+--  - address, direction and write data are latched when the command is
+--    accepted and held on the register interface until done
+--  - read data is returned with avs_readdatavalid, write completion with
+--    avs_writeresponsevalid, and avs_response is SLVERR on access error and
+--    DECODEERROR on address error
+--  - write with partial byte enable is not supported: it is answered with
+--    SLVERR without accessing the registers
+-- Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity bridge_avalon_rif is
+   generic (
+      W_ADDR : natural := 16;
+      W_DATA : natural := 32
+   );
+   port (
+      clk                    : in  std_logic;                             -- Software clock
+      rst_n                  : in  std_logic;                             -- Software reset
+      avs_address            : in  std_logic_vector(W_ADDR-1 downto 0);   -- Avalon Address
+      avs_read               : in  std_logic;                             -- Avalon Read
+      avs_write              : in  std_logic;                             -- Avalon Write
+      avs_writedata          : in  std_logic_vector(W_DATA-1 downto 0);   -- Avalon Write Data
+      avs_byteenable         : in  std_logic_vector(W_DATA/8-1 downto 0); -- Avalon Byte Enable
+      avs_readdata           : out std_logic_vector(W_DATA-1 downto 0);   -- Avalon Read Data
+      avs_waitrequest        : out std_logic;                             -- Avalon Wait Request
+      avs_readdatavalid      : out std_logic;                             -- Avalon Read Data Valid
+      avs_writeresponsevalid : out std_logic;                             -- Avalon Write Response Valid
+      avs_response           : out std_logic_vector(1 downto 0);          -- Avalon Response
+      if_rif_en              : out std_logic;                             -- Access enable, held until done_next
+      if_rif_rd_wrn          : out std_logic;                             -- Read (high) / Write (low)
+      if_rif_addr            : out std_logic_vector(W_ADDR-1 downto 0);   -- Address
+      if_rif_wr_data         : out std_logic_vector(W_DATA-1 downto 0);   -- Write data
+      if_rif_rd_data         : in  std_logic_vector(W_DATA-1 downto 0);   -- Read data
+      if_rif_done            : in  std_logic;                             -- Access done
+      if_rif_err_addr        : in  std_logic;                             -- Address error
+      if_rif_err_access      : in  std_logic;                             -- Access error
+      if_rif_done_next       : in  std_logic;                             -- Access done (combinatorial)
+      if_rif_err_addr_next   : in  std_logic;                             -- Address error (combinatorial)
+      if_rif_err_access_next : in  std_logic                              -- Access error (combinatorial)
+   );
+end entity bridge_avalon_rif;
+
+architecture rtl of bridge_avalon_rif is
+
+   constant OKAY        : std_logic_vector(1 downto 0) := "00";
+   constant SLVERR      : std_logic_vector(1 downto 0) := "10";
+   constant DECODEERROR : std_logic_vector(1 downto 0) := "11";
+
+   signal req        : std_logic;                           -- Command accepted
+   signal partial    : std_logic;                           -- Write with partial byte enable
+   signal pending    : std_logic;                           -- Access started, waiting for done
+   signal pending_rd : std_logic;                           -- Pending access is a read
+   signal acc_addr   : std_logic_vector(W_ADDR-1 downto 0); -- Address of the pending access
+   signal acc_data   : std_logic_vector(W_DATA-1 downto 0); -- Write data of the pending access
+   signal done_cur   : std_logic;                           -- Pending access is done
+   signal partial_l  : std_logic;                           -- Partial write rejected, answered on the next cycle
+   signal resp       : std_logic_vector(1 downto 0);        -- Response of the access
+
+begin
+
+   req     <= (avs_read or avs_write) and not pending;
+   partial <= avs_write and not (and avs_byteenable);
+
+   if_rif_en      <= (req and not partial) or (pending and not if_rif_done);
+   if_rif_rd_wrn  <= pending_rd when pending else avs_read;
+   if_rif_addr    <= acc_addr   when pending else avs_address;
+   if_rif_wr_data <= acc_data   when pending else avs_writedata;
+
+   avs_waitrequest <= pending;
+   done_cur        <= if_rif_done and pending;
+   resp            <= DECODEERROR when if_rif_err_addr else SLVERR when if_rif_err_access else OKAY;
+
+   proc_avalon : process(clk, rst_n)
+   begin
+      if rst_n = '0' then
+         pending    <= '0';
+         pending_rd <= '0';
+         acc_addr   <= (others => '0');
+         acc_data   <= (others => '0');
+         partial_l  <= '0';
+      elsif rising_edge(clk) then
+         if if_rif_done then
+            pending    <= '0';
+         elsif req and not partial then
+            pending    <= '1';
+            pending_rd <= avs_read;
+            acc_addr   <= avs_address;
+            acc_data   <= avs_writedata;
+         end if;
+         partial_l <= req and partial;
+      end if;
+   end process proc_avalon;
+
+   avs_readdatavalid      <= done_cur and pending_rd;
+   avs_writeresponsevalid <= (done_cur and not pending_rd) or partial_l;
+   avs_response           <= SLVERR when partial_l else resp;
+   avs_readdata           <= if_rif_rd_data;
+
+end architecture rtl;
@@ -0,0 +1,174 @@
+-- File generated automatically: DO NOT EDIT.
+-- Bridge from AMBA AXI4-Lite to the internal register interface:
+--  - write address, write data and read address are each stored in a one
+--    entry buffer, so all channels are independent
+--  - only one register access is done at a time: when both a read and a write
+--    are ready, priority alternates between them. The access is held from the
+--    buffers on the register interface until done
+--  - a new access only starts once the response of the previous access of the
+--    same kind has been accepted (bready/rready backpressure)
+--  - address error is answered with DECERR and access error with SLVERR
+--  - write with partial strobe is not supported: it is answered with SLVERR
+--    without accessing the registers
+-- Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity bridge_axi4lite_rif is
+   generic (
+      W_ADDR : natural := 16;
+      W_DATA : natural := 32
+   );
+   port (
+      clk                    : in  std_logic;                             -- Software clock
+      rst_n                  : in  std_logic;                             -- Software reset
+      awaddr                 : in  std_logic_vector(W_ADDR-1 downto 0);   -- AXI Write Address
+      awprot                 : in  std_logic_vector(2 downto 0);          -- AXI Write Protection (unused)
+      awvalid                : in  std_logic;                             -- AXI Write Address Valid
+      awready                : out std_logic;                             -- AXI Write Address Ready
+      wdata                  : in  std_logic_vector(W_DATA-1 downto 0);   -- AXI Write Data
+      wstrb                  : in  std_logic_vector(W_DATA/8-1 downto 0); -- AXI Write Strobe
+      wvalid                 : in  std_logic;                             -- AXI Write Data Valid
+      wready                 : out std_logic;                             -- AXI Write Data Ready
+      bresp                  : out std_logic_vector(1 downto 0);          -- AXI Write Response
+      bvalid                 : out std_logic;                             -- AXI Write Response Valid
+      bready                 : in  std_logic;                             -- AXI Write Response Ready
+      araddr                 : in  std_logic_vector(W_ADDR-1 downto 0);   -- AXI Read Address
+      arprot                 : in  std_logic_vector(2 downto 0);          -- AXI Read Protection (unused)
+      arvalid                : in  std_logic;                             -- AXI Read Address Valid
+      arready                : out std_logic;                             -- AXI Read Address Ready
+      rdata                  : out std_logic_vector(W_DATA-1 downto 0);   -- AXI Read Data
+      rresp                  : out std_logic_vector(1 downto 0);          -- AXI Read Response
+      rvalid                 : out std_logic;                             -- AXI Read Data Valid
+      rready                 : in  std_logic;                             -- AXI Read Data Ready
+      if_rif_en              : out std_logic;                             -- Access enable, held until done_next
+      if_rif_rd_wrn          : out std_logic;                             -- Read (high) / Write (low)
+      if_rif_addr            : out std_logic_vector(W_ADDR-1 downto 0);   -- Address
+      if_rif_wr_data         : out std_logic_vector(W_DATA-1 downto 0);   -- Write data
+      if_rif_rd_data         : in  std_logic_vector(W_DATA-1 downto 0);   -- Read data
+      if_rif_done            : in  std_logic;                             -- Access done
+      if_rif_err_addr        : in  std_logic;                             -- Address error
+      if_rif_err_access      : in  std_logic;                             -- Access error
+      if_rif_done_next       : in  std_logic;                             -- Access done (combinatorial)
+      if_rif_err_addr_next   : in  std_logic;                             -- Address error (combinatorial)
+      if_rif_err_access_next : in  std_logic                              -- Access error (combinatorial)
+   );
+end entity bridge_axi4lite_rif;
+
+architecture rtl of bridge_axi4lite_rif is
+
+   constant OKAY   : std_logic_vector(1 downto 0) := "00";
+   constant SLVERR : std_logic_vector(1 downto 0) := "10";
+   constant DECERR : std_logic_vector(1 downto 0) := "11";
+
+   signal aw_full    : std_logic;                           -- Write address buffer is full
+   signal aw_addr    : std_logic_vector(W_ADDR-1 downto 0); -- Write address buffer
+   signal w_full     : std_logic;                           -- Write data buffer is full
+   signal w_data     : std_logic_vector(W_DATA-1 downto 0); -- Write data buffer
+   signal w_partial  : std_logic;                           -- Write strobe does not cover the full data
+   signal ar_full    : std_logic;                           -- Read address buffer is full
+   signal ar_addr    : std_logic_vector(W_ADDR-1 downto 0); -- Read address buffer
+   signal pending    : std_logic;                           -- Access started, waiting for done
+   signal pending_rd : std_logic;                           -- Pending access is a read
+   signal last_rd    : std_logic;                           -- Last access was a read: write has priority
+   signal wr_ok      : std_logic;                           -- Write can start
+   signal rd_ok      : std_logic;                           -- Read can start
+   signal wr_start   : std_logic;                           -- Start a write
+   signal rd_start   : std_logic;                           -- Start a read
+   signal done       : std_logic;                           -- Access done
+   signal acc_rd     : std_logic;                           -- Current access is a read
+   signal resp       : std_logic_vector(1 downto 0);        -- Response of the access
+
+begin
+
+   awready <= not aw_full;
+   wready  <= not w_full;
+   arready <= not ar_full;
+
+   -- Arbitration between read and write
+   wr_ok    <= aw_full and w_full and not bvalid;
+   rd_ok    <= ar_full and not rvalid;
+   rd_start <= not pending and rd_ok and not (wr_ok and last_rd);
+   wr_start <= not pending and wr_ok and not rd_start;
+
+   done    <= if_rif_done and pending;
+   acc_rd  <= pending_rd when pending else rd_start;
+   resp    <= DECERR when if_rif_err_addr else SLVERR when if_rif_err_access else OKAY;
+
+   -- Partial write is answered directly without accessing the registers
+   if_rif_en      <= rd_start or (wr_start and not w_partial) or (pending and not if_rif_done);
+   if_rif_rd_wrn  <= acc_rd;
+   if_rif_addr    <= ar_addr when acc_rd else aw_addr;
+   if_rif_wr_data <= w_data;
+
+   proc_axi : process(clk, rst_n)
+   begin
+      if rst_n = '0' then
+         aw_full    <= '0';
+         aw_addr    <= (others => '0');
+         w_full     <= '0';
+         w_data     <= (others => '0');
+         w_partial  <= '0';
+         ar_full    <= '0';
+         ar_addr    <= (others => '0');
+         pending    <= '0';
+         pending_rd <= '0';
+         last_rd    <= '0';
+         bvalid     <= '0';
+         bresp      <= OKAY;
+         rvalid     <= '0';
+         rresp      <= OKAY;
+         rdata      <= (others => '0');
+      elsif rising_edge(clk) then
+         -- Request channels
+         if awvalid and not aw_full then
+            aw_full <= '1';
+            aw_addr <= awaddr;
+         end if;
+         if wvalid and not w_full then
+            w_full    <= '1';
+            w_data    <= wdata;
+            w_partial <= not (and wstrb);
+         end if;
+         if arvalid and not ar_full then
+            ar_full <= '1';
+            ar_addr <= araddr;
+         end if;
+         -- Register access
+         if if_rif_done then
+            pending    <= '0';
+         elsif rd_start or (wr_start and not w_partial) then
+            pending    <= '1';
+            pending_rd <= rd_start;
+         end if;
+         if rd_start or wr_start then
+            last_rd <= rd_start;
+         end if;
+         -- Read response channel
+         if done and acc_rd then
+            ar_full <= '0';
+            rvalid  <= '1';
+            rresp   <= resp;
+            rdata   <= if_rif_rd_data;
+         elsif rready then
+            rvalid  <= '0';
+         end if;
+         -- Write response channel
+         if done and not acc_rd then
+            aw_full <= '0';
+            w_full  <= '0';
+            bvalid  <= '1';
+            bresp   <= resp;
+         elsif wr_start and w_partial then
+            aw_full <= '0';
+            w_full  <= '0';
+            bvalid  <= '1';
+            bresp   <= SLVERR;
+         elsif bready then
+            bvalid  <= '0';
+         end if;
+      end if;
+   end process proc_axi;
+
+end architecture rtl;
@@ -0,0 +1,128 @@
+-- File generated automatically: DO NOT EDIT.
+-- Bridge from the ARC auxiliary register interface (UAUX) to the internal
+-- register interface:
+--  - a read is started in the prepare stage (uaux_en and uaux_read) and stalls
+--    the core with uaux_busy until the access is done
+--  - a write is started when the instruction commits (uaux_cmt_phase and uaux_cmt_valid)
+--    using the address captured in the prepare stage. Any following auxiliary
+--    access is stalled until the write is done. A write committed during a
+--    pending read waits for the end of the read
+--  - the access is held on the register interface until done, with the
+--    address captured when it started
+--  - on read, address error is reported as unimplemented and access error as
+--    read privilege violation. Write errors are not reported.
+-- Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity bridge_uaux_rif is
+   generic (
+      W_ADDR : natural := 16;
+      W_DATA : natural := 32
+   );
+   port (
+      clk                    : in  std_logic;                           -- Software clock
+      rst_n                  : in  std_logic;                           -- Software reset
+      uaux_addr              : in  std_logic_vector(W_ADDR-1 downto 0); -- AUX Address
+      uaux_en                : in  std_logic;                           -- AUX Enable
+      uaux_cmt_phase         : in  std_logic;                           -- AUX Commit status
+      uaux_cmt_valid         : in  std_logic;                           -- AUX Commit Valid
+      uaux_read              : in  std_logic;                           -- AUX Read
+      uaux_write             : in  std_logic;                           -- AUX Write
+      uaux_wdata             : in  std_logic_vector(W_DATA-1 downto 0); -- AUX Write Data
+      uaux_rdata             : out std_logic_vector(W_DATA-1 downto 0); -- AUX Read Data
+      uaux_busy              : out std_logic;                           -- AUX Busy
+      uaux_illegal           : out std_logic;                           -- SR/LR illegal
+      uaux_k_rd              : out std_logic;                           -- AUX read privilege violation
+      uaux_k_wr              : out std_logic;                           -- AUX write privilege violation
+      uaux_unimpl            : out std_logic;                           -- AUX unimplemented address
+      uaux_serial_sr         : out std_logic;                           -- AUX SR group flush
+      uaux_strict_sr         : out std_logic;                           -- AUX SR single flush
+      if_rif_en              : out std_logic;                           -- Access enable, held until done_next
+      if_rif_rd_wrn          : out std_logic;                           -- Read (high) / Write (low)
+      if_rif_addr            : out std_logic_vector(W_ADDR-1 downto 0); -- Address
+      if_rif_wr_data         : out std_logic_vector(W_DATA-1 downto 0); -- Write data
+      if_rif_rd_data         : in  std_logic_vector(W_DATA-1 downto 0); -- Read data
+      if_rif_done            : in  std_logic;                           -- Access done
+      if_rif_err_addr        : in  std_logic;                           -- Address error
+      if_rif_err_access      : in  std_logic;                           -- Access error
+      if_rif_done_next       : in  std_logic;                           -- Access done (combinatorial)
+      if_rif_err_addr_next   : in  std_logic;                           -- Address error (combinatorial)
+      if_rif_err_access_next : in  std_logic                            -- Access error (combinatorial)
+   );
+end entity bridge_uaux_rif;
+
+architecture rtl of bridge_uaux_rif is
+
+   signal wr_addr    : std_logic_vector(W_ADDR-1 downto 0); -- Address of the write captured in the prepare stage
+   signal wr_data    : std_logic_vector(W_DATA-1 downto 0); -- Data of the write captured when the instruction commits
+   signal acc_addr   : std_logic_vector(W_ADDR-1 downto 0); -- Address of the pending access
+   signal addr       : std_logic_vector(W_ADDR-1 downto 0); -- Address of the access
+   signal wr_cmt     : std_logic;                           -- Write instruction commits
+   signal wr_wait    : std_logic;                           -- Write committed during a pending read
+   signal rd_req     : std_logic;                           -- Start a read access
+   signal wr_req     : std_logic;                           -- Start a write access
+   signal rd_pending : std_logic;                           -- Read started, waiting for done
+   signal wr_pending : std_logic;                           -- Write started, waiting for done
+   signal rd_done    : std_logic;                           -- Read access done
+
+begin
+
+   -- The write of an older instruction has priority over a new read
+   wr_cmt  <= uaux_cmt_valid and uaux_cmt_phase and uaux_write;
+   wr_req  <= (wr_cmt or wr_wait) and not rd_pending and not wr_pending;
+   rd_req  <= uaux_en and uaux_read and not rd_pending and not wr_pending and not wr_req;
+   rd_done <= if_rif_done and rd_pending;
+
+   -- Access is held until the cycle before done
+   addr <= acc_addr when (rd_pending or wr_pending) else wr_addr when wr_req else uaux_addr;
+
+   if_rif_en      <= rd_req or wr_req or ((rd_pending or wr_pending) and not if_rif_done);
+   if_rif_rd_wrn  <= rd_req or rd_pending;
+   if_rif_addr    <= addr;
+   if_rif_wr_data <= wr_data when (wr_wait or wr_pending) else uaux_wdata;
+
+   proc_pending : process(clk, rst_n)
+   begin
+      if rst_n = '0' then
+         wr_addr    <= (others => '0');
+         wr_data    <= (others => '0');
+         acc_addr   <= (others => '0');
+         wr_wait    <= '0';
+         rd_pending <= '0';
+         wr_pending <= '0';
+      elsif rising_edge(clk) then
+         if uaux_en and uaux_write then
+            wr_addr <= uaux_addr;
+         end if;
+         if wr_cmt then
+            wr_data <= uaux_wdata;
+         end if;
+         if rd_req or wr_req then
+            acc_addr <= addr;
+         end if;
+         wr_wait <= (wr_cmt or wr_wait) and not wr_req;
+         if if_rif_done then
+            rd_pending <= '0';
+         elsif rd_req then
+            rd_pending <= '1';
+         end if;
+         if if_rif_done then
+            wr_pending <= '0';
+         elsif wr_req then
+            wr_pending <= '1';
+         end if;
+      end if;
+   end process proc_pending;
+
+   uaux_busy      <= uaux_en and ((uaux_read and not rd_done) or wr_pending or wr_wait);
+   uaux_rdata     <= if_rif_rd_data;
+   uaux_unimpl    <= rd_done and if_rif_err_addr;
+   uaux_k_rd      <= rd_done and if_rif_err_access;
+   uaux_k_wr      <= '0';
+   uaux_illegal   <= '0';
+   uaux_serial_sr <= '0';
+   uaux_strict_sr <= '0';
+
+end architecture rtl;
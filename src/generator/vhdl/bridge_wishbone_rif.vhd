@@ -0,0 +1,101 @@
+-- File generated automatically: DO NOT EDIT.
+-- Bridge from Wishbone B4 pipelined to the internal register interface:
+--  - a request is accepted when wb_cyc_i & wb_stb_i & ~wb_stall_o
+--  - only one register access is done at a time: wb_stall_o is high until the
+--    access is done, which covers the latency of external registers
+--  - address, direction and write data are latched when the request is
+--    accepted and held on the register interface until done
+--  - wb_ack_o or wb_err_o is high during one cycle per accepted request,
+--    wb_err_o on address or access error
+--  - write with partial byte select is not supported: it is answered with
+--    wb_err_o without accessing the registers
+-- Clock and reset (asynchronous, active low) are the software clock and reset of the RIF.
+
+library ieee;
+use ieee.std_logic_1164.all;
+
+entity bridge_wishbone_rif is
+   generic (
+      W_ADDR : natural := 16;
+      W_DATA : natural := 32
+   );
+   port (
+      clk                    : in  std_logic;                             -- Software clock
+      rst_n                  : in  std_logic;                             -- Software reset
+      wb_adr_i               : in  std_logic_vector(W_ADDR-1 downto 0);   -- Wishbone Address
+      wb_dat_i               : in  std_logic_vector(W_DATA-1 downto 0);   -- Wishbone Write Data
+      wb_dat_o               : out std_logic_vector(W_DATA-1 downto 0);   -- Wishbone Read Data
+      wb_sel_i               : in  std_logic_vector(W_DATA/8-1 downto 0); -- Wishbone Byte Select
+      wb_we_i                : in  std_logic;                             -- Wishbone Write Enable
+      wb_cyc_i               : in  std_logic;                             -- Wishbone Cycle
+      wb_stb_i               : in  std_logic;                             -- Wishbone Strobe
+      wb_ack_o               : out std_logic;                             -- Wishbone Acknowledge
+      wb_err_o               : out std_logic;                             -- Wishbone Error
+      wb_stall_o             : out std_logic;                             -- Wishbone Stall
+      if_rif_en              : out std_logic;                             -- Access enable, held until done_next
+      if_rif_rd_wrn          : out std_logic;                             -- Read (high) / Write (low)
+      if_rif_addr            : out std_logic_vector(W_ADDR-1 downto 0);   -- Address
+      if_rif_wr_data         : out std_logic_vector(W_DATA-1 downto 0);   -- Write data
+      if_rif_rd_data         : in  std_logic_vector(W_DATA-1 downto 0);   -- Read data
+      if_rif_done            : in  std_logic;                             -- Access done
+      if_rif_err_addr        : in  std_logic;                             -- Address error
+      if_rif_err_access      : in  std_logic;                             -- Access error
+      if_rif_done_next       : in  std_logic;                             -- Access done (combinatorial)
+      if_rif_err_addr_next   : in  std_logic;                             -- Address error (combinatorial)
+      if_rif_err_access_next : in  std_logic                              -- Access error (combinatorial)
+   );
+end entity bridge_wishbone_rif;
+
+architecture rtl of bridge_wishbone_rif is
+
+   signal req       : std_logic;                           -- Request accepted
+   signal partial   : std_logic;                           -- Write with partial byte select
+   signal pending   : std_logic;                           -- Access started, waiting for done
+   signal acc_rd    : std_logic;                           -- Pending access is a read
+   signal acc_addr  : std_logic_vector(W_ADDR-1 downto 0); -- Address of the pending access
+   signal acc_data  : std_logic_vector(W_DATA-1 downto 0); -- Write data of the pending access
+   signal done_cur  : std_logic;                           -- Pending access is done
+   signal partial_l : std_logic;                           -- Partial write rejected, answered on the next cycle
+   signal err       : std_logic;                           -- Error of the pending access
+
+begin
+
+   req     <= wb_cyc_i and wb_stb_i and not pending;
+   partial <= wb_we_i and not (and wb_sel_i);
+
+   if_rif_en      <= (req and not partial) or (pending and not if_rif_done);
+   if_rif_rd_wrn  <= acc_rd   when pending else not wb_we_i;
+   if_rif_addr    <= acc_addr when pending else wb_adr_i;
+   if_rif_wr_data <= acc_data when pending else wb_dat_i;
+
+   wb_stall_o <= pending;
+   done_cur   <= if_rif_done and pending;
+   err        <= if_rif_err_addr or if_rif_err_access;
+
+   proc_wishbone : process(clk, rst_n)
+   begin
+      if rst_n = '0' then
+         pending   <= '0';
+         acc_rd    <= '0';
+         acc_addr  <= (others => '0');
+         acc_data  <= (others => '0');
+         partial_l <= '0';
+      elsif rising_edge(clk) then
+         if if_rif_done then
+            pending  <= '0';
+         elsif req and not partial then
+            pending  <= '1';
+            acc_rd   <= not wb_we_i;
+            acc_addr <= wb_adr_i;
+            acc_data <= wb_dat_i;
+         end if;
+         partial_l <= req and partial;
+      end if;
+   end process proc_wishbone;
+
+   -- Response is dropped when the cycle is aborted
+   wb_ack_o <= wb_cyc_i and done_cur and not err;
+   wb_err_o <= wb_cyc_i and ((done_cur and err) or partial_l);
+   wb_dat_o <= if_rif_rd_data;
+
+end architecture rtl;
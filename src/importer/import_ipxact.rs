@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use crate::rifgen::{
+    Access, AddressKind, Field, FieldSwKind, Limit, LimitValue, RegDef, RegDefOrIncl, RegInst,
+    ResetVal, Rif, RifPage, Visibility,
+};
+use crate::parser::parser_expr::ExprTokens;
+
+use super::{
+    add_enum, addr_width, child_int, clean_desc, field_range, field_sw_kind, mask, msb_lsb, node_desc,
+    parse_int, reg_size, to_ident, to_ident_case, xml::XmlNode, ImportResult,
+};
+
+/// Convert an IP-XACT component (1685-2009 or 1685-2014) into a RIF:
+/// each address block of the first memory map becomes a page
+pub fn import(comp: &XmlNode) -> Result<ImportResult, String> {
+    let name = to_ident(comp.child_text("name").ok_or("Component without name")?);
+    let mut rif = Rif::new(name);
+    rif.description = clean_desc(comp.child_text("description").unwrap_or_default()).into();
+    for key in ["vendor", "library", "version"] {
+        if let Some(v) = comp.child_text(key) {
+            rif.add_info((key, v));
+        }
+    }
+    let map = comp.child("memoryMaps")
+        .and_then(|m| m.child("memoryMap"))
+        .ok_or("Component without memory map")?;
+    let blocks : Vec<&XmlNode> = map.children("addressBlock").collect();
+    let get_int = |n: &XmlNode, k: &str| child_int(n, k).map(|v| v.unwrap_or(0) as u64);
+    // Page address are relative to the first block
+    let mut base = u64::MAX;
+    for block in blocks.iter() {
+        base = base.min(get_int(block, "baseAddress")?);
+    }
+    let mut end = 0;
+    let mut ctxt = BlockCtxt {defs: HashSet::new(), insts: HashSet::new(), data_width: 8};
+    for block in blocks {
+        let block_name = block.child_text("name").unwrap_or("main");
+        let mut page = RifPage::new(to_ident_case(block_name));
+        page.description = clean_desc(block.child_text("description").unwrap_or_default()).into();
+        page.addr = get_int(block, "baseAddress")? - base;
+        let range = get_int(block, "range")?;
+        end = end.max(page.addr + range);
+        if let Some(width) = child_int(block, "width")? {
+            ctxt.data_width = ctxt.data_width.max(reg_size(width, &node_desc(block))?);
+        }
+        // Memory are handled externally
+        if block.child_text("usage") == Some("memory") {
+            page.external = true;
+            page.addr_width = addr_width(range);
+        } else {
+            let access = block.child_text("access").unwrap_or("read-write");
+            ctxt.add_registers(&mut rif, &mut page, block, &to_ident(block_name), "", 0, access)?;
+        }
+        rif.pages.push(page);
+    }
+    rif.addr_width = addr_width(end);
+    rif.data_width = ctxt.data_width.next_power_of_two();
+    Ok(ImportResult {rifs: vec![rif], rifmux: None})
+}
+
+struct BlockCtxt {
+    /// Register definition/instance names already used in the RIF
+    defs: HashSet<String>,
+    insts: HashSet<String>,
+    data_width: u8,
+}
+
+impl BlockCtxt {
+
+    /// Add all registers of a block or register file with a given name prefix and address offset
+    #[allow(clippy::too_many_arguments)]
+    fn add_registers(&mut self, rif: &mut Rif, page: &mut RifPage, node: &XmlNode, page_name: &str, prefix: &str, offset: u64, access: &str) -> Result<(), String> {
+        for child in node.children.iter() {
+            if child.name != "register" && child.name != "registerFile" {
+                continue;
+            }
+            let name = to_ident(child.child_text("name").ok_or("Register without name")?);
+            let addr = offset + child_int(child, "addressOffset")?.unwrap_or(0) as u64;
+            let access = child.child_text("access").unwrap_or(access);
+            // Arrays are unrolled: dimension can be multiple
+            let dims = child.children("dim")
+                .map(|d| parse_int(&d.text).map(|v| v as u64).ok_or(format!("Invalid dim \"{}\" in register {name}", d.text.trim())))
+                .collect::<Result<Vec<u64>, String>>()?;
+            let nb : u64 = dims.iter().product();
+            if child.name == "registerFile" {
+                let range = child_int(child, "range")?.unwrap_or(0) as u64;
+                for i in 0..nb {
+                    let sub_prefix = if dims.is_empty() {format!("{prefix}{name}_")} else {format!("{prefix}{name}{i}_")};
+                    self.add_registers(rif, page, child, page_name, &sub_prefix, addr + i * range, access)?;
+                }
+                continue;
+            }
+            let size = reg_size(child_int(child, "size")?.unwrap_or(32), &format!("register {name}"))?;
+            self.data_width = self.data_width.max(size);
+            // Register name must be unique in the RIF: prefix with the page name when needed
+            let mut def_name = format!("{prefix}{name}");
+            if self.defs.contains(&def_name) {
+                def_name = format!("{page_name}_{def_name}");
+            }
+            self.defs.insert(def_name.clone());
+            let reg = Self::import_register(rif, child, &def_name, size, access)?;
+            page.registers.push(RegDefOrIncl::Def(Box::new(reg)));
+            for i in 0..nb {
+                let mut inst_name = if dims.is_empty() {def_name.clone()} else {format!("{def_name}{i}")};
+                if self.insts.contains(&inst_name) {
+                    inst_name = format!("{page_name}_{inst_name}");
+                }
+                self.insts.insert(inst_name.clone());
+                let type_name = if inst_name != def_name {Some(def_name.as_str())} else {None};
+                let addr = addr + i * (size as u64 / 8);
+                page.instances.push(RegInst::from((inst_name.as_str(), ExprTokens::new(0), type_name, None, Some((AddressKind::Absolute, addr)))));
+            }
+        }
+        Ok(())
+    }
+
+    fn import_register(rif: &mut Rif, node: &XmlNode, name: &str, size: u8, access: &str) -> Result<RegDef, String> {
+        let desc = clean_desc(node.child_text("description").unwrap_or_default());
+        let mut reg = RegDef::new(name, None, None, &desc);
+        // Register level reset (IP-XACT 2009)
+        let reg_reset = match node.child("reset") {
+            Some(r) => child_int(r, "value")?.unwrap_or(0),
+            None => 0,
+        };
+        let reg_volatile = node.child_text("volatile") == Some("true");
+        let fields : Vec<&XmlNode> = node.children("field").collect();
+        if fields.is_empty() {
+            let kind = field_sw_kind(access, None, None);
+            reg.add_field(Field::new("val", vec![ResetVal::Unsigned(reg_reset)], msb_lsb(size-1, 0), Some(kind), None, desc.as_str()));
+            return Ok(reg);
+        }
+        for f in fields {
+            let fname = to_ident(f.child_text("name").ok_or("Field without name")?);
+            let full_name = format!("{name}.{fname}");
+            let lsb = child_int(f, "bitOffset")?.ok_or(format!("Missing bitOffset for {full_name}"))?;
+            let width = child_int(f, "bitWidth")?.ok_or(format!("Missing bitWidth for {full_name}"))?;
+            let (msb, lsb) = field_range(lsb, width, size, &full_name)?;
+            let reset = match f.child("resets").and_then(|r| r.child("reset")) {
+                Some(r) => child_int(r, "value")?,
+                None => None,
+            };
+            let reset = reset.unwrap_or((reg_reset >> lsb) & mask(msb - lsb + 1));
+            let kind = field_sw_kind(
+                f.child_text("access").unwrap_or(access),
+                f.child_text("modifiedWriteValue"),
+                f.child_text("readAction"),
+            );
+            let fdesc = clean_desc(f.child_text("description").unwrap_or_default());
+            let mut field = Field::new(fname.as_str(), vec![ResetVal::Unsigned(reset)], msb_lsb(msb, lsb), Some(kind), None, fdesc.as_str());
+            // Read-only field never changed by hardware is a constant
+            let volatile = f.child_text("volatile").map(|v| v == "true").unwrap_or(reg_volatile);
+            if field.sw_kind == FieldSwKind::ReadOnly && !volatile {
+                field.hw_acc = Access::NA;
+            }
+            if f.child_text("reserved") == Some("true") {
+                field.visibility = Visibility::Reserved;
+            }
+            if let Some(enums) = f.child("enumeratedValues") {
+                let values = enums.children("enumeratedValue")
+                    .filter_map(|e| Some((
+                        e.child_text("name")?,
+                        e.child_text("value").and_then(parse_int)?,
+                        e.child_text("description").unwrap_or_default(),
+                    )))
+                    .collect();
+                field.enum_kind = add_enum(rif, name, &fname, &fdesc, values)?;
+            }
+            if let Some(c) = f.child("writeValueConstraint") {
+                if c.child_text("useEnumeratedValues") == Some("true") && field.enum_kind.is_type() {
+                    field.limit = Limit {value: LimitValue::Enum, bypass: "".to_owned()};
+                } else if let (Some(min), Some(max)) = (child_int(c, "minimum")?, child_int(c, "maximum")?) {
+                    field.limit = Limit {value: LimitValue::MinMax(ResetVal::Unsigned(min), ResetVal::Unsigned(max)), bypass: "".to_owned()};
+                }
+            }
+            reg.add_field(field);
+        }
+        Ok(reg)
+    }
+}
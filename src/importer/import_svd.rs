@@ -0,0 +1,351 @@
+use std::collections::HashSet;
+
+use crate::rifgen::{
+    AddressKind, AddressOffset, Field, FieldPos, Limit, LimitValue, RegDef, RegDefOrIncl,
+    RegInst, ResetVal, Rif, RifPage, RifType, Rifmux, RifmuxItem, Width,
+};
+use crate::parser::parser_expr::ExprTokens;
+
+use super::{
+    add_enum, addr_width, child_int, clean_desc, field_range, field_sw_kind, mask, msb_lsb, node_desc, parse_int,
+    reg_size, to_ident, xml::XmlNode, ImportResult,
+};
+
+/// Register properties inherited from device to peripheral, cluster and register
+#[derive(Clone, Debug)]
+struct RegProps {
+    size: u8,
+    access: String,
+    reset: u128,
+}
+
+impl RegProps {
+    fn updt(&self, node: &XmlNode) -> Result<RegProps, String> {
+        Ok(RegProps {
+            size: match child_int(node, "size")? {
+                Some(size) => reg_size(size, &node_desc(node))?,
+                None => self.size,
+            },
+            access: node.child_text("access").unwrap_or(&self.access).to_owned(),
+            reset: child_int(node, "resetValue")?.unwrap_or(self.reset),
+        })
+    }
+}
+
+/// Convert a CMSIS-SVD device into a list of RIF (one per peripheral type)
+/// and a rifmux when the device contains more than one peripheral
+pub fn import(device: &XmlNode) -> Result<ImportResult, String> {
+    let dev_name = to_ident(device.child_text("name").unwrap_or("device"));
+    let dev_props = RegProps {size: 32, access: "read-write".to_owned(), reset: 0}.updt(device)?;
+    let peripherals : Vec<&XmlNode> = device.child("peripherals")
+        .map(|p| p.children("peripheral").collect())
+        .unwrap_or_default();
+    let mut result = ImportResult::default();
+    let mut items = Vec::with_capacity(peripherals.len());
+    let mut addr_max = 0;
+    // Peripheral name to RIF type
+    let mut types : Vec<(&str, String)> = Vec::new();
+    for periph in peripherals.iter() {
+        let name = to_ident(periph.child_text("name").ok_or("Peripheral without name")?);
+        let base = child_int(periph, "baseAddress")?.unwrap_or(0) as u64;
+        // Resolve inheritance: a peripheral without its own registers is an instance of the parent
+        let parent = periph.attr("derivedFrom")
+            .map(|p| peripherals.iter().find(|x| x.child_text("name") == Some(p)).ok_or(format!("Unknown peripheral {p}")))
+            .transpose()?;
+        let merged;
+        let node = match parent {
+            Some(p) if periph.child("registers").is_none() => *p,
+            Some(p) => {
+                merged = merge(p, periph);
+                &merged
+            }
+            None => *periph,
+        };
+        // RIF type is named after the group name when available
+        let rif_type = match types.iter().find(|(n,_)| *n == node.child_text("name").unwrap_or_default()) {
+            Some((_,t)) => t.to_owned(),
+            None => {
+                let t = periph.child_text("groupName").map(to_ident)
+                    .filter(|t| !types.iter().any(|(_,x)| x == t))
+                    .unwrap_or(name.clone());
+                types.push((periph.child_text("name").unwrap_or_default(), t.clone()));
+                t
+            }
+        };
+        let desc = clean_desc(periph.child_text("description").or(node.child_text("description")).unwrap_or_default());
+        let mut size = 0;
+        for b in node.children("addressBlock") {
+            size = size.max(child_int(b, "offset")?.unwrap_or(0) + child_int(b, "size")?.unwrap_or(0));
+        }
+        let size = size as u64;
+        // Peripheral without registers is seen as an external memory
+        let kind = if node.child("registers").is_none() {
+            RifType::Ext(addr_width(size))
+        } else {
+            if !result.rifs.iter().any(|r| r.name == rif_type) {
+                let rif = import_peripheral(node, &rif_type, &dev_props, size)?;
+                result.rifs.push(rif);
+            }
+            RifType::Rif(rif_type)
+        };
+        let item_size = match &kind {
+            RifType::Ext(w) => 1 << w,
+            RifType::Rif(t) => 1 << result.rifs.iter().find(|r| &r.name == t).map(|r| r.addr_width).unwrap_or(0),
+        };
+        addr_max = addr_max.max(base + item_size);
+        items.push(RifmuxItem::new((&name, kind, Some((AddressKind::Absolute, AddressOffset::Value(base))), Some(&desc)), ""));
+    }
+    // Single peripheral mapped at 0: no need for a rifmux
+    if items.len() > 1 || items.first().map(|i| i.addr != AddressOffset::Value(0)).unwrap_or(false) {
+        let name = if result.rifs.iter().any(|r| r.name == dev_name) {format!("{dev_name}_top")} else {dev_name};
+        let mut rifmux = Rifmux::new(name);
+        rifmux.addr_width = addr_width(addr_max);
+        rifmux.data_width = result.rifs.iter().map(|r| r.data_width).max().unwrap_or(dev_props.size);
+        rifmux.description = clean_desc(device.child_text("description").unwrap_or_default()).into();
+        rifmux.items = items;
+        result.rifmux = Some(rifmux);
+    }
+    Ok(result)
+}
+
+/// Create a RIF with a single page from a peripheral definition
+fn import_peripheral(periph: &XmlNode, name: &str, props: &RegProps, size: u64) -> Result<Rif, String> {
+    let props = props.updt(periph)?;
+    let mut rif = Rif::new(name);
+    rif.description = clean_desc(periph.child_text("description").unwrap_or_default()).into();
+    for key in ["vendor", "version"] {
+        if let Some(v) = periph.child_text(key) {
+            rif.add_info((key, v));
+        }
+    }
+    let mut page = RifPage::new("Main");
+    page.description = rif.description.clone();
+    if let Some(regs) = periph.child("registers") {
+        let mut ctxt = PeriphCtxt {rif: &mut rif, page: &mut page, defs: HashSet::new(), root: regs};
+        ctxt.add_registers(regs, "", &[("".to_owned(), 0)], &props)?;
+    }
+    page.instances.sort_by_key(|i| i.addr);
+    // Address width large enough for all registers
+    let end = page.instances.iter()
+        .map(|i| i.addr + 4)
+        .max()
+        .unwrap_or(0)
+        .max(size);
+    rif.addr_width = addr_width(end);
+    rif.data_width = page.registers.iter()
+        .filter_map(|r| if let RegDefOrIncl::Def(d) = r {Some(d)} else {None})
+        .flat_map(|d| d.fields.iter())
+        .map(|f| if let FieldPos::MsbLsb((Width::Value(msb),_)) = f.pos {msb+1} else {0})
+        .max()
+        .unwrap_or(0)
+        .max(props.size)
+        .next_power_of_two()
+        .max(8);
+    rif.pages.push(page);
+    Ok(rif)
+}
+
+/// Context used to add registers of a peripheral
+struct PeriphCtxt<'a> {
+    rif: &'a mut Rif,
+    page: &'a mut RifPage,
+    /// Register definition already created (shared by all elements of an array)
+    defs: HashSet<String>,
+    /// Root registers node, used to resolve derivedFrom
+    root: &'a XmlNode,
+}
+
+impl PeriphCtxt<'_> {
+
+    /// Add registers and clusters of a node.
+    /// Each instance prefix is associated with an address offset (multiple prefixes for cluster arrays)
+    fn add_registers(&mut self, node: &XmlNode, def_prefix: &str, insts: &[(String,u64)], props: &RegProps) -> Result<(), String> {
+        for child in node.children.iter() {
+            if child.name != "register" && child.name != "cluster" {
+                continue;
+            }
+            // Alternate register share address with another one: not supported
+            if child.child("alternateRegister").is_some() || child.child("alternateGroup").is_some() {
+                continue;
+            }
+            let child = match child.attr("derivedFrom") {
+                Some(p) => match self.find_derived(node, p) {
+                    Some(parent) => merge(parent, child),
+                    None => return Err(format!("Unknown register {p}")),
+                },
+                None => child.clone(),
+            };
+            let name = child.child_text("name").ok_or("Register without name")?;
+            let offset = child_int(&child, "addressOffset")?.unwrap_or(0) as u64;
+            let elts = dim_list(&child, name)?;
+            let base_name = to_ident(&name.replace("[%s]", "").replace("%s", ""));
+            let props = props.updt(&child)?;
+            match child.name.as_str() {
+                "register" => {
+                    let def_name = format!("{def_prefix}{base_name}");
+                    if !self.defs.contains(&def_name) {
+                        self.defs.insert(def_name.clone());
+                        let reg = self.import_register(&child, &def_name, &props)?;
+                        self.page.registers.push(RegDefOrIncl::Def(Box::new(reg)));
+                    }
+                    for (prefix, addr) in insts.iter() {
+                        for (elt_name, elt_offset) in elts.iter() {
+                            let inst_name = format!("{prefix}{}", to_ident(elt_name));
+                            let addr = addr + offset + elt_offset;
+                            let type_name = if inst_name != def_name {Some(def_name.as_str())} else {None};
+                            self.page.instances.push(RegInst::from((inst_name.as_str(), ExprTokens::new(0), type_name, None, Some((AddressKind::Absolute, addr)))));
+                        }
+                    }
+                }
+                "cluster" => {
+                    let sub_insts : Vec<(String,u64)> = insts.iter()
+                        .flat_map(|(prefix, addr)| elts.iter().map(move |(n,o)| (format!("{prefix}{}_", to_ident(n)), addr + offset + o)))
+                        .collect();
+                    self.add_registers(&child, &format!("{def_prefix}{base_name}_"), &sub_insts, &props)?;
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Find a register/cluster by name: either a sibling or a full path from the peripheral registers
+    fn find_derived<'b>(&'b self, node: &'b XmlNode, path: &str) -> Option<&'b XmlNode> {
+        let find = |n: &'b XmlNode, name: &str| n.children.iter().find(|c| c.child_text("name") == Some(name));
+        if let Some(n) = find(node, path) {
+            return Some(n);
+        }
+        // Path relative to the peripheral (first element is the peripheral name)
+        let mut n = self.root;
+        for name in path.split('.').skip(1) {
+            n = find(n, name)?;
+        }
+        Some(n)
+    }
+
+    fn import_register(&mut self, node: &XmlNode, name: &str, props: &RegProps) -> Result<RegDef, String> {
+        let desc = clean_desc(node.child_text("description").unwrap_or_default());
+        let mut reg = RegDef::new(name, None, None, &desc);
+        let fields : Vec<&XmlNode> = node.child("fields").map(|f| f.children("field").collect()).unwrap_or_default();
+        // Register without fields: create one covering the full register
+        if fields.is_empty() {
+            let kind = field_sw_kind(&props.access, node.child_text("modifiedWriteValues"), node.child_text("readAction"));
+            let field = Field::new("val", vec![ResetVal::Unsigned(props.reset)], msb_lsb(props.size-1, 0), Some(kind), None, desc.as_str());
+            reg.add_field(field);
+            return Ok(reg);
+        }
+        for f in fields {
+            let field = self.import_field(node, f, name, props)?;
+            reg.add_field(field);
+        }
+        Ok(reg)
+    }
+
+    fn import_field(&mut self, reg: &XmlNode, node: &XmlNode, reg_name: &str, props: &RegProps) -> Result<Field, String> {
+        let name = to_ident(node.child_text("name").ok_or("Field without name")?);
+        let full_name = format!("{reg_name}.{name}");
+        let (lsb, width) = field_pos(node, &full_name)?.ok_or(format!("Unable to get position of field {full_name}"))?;
+        let (msb, lsb) = field_range(lsb, width, props.size, &full_name)?;
+        let width = msb - lsb + 1;
+        let access = node.child_text("access").unwrap_or(&props.access);
+        let kind = field_sw_kind(
+            access,
+            node.child_text("modifiedWriteValues").or(reg.child_text("modifiedWriteValues")),
+            node.child_text("readAction").or(reg.child_text("readAction")),
+        );
+        let reset = (props.reset >> lsb) & mask(width);
+        let desc = clean_desc(node.child_text("description").unwrap_or_default());
+        let mut field = Field::new(name.as_str(), vec![ResetVal::Unsigned(reset)], msb_lsb(msb, lsb), Some(kind), None, desc.as_str());
+        // Enumerated values
+        if let Some(enums) = node.children("enumeratedValues").find(|e| e.child_text("usage") != Some("write")).or(node.child("enumeratedValues")) {
+            let enums = match enums.attr("derivedFrom") {
+                Some(p) => find_enum(self.root, p).unwrap_or(enums),
+                None => enums,
+            };
+            let values = enums.children("enumeratedValue")
+                .filter_map(|e| Some((
+                    e.child_text("name")?,
+                    e.child_text("value").and_then(parse_int)?,
+                    e.child_text("description").unwrap_or_default(),
+                )))
+                .collect();
+            field.enum_kind = add_enum(self.rif, reg_name, &name, &desc, values)?;
+        }
+        // Write constraint
+        if let Some(c) = node.child("writeConstraint") {
+            if c.child_text("useEnumeratedValues") == Some("true") && field.enum_kind.is_type() {
+                field.limit = Limit {value: LimitValue::Enum, bypass: "".to_owned()};
+            } else if let Some(range) = c.child("range") {
+                let min = child_int(range, "minimum")?.unwrap_or(0);
+                let max = child_int(range, "maximum")?.unwrap_or(mask(width));
+                field.limit = Limit {value: LimitValue::MinMax(ResetVal::Unsigned(min), ResetVal::Unsigned(max)), bypass: "".to_owned()};
+            }
+        }
+        Ok(field)
+    }
+}
+
+
+/// Extract field position (lsb, width) from one of the three possible SVD format
+fn field_pos(node: &XmlNode, name: &str) -> Result<Option<(u128,u128)>, String> {
+    let (msb, lsb) =
+        if let (Some(lsb), Some(width)) = (child_int(node, "bitOffset")?, child_int(node, "bitWidth")?) {
+            return Ok(Some((lsb, width)));
+        } else if let (Some(lsb), Some(msb)) = (child_int(node, "lsb")?, child_int(node, "msb")?) {
+            (msb, lsb)
+        } else if let Some(range) = node.child_text("bitRange") {
+            range.trim_start_matches('[').trim_end_matches(']')
+                .split_once(':')
+                .and_then(|(msb, lsb)| Some((parse_int(msb)?, parse_int(lsb)?)))
+                .ok_or(format!("Invalid bitRange \"{range}\" for field {name}"))?
+        } else {
+            return Ok(None);
+        };
+    let width = msb.checked_sub(lsb).ok_or(format!("Field {name} has its msb ({msb}) lower than its lsb ({lsb})"))?;
+    Ok(Some((lsb, width + 1)))
+}
+
+/// List of element name/offset of a dim array (or just the name when not an array)
+fn dim_list(node: &XmlNode, name: &str) -> Result<Vec<(String,u64)>, String> {
+    let Some(dim) = child_int(node, "dim")? else {
+        return Ok(vec![(name.to_owned(), 0)]);
+    };
+    let incr = child_int(node, "dimIncrement")?.unwrap_or(0) as u64;
+    // Index can be a list (A,B,C), a range (3-6) or implicit (0 to dim-1)
+    let idx : Vec<String> = match node.child_text("dimIndex") {
+        Some(s) if s.contains(',') => s.split(',').map(|s| s.trim().to_owned()).collect(),
+        Some(s) if s.contains('-') => {
+            let (a, b) = s.split_once('-').unwrap();
+            match (parse_int(a), parse_int(b)) {
+                (Some(a), Some(b)) => (a..=b).map(|i| i.to_string()).collect(),
+                _ => (0..dim).map(|i| i.to_string()).collect(),
+            }
+        }
+        _ => (0..dim).map(|i| i.to_string()).collect(),
+    };
+    Ok(idx.iter()
+        .enumerate()
+        .map(|(i, s)| (name.replace("[%s]", s).replace("%s", s), i as u64 * incr))
+        .collect())
+}
+
+/// Create a node from a parent node and a derived node overriding some elements
+fn merge(parent: &XmlNode, node: &XmlNode) -> XmlNode {
+    let mut merged = node.clone();
+    merged.attrs.retain(|(k,_)| k != "derivedFrom");
+    for c in parent.children.iter() {
+        if node.child(&c.name).is_none() {
+            merged.children.push(c.clone());
+        }
+    }
+    merged
+}
+
+/// Find an enumerated values definition by name (last element of the path)
+fn find_enum<'a>(root: &'a XmlNode, path: &str) -> Option<&'a XmlNode> {
+    let name = path.rsplit('.').next()?;
+    if root.name == "enumeratedValues" && root.child_text("name") == Some(name) {
+        return Some(root);
+    }
+    root.children.iter().find_map(|c| find_enum(c, name))
+}
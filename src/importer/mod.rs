@@ -0,0 +1,285 @@
+mod xml;
+mod import_svd;
+mod import_ipxact;
+mod rif_writer;
+
+use std::{fs::create_dir_all, path::{Path, PathBuf}};
+
+use crate::rifgen::{EnumDef, EnumEntry, EnumKind, FieldPos, FieldSwKind, Rif, Rifmux, Width};
+
+use self::{rif_writer::RifWriter, xml::XmlNode};
+
+/// Result of an import: a list of RIF definitions and an optional rifmux instantiating them
+#[derive(Debug, Default)]
+pub struct ImportResult {
+    pub rifs: Vec<Rif>,
+    pub rifmux: Option<Rifmux>,
+}
+
+impl ImportResult {
+    /// Name of the top level definition
+    pub fn top_name(&self) -> Option<&str> {
+        match &self.rifmux {
+            Some(rifmux) => Some(&rifmux.name),
+            None => self.rifs.first().map(|r| r.name.as_str()),
+        }
+    }
+}
+
+/// Import a CMSIS-SVD or IP-XACT file and write one .rif file per RIF/Rifmux in the output directory
+/// Return the path to the top level file
+pub fn import_file(path: &Path, out_dir: &Path) -> Result<PathBuf, String> {
+    let src = std::fs::read_to_string(path).map_err(|e| format!("Unable to read {}: {e}", path.display()))?;
+    let root = XmlNode::parse(&src)?;
+    let result = match root.name.as_str() {
+        "device" => import_svd::import(&root)?,
+        "component" => import_ipxact::import(&root)?,
+        n => return Err(format!("Unsupported root element {n}: expecting an SVD device or an IP-XACT component")),
+    };
+    let top = result.top_name().ok_or("No register definition found")?.to_owned();
+    create_dir_all(out_dir).map_err(|e| e.to_string())?;
+    let mut writer = RifWriter::new();
+    for rif in result.rifs.iter() {
+        writer.add_rif(rif);
+        save(out_dir, &rif.name, writer.take())?;
+    }
+    if let Some(rifmux) = &result.rifmux {
+        writer.add_rifmux(rifmux);
+        save(out_dir, &rifmux.name, writer.take())?;
+    }
+    Ok(out_dir.join(format!("{top}.rif")))
+}
+
+fn save(out_dir: &Path, name: &str, txt: String) -> Result<(), String> {
+    let path = out_dir.join(format!("{name}.rif"));
+    std::fs::write(&path, txt).map_err(|e| format!("Unable to write {}: {e}", path.display()))
+}
+
+//-----------------------------
+// Helpers common to all importers
+
+/// Convert a name into a valid RIF identifier (lowercase)
+fn to_ident(name: &str) -> String {
+    to_ident_case(name).to_ascii_lowercase()
+}
+
+/// Convert a name into a valid RIF identifier, preserving the case
+fn to_ident_case(name: &str) -> String {
+    let mut s : String = name.trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() {c} else {'_'})
+        .collect();
+    if !s.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        s.insert(0, '_');
+    }
+    s
+}
+
+/// Software kind of a field from the access, modified write value and read action
+/// (same vocabulary in SVD and IP-XACT)
+fn field_sw_kind(access: &str, modified_write: Option<&str>, read_action: Option<&str>) -> FieldSwKind {
+    match access {
+        "read-only" => {
+            if read_action == Some("clear") {FieldSwKind::ReadClr} else {FieldSwKind::ReadOnly}
+        }
+        "write-only" | "writeOnce" => FieldSwKind::WriteOnly,
+        _ => match modified_write {
+            Some("oneToClear") => FieldSwKind::W1Clr,
+            Some("zeroToClear") => FieldSwKind::W0Clr,
+            Some("oneToSet") => FieldSwKind::W1Set,
+            Some("oneToToggle") => FieldSwKind::W1Tgl,
+            _ => FieldSwKind::ReadWrite,
+        }
+    }
+}
+
+/// Parse an integer in decimal, hexadecimal (0x, 'h, N'h), binary (0b, #, 'b) or octal ('o)
+fn parse_int(value: &str) -> Option<u128> {
+    let v = value.trim().replace('_', "");
+    let v = v.as_str();
+    let (radix, digits) =
+        if let Some(idx) = v.find('\'') {
+            let digits = v.get(idx+2..)?;
+            match v[idx+1..].chars().next()? {
+                'h' | 'H' => (16, digits),
+                'b' | 'B' => (2, digits),
+                'o' | 'O' => (8, digits),
+                'd' | 'D' => (10, digits),
+                _ => return None,
+            }
+        }
+        else if let Some(d) = v.strip_prefix("0x").or(v.strip_prefix("0X")) {(16, d)}
+        else if let Some(d) = v.strip_prefix("0b").or(v.strip_prefix("0B")) {(2, d)}
+        else if let Some(d) = v.strip_prefix('#') {(2, d)}
+        else {(10, v)};
+    u128::from_str_radix(digits, radix).ok()
+}
+
+/// Integer value of a child element: None when the element is absent,
+/// error when its content is not a valid integer
+fn child_int(node: &XmlNode, key: &str) -> Result<Option<u128>, String> {
+    node.child_text(key)
+        .map(|v| parse_int(v).ok_or_else(|| format!("Invalid {key} \"{v}\" in {}", node_desc(node))))
+        .transpose()
+}
+
+/// Element kind and name used in error messages (e.g. register CR1)
+fn node_desc(node: &XmlNode) -> String {
+    match node.child_text("name") {
+        Some(n) => format!("{} {n}", node.name),
+        None => node.name.to_owned(),
+    }
+}
+
+/// Check the size of a register: between 1 and 128 bits
+fn reg_size(size: u128, name: &str) -> Result<u8, String> {
+    if size == 0 || size > 128 {
+        return Err(format!("Unsupported size {size} in {name}: expecting 1 to 128 bits"));
+    }
+    Ok(size as u8)
+}
+
+/// Check a field (lsb, width) is not empty and fits inside its register
+/// and return its position as (msb, lsb)
+fn field_range(lsb: u128, width: u128, size: u8, name: &str) -> Result<(u8,u8), String> {
+    if width == 0 {
+        return Err(format!("Field {name} has a null width"));
+    }
+    match lsb.checked_add(width - 1) {
+        Some(msb) if msb < size as u128 => Ok((msb as u8, lsb as u8)),
+        _ => Err(format!("Field {name} (lsb={lsb}, width={width}) is outside of the {size}-bit register")),
+    }
+}
+
+/// Mask covering the lower width bits (width between 1 and 128)
+fn mask(width: u8) -> u128 {
+    u128::MAX >> (128 - width as u32)
+}
+
+/// Create an enum definition in the RIF and return the corresponding enum kind
+/// Enum with values not fitting on 8 bits are rejected
+fn add_enum(rif: &mut Rif, reg: &str, field: &str, desc: &str, values: Vec<(&str,u128,&str)>) -> Result<EnumKind, String> {
+    if values.is_empty() {
+        return Ok(EnumKind::None);
+    }
+    // Enum values are limited to 8 bits in the RIF description
+    if let Some((n, v, _)) = values.iter().find(|v| v.1 > 255) {
+        return Err(format!("Value {v} of enum {n} in field {reg}.{field} does not fit in 8 bits"));
+    }
+    let name = format!("e_{reg}_{field}");
+    let mut def = EnumDef::new(name.clone(), desc.lines().next().unwrap_or_default().to_owned());
+    for (n, v, d) in values {
+        def.values.push(EnumEntry::new(to_ident_case(n), v as u8, clean_desc(d)));
+    }
+    rif.enum_defs.push(def);
+    Ok(EnumKind::Type(name))
+}
+
+fn msb_lsb(msb: u8, lsb: u8) -> FieldPos {
+    FieldPos::MsbLsb((Width::Value(msb), Width::Value(lsb)))
+}
+
+/// Number of bits needed to address a given range
+fn addr_width(range: u64) -> u8 {
+    (64 - range.saturating_sub(1).leading_zeros()).max(2) as u8
+}
+
+/// Join lines of a description, removing indentation
+fn clean_desc(desc: &str) -> String {
+    desc.lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests_parsing {
+    use super::*;
+
+    #[test]
+    fn test_parse_int() {
+        assert_eq!(parse_int("0x1F"), Some(31));
+        assert_eq!(parse_int("'hA0"), Some(160));
+        assert_eq!(parse_int("8'h0_F"), Some(15));
+        assert_eq!(parse_int("#101"), Some(5));
+        assert_eq!(parse_int("42"), Some(42));
+        assert_eq!(parse_int("0xZ"), None);
+        assert_eq!(parse_int("8'"), None);
+        assert_eq!(parse_int("8'h"), None);
+    }
+
+    #[test]
+    fn test_to_ident() {
+        assert_eq!(to_ident("CR1"), "cr1");
+        assert_eq!(to_ident("DMA.CH[0]"), "dma_ch_0_");
+        assert_eq!(to_ident("3V3"), "_3v3");
+    }
+}
+
+#[cfg(test)]
+mod tests_import {
+    use super::*;
+
+    /// Import a SVD device with a single register containing the given fields
+    fn svd(reg: &str) -> Result<ImportResult, String> {
+        let src = format!("<device><name>dev</name><peripherals><peripheral><name>periph</name><baseAddress>0x1000</baseAddress>\
+            <registers><register><name>ctrl</name>{reg}</register></registers></peripheral></peripherals></device>");
+        import_svd::import(&XmlNode::parse(&src)?)
+    }
+
+    /// Import an IP-XACT component with a single register
+    fn ipxact(reg: &str) -> Result<ImportResult, String> {
+        let src = format!("<component><name>comp</name><memoryMaps><memoryMap><name>map</name><addressBlock><name>main</name>\
+            <baseAddress>0</baseAddress><range>0x100</range><width>32</width><register><name>ctrl</name>{reg}</register>\
+            </addressBlock></memoryMap></memoryMaps></component>");
+        import_ipxact::import(&XmlNode::parse(&src)?)
+    }
+
+    #[test]
+    fn test_svd_valid() {
+        let res = svd("<addressOffset>0x4</addressOffset><resetValue>0x12</resetValue><fields>\
+            <field><name>en</name><bitRange>[0:0]</bitRange></field>\
+            <field><name>mode</name><bitOffset>4</bitOffset><bitWidth>4</bitWidth></field></fields>").unwrap();
+        assert_eq!(res.rifs.len(), 1);
+        assert_eq!(res.rifs[0].data_width, 32);
+    }
+
+    #[test]
+    fn test_svd_malformed() {
+        let err = svd("<addressOffset>0xZZ</addressOffset>").unwrap_err();
+        assert!(err.contains("addressOffset"), "{err}");
+        let err = svd("<resetValue>8'</resetValue>").unwrap_err();
+        assert!(err.contains("resetValue"), "{err}");
+        let err = svd("<size>0</size>").unwrap_err();
+        assert!(err.contains("size"), "{err}");
+        let err = svd("<fields><field><name>rev</name><bitRange>[0:3]</bitRange></field></fields>").unwrap_err();
+        assert!(err.contains("ctrl.rev"), "{err}");
+        let err = svd("<fields><field><name>nul</name><bitOffset>2</bitOffset><bitWidth>0</bitWidth></field></fields>").unwrap_err();
+        assert!(err.contains("ctrl.nul"), "{err}");
+        let err = svd("<fields><field><name>big</name><lsb>24</lsb><msb>39</msb></field></fields>").unwrap_err();
+        assert!(err.contains("outside"), "{err}");
+    }
+
+    #[test]
+    fn test_svd_enum() {
+        let enums = |v: &str| format!("<fields><field><name>mode</name><bitOffset>0</bitOffset><bitWidth>10</bitWidth><enumeratedValues>\
+            <enumeratedValue><name>off</name><value>0</value></enumeratedValue>\
+            <enumeratedValue><name>max</name><value>{v}</value></enumeratedValue></enumeratedValues></field></fields>");
+        let res = svd(&enums("255")).unwrap();
+        assert_eq!(res.rifs[0].enum_defs.len(), 1);
+        let err = svd(&enums("256")).unwrap_err();
+        assert!(err.contains("ctrl.mode") && err.contains("256"), "{err}");
+    }
+
+    #[test]
+    fn test_ipxact_malformed() {
+        assert!(ipxact("<addressOffset>0</addressOffset><size>32</size><field><name>en</name><bitOffset>0</bitOffset><bitWidth>1</bitWidth></field>").is_ok());
+        let err = ipxact("<addressOffset>foo</addressOffset>").unwrap_err();
+        assert!(err.contains("addressOffset"), "{err}");
+        let err = ipxact("<size>32</size><field><name>big</name><bitOffset>16</bitOffset><bitWidth>24</bitWidth></field>").unwrap_err();
+        assert!(err.contains("outside"), "{err}");
+        let err = ipxact("<field><name>en</name><bitOffset>0</bitOffset><bitWidth>1</bitWidth><resets><reset><value>0xG</value></reset></resets></field>").unwrap_err();
+        assert!(err.contains("value"), "{err}");
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+
+use crate::rifgen::{
+    Access, Description, EnumDef, EnumKind, Field, FieldPos, FieldSwKind, LimitValue,
+    RegDef, RegDefOrIncl, ResetVal, Rif, RifPage, RifType, Rifmux, AddressOffset, Visibility,
+};
+
+/// Serialize RIF/Rifmux definition into the RIF text format
+pub struct RifWriter {
+    txt: String,
+    /// Enum already defined in the current RIF
+    enum_done: HashSet<String>,
+}
+
+impl RifWriter {
+
+    pub fn new() -> Self {
+        RifWriter {
+            txt: String::with_capacity(10000),
+            enum_done: HashSet::new(),
+        }
+    }
+
+    fn write(&mut self, string: &str) {
+        self.txt.push_str(string);
+    }
+
+    fn write_line(&mut self, ind: usize, string: &str) {
+        self.txt.push_str(&format!("{:ind$}{string}\n", ""));
+    }
+
+    /// Write a property description: first line inline, remaining lines indented below
+    fn write_desc(&mut self, ind: usize, key: &str, desc: &Description) {
+        let mut lines = desc.get().lines().map(|l| l.trim_end()).filter(|l| !l.trim().is_empty());
+        let Some(first) = lines.next() else {return;};
+        self.write_line(ind, &format!("{key}: {}", first.trim()));
+        for l in lines {
+            self.write_line(ind+2, l.trim());
+        }
+    }
+
+    /// Write the extra lines of a description (first line being written inline)
+    fn write_desc_extra(&mut self, ind: usize, desc: &Description) {
+        let mut lines = desc.get().lines().filter(|l| !l.trim().is_empty()).skip(1).peekable();
+        if lines.peek().is_none() {
+            return;
+        }
+        self.write_line(ind, "description:");
+        for l in lines {
+            self.write_line(ind+2, l.trim());
+        }
+    }
+
+    /// Return the consumed text
+    pub fn take(&mut self) -> String {
+        self.enum_done.clear();
+        std::mem::take(&mut self.txt)
+    }
+
+    //-----------------------------
+
+    pub fn add_rif(&mut self, rif: &Rif) {
+        self.write("// File generated by the RIF importer\n");
+        self.write(&format!("rif: {}\n", rif.name));
+        self.write_line(2, &format!("addrWidth: {}", rif.addr_width));
+        self.write_line(2, &format!("dataWidth: {}", rif.data_width));
+        if !rif.interface.is_default() {
+            self.write_line(2, &format!("interface: {}", rif.interface.name()));
+        }
+        self.write_desc(2, "description", &rif.description);
+        if !rif.info.is_empty() {
+            self.write_line(2, "info:");
+            for (k,v) in rif.info.iter() {
+                self.write_line(4, &format!("- {k}: {v}"));
+            }
+        }
+        for page in rif.pages.iter() {
+            self.add_page(rif, page);
+        }
+    }
+
+    fn add_page(&mut self, rif: &Rif, page: &RifPage) {
+        self.write_line(2, &format!("- {} : \"{}\"", page.name, inline(&page.description)));
+        self.write_desc_extra(4, &page.description);
+        if page.addr != 0 {
+            self.write_line(4, &format!("baseAddress : 0x{:X}", page.addr));
+        }
+        if page.external {
+            self.write_line(4, &format!("external {}", page.addr_width));
+            return;
+        }
+        self.write_line(4, "registers:");
+        for reg in page.registers.iter() {
+            match reg {
+                RegDefOrIncl::Def(reg) => self.add_reg(rif, reg),
+                RegDefOrIncl::Include(inc) => self.write_line(6, &format!("- include {inc}")),
+            }
+        }
+        if page.inst_auto {
+            self.write_line(4, "instances: auto");
+            return;
+        }
+        self.write_line(4, "instances:");
+        for inst in page.instances.iter() {
+            let type_name = if inst.type_name != inst.inst_name {format!(" = {}", inst.type_name)} else {"".to_owned()};
+            self.write_line(6, &format!("- {}{type_name} @ 0x{:X}", inst.inst_name, inst.addr));
+        }
+    }
+
+    fn add_reg(&mut self, rif: &Rif, reg: &RegDef) {
+        self.write_line(6, &format!("- {}: \"{}\"", reg.name, inline(&reg.description)));
+        self.write_desc_extra(8, &reg.description);
+        if let Some(v) = visibility(&reg.visibility) {
+            self.write_line(8, v);
+        }
+        if !reg.info.is_empty() {
+            self.write_line(8, "info:");
+            let mut info : Vec<_> = reg.info.iter().collect();
+            info.sort();
+            for (k,v) in info {
+                self.write_line(10, &format!("- {k}: {v}"));
+            }
+        }
+        for field in reg.fields.iter() {
+            self.add_field(rif, field);
+        }
+    }
+
+    fn add_field(&mut self, rif: &Rif, field: &Field) {
+        let pos = match &field.pos {
+            FieldPos::MsbLsb((msb,lsb)) => format!("{msb}:{lsb}"),
+            FieldPos::LsbSize((lsb,w)) => format!("{lsb}+:{w}"),
+            FieldPos::Size(w) => format!("{w}b"),
+        };
+        let reset = field.reset.first().map(|r| match r {
+            ResetVal::Unsigned(v) => format!("0x{v:X}"),
+            ResetVal::Signed(v) => format!("{v:+}"),
+            ResetVal::Param(p) => p.to_owned(),
+        }).unwrap_or("0".to_owned());
+        // Password and pulse properties are not supported inline
+        let kind = match &field.sw_kind {
+            FieldSwKind::W1Pulse(_,_) |
+            FieldSwKind::Password(_) => "".to_owned(),
+            k => format!(" {}", k.keyword()),
+        };
+        self.write_line(8, &format!("- {} = {reset} {pos}{kind} \"{}\"", field.name, inline(&field.description)));
+        self.write_desc_extra(10, &field.description);
+        if let FieldSwKind::W1Pulse(reg,_) = field.sw_kind {
+            self.write_line(10, if reg {"pulse reg"} else {"pulse comb"});
+        }
+        // Hardware access only when different from the default one
+        let hw_default = Field::new("", vec![], field.pos.clone(), Some(field.sw_kind.clone()), None, "").hw_acc;
+        if field.hw_acc != hw_default {
+            let acc = match field.hw_acc {
+                Access::RW => "rw",
+                Access::RO => "r",
+                Access::WO => "w",
+                Access::NA => "na",
+            };
+            self.write_line(10, &format!("hw {acc}"));
+        }
+        if let Some(v) = visibility(&field.visibility) {
+            self.write_line(10, v);
+        }
+        if field.reset.iter().any(|r| r.is_signed()) {
+            self.write_line(10, "signed");
+        }
+        match &field.enum_kind {
+            EnumKind::None => {},
+            EnumKind::Doc(name) => self.add_enum(rif, name, ""),
+            EnumKind::Type(name) => self.add_enum(rif, name, name),
+        }
+        let limit = match &field.limit.value {
+            LimitValue::None => None,
+            LimitValue::Min(min) => Some(format!("[{}:]", limit_val(min))),
+            LimitValue::Max(max) => Some(format!("[:{}]", limit_val(max))),
+            LimitValue::MinMax(min,max) => Some(format!("[{}:{}]", limit_val(min), limit_val(max))),
+            LimitValue::List(l) => Some(format!("{{{}}}", l.iter().map(limit_val).collect::<Vec<String>>().join(","))),
+            LimitValue::Enum => Some("enum".to_owned()),
+        };
+        if let Some(limit) = limit {
+            self.write_line(10, format!("limit {limit} {}", field.limit.bypass).trim_end());
+        }
+    }
+
+    /// Write the enum property: values are written only the first time the enum is referenced
+    fn add_enum(&mut self, rif: &Rif, name: &str, kind: &str) {
+        let def = rif.enum_defs.iter().find(|d| d.name == name);
+        match def {
+            Some(def) if !self.enum_done.contains(name) => {
+                self.enum_done.insert(name.to_owned());
+                let desc = def.description.replace('"', "'");
+                let kind = if kind.is_empty() {"".to_owned()} else {format!("{kind} ")};
+                self.write_line(10, &format!("enum: {kind}\"{desc}\""));
+                self.add_enum_values(def);
+            }
+            _ => self.write_line(10, format!("enum: {kind}").trim_end()),
+        }
+    }
+
+    fn add_enum_values(&mut self, def: &EnumDef) {
+        for e in def.iter() {
+            self.write_line(12, &format!("- {} = {} \"{}\"", e.name, e.value, inline(&e.description)));
+        }
+    }
+
+    //-----------------------------
+
+    pub fn add_rifmux(&mut self, rifmux: &Rifmux) {
+        self.write("// File generated by the RIF importer\n");
+        self.write(&format!("rifmux: {}\n", rifmux.name));
+        self.write_line(2, &format!("addrWidth: {}", rifmux.addr_width));
+        self.write_line(2, &format!("dataWidth: {}", rifmux.data_width));
+        if !rifmux.interface.is_default() {
+            self.write_line(2, &format!("interface: {}", rifmux.interface.name()));
+        }
+        self.write_desc(2, "description", &rifmux.description);
+        self.write_line(2, "map:");
+        for item in rifmux.items.iter() {
+            let kind = match &item.rif_type {
+                RifType::Rif(t) => format!("= {t}"),
+                RifType::Ext(w) => format!("external {w}"),
+            };
+            let addr = match &item.addr {
+                AddressOffset::Value(v) => format!("0x{v:X}"),
+                AddressOffset::Param(p) => p.to_owned(),
+            };
+            self.write_line(4, &format!("- {} {kind} @ {addr} \"{}\"", item.name, inline(&item.description)));
+        }
+    }
+
+}
+
+/// First line of a description compatible with a quoted string
+fn inline(desc: &Description) -> String {
+    desc.get_short().trim().replace('"', "'")
+}
+
+fn visibility(v: &Visibility) -> Option<&'static str> {
+    match v {
+        Visibility::Full => None,
+        Visibility::Hidden => Some("hidden"),
+        Visibility::Reserved => Some("reserved"),
+        Visibility::Disabled => Some("disabled"),
+    }
+}
+
+fn limit_val(v: &ResetVal) -> String {
+    match v {
+        ResetVal::Unsigned(v) => format!("{v}"),
+        ResetVal::Signed(v) => format!("{v:+}"),
+        ResetVal::Param(p) => p.to_owned(),
+    }
+}
@@ -0,0 +1,209 @@
+/// Minimal XML element tree: enough to read SVD and IP-XACT files
+/// (no DTD, no namespace resolution: prefixes are simply removed from names)
+#[derive(Clone, Debug, Default)]
+pub struct XmlNode {
+    /// Local name of the element
+    pub name: String,
+    pub attrs: Vec<(String,String)>,
+    pub children: Vec<XmlNode>,
+    /// Concatenation of all text content directly inside the element
+    pub text: String,
+}
+
+impl XmlNode {
+
+    /// Parse a complete XML document and return the root element
+    pub fn parse(src: &str) -> Result<XmlNode, String> {
+        let mut p = XmlParser {src, pos: 0};
+        p.skip_misc()?;
+        let root = p.element()?;
+        p.skip_misc()?;
+        if p.pos < src.len() {
+            return Err(p.error("Unexpected content after root element"));
+        }
+        Ok(root)
+    }
+
+    /// First child element with a given name
+    pub fn child(&self, name: &str) -> Option<&XmlNode> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    /// All children elements with a given name
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item=&'a XmlNode> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Trimmed text of the first child element with a given name
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.child(name).map(|c| c.text.trim())
+    }
+
+    /// Attribute value
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|(k,_)| k == name).map(|(_,v)| v.as_str())
+    }
+}
+
+struct XmlParser<'a> {
+    src: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlParser<'a> {
+
+    fn rest(&self) -> &'a str {
+        &self.src[self.pos..]
+    }
+
+    fn error(&self, msg: &str) -> String {
+        let line = self.src[..self.pos].lines().count().max(1);
+        format!("{msg} (line {line})")
+    }
+
+    fn skip_ws(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Skip everything up to (and including) a given pattern
+    fn skip_after(&mut self, pat: &str) -> Result<&'a str, String> {
+        match self.rest().find(pat) {
+            Some(idx) => {
+                let s = &self.rest()[..idx];
+                self.pos += idx + pat.len();
+                Ok(s)
+            }
+            None => Err(self.error(&format!("Missing {pat}"))),
+        }
+    }
+
+    /// Skip prolog, comments, processing instructions and doctype
+    fn skip_misc(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_ws();
+            let rest = self.rest();
+            if rest.starts_with("<?") {
+                self.skip_after("?>")?;
+            } else if rest.starts_with("<!--") {
+                self.skip_after("-->")?;
+            } else if rest.starts_with("<!") {
+                self.skip_after(">")?;
+            } else {
+                return Ok(());
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<String, String> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| c.is_whitespace() || c == '>' || c == '/' || c == '=').unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("Expecting a name"));
+        }
+        self.pos += len;
+        let name = &rest[..len];
+        // Remove namespace prefix
+        Ok(name.rsplit(':').next().unwrap_or(name).to_owned())
+    }
+
+    fn element(&mut self) -> Result<XmlNode, String> {
+        if !self.rest().starts_with('<') {
+            return Err(self.error("Expecting an element"));
+        }
+        self.pos += 1;
+        let mut node = XmlNode {name: self.name()?, ..Default::default()};
+        // Attributes
+        loop {
+            self.skip_ws();
+            let rest = self.rest();
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(node);
+            }
+            if rest.starts_with('>') {
+                self.pos += 1;
+                break;
+            }
+            let key = self.name()?;
+            self.skip_ws();
+            if !self.rest().starts_with('=') {
+                return Err(self.error(&format!("Missing value for attribute {key}")));
+            }
+            self.pos += 1;
+            self.skip_ws();
+            let quote = match self.rest().chars().next() {
+                Some(c) if c == '"' || c == '\'' => c,
+                _ => return Err(self.error("Expecting a quoted attribute value")),
+            };
+            self.pos += 1;
+            let value = self.skip_after(&quote.to_string())?;
+            node.attrs.push((key, unescape(value)));
+        }
+        // Content
+        loop {
+            let rest = self.rest();
+            if rest.starts_with("</") {
+                self.pos += 2;
+                let name = self.name()?;
+                if name != node.name {
+                    return Err(self.error(&format!("Closing tag {name} does not match {}", node.name)));
+                }
+                self.skip_after(">")?;
+                return Ok(node);
+            } else if rest.starts_with("<!--") {
+                self.skip_after("-->")?;
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                let s = self.skip_after("]]>")?;
+                node.text.push_str(s);
+            } else if rest.starts_with("<?") {
+                self.skip_after("?>")?;
+            } else if rest.starts_with('<') {
+                node.children.push(self.element()?);
+            } else if rest.is_empty() {
+                return Err(self.error(&format!("Unclosed element {}", node.name)));
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                node.text.push_str(&unescape(&rest[..len]));
+                self.pos += len;
+            }
+        }
+    }
+}
+
+/// Replace XML entities by their value
+fn unescape(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_owned();
+    }
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(idx) = rest.find('&') {
+        out.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        let end = rest.find(';').unwrap_or(0);
+        let c = match &rest[1..end.max(1)] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            e if e.starts_with("#x") => u32::from_str_radix(&e[2..], 16).ok().and_then(char::from_u32),
+            e if e.starts_with('#') => e[1..].parse().ok().and_then(char::from_u32),
+            _ => None,
+        };
+        match c {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end+1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
@@ -0,0 +1,9 @@
+//! Register Interface Generator: parsing and compilation of RIF descriptions,
+//! generators for all targets, importers and the behavioural model of a register interface
+
+mod error;
+pub mod parser;
+pub mod rifgen;
+pub mod comp;
+pub mod generator;
+pub mod importer;
@@ -1,18 +1,11 @@
-mod error;
-mod parser;
-mod rifgen;
-mod comp;
-mod generator;
-
 use std::{collections::HashMap, error::Error, fs, path::PathBuf};
 use clap::{Parser, ValueEnum};
-use generator::{
-    casing::Casing, gen_c::GeneratorC, gen_common::{GeneratorBaseSetting, Privacy}, gen_html::GeneratorHtml, gen_sv::GeneratorSv
+use rifgen::generator::{
+    casing::Casing, gen_c::GeneratorC, gen_cocotb::GeneratorCocotb, gen_common::{GeneratorBaseSetting, Privacy}, gen_cpp::GeneratorCpp, gen_html::GeneratorHtml, gen_ipxact::GeneratorIpxact, gen_json::GeneratorJson, gen_latex::GeneratorLatex, gen_linux::GeneratorLinux, gen_md::GeneratorMd, gen_py::GeneratorPy, gen_qemu::GeneratorQemu, gen_renode::GeneratorRenode, gen_sv::GeneratorSv, gen_svlib::GeneratorSvlib, gen_svd::GeneratorSvd, gen_systemc::GeneratorSystemc, gen_rust::GeneratorRust, gen_uvm::GeneratorUvm, gen_vhdl::GeneratorVhdl, gen_verilog::GeneratorVerilog
 };
-use parser::parser_expr::ParamValues;
-use rifgen::SuffixInfo;
+use rifgen::{importer, parser::{self, parser_expr::ParamValues}, rifgen::SuffixInfo};
 
-use comp::comp_inst::Comp;
+use rifgen::comp::comp_inst::Comp;
 
 // use crate::comp::comp_inst::RifmuxMap;
 
@@ -34,18 +27,75 @@ struct RifGenArgs{
     /// Output path for C header
     #[arg(long, default_value_t = String::from("c"))]
     output_c: String,
+    /// Output path for C++ header
+    #[arg(long, default_value_t = String::from("cpp"))]
+    output_cpp: String,
     /// C macro name defining the base address of the top level
     #[arg(long, default_value_t = String::from("PERIPH_BASE_ADDR"))]
     c_base_addr_name: String,
+    /// Add static inline read/write functions for each register and field in the C header
+    #[arg(long, action)]
+    c_driver: bool,
     /// Output path for documentation output (HTML, latex, ...)
     #[arg(long, default_value_t = String::from("doc"))]
     output_doc: String,
     /// Output path for documentation output (HTML, latex, ...)
     #[arg(long, default_value_t = String::from("rtl"))]
     output_rtl: String,
+    /// Generate also a bindable SVA module checking the register behaviour
+    #[arg(long, action)]
+    sv_sva: bool,
+    /// Output path for CMSIS-SVD file
+    #[arg(long, default_value_t = String::from("svd"))]
+    output_svd: String,
+    /// Output path for IP-XACT component
+    #[arg(long, default_value_t = String::from("ipxact"))]
+    output_ipxact: String,
+    /// Output path for python modules
+    #[arg(long, default_value_t = String::from("py"))]
+    output_py: String,
+    /// Generate also a hierarchical register object model for python
+    #[arg(long, action)]
+    py_model: bool,
+    /// Output path for JSON files
+    #[arg(long, default_value_t = String::from("json"))]
+    output_json: String,
+    /// Generate JSON as one flat file per RIF type instead of a single hierarchical file
+    #[arg(long, action)]
+    json_flat: bool,
+    /// Output path for UVM register model
+    #[arg(long, default_value_t = String::from("uvm"))]
+    output_uvm: String,
+    /// Output path for Linux kernel regmap header
+    #[arg(long, default_value_t = String::from("linux"))]
+    output_linux: String,
+    /// Output path for QEMU device models
+    #[arg(long, default_value_t = String::from("qemu"))]
+    output_qemu: String,
+    /// Output path for Renode peripheral models
+    #[arg(long, default_value_t = String::from("renode"))]
+    output_renode: String,
+    /// Output path for SystemC/TLM-2.0 models
+    #[arg(long, default_value_t = String::from("systemc"))]
+    output_systemc: String,
+    /// Output path for cocotb testbench helpers
+    #[arg(long, default_value_t = String::from("cocotb"))]
+    output_cocotb: String,
+    /// Output path for Rust register access modules
+    #[arg(long, default_value_t = String::from("rust"))]
+    output_rust: String,
+    /// Import a CMSIS-SVD or IP-XACT file and convert it to RIF before parsing
+    #[arg(long)]
+    import: Option<String>,
+    /// Output path for RIF files created by the import
+    #[arg(long, default_value_t = String::from("rif"))]
+    output_rif: String,
     /// Public documentation (hide all private registers/fields)
     #[arg(long, action)]
     public: bool,
+    /// Documentation with one section per register instance instead of one per register type
+    #[arg(long, action)]
+    doc_expand: bool,
     /// Set parameters value
     #[arg(short = 'P', value_parser = parse_key_val::<String, isize>)]
     parameters: Vec<(String, isize)>,
@@ -57,7 +107,7 @@ struct RifGenArgs{
 
 #[derive(ValueEnum, Debug, Clone)]
 enum RifGenTargets {
-    Sv, Vhdl, C, Html, Py, Svd, Json
+    Sv, Svlib, Vhdl, Verilog, C, Html, Latex, Md, Py, Svd, Json, Ipxact, Uvm, Rust, Cpp, Linux, Qemu, Renode, Systemc, Cocotb
 }
 
 /// Parse a single key-value pair
@@ -78,7 +128,19 @@ where
 fn main() {
 
     let args = RifGenArgs::parse();
-    let rif_path : PathBuf = args.rif.into();
+    let rif_path : PathBuf = match &args.import {
+        Some(f) => {
+            println!("Importing {f}");
+            match importer::import_file(f.as_ref(), args.output_rif.as_ref()) {
+                Ok(p) => p,
+                Err(e) => {
+                    println!(" -> Import failed: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+        None => args.rif.into(),
+    };
 
     let filelist: Vec<PathBuf> =
         if rif_path.is_dir() {
@@ -98,7 +160,7 @@ fn main() {
         suffix: SuffixInfo::new("".to_owned(),false,false),
         casing: Casing::Snake,
         privacy: if args.public {Privacy::Public} else {Privacy::Internal},
-        compact: true,
+        compact: !args.doc_expand,
         gen_inc: args.gen_inc
     };
 
@@ -131,11 +193,18 @@ fn main() {
                             match target {
                                 RifGenTargets::C => {
                                     setting.path = args.output_c.clone();
-                                    let mut gen = GeneratorC::new(setting.clone(), args.c_base_addr_name.to_owned());
+                                    let mut gen = GeneratorC::new(setting.clone(), args.c_base_addr_name.to_owned(), args.c_driver);
                                     if let Err(e) = gen.gen(o) {
                                         println!(" -> C generation failed: {}", e)
                                     }
                                 },
+                                RifGenTargets::Cpp => {
+                                    setting.path = args.output_cpp.clone();
+                                    let mut gen = GeneratorCpp::new(setting.clone(), args.c_base_addr_name.to_owned());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> C++ generation failed: {}", e)
+                                    }
+                                },
                                 RifGenTargets::Html => {
                                     setting.path = args.output_doc.clone();
                                     let mut gen = GeneratorHtml::new(setting.clone());
@@ -143,14 +212,125 @@ fn main() {
                                         println!(" -> HTML generation failed: {}", e)
                                     }
                                 }
+                                RifGenTargets::Latex => {
+                                    setting.path = args.output_doc.clone();
+                                    let mut gen = GeneratorLatex::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> LaTeX generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Md => {
+                                    setting.path = args.output_doc.clone();
+                                    let mut gen = GeneratorMd::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> Markdown generation failed: {}", e)
+                                    }
+                                }
                                 RifGenTargets::Sv => {
                                     setting.path = args.output_rtl.clone();
-                                    let mut gen = GeneratorSv::new(setting.clone());
+                                    let mut gen = GeneratorSv::new(setting.clone(), args.sv_sva);
                                     if let Err(e) = gen.gen(o) {
                                         println!(" -> SV generation failed: {}", e)
                                     }
                                 }
-                                t => println!("Target {t:?} not supported -> skipping"),
+                                RifGenTargets::Svlib => {
+                                    setting.path = args.output_rtl.clone();
+                                    let mut gen = GeneratorSvlib::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> SV library generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Vhdl => {
+                                    setting.path = args.output_rtl.clone();
+                                    let mut gen = GeneratorVhdl::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> VHDL generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Verilog => {
+                                    setting.path = args.output_rtl.clone();
+                                    let mut gen = GeneratorVerilog::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> Verilog generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Svd => {
+                                    setting.path = args.output_svd.clone();
+                                    let mut gen = GeneratorSvd::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> SVD generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Ipxact => {
+                                    setting.path = args.output_ipxact.clone();
+                                    let mut gen = GeneratorIpxact::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> IP-XACT generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Uvm => {
+                                    setting.path = args.output_uvm.clone();
+                                    let mut gen = GeneratorUvm::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> UVM generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Linux => {
+                                    setting.path = args.output_linux.clone();
+                                    let mut gen = GeneratorLinux::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> Linux generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Qemu => {
+                                    setting.path = args.output_qemu.clone();
+                                    let mut gen = GeneratorQemu::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> QEMU generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Renode => {
+                                    setting.path = args.output_renode.clone();
+                                    let mut gen = GeneratorRenode::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> Renode generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Systemc => {
+                                    setting.path = args.output_systemc.clone();
+                                    let mut gen = GeneratorSystemc::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> SystemC generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Cocotb => {
+                                    setting.path = args.output_cocotb.clone();
+                                    let mut gen = GeneratorCocotb::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> Cocotb generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Rust => {
+                                    setting.path = args.output_rust.clone();
+                                    let mut gen = GeneratorRust::new(setting.clone());
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> Rust generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Py => {
+                                    setting.path = args.output_py.clone();
+                                    let mut gen = GeneratorPy::new(setting.clone(), args.py_model);
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> Python generation failed: {}", e)
+                                    }
+                                }
+                                RifGenTargets::Json => {
+                                    setting.path = args.output_json.clone();
+                                    let mut gen = GeneratorJson::new(setting.clone(), args.json_flat);
+                                    if let Err(e) = gen.gen(o) {
+                                        println!(" -> JSON generation failed: {}", e)
+                                    }
+                                }
                             }
                         }
                         // println!(" -> Compile Ok: \n{:?}",o),
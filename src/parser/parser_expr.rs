@@ -421,7 +421,7 @@ impl From<ExprError> for String {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ParamValues(OrderDict<String,isize>);
 
 impl ParamValues {
@@ -436,6 +436,7 @@ impl ParamValues {
         params
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_iter<'a, I>(dict: I) -> Result<Self,String>
     where I: Iterator<Item = (&'a String,&'a ExprTokens)> {
         let mut params = ParamValues(OrderDict::new());
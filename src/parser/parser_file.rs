@@ -11,7 +11,7 @@ use crate::parser::{
     bool_or_default, clk_en, enum_kind, generic_def, intr_desc, limit_def, password_info, path_val, reg_incl_or_decl, reg_inst_array_properties, reg_inst_properties, reg_pulse_info, rif_inst_suffix, rifmux_group, rifmux_map, signal_or_expr, val_u16
 };
 use crate::rifgen::{
-    Access, ClockingInfo, Context, EnumDef, EnumKind, ExternalKind, Field, FieldHwKind, FieldSwKind, Interface, Lock, OverrideIndex, RegDef, RegDefOrIncl, RegInst, RegPulseKind, ResetDef, Rif, RifPage, RifType, Rifmux, RifmuxItem, RifmuxTop, Visibility
+    Access, ClockingInfo, Context, EnumDef, EnumKind, ExternalKind, Field, FieldHwKind, FieldSwKind, Lock, OverrideIndex, RegDef, RegDefOrIncl, RegInst, RegPulseKind, Rif, RifPage, RifType, Rifmux, RifmuxItem, RifmuxTop, Visibility
 };
 
 use super::{
@@ -51,6 +51,12 @@ where
 type ContextStack = Vec<(Context, usize)>;
 
 
+impl Default for RifGenSrc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RifGenSrc {
     pub fn new() -> RifGenSrc {
         RifGenSrc {
@@ -181,9 +187,10 @@ impl RifGenSrc {
                         Context::Info => context_stack.push((Context::Info, ilvl + 1)),
                         Context::Interface => {
                             let intf = val_intf(&mut l)?;
-                            if intf == Interface::Apb {
-                                if !sw_clk_defined.0 {self.last_rif().sw_clocking.clk = "pclk".to_owned();}
-                                if !sw_clk_defined.1 {self.last_rif().sw_clocking.rst = ResetDef::new("presetn".to_owned());}
+                            // Default clock/reset defined by the bus protocol
+                            if let Some((clk, rst)) = intf.default_clocking() {
+                                if !sw_clk_defined.0 {self.last_rif().sw_clocking.clk = clk.to_owned();}
+                                if !sw_clk_defined.1 {self.last_rif().sw_clocking.rst = rst;}
                             }
                             self.last_rif().interface = intf;
                         }
@@ -552,10 +559,10 @@ impl RifGenSrc {
                         Context::Info => context_stack.push((Context::Info, ilvl + 1)),
                         Context::Interface => {
                             let intf = val_intf(&mut l)?;
-                            // Default clock/reset for APB
-                            if intf == Interface::Apb {
-                                if !sw_clk_defined.0 {self.last_rifmux().sw_clocking.clk = "pclk".to_owned();}
-                                if !sw_clk_defined.1 {self.last_rifmux().sw_clocking.rst = ResetDef::new("presetn".to_owned());}
+                            // Default clock/reset defined by the bus protocol
+                            if let Some((clk, rst)) = intf.default_clocking() {
+                                if !sw_clk_defined.0 {self.last_rifmux().sw_clocking.clk = clk.to_owned();}
+                                if !sw_clk_defined.1 {self.last_rifmux().sw_clocking.rst = rst;}
                             }
                             self.last_rifmux().interface = intf;
                         }
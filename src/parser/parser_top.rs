@@ -130,6 +130,10 @@ mod tests_parsing {
     assert_eq!(val_intf(&mut "Default "), Ok(Interface::Default) );
     assert_eq!(val_intf(&mut "apb"), Ok(Interface::Apb));
     assert_eq!(val_intf(&mut "Apb "), Ok(Interface::Apb));
+    assert_eq!(val_intf(&mut "axi4lite"), Ok(Interface::Axi4Lite));
+    assert_eq!(val_intf(&mut "AHB"), Ok(Interface::Ahb));
+    assert_eq!(val_intf(&mut "wishbone"), Ok(Interface::Wishbone));
+    assert_eq!(val_intf(&mut "Avalon"), Ok(Interface::Avalon));
     assert_eq!(val_intf(&mut "my_intf5"), Ok(Interface::Custom("my_intf5".to_owned())));
     assert_eq!(val_intf(&mut "543 ").is_err(), true);
     // assert_eq!(val_intf(&mut "543 "), Err(ErrMode::Backtrack(winnow::error::InputError{input:"543 ", kind:ErrorKind::Tag})) );
@@ -126,6 +126,10 @@ impl EnumDef {
         self.values.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
     pub fn iter(&self) -> impl Iterator<Item=&EnumEntry> {
         self.values.iter()
     }
@@ -298,6 +302,23 @@ impl FieldSwKind {
     pub fn is_wo(&self) -> bool {
         matches!(self, FieldSwKind::WriteOnly | FieldSwKind::W1Pulse(_,true) | FieldSwKind::Password(_))
     }
+
+    /// Keyword used in the RIF description
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            FieldSwKind::ReadWrite => "rw",
+            FieldSwKind::ReadOnly => "ro",
+            FieldSwKind::WriteOnly => "wo",
+            FieldSwKind::ReadClr => "rclr",
+            FieldSwKind::W1Clr => "w1clr",
+            FieldSwKind::W0Clr => "w0clr",
+            FieldSwKind::W1Set => "w1set",
+            FieldSwKind::W1Tgl => "toggle",
+            FieldSwKind::W1Pulse(true,_) => "pulsereg",
+            FieldSwKind::W1Pulse(false,_) => "pulse",
+            FieldSwKind::Password(_) => "password",
+        }
+    }
 }
 
 
@@ -6,6 +6,14 @@ pub struct OrderDict<K,V> {
     values: Vec<V>
 }
 
+impl<K,V> Default for OrderDict<K,V>
+    where K: Eq + Hash
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<K,V> OrderDict<K,V>
     where K: Eq + Hash
 {
@@ -37,6 +45,10 @@ impl<K,V> OrderDict<K,V>
         self.values.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
     pub fn get(&self, k: &K) -> Option<&V> {
         let i = self.keys.get(k)?;
         Some(&self.values[*i])
@@ -55,6 +55,14 @@ pub enum Interface { #[default]
     Apb,
     /// Auxiliary peripheral bus
     Uaux,
+    /// AMBA AXI4-Lite
+    Axi4Lite,
+    /// AMBA AHB-Lite
+    Ahb,
+    /// Wishbone B4 pipelined
+    Wishbone,
+    /// Intel Avalon memory mapped
+    Avalon,
     /// Custom interface
     Custom(String)
 }
@@ -68,6 +76,10 @@ impl FromStr for Interface {
             "default" => Ok(Interface::Default),
             "apb"     => Ok(Interface::Apb),
             "uaux"    => Ok(Interface::Uaux),
+            "axi4lite" => Ok(Interface::Axi4Lite),
+            "ahb"     => Ok(Interface::Ahb),
+            "wishbone" => Ok(Interface::Wishbone),
+            "avalon"  => Ok(Interface::Avalon),
             custom    => Ok(Interface::Custom(custom.to_owned())),
         }
     }
@@ -79,6 +91,10 @@ impl Interface {
             Interface::Default => "rif",
             Interface::Apb => "apb",
             Interface::Uaux => "uaux",
+            Interface::Axi4Lite => "axi4lite",
+            Interface::Ahb => "ahb",
+            Interface::Wishbone => "wishbone",
+            Interface::Avalon => "avalon",
             Interface::Custom(n) => n,
         }
     }
@@ -86,6 +102,16 @@ impl Interface {
     pub fn is_default(&self) -> bool {
         *self==Interface::Default
     }
+
+    /// Clock and reset name defined by the bus protocol, used when no software clock/reset is set
+    pub fn default_clocking(&self) -> Option<(&str, ResetDef)> {
+        match self {
+            Interface::Apb => Some(("pclk", ResetDef::new("presetn".to_owned()))),
+            Interface::Axi4Lite => Some(("aclk", ResetDef::new("aresetn".to_owned()))),
+            Interface::Ahb => Some(("hclk", ResetDef::new("hresetn".to_owned()))),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]